@@ -20,6 +20,7 @@ use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use std::convert::TryFrom;
 use std::path::Path;
+use std::sync::Arc;
 
 pub const SETTING_PREFIX_NAME: ColumnFamilyName = "account_settings";
 pub const ENCRYPTED_PRIVATE_KEY_PREFIX_NAME: ColumnFamilyName = "encrypted_private_key";
@@ -186,6 +187,7 @@ pub struct AccountStorage {
     public_key_store: PublicKeyStore,
     global_value_store: GlobalSettingStore,
     accepted_token_store: AcceptedTokenStore,
+    db: Option<Arc<DBStorage>>,
 }
 
 impl AccountStorage {
@@ -208,15 +210,22 @@ impl AccountStorage {
     }
 
     pub fn new(store: StorageInstance) -> Self {
+        let db = store.db();
         Self {
             setting_store: AccountSettingStore::new(store.clone()),
             private_key_store: PrivateKeyStore::new(store.clone()),
             public_key_store: PublicKeyStore::new(store.clone()),
             accepted_token_store: AcceptedTokenStore::new(store.clone()),
             global_value_store: GlobalSettingStore::new(store),
+            db,
         }
     }
 
+    /// The underlying rocksdb storage, None if the storage is a mock.
+    pub fn db(&self) -> Option<Arc<DBStorage>> {
+        self.db.clone()
+    }
+
     pub fn mock() -> Self {
         let storage_instance = StorageInstance::new_cache_instance();
         Self::new(storage_instance)
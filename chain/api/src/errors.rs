@@ -37,6 +37,7 @@ pub enum VerifyBlockField {
     Body,
     Uncle,
     Consensus,
+    AuthorSignature,
     // block field verified base on block executed result.
     State,
 }
@@ -48,6 +49,7 @@ impl Display for VerifyBlockField {
             VerifyBlockField::Header => write!(f, "header"),
             VerifyBlockField::Uncle => write!(f, "uncle"),
             VerifyBlockField::Consensus => write!(f, "consensus"),
+            VerifyBlockField::AuthorSignature => write!(f, "author_signature"),
             VerifyBlockField::State => write!(f, "state"),
         }
     }
@@ -80,4 +82,14 @@ impl ConnectBlockError {
             }
         }
     }
+
+    /// Whether the block should be saved as a failed block. A bad author signature does not fail
+    /// the block, so a relayed copy with a broken signature does not stop syncing the block.
+    pub fn is_failed_block(&self) -> bool {
+        !matches!(
+            self,
+            ConnectBlockError::FutureBlock(_)
+                | ConnectBlockError::VerifyBlockFailed(VerifyBlockField::AuthorSignature, _)
+        )
+    }
 }
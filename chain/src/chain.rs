@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::verifier::{BlockVerifier, FullVerifier};
+use crate::verifier::{BlockVerifier, CheckpointVerifier, FullVerifier};
 use anyhow::{ensure, format_err, Result};
 use consensus::Consensus;
 use crypto::HashValue;
@@ -14,6 +14,8 @@ use starcoin_chain_api::{
     verify_block, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock,
     MintedUncleNumber, VerifiedBlock, VerifyBlockField,
 };
+use starcoin_config::Checkpoints;
+use starcoin_executor::BlockExecutedData;
 use starcoin_open_block::OpenedBlock;
 use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, ChainStateWriter};
 use starcoin_statedb::ChainStateDB;
@@ -25,9 +27,8 @@ use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
     account_address::AccountAddress,
     block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
-    contract_event::ContractEvent,
     error::BlockExecutorError,
-    transaction::{SignedUserTransaction, Transaction, TransactionInfo},
+    transaction::{SignedUserTransaction, Transaction},
     U256,
 };
 use starcoin_vm_types::account_config::genesis_address;
@@ -55,6 +56,8 @@ pub struct BlockChain {
     time_service: Arc<dyn TimeService>,
     uncles: HashMap<HashValue, MintedUncleNumber>,
     epoch: Epoch,
+    // blocks conflicting with the trusted checkpoints are rejected by the chain verify.
+    checkpoints: Arc<Checkpoints>,
 }
 
 impl BlockChain {
@@ -87,6 +90,7 @@ impl BlockChain {
         let genesis = storage
             .get_genesis()?
             .ok_or_else(|| format_err!("Can not find genesis hash in storage."))?;
+        let checkpoints = Checkpoints::builtin(head_block.header().chain_id());
         watch(CHAIN_WATCH_NAME, "n1253");
         let mut chain = Self {
             genesis_hash: genesis,
@@ -109,6 +113,7 @@ impl BlockChain {
             storage,
             uncles: HashMap::new(),
             epoch,
+            checkpoints,
         };
         watch(CHAIN_WATCH_NAME, "n1251");
         match uncles {
@@ -145,6 +150,17 @@ impl BlockChain {
         Self::new(time_service, executed_block.block.id(), storage)
     }
 
+    /// Add the trusted checkpoints to the builtin checkpoints of the chain, the blocks conflict with
+    /// the checkpoints are rejected.
+    pub fn with_checkpoints(mut self, checkpoints: &Checkpoints) -> Result<Self> {
+        Arc::make_mut(&mut self.checkpoints).extend(checkpoints.iter().cloned().collect())?;
+        Ok(self)
+    }
+
+    pub fn checkpoints(&self) -> &Checkpoints {
+        &self.checkpoints
+    }
+
     pub fn current_epoch_uncles_size(&self) -> u64 {
         self.uncles.len() as u64
     }
@@ -313,6 +329,7 @@ impl BlockChain {
     where
         V: BlockVerifier,
     {
+        CheckpointVerifier::verify_header(&self.checkpoints, block.header())?;
         V::verify_block(self, block)
     }
 
@@ -370,8 +387,15 @@ impl BlockChain {
         };
 
         watch(CHAIN_WATCH_NAME, "n21");
-        let executed_data =
-            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?;
+        let executed_data = if storage.write_set_enabled() {
+            starcoin_executor::block_execute_with_write_sets(
+                &statedb,
+                txns.clone(),
+                epoch.block_gas_limit(),
+            )?
+        } else {
+            starcoin_executor::block_execute(&statedb, txns.clone(), epoch.block_gas_limit())?
+        };
         watch(CHAIN_WATCH_NAME, "n22");
         let state_root = executed_data.state_root;
         let vec_transaction_info = &executed_data.txn_infos;
@@ -445,7 +469,7 @@ impl BlockChain {
             block.clone(),
             block_info.clone(),
             txns,
-            (executed_data.txn_infos, executed_data.txn_events),
+            executed_data,
         )?;
         watch(CHAIN_WATCH_NAME, "n26");
         Ok(ExecutedBlock { block, block_info })
@@ -456,10 +480,16 @@ impl BlockChain {
         block: Block,
         block_info: BlockInfo,
         transactions: Vec<Transaction>,
-        txn_infos: (Vec<TransactionInfo>, Vec<Vec<ContractEvent>>),
+        executed_data: BlockExecutedData,
     ) -> Result<()> {
         let block_id = block.id();
-        let (txn_infos, txn_events) = txn_infos;
+        let BlockExecutedData {
+            txn_infos,
+            txn_events,
+            txn_abort_contexts,
+            txn_write_sets,
+            ..
+        } = executed_data;
         debug_assert!(
             transactions.len() == txn_infos.len(),
             "block txns' length should be equal to txn infos' length"
@@ -472,6 +502,22 @@ impl BlockChain {
         for (info_id, events) in txn_info_ids.iter().zip(txn_events.into_iter()) {
             storage.save_contract_events(*info_id, events)?;
         }
+        storage.save_transaction_abort_contexts(
+            txn_info_ids
+                .iter()
+                .zip(txn_abort_contexts.into_iter())
+                .filter_map(|(info_id, abort_context)| {
+                    abort_context.map(|abort_context| (*info_id, abort_context))
+                })
+                .collect(),
+        )?;
+        storage.save_transaction_write_sets(
+            txn_info_ids
+                .iter()
+                .copied()
+                .zip(txn_write_sets.into_iter())
+                .collect(),
+        )?;
 
         storage.save_transaction_infos(
             txn_infos
@@ -713,12 +759,14 @@ impl ChainReader for BlockChain {
         } else {
             None
         };
-        BlockChain::new_with_uncles(
+        let mut chain = BlockChain::new_with_uncles(
             self.time_service.clone(),
             head,
             uncles,
             self.storage.clone(),
-        )
+        )?;
+        chain.checkpoints = self.checkpoints.clone();
+        Ok(chain)
     }
 
     fn epoch_uncles(&self) -> &HashMap<HashValue, MintedUncleNumber> {
@@ -749,11 +797,12 @@ impl ChainReader for BlockChain {
     }
 
     fn verify(&self, block: Block) -> Result<VerifiedBlock> {
+        CheckpointVerifier::verify_header(&self.checkpoints, block.header())?;
         FullVerifier::verify_block(self, block)
     }
 
     fn execute(&self, verified_block: VerifiedBlock) -> Result<ExecutedBlock> {
-        Self::execute_block_and_save(
+        let executed_block = Self::execute_block_and_save(
             self.storage.as_ref(),
             self.statedb.fork(),
             self.txn_accumulator.fork(),
@@ -761,7 +810,13 @@ impl ChainReader for BlockChain {
             &self.epoch,
             Some(self.status.status.clone()),
             verified_block.0,
-        )
+        )?;
+        CheckpointVerifier::verify_block_info(
+            &self.checkpoints,
+            executed_block.header(),
+            executed_block.block_info(),
+        )?;
+        Ok(executed_block)
     }
 }
 
@@ -8,7 +8,8 @@ use sp_utils::stop_watch::{watch, CHAIN_WATCH_NAME};
 use starcoin_chain_api::{
     verify_block, ChainReader, ConnectBlockError, VerifiedBlock, VerifyBlockField,
 };
-use starcoin_types::block::{Block, BlockHeader, ALLOWED_FUTURE_BLOCKTIME};
+use starcoin_config::Checkpoints;
+use starcoin_types::block::{Block, BlockHeader, BlockInfo, ALLOWED_FUTURE_BLOCKTIME};
 use std::collections::HashSet;
 
 arg_enum! {
@@ -236,13 +237,20 @@ impl BlockVerifier for ConsensusVerifier {
     {
         let epoch = current_chain.epoch();
         let consensus = epoch.strategy();
-        if let Err(e) = consensus.verify(current_chain, new_block_header) {
+        if let Err(e) = consensus
+            .verify(current_chain, new_block_header)
+            .and_then(|_| consensus.verify_author(current_chain, new_block_header))
+        {
             return match e.downcast::<ConsensusVerifyError>() {
-                Ok(e) => Err(ConnectBlockError::VerifyBlockFailed(
-                    VerifyBlockField::Consensus,
-                    e.into(),
-                )
-                .into()),
+                Ok(e) => {
+                    let field = match e {
+                        ConsensusVerifyError::VerifyAuthorSignatureError { .. } => {
+                            VerifyBlockField::AuthorSignature
+                        }
+                        _ => VerifyBlockField::Consensus,
+                    };
+                    Err(ConnectBlockError::VerifyBlockFailed(field, e.into()).into())
+                }
                 Err(e) => Err(e),
             };
         }
@@ -290,3 +298,46 @@ impl BlockVerifier for NoneVerifier {
         Ok(())
     }
 }
+
+/// Verify block does not conflict with the trusted checkpoints.
+pub struct CheckpointVerifier;
+
+impl CheckpointVerifier {
+    pub fn verify_header(checkpoints: &Checkpoints, new_block_header: &BlockHeader) -> Result<()> {
+        if let Some(checkpoint) = checkpoints.get(new_block_header.number()) {
+            verify_block!(
+                VerifyBlockField::Header,
+                checkpoint.block_hash == new_block_header.id(),
+                "Invalid block: block {} at number {} conflict with checkpoint {}.",
+                new_block_header.id(),
+                new_block_header.number(),
+                checkpoint
+            );
+        }
+        Ok(())
+    }
+
+    /// Verify the block accumulator root of the executed block.
+    pub fn verify_block_info(
+        checkpoints: &Checkpoints,
+        new_block_header: &BlockHeader,
+        block_info: &BlockInfo,
+    ) -> Result<()> {
+        if let Some(checkpoint) = checkpoints.get(new_block_header.number()) {
+            if let Some(block_accumulator_root) = checkpoint.block_accumulator_root {
+                let actual_root = *block_info
+                    .get_block_accumulator_info()
+                    .get_accumulator_root();
+                verify_block!(
+                    VerifyBlockField::State,
+                    block_accumulator_root == actual_root,
+                    "Invalid block: block {} accumulator root {} conflict with checkpoint {}.",
+                    new_block_header.id(),
+                    actual_root,
+                    checkpoint
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,19 +2,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use bcs_ext::BCSCodec;
+use consensus::authority::AuthorityConsensus;
 use consensus::Consensus;
-use crypto::{ed25519::Ed25519PrivateKey, Genesis, PrivateKey};
+use crypto::keygen::KeyGen;
+use crypto::{ed25519::Ed25519PrivateKey, Genesis, HashValue, PrivateKey};
 use starcoin_account_api::AccountInfo;
 use starcoin_accumulator::Accumulator;
+use starcoin_chain::verifier::CheckpointVerifier;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
+use starcoin_chain_api::ConnectBlockError;
 use starcoin_chain_mock::MockChain;
 use starcoin_config::NodeConfig;
-use starcoin_config::{BuiltinNetworkID, ChainNetwork};
+use starcoin_config::{BuiltinNetworkID, ChainNetwork, Checkpoint, Checkpoints};
 use starcoin_executor::{build_transfer_from_association, DEFAULT_EXPIRATION_TIME};
-use starcoin_types::account_address;
+use starcoin_types::account_address::{self, AccountAddress};
 use starcoin_types::block::{Block, BlockHeader};
 use starcoin_types::filter::Filter;
+use starcoin_types::genesis_config::{ChainId, ConsensusStrategy};
+use starcoin_types::sign_message::SignedMessage;
+use starcoin_types::transaction::authenticator::AccountPrivateKey;
 use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::event::EventKey;
 use std::sync::Arc;
@@ -475,3 +483,185 @@ fn test_get_blocks_by_number() -> Result<()> {
     assert_eq!(blocks.len(), 11);
     Ok(())
 }
+
+#[stest::test]
+fn test_checkpoint_verifier() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(3)?;
+    let header = mock_chain.head().current_header();
+    let info = mock_chain.head().status().info;
+    let block_accumulator_root = *info.get_block_accumulator_info().get_accumulator_root();
+
+    let checkpoints = Checkpoints::new(vec![Checkpoint::new(header.number(), header.id())
+        .with_block_accumulator_root(block_accumulator_root)])?;
+    CheckpointVerifier::verify_header(&checkpoints, &header)?;
+    CheckpointVerifier::verify_block_info(&checkpoints, &header, &info)?;
+
+    let conflict_checkpoints =
+        Checkpoints::new(vec![Checkpoint::new(header.number(), HashValue::random())])?;
+    assert!(CheckpointVerifier::verify_header(&conflict_checkpoints, &header).is_err());
+    let conflict_checkpoints =
+        Checkpoints::new(vec![Checkpoint::new(header.number(), header.id())
+            .with_block_accumulator_root(HashValue::random())])?;
+    assert!(CheckpointVerifier::verify_block_info(&conflict_checkpoints, &header, &info).is_err());
+    Ok(())
+}
+
+#[stest::test]
+fn test_apply_block_with_checkpoints() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(2)?;
+    let head_id = mock_chain.head().current_header().id();
+    let block = mock_chain.produce()?;
+
+    let conflict_checkpoints = Checkpoints::new(vec![Checkpoint::new(
+        block.header().number(),
+        HashValue::random(),
+    )])?;
+    let mut chain = mock_chain
+        .head()
+        .fork(head_id)?
+        .with_checkpoints(&conflict_checkpoints)?;
+    assert!(chain.apply(block.clone()).is_err());
+    let chain = mock_chain
+        .head()
+        .fork(head_id)?
+        .with_checkpoints(&conflict_checkpoints)?;
+    assert!(chain.verify(block.clone()).is_err());
+
+    let checkpoints = Checkpoints::new(vec![Checkpoint::new(block.header().number(), block.id())
+        .with_block_accumulator_root(HashValue::random())])?;
+    let mut chain = mock_chain
+        .head()
+        .fork(head_id)?
+        .with_checkpoints(&checkpoints)?;
+    // the block matches the checkpoint, but its block accumulator root conflicts.
+    assert!(chain.apply(block.clone()).is_err());
+
+    let checkpoints = Checkpoints::new(vec![Checkpoint::new(block.header().number(), block.id())])?;
+    let mut chain = mock_chain
+        .head()
+        .fork(head_id)?
+        .with_checkpoints(&checkpoints)?;
+    chain.apply(block)?;
+    Ok(())
+}
+
+fn gen_authority_chain(
+    chain_id: u8,
+    validators: Vec<AccountAddress>,
+) -> Result<(ChainNetwork, BlockChain)> {
+    let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+    genesis_config.consensus_config.strategy = ConsensusStrategy::Custom.value();
+    genesis_config.authority_validators = validators.clone();
+    let net = ChainNetwork::new_custom(
+        format!("authority{}", chain_id),
+        ChainId::new(chain_id),
+        genesis_config,
+    )?;
+    consensus::register_custom_consensus(
+        net.chain_id(),
+        Arc::new(AuthorityConsensus::new(validators)),
+    );
+    let chain = test_helper::gen_blockchain_for_test(&net)?;
+    Ok((net, chain))
+}
+
+fn create_unsigned_block(
+    net: &ChainNetwork,
+    chain: &BlockChain,
+    author: AccountAddress,
+) -> Result<Block> {
+    let (block_template, _) = chain.create_block_template(author, None, vec![], vec![], None)?;
+    chain
+        .consensus()
+        .create_block(block_template, net.time_service().as_ref())
+}
+
+fn sign_block(block: Block, signer: &AccountPrivateKey) -> Result<Block> {
+    let message = block.header().author_signing_message();
+    let signed_message = SignedMessage::new(
+        block.header().author(),
+        message.clone(),
+        signer.sign_message(&message),
+        block.header().chain_id(),
+    );
+    let Block { header, body } = block;
+    Ok(Block::new(
+        header.with_author_signature(signed_message)?,
+        body,
+    ))
+}
+
+fn create_authority_block(
+    net: &ChainNetwork,
+    chain: &BlockChain,
+    author: AccountAddress,
+    signer: &AccountPrivateKey,
+) -> Result<Block> {
+    sign_block(create_unsigned_block(net, chain, author)?, signer)
+}
+
+fn gen_validator() -> (AccountAddress, AccountPrivateKey) {
+    let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+    (
+        account_address::from_public_key(&public_key),
+        AccountPrivateKey::Single(private_key),
+    )
+}
+
+#[stest::test]
+fn test_authority_block_author() -> Result<()> {
+    let (validator, validator_key) = gen_validator();
+    let (net, mut chain) = gen_authority_chain(101, vec![validator])?;
+    let unsigned_block = create_unsigned_block(&net, &chain, validator)?;
+    let block = sign_block(unsigned_block.clone(), &validator_key)?;
+
+    // the signature is a part of the block id, and is kept by the encoding.
+    assert_ne!(block.id(), unsigned_block.id());
+    assert_eq!(
+        block.header().author_signing_message(),
+        unsigned_block.header().author_signing_message()
+    );
+    let decoded = Block::decode(block.encode()?.as_slice())?;
+    assert_eq!(decoded.id(), block.id());
+    assert_eq!(
+        decoded.header().author_signature(),
+        block.header().author_signature()
+    );
+    let decoded: Block = serde_json::from_str(serde_json::to_string(&block)?.as_str())?;
+    assert_eq!(decoded, block);
+
+    assert!(chain.apply(unsigned_block).is_err());
+    chain.apply(block)?;
+    let block = create_authority_block(&net, &chain, validator, &validator_key)?;
+    chain.apply(block)?;
+    Ok(())
+}
+
+#[stest::test]
+fn test_authority_reject_non_validator_author() -> Result<()> {
+    let (validator, _validator_key) = gen_validator();
+    let (net, mut chain) = gen_authority_chain(103, vec![validator])?;
+    let (other, other_key) = gen_validator();
+    // the block is signed correctly by its author, but the author is not a validator.
+    let block = create_authority_block(&net, &chain, other, &other_key)?;
+    assert!(chain.apply(block).is_err());
+    Ok(())
+}
+
+#[stest::test]
+fn test_authority_reject_forged_signature() -> Result<()> {
+    let (validator, _validator_key) = gen_validator();
+    let (net, mut chain) = gen_authority_chain(104, vec![validator])?;
+    let (_other, other_key) = gen_validator();
+    // the author is a validator, but the signature is forged by another key.
+    let block = create_authority_block(&net, &chain, validator, &other_key)?;
+    let err = chain
+        .apply(block)
+        .expect_err("The forged signature should be rejected.")
+        .downcast::<ConnectBlockError>()?;
+    // the block with a bad signature is not saved as a failed block.
+    assert!(!err.is_failed_block());
+    Ok(())
+}
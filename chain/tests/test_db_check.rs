@@ -0,0 +1,78 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use crypto::HashValue;
+use starcoin_account_api::AccountInfo;
+use starcoin_chain::ChainReader;
+use starcoin_chain_mock::MockChain;
+use starcoin_config::{BuiltinNetworkID, ChainNetwork, RocksdbConfig};
+use starcoin_genesis::Genesis;
+use starcoin_types::startup_info::StartupInfo;
+use std::sync::Arc;
+use storage::block_info::BlockInfoStore;
+use storage::db_check::{CheckOption, DBChecker};
+use storage::db_storage::DBStorage;
+use storage::storage::{InnerStore, StorageInstance};
+use storage::{BlockStore, Storage, BLOCK_HEADER_PREFIX_NAME, VEC_PREFIX_NAME};
+
+#[stest::test(timeout = 120)]
+fn test_db_check_chain() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    // use db instance only, so the records changed in db are not read from cache.
+    let storage = Arc::new(Storage::new(StorageInstance::new_db_instance(
+        DBStorage::open_with_cfs(
+            tmpdir.path(),
+            VEC_PREFIX_NAME.to_vec(),
+            false,
+            RocksdbConfig::default(),
+        )?,
+    ))?);
+    let db = storage.db().expect("db must exist");
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let genesis = Genesis::load_or_build(&net)?;
+    let chain_info = genesis.execute_genesis_block(&net, storage.clone())?;
+    let genesis_id = chain_info.head().id();
+
+    let mut mock_chain =
+        MockChain::new_with_storage(net, storage.clone(), genesis_id, AccountInfo::random())?;
+    mock_chain.produce_and_apply_times(3)?;
+    let head = mock_chain.head().current_header();
+    storage.save_startup_info(StartupInfo::new(head.id()))?;
+    // a fork of the genesis is another head.
+    let mut fork = mock_chain.fork(Some(genesis_id))?;
+    fork.produce_and_apply()?;
+
+    let check_option = CheckOption {
+        check_state: true,
+        repair: false,
+    };
+    let report = DBChecker::new(db.as_ref(), &storage, check_option).check()?;
+    assert!(report.is_ok(), "{:?}", report.issues);
+    assert_eq!(report.head_block_hash, Some(head.id()));
+    assert_eq!(report.main_chain_blocks, 4);
+    assert_eq!(report.heads, 2);
+    assert!(report.state_nodes > 0);
+
+    // the accumulator root in the block info is recomputed from the parent.
+    let head_info = storage
+        .get_block_info(head.id())?
+        .expect("head info must exist");
+    let mut broken_info = head_info.clone();
+    broken_info.txn_accumulator_info.accumulator_root = HashValue::random();
+    storage.save_block_info(broken_info)?;
+    let report = DBChecker::new(db.as_ref(), &storage, check_option).check()?;
+    assert!(report.issues.iter().any(|issue| issue
+        .message
+        .starts_with("recomputed Transaction accumulator root")));
+    storage.save_block_info(head_info)?;
+
+    // the missing head is reported instead of aborting the check.
+    db.remove(BLOCK_HEADER_PREFIX_NAME, head.id().to_vec())?;
+    let report = DBChecker::new(db.as_ref(), &storage, check_option).check()?;
+    assert!(!report.is_ok());
+    assert!(report.issues.iter().any(
+        |issue| issue.cf_name == BLOCK_HEADER_PREFIX_NAME && issue.key == head.id().to_string()
+    ));
+    Ok(())
+}
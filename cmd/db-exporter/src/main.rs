@@ -1,210 +1,419 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{bail, Result};
-use bcs_ext::Sample;
+use crate::schema::{BlockKeyType, DbSchema};
+use anyhow::{bail, ensure, format_err, Result};
 use csv::Writer;
-use starcoin_storage::block::FailedBlock;
+use serde_json::Value;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_chain::{BlockChain, ChainReader, ChainWriter};
+use starcoin_config::{BuiltinNetworkID, ChainNetwork, RocksdbConfig};
+use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
-use starcoin_storage::storage::ValueCodec;
+use starcoin_storage::storage::{InnerStore, StorageInstance, ValueCodec};
 use starcoin_storage::{
-    BLOCK_HEADER_PREFIX_NAME, BLOCK_PREFIX_NAME, FAILED_BLOCK_PREFIX_NAME, VEC_PREFIX_NAME,
+    BlockStore, Storage, Store, BLOCK_TRANSACTIONS_PREFIX_NAME,
+    BLOCK_TRANSACTION_INFOS_PREFIX_NAME, VEC_PREFIX_NAME,
 };
-use starcoin_types::block::{Block, BlockHeader};
-use std::fmt::{Debug, Formatter};
-use std::path::PathBuf;
+use starcoin_types::block::Block;
+use starcoin_types::startup_info::StartupInfo;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
 use structopt::StructOpt;
 
-pub fn export<W: std::io::Write>(
-    db: &str,
-    mut csv_writer: Writer<W>,
-    schema: DbSchema,
-) -> anyhow::Result<()> {
-    let db_storage =
-        DBStorage::open_with_cfs(db, VEC_PREFIX_NAME.to_vec(), true, Default::default())?;
-    let mut iter = db_storage.iter(schema.to_string().as_str())?;
-    iter.seek_to_first();
-    let key_codec = schema.get_key_codec();
-    let value_codec = schema.get_value_codec();
-    let fields = schema.get_fields();
-    // write csv header.
-    {
-        csv_writer.write_field("key")?;
-        for field in fields.as_slice() {
-            csv_writer.write_field(field)?;
+mod schema;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// Tab separated, the top level fields of the value are the columns.
+    Csv,
+    /// One json object `{"key": .., "value": ..}` per line.
+    JsonLines,
+    /// The raw bcs bytes of the records, every record is
+    /// `key_len(u32 le) | key | value_len(u32 le) | value`.
+    Bcs,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "csv" => ExportFormat::Csv,
+            "json" | "jsonl" => ExportFormat::JsonLines,
+            "bcs" => ExportFormat::Bcs,
+            _ => bail!("Unsupported format: {}", s),
+        })
+    }
+}
+
+pub enum RecordWriter<W: Write> {
+    Csv {
+        writer: Writer<W>,
+        /// The csv header, decided by the first record.
+        fields: Option<Vec<String>>,
+    },
+    JsonLines(W),
+    Bcs(W),
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(format: ExportFormat, writer: W) -> Self {
+        match format {
+            ExportFormat::Csv => RecordWriter::Csv {
+                writer: csv::WriterBuilder::new()
+                    .delimiter(b'\t')
+                    .double_quote(false)
+                    .from_writer(writer),
+                fields: None,
+            },
+            ExportFormat::JsonLines => RecordWriter::JsonLines(writer),
+            ExportFormat::Bcs => RecordWriter::Bcs(writer),
         }
-        csv_writer.write_record(None::<&[u8]>)?;
     }
 
-    for item in iter {
-        let (k, v) = item?;
-        let key = key_codec(k);
-        let value = value_codec(v)?;
-        let object = value.as_object().expect("should be object.");
-
-        let mut record = vec![key];
-        for field in fields.as_slice() {
-            let field_value: Option<&serde_json::Value> = object.get(field);
-            match field_value {
-                Some(value) => {
-                    let record_field = match value {
-                        serde_json::Value::Number(n) => n.to_string(),
-                        serde_json::Value::String(s) => s.clone(),
-                        serde_json::Value::Null => "null".to_string(),
-                        serde_json::Value::Bool(b) => b.to_string(),
-                        value => serde_json::to_string(value)?,
+    pub fn write(&mut self, schema: DbSchema, key: &[u8], value: &[u8]) -> Result<()> {
+        match self {
+            RecordWriter::Csv { writer, fields } => {
+                let (_, value) = schema.decode(key, value)?;
+                if fields.is_none() {
+                    let header: Vec<String> = match value.as_object() {
+                        Some(object) => object.keys().cloned().collect(),
+                        None => vec!["value".to_string()],
                     };
-                    record.push(record_field);
+                    writer.write_field("key")?;
+                    for field in header.as_slice() {
+                        writer.write_field(field)?;
+                    }
+                    writer.write_record(None::<&[u8]>)?;
+                    *fields = Some(header);
                 }
-                None => {
-                    record.push("null".to_string());
+                let mut record = vec![hex::encode(key)];
+                for field in fields.iter().flatten() {
+                    let field_value = match value.as_object() {
+                        Some(object) => object.get(field),
+                        None => Some(&value),
+                    };
+                    let record_field = match field_value {
+                        Some(Value::Number(n)) => n.to_string(),
+                        Some(Value::String(s)) => s.clone(),
+                        Some(Value::Bool(b)) => b.to_string(),
+                        Some(Value::Null) | None => "null".to_string(),
+                        Some(value) => serde_json::to_string(value)?,
+                    };
+                    record.push(record_field);
                 }
+                writer.serialize(record)?;
+            }
+            RecordWriter::JsonLines(writer) => {
+                let (key, value) = schema.decode(key, value)?;
+                serde_json::to_writer(
+                    &mut *writer,
+                    &serde_json::json!({"key": key, "value": value}),
+                )?;
+                writer.write_all(b"\n")?;
+            }
+            RecordWriter::Bcs(writer) => {
+                writer.write_all(&(key.len() as u32).to_le_bytes())?;
+                writer.write_all(key)?;
+                writer.write_all(&(value.len() as u32).to_le_bytes())?;
+                writer.write_all(value)?;
             }
         }
-
-        csv_writer.serialize(record)?;
+        Ok(())
     }
-    // flush csv writer
-    csv_writer.flush()?;
-    Ok(())
-}
 
-#[derive(Debug, Copy, Clone)]
-pub enum DbSchema {
-    Block,
-    BlockHeader,
-    FailedBlock,
+    pub fn flush(&mut self) -> Result<()> {
+        match self {
+            RecordWriter::Csv { writer, .. } => writer.flush()?,
+            RecordWriter::JsonLines(writer) | RecordWriter::Bcs(writer) => writer.flush()?,
+        }
+        Ok(())
+    }
 }
 
-impl DbSchema {
-    pub fn get_key_codec(&self) -> Box<dyn Fn(Vec<u8>) -> String> {
-        Box::new(|arg| -> String { hex::encode(arg) })
+/// Read the next record of the bcs format, `None` at the end of the input.
+fn read_bcs_record<R: Read>(reader: &mut BufReader<R>) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+    fn read_bytes<R: Read>(reader: &mut BufReader<R>) -> Result<Vec<u8>> {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
     }
-
-    pub fn get_fields(&self) -> Vec<String> {
-        let sample_json = match self {
-            DbSchema::Block => {
-                serde_json::to_value(Block::sample()).expect("block to json should success")
-            }
-            DbSchema::BlockHeader => serde_json::to_value(BlockHeader::sample())
-                .expect("block header to json should success"),
-            DbSchema::FailedBlock => serde_json::to_value(FailedBlock::sample())
-                .expect("block header to json should success"),
-        };
-        sample_json
-            .as_object()
-            .expect("should be object")
-            .keys()
-            .cloned()
-            .collect()
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
     }
+    Ok(Some((read_bytes(reader)?, read_bytes(reader)?)))
+}
 
-    pub fn get_value_codec(&self) -> Box<dyn Fn(Vec<u8>) -> Result<serde_json::Value>> {
-        Box::new(match self {
-            DbSchema::Block => |arg| -> Result<serde_json::Value> {
-                Ok(serde_json::to_value(Block::decode_value(arg.as_slice())?)?)
-            },
-            DbSchema::BlockHeader => |arg| -> Result<serde_json::Value> {
-                Ok(serde_json::to_value(BlockHeader::decode_value(
-                    arg.as_slice(),
-                )?)?)
-            },
-            DbSchema::FailedBlock => |arg| -> Result<serde_json::Value> {
-                Ok(serde_json::to_value(FailedBlock::decode_value(
-                    arg.as_slice(),
-                )?)?)
-            },
+/// Get the block ids of the main chain from `start` to `end`(include), `end` is the head if not set.
+fn main_chain_block_ids(storage: &Storage, start: u64, end: Option<u64>) -> Result<Vec<HashValue>> {
+    let head_id = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info not found in db."))?
+        .main;
+    let head = storage
+        .get_block_header_by_hash(head_id)?
+        .ok_or_else(|| format_err!("Head block header {} not found.", head_id))?;
+    let head_info = storage
+        .get_block_info(head_id)?
+        .ok_or_else(|| format_err!("Head block info {} not found.", head_id))?;
+    let end = end.unwrap_or_else(|| head.number()).min(head.number());
+    ensure!(
+        start <= end,
+        "Invalid block number range {}..={}, the head number is {}.",
+        start,
+        end,
+        head.number()
+    );
+    let block_accumulator = MerkleAccumulator::new_with_info(
+        head_info.block_accumulator_info,
+        storage.get_accumulator_store(AccumulatorStoreType::Block),
+    );
+    (start..=end)
+        .map(|number| {
+            block_accumulator
+                .get_leaf(number)?
+                .ok_or_else(|| format_err!("Block {} not found in main chain.", number))
         })
-    }
+        .collect()
+}
 
-    pub fn name(&self) -> &'static str {
-        match self {
-            DbSchema::Block => BLOCK_PREFIX_NAME,
-            DbSchema::BlockHeader => BLOCK_HEADER_PREFIX_NAME,
-            DbSchema::FailedBlock => FAILED_BLOCK_PREFIX_NAME,
+fn get_hash_list(db: &DBStorage, cf_name: &str, block_id: HashValue) -> Result<Vec<HashValue>> {
+    db.get(cf_name, block_id.to_vec())?
+        .map(|bytes| Vec::<HashValue>::decode_value(bytes.as_slice()))
+        .transpose()?
+        .ok_or_else(|| format_err!("{} of block {} not found.", cf_name, block_id))
+}
+
+/// Export the records of the `schema`, only the records of the main chain blocks in `range` are
+/// exported if the range is set.
+pub fn export<W: Write>(
+    db_path: &Path,
+    schema: DbSchema,
+    range: Option<(u64, Option<u64>)>,
+    mut writer: RecordWriter<W>,
+) -> Result<()> {
+    let db = Arc::new(DBStorage::open_with_cfs(
+        db_path,
+        VEC_PREFIX_NAME.to_vec(),
+        true,
+        RocksdbConfig::default(),
+    )?);
+    match range {
+        None => {
+            let mut iter = db.iter(schema.name())?;
+            iter.seek_to_first();
+            for item in iter {
+                let (key, value) = item?;
+                writer.write(schema, key.as_slice(), value.as_slice())?;
+            }
+        }
+        Some((start, end)) => {
+            let key_type = schema.block_key_type().ok_or_else(|| {
+                format_err!("Schema {} does not support block number range.", schema)
+            })?;
+            let storage = Storage::new(StorageInstance::DB { db: db.clone() })?;
+            for block_id in main_chain_block_ids(&storage, start, end)? {
+                let keys = match key_type {
+                    BlockKeyType::BlockId => vec![block_id],
+                    BlockKeyType::TransactionId => {
+                        get_hash_list(&db, BLOCK_TRANSACTIONS_PREFIX_NAME, block_id)?
+                    }
+                    BlockKeyType::TransactionInfoId => {
+                        get_hash_list(&db, BLOCK_TRANSACTION_INFOS_PREFIX_NAME, block_id)?
+                    }
+                };
+                for key in keys {
+                    // the optional records, like write set, may not exist.
+                    if let Some(value) = db.get(schema.name(), key.to_vec())? {
+                        writer.write(schema, key.as_slice(), value.as_slice())?;
+                    }
+                }
+            }
         }
     }
+    writer.flush()
 }
 
-impl std::fmt::Display for DbSchema {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name())
-    }
+/// Open the storage of the node `data_dir`.
+fn open_storage(data_dir: &Path) -> Result<Arc<Storage>> {
+    Ok(Arc::new(Storage::new(
+        StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            DBStorage::new(data_dir.join("starcoindb/db"), RocksdbConfig::default())?,
+        ),
+    )?))
 }
 
-impl FromStr for DbSchema {
-    type Err = anyhow::Error;
+/// Import the blocks exported by `export` into the db of `data_dir` by executing them,
+/// the blocks must be exported by block number range to keep them in order.
+/// Return the number of the imported blocks.
+pub fn import(
+    input: &Path,
+    format: ExportFormat,
+    data_dir: &Path,
+    net: &ChainNetwork,
+) -> Result<u64> {
+    let storage = open_storage(data_dir)?;
+    let (chain_info, _) = Genesis::init_and_check_storage(net, storage.clone(), data_dir)?;
+    let mut chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let schema = match s {
-            BLOCK_PREFIX_NAME => DbSchema::Block,
-            BLOCK_HEADER_PREFIX_NAME => DbSchema::BlockHeader,
-            FAILED_BLOCK_PREFIX_NAME => DbSchema::FailedBlock,
-            _ => {
-                bail!("Unsupported schema: {}", s)
+    let mut reader = BufReader::new(File::open(input)?);
+    let mut next_block = || -> Result<Option<Block>> {
+        match format {
+            ExportFormat::JsonLines => {
+                let mut line = String::new();
+                if reader.read_line(&mut line)? == 0 {
+                    return Ok(None);
+                }
+                let mut record: Value = serde_json::from_str(line.as_str())?;
+                Ok(Some(serde_json::from_value(record["value"].take())?))
             }
-        };
-        Ok(schema)
+            ExportFormat::Bcs => read_bcs_record(&mut reader)?
+                .map(|(_, value)| Block::decode_value(value.as_slice()))
+                .transpose(),
+            ExportFormat::Csv => bail!("Import from csv is not supported."),
+        }
+    };
+    let mut imported = 0;
+    while let Some(block) = next_block()? {
+        // skip the genesis and the blocks imported before, and continue from the skipped block.
+        if storage.get_block_info(block.id())?.is_some() {
+            if block.id() != chain.current_header().id() {
+                chain = BlockChain::new(net.time_service(), block.id(), storage.clone())?;
+            }
+            continue;
+        }
+        let head = chain.current_header();
+        ensure!(
+            block.header().parent_hash() == head.id(),
+            "Block {}:{} is not the child of the head {}:{}, please export the blocks by block number range.",
+            block.header().number(),
+            block.id(),
+            head.number(),
+            head.id()
+        );
+        chain.apply(block)?;
+        // save the head after every block, so an interrupted import is continued from it.
+        storage.save_startup_info(StartupInfo::new(chain.current_header().id()))?;
+        imported += 1;
     }
+    Ok(imported)
 }
 
 #[derive(Debug, Clone, StructOpt)]
-#[structopt(name = "db-exporter", about = "starcoin db exporter")]
-pub struct ExporterOptions {
+pub struct ExportOptions {
     #[structopt(long, short = "o", parse(from_os_str))]
     /// output file, like accounts.csv, default is stdout.
     pub output: Option<PathBuf>,
     #[structopt(long, short = "i", parse(from_os_str))]
     /// starcoin node db path. like ~/.starcoin/barnard/starcoindb/db/starcoindb
     pub db_path: PathBuf,
-
     #[structopt(long, short = "s")]
-    /// the table of database which to export, block,block_header
+    /// the column family of database which to export, like block, block_header, transaction_info, contract_event.
     pub schema: DbSchema,
+    #[structopt(long, short = "f", default_value = "csv")]
+    /// the output format: csv, jsonl or bcs.
+    pub format: ExportFormat,
+    #[structopt(long)]
+    /// only export the records of the main chain blocks from the start number, the records are exported in block order.
+    pub start_number: Option<u64>,
+    #[structopt(long)]
+    /// only export the records of the main chain blocks to the end number(include), default is the head.
+    pub end_number: Option<u64>,
 }
 
-fn main() -> anyhow::Result<()> {
-    let option: ExporterOptions = ExporterOptions::from_args();
-    let output = option.output.as_deref();
-    let mut writer_builder = csv::WriterBuilder::new();
-    let writer_builder = writer_builder.delimiter(b'\t').double_quote(false);
-    let result = match output {
-        Some(output) => {
-            let writer = writer_builder.from_path(output)?;
-            export(
-                option.db_path.display().to_string().as_str(),
-                writer,
-                option.schema,
-            )
-        }
-        None => {
-            let writer = writer_builder.from_writer(std::io::stdout());
-            export(
-                option.db_path.display().to_string().as_str(),
-                writer,
-                option.schema,
-            )
-        }
+#[derive(Debug, Clone, StructOpt)]
+pub struct ImportOptions {
+    #[structopt(long, short = "i", parse(from_os_str))]
+    /// the file of the blocks exported with `-s block` and block number range.
+    pub input: PathBuf,
+    #[structopt(long, short = "f", default_value = "jsonl")]
+    /// the input format: jsonl or bcs.
+    pub format: ExportFormat,
+    #[structopt(long, short = "n")]
+    /// chain network of the blocks.
+    pub net: BuiltinNetworkID,
+    #[structopt(long, short = "d", parse(from_os_str))]
+    /// the data dir of the new node, like ~/.starcoin/barnard
+    pub data_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, StructOpt)]
+#[structopt(name = "db-exporter", about = "starcoin db exporter")]
+pub enum ExporterCommand {
+    /// Export the records of a column family.
+    #[structopt(name = "export")]
+    Export(ExportOptions),
+    /// Import the exported blocks into a new db by executing them.
+    #[structopt(name = "import")]
+    Import(ImportOptions),
+}
+
+fn run_export(option: ExportOptions) -> Result<()> {
+    let range = match (option.start_number, option.end_number) {
+        (None, None) => None,
+        (start, end) => Some((start.unwrap_or_default(), end)),
     };
-    if let Err(err) = result {
-        let broken_pipe_err = err.downcast_ref::<csv::Error>().and_then(|err| {
-            if let csv::ErrorKind::Io(io_err) = err.kind() {
-                if io_err.kind() == std::io::ErrorKind::BrokenPipe {
-                    Some(io_err)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        });
-        //ignore BrokenPipe
-        return if let Some(_broken_pipe_err) = broken_pipe_err {
+    match option.output.as_deref() {
+        Some(output) => export(
+            option.db_path.as_path(),
+            option.schema,
+            range,
+            RecordWriter::new(
+                option.format,
+                std::io::BufWriter::new(File::create(output)?),
+            ),
+        ),
+        None => export(
+            option.db_path.as_path(),
+            option.schema,
+            range,
+            RecordWriter::new(option.format, std::io::stdout()),
+        ),
+    }
+}
+
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    let io_err = match err.downcast_ref::<csv::Error>() {
+        Some(err) => match err.kind() {
+            csv::ErrorKind::Io(io_err) => Some(io_err),
+            _ => None,
+        },
+        None => err.downcast_ref::<std::io::Error>(),
+    };
+    matches!(io_err, Some(io_err) if io_err.kind() == std::io::ErrorKind::BrokenPipe)
+}
+
+fn main() -> anyhow::Result<()> {
+    let _logger = starcoin_logger::init();
+    match ExporterCommand::from_args() {
+        ExporterCommand::Export(option) => match run_export(option) {
+            //ignore BrokenPipe
+            Err(err) if is_broken_pipe(&err) => Ok(()),
+            result => result,
+        },
+        ExporterCommand::Import(option) => {
+            let net = ChainNetwork::new_builtin(option.net);
+            let imported = import(
+                option.input.as_path(),
+                option.format,
+                option.data_dir.as_path(),
+                &net,
+            )?;
+            println!("Imported {} blocks.", imported);
             Ok(())
-        } else {
-            Err(err)
-        };
+        }
     }
-    result
 }
@@ -0,0 +1,184 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use starcoin_accumulator::AccumulatorNode;
+use starcoin_crypto::HashValue;
+use starcoin_storage::block::FailedBlock;
+use starcoin_storage::error_map::ModuleErrorMap;
+use starcoin_storage::storage::{KeyCodec, ValueCodec};
+use starcoin_storage::{
+    BLOCK_ACCUMULATOR_NODE_PREFIX_NAME, BLOCK_BODY_PREFIX_NAME, BLOCK_HEADER_PREFIX_NAME,
+    BLOCK_INFO_PREFIX_NAME, BLOCK_PREFIX_NAME, BLOCK_TRANSACTIONS_PREFIX_NAME,
+    BLOCK_TRANSACTION_INFOS_PREFIX_NAME, CHAIN_INFO_PREFIX_NAME, CONTRACT_EVENT_PREFIX_NAME,
+    ERROR_MAP_PREFIX_NAME, FAILED_BLOCK_PREFIX_NAME, STATE_NODE_PREFIX_NAME,
+    TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME, TRANSACTION_INFO_EXT_PREFIX_NAME,
+    TRANSACTION_INFO_HASH_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME, TRANSACTION_PREFIX_NAME,
+    WRITE_SET_PREFIX_NAME,
+};
+use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockInfo};
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_types::transaction::{AbortContext, BlockTransactionInfo, Transaction};
+use starcoin_types::write_set::WriteSet;
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+
+/// The column families of the chain db, see `starcoin_storage::VEC_PREFIX_NAME`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DbSchema {
+    BlockAccumulatorNode,
+    TransactionAccumulatorNode,
+    Block,
+    BlockHeader,
+    BlockBody,
+    BlockInfo,
+    BlockTransactions,
+    BlockTransactionInfos,
+    StateNode,
+    ChainInfo,
+    Transaction,
+    TransactionInfo,
+    TransactionInfoHash,
+    TransactionInfoExt,
+    WriteSet,
+    ContractEvent,
+    FailedBlock,
+    ErrorMap,
+}
+
+/// Which records of a schema belong to a block, used to export the records of a block range.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BlockKeyType {
+    /// Keyed by the block id.
+    BlockId,
+    /// Keyed by the ids of the txns in the block.
+    TransactionId,
+    /// Keyed by the ids of the txn infos in the block.
+    TransactionInfoId,
+}
+
+fn decode<K, V>(key: &[u8], value: &[u8]) -> Result<(Value, Value)>
+where
+    K: KeyCodec + Serialize,
+    V: ValueCodec + Serialize,
+{
+    Ok((
+        serde_json::to_value(K::decode_key(key)?)?,
+        serde_json::to_value(V::decode_value(value)?)?,
+    ))
+}
+
+impl DbSchema {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DbSchema::BlockAccumulatorNode => BLOCK_ACCUMULATOR_NODE_PREFIX_NAME,
+            DbSchema::TransactionAccumulatorNode => TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME,
+            DbSchema::Block => BLOCK_PREFIX_NAME,
+            DbSchema::BlockHeader => BLOCK_HEADER_PREFIX_NAME,
+            DbSchema::BlockBody => BLOCK_BODY_PREFIX_NAME,
+            DbSchema::BlockInfo => BLOCK_INFO_PREFIX_NAME,
+            DbSchema::BlockTransactions => BLOCK_TRANSACTIONS_PREFIX_NAME,
+            DbSchema::BlockTransactionInfos => BLOCK_TRANSACTION_INFOS_PREFIX_NAME,
+            DbSchema::StateNode => STATE_NODE_PREFIX_NAME,
+            DbSchema::ChainInfo => CHAIN_INFO_PREFIX_NAME,
+            DbSchema::Transaction => TRANSACTION_PREFIX_NAME,
+            DbSchema::TransactionInfo => TRANSACTION_INFO_PREFIX_NAME,
+            DbSchema::TransactionInfoHash => TRANSACTION_INFO_HASH_PREFIX_NAME,
+            DbSchema::TransactionInfoExt => TRANSACTION_INFO_EXT_PREFIX_NAME,
+            DbSchema::WriteSet => WRITE_SET_PREFIX_NAME,
+            DbSchema::ContractEvent => CONTRACT_EVENT_PREFIX_NAME,
+            DbSchema::FailedBlock => FAILED_BLOCK_PREFIX_NAME,
+            DbSchema::ErrorMap => ERROR_MAP_PREFIX_NAME,
+        }
+    }
+
+    /// Decode the raw key and value to json by the codecs of the schema.
+    pub fn decode(&self, key: &[u8], value: &[u8]) -> Result<(Value, Value)> {
+        match self {
+            DbSchema::BlockAccumulatorNode | DbSchema::TransactionAccumulatorNode => {
+                decode::<HashValue, AccumulatorNode>(key, value)
+            }
+            DbSchema::Block => decode::<HashValue, Block>(key, value),
+            DbSchema::BlockHeader => decode::<HashValue, BlockHeader>(key, value),
+            DbSchema::BlockBody => decode::<HashValue, BlockBody>(key, value),
+            DbSchema::BlockInfo => decode::<HashValue, BlockInfo>(key, value),
+            DbSchema::BlockTransactions
+            | DbSchema::BlockTransactionInfos
+            | DbSchema::TransactionInfoHash => decode::<HashValue, Vec<HashValue>>(key, value),
+            // The state node is the bcs bytes of the jellyfish merkle node, export it as hex.
+            DbSchema::StateNode => Ok((
+                serde_json::to_value(HashValue::decode_key(key)?)?,
+                Value::String(format!("0x{}", hex::encode(value))),
+            )),
+            DbSchema::ChainInfo => Ok((
+                Value::String(String::from_utf8_lossy(key).to_string()),
+                Value::String(format!("0x{}", hex::encode(value))),
+            )),
+            DbSchema::Transaction => decode::<HashValue, Transaction>(key, value),
+            DbSchema::TransactionInfo => decode::<HashValue, BlockTransactionInfo>(key, value),
+            DbSchema::TransactionInfoExt => decode::<HashValue, AbortContext>(key, value),
+            DbSchema::WriteSet => decode::<HashValue, WriteSet>(key, value),
+            DbSchema::ContractEvent => decode::<HashValue, Vec<ContractEvent>>(key, value),
+            DbSchema::FailedBlock => decode::<HashValue, FailedBlock>(key, value),
+            DbSchema::ErrorMap => decode::<ModuleId, ModuleErrorMap>(key, value),
+        }
+    }
+
+    /// The key type of the schema if its records belong to a block, `None` if not.
+    pub fn block_key_type(&self) -> Option<BlockKeyType> {
+        match self {
+            DbSchema::Block
+            | DbSchema::BlockHeader
+            | DbSchema::BlockBody
+            | DbSchema::BlockInfo
+            | DbSchema::BlockTransactions
+            | DbSchema::BlockTransactionInfos => Some(BlockKeyType::BlockId),
+            DbSchema::Transaction => Some(BlockKeyType::TransactionId),
+            DbSchema::TransactionInfo
+            | DbSchema::TransactionInfoExt
+            | DbSchema::WriteSet
+            | DbSchema::ContractEvent => Some(BlockKeyType::TransactionInfoId),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DbSchema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for DbSchema {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let schema = match s {
+            BLOCK_ACCUMULATOR_NODE_PREFIX_NAME => DbSchema::BlockAccumulatorNode,
+            TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME => DbSchema::TransactionAccumulatorNode,
+            BLOCK_PREFIX_NAME => DbSchema::Block,
+            BLOCK_HEADER_PREFIX_NAME => DbSchema::BlockHeader,
+            BLOCK_BODY_PREFIX_NAME => DbSchema::BlockBody,
+            BLOCK_INFO_PREFIX_NAME => DbSchema::BlockInfo,
+            BLOCK_TRANSACTIONS_PREFIX_NAME => DbSchema::BlockTransactions,
+            BLOCK_TRANSACTION_INFOS_PREFIX_NAME => DbSchema::BlockTransactionInfos,
+            STATE_NODE_PREFIX_NAME => DbSchema::StateNode,
+            CHAIN_INFO_PREFIX_NAME => DbSchema::ChainInfo,
+            TRANSACTION_PREFIX_NAME => DbSchema::Transaction,
+            TRANSACTION_INFO_PREFIX_NAME => DbSchema::TransactionInfo,
+            TRANSACTION_INFO_HASH_PREFIX_NAME => DbSchema::TransactionInfoHash,
+            TRANSACTION_INFO_EXT_PREFIX_NAME => DbSchema::TransactionInfoExt,
+            WRITE_SET_PREFIX_NAME => DbSchema::WriteSet,
+            CONTRACT_EVENT_PREFIX_NAME => DbSchema::ContractEvent,
+            FAILED_BLOCK_PREFIX_NAME => DbSchema::FailedBlock,
+            ERROR_MAP_PREFIX_NAME => DbSchema::ErrorMap,
+            _ => {
+                bail!("Unsupported schema: {}", s)
+            }
+        };
+        Ok(schema)
+    }
+}
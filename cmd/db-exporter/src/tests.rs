@@ -0,0 +1,200 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::DbSchema;
+use crate::{export, import, open_storage, ExportFormat, RecordWriter};
+use anyhow::{format_err, Result};
+use serde_json::Value;
+use starcoin_account_api::AccountInfo;
+use starcoin_chain::ChainReader;
+use starcoin_chain_mock::MockChain;
+use starcoin_config::{temp_path, BuiltinNetworkID, ChainNetwork};
+use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
+use starcoin_storage::{BlockStore, BlockTransactionInfoStore, ContractEventStore};
+use starcoin_types::startup_info::StartupInfo;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Init a node db in `data_dir` with `blocks` blocks after the genesis, return the block ids
+/// ordered by number.
+fn init_chain(net: &ChainNetwork, data_dir: &Path, blocks: u64) -> Result<Vec<HashValue>> {
+    let storage = open_storage(data_dir)?;
+    let (chain_info, _) = Genesis::init_and_check_storage(net, storage.clone(), data_dir)?;
+    let mut mock_chain = MockChain::new_with_storage(
+        net.clone(),
+        storage.clone(),
+        chain_info.head().id(),
+        AccountInfo::random(),
+    )?;
+    mock_chain.produce_and_apply_times(blocks)?;
+    let head_id = mock_chain.head().current_header().id();
+    storage.save_startup_info(StartupInfo::new(head_id))?;
+    (0..=blocks)
+        .map(|number| {
+            mock_chain
+                .head()
+                .get_hash_by_number(number)?
+                .ok_or_else(|| format_err!("Block {} not found.", number))
+        })
+        .collect()
+}
+
+fn db_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("starcoindb/db/starcoindb")
+}
+
+fn export_to_file(
+    data_dir: &Path,
+    schema: DbSchema,
+    range: Option<(u64, Option<u64>)>,
+    format: ExportFormat,
+    output: &Path,
+) -> Result<()> {
+    export(
+        db_path(data_dir).as_path(),
+        schema,
+        range,
+        RecordWriter::new(format, File::create(output)?),
+    )
+}
+
+/// The keys of the records exported in jsonl.
+fn read_jsonl_keys(input: &Path) -> Result<Vec<Value>> {
+    BufReader::new(File::open(input)?)
+        .lines()
+        .map(|line| {
+            let mut record: Value = serde_json::from_str(line?.as_str())?;
+            Ok(record["key"].take())
+        })
+        .collect()
+}
+
+fn get_head_id(data_dir: &Path) -> Result<Option<HashValue>> {
+    Ok(open_storage(data_dir)?
+        .get_startup_info()?
+        .map(|startup_info| startup_info.main))
+}
+
+#[stest::test(timeout = 120)]
+fn test_export_and_import_blocks() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let tmpdir = temp_path();
+    let source_dir = tmpdir.path().join("source");
+    let head_id = *init_chain(&net, source_dir.as_path(), 3)?
+        .last()
+        .expect("block ids must not be empty");
+
+    for &(format, file_name) in &[
+        (ExportFormat::Bcs, "blocks.bcs"),
+        (ExportFormat::JsonLines, "blocks.jsonl"),
+    ] {
+        let output = tmpdir.path().join(file_name);
+        export_to_file(
+            source_dir.as_path(),
+            DbSchema::Block,
+            Some((0, None)),
+            format,
+            output.as_path(),
+        )?;
+        let target_dir = tmpdir.path().join(format!("target_{}", file_name));
+        // the genesis is skipped.
+        assert_eq!(
+            import(output.as_path(), format, target_dir.as_path(), &net)?,
+            3
+        );
+        assert_eq!(get_head_id(target_dir.as_path())?, Some(head_id));
+        // import again, all the blocks are skipped.
+        assert_eq!(
+            import(output.as_path(), format, target_dir.as_path(), &net)?,
+            0
+        );
+        assert_eq!(get_head_id(target_dir.as_path())?, Some(head_id));
+    }
+
+    // the import continues from the imported blocks even if the saved head is behind them.
+    let partial_output = tmpdir.path().join("partial_blocks.bcs");
+    export_to_file(
+        source_dir.as_path(),
+        DbSchema::Block,
+        Some((0, Some(2))),
+        ExportFormat::Bcs,
+        partial_output.as_path(),
+    )?;
+    let target_dir = tmpdir.path().join("target_partial");
+    assert_eq!(
+        import(
+            partial_output.as_path(),
+            ExportFormat::Bcs,
+            target_dir.as_path(),
+            &net
+        )?,
+        2
+    );
+    {
+        let storage = open_storage(target_dir.as_path())?;
+        let genesis_id = storage
+            .get_genesis()?
+            .expect("genesis id must exist after import");
+        storage.save_startup_info(StartupInfo::new(genesis_id))?;
+    }
+    let output = tmpdir.path().join("blocks.bcs");
+    assert_eq!(
+        import(
+            output.as_path(),
+            ExportFormat::Bcs,
+            target_dir.as_path(),
+            &net
+        )?,
+        1
+    );
+    assert_eq!(get_head_id(target_dir.as_path())?, Some(head_id));
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_export_block_range() -> Result<()> {
+    let net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let tmpdir = temp_path();
+    let source_dir = tmpdir.path().join("source");
+    let block_ids = init_chain(&net, source_dir.as_path(), 3)?;
+
+    let (txn_info_ids, event_keys) = {
+        let storage = open_storage(source_dir.as_path())?;
+        let mut txn_info_ids = vec![];
+        let mut event_keys = vec![];
+        for block_id in &block_ids[1..=2] {
+            for txn_info_id in storage.get_block_txn_info_ids(*block_id)? {
+                assert!(storage.get_transaction_info(txn_info_id)?.is_some());
+                if storage.get_contract_events(txn_info_id)?.is_some() {
+                    event_keys.push(serde_json::to_value(txn_info_id)?);
+                }
+                txn_info_ids.push(serde_json::to_value(txn_info_id)?);
+            }
+        }
+        (txn_info_ids, event_keys)
+    };
+    assert!(!event_keys.is_empty());
+
+    let output = tmpdir.path().join("transaction_info.jsonl");
+    export_to_file(
+        source_dir.as_path(),
+        DbSchema::TransactionInfo,
+        Some((1, Some(2))),
+        ExportFormat::JsonLines,
+        output.as_path(),
+    )?;
+    assert_eq!(read_jsonl_keys(output.as_path())?, txn_info_ids);
+
+    let output = tmpdir.path().join("contract_event.jsonl");
+    export_to_file(
+        source_dir.as_path(),
+        DbSchema::ContractEvent,
+        Some((1, Some(2))),
+        ExportFormat::JsonLines,
+        output.as_path(),
+    )?;
+    assert_eq!(read_jsonl_keys(output.as_path())?, event_keys);
+    Ok(())
+}
@@ -83,7 +83,7 @@ impl Solver for CpuSolver {
                                 break;
                             }
                             match strategy {
-                                ConsensusStrategy::Dummy => {
+                                ConsensusStrategy::Dummy | ConsensusStrategy::Custom => {
                                     let nonce = strategy.solve_consensus_nonce(
                                         &minting_blob,
                                         diff,
@@ -0,0 +1,150 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use serde::Serialize;
+use starcoin_account::account_storage::AccountStorage;
+use starcoin_config::RocksdbConfig;
+use starcoin_storage::backup::{self, BackupManifest, BACKUP_MANIFEST_FILE};
+use starcoin_storage::db_check::{CheckOption, DBChecker};
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{Storage, VEC_PREFIX_NAME};
+use std::path::PathBuf;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+#[derive(Debug, Clone, StructOpt)]
+#[structopt(name = "starcoin-db", about = "starcoin database tools")]
+pub enum DbOpt {
+    /// Backup the databases of a stopped node, use `starcoin node manager backup` for a running node.
+    #[structopt(name = "backup")]
+    Backup {
+        #[structopt(long, short = "i", parse(from_os_str))]
+        /// starcoin node chain db path. like ~/.starcoin/barnard/starcoindb/db/starcoindb
+        db_path: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        /// starcoin node account vault path. like ~/.starcoin/barnard/account_vaults
+        vault_dir: Option<PathBuf>,
+        #[structopt(long, short = "o", parse(from_os_str))]
+        /// the backup dir, should be empty or not exist.
+        backup_dir: PathBuf,
+    },
+    /// Restore a backup to the databases of a new node, the node checks the restored chain at startup.
+    #[structopt(name = "restore")]
+    Restore {
+        #[structopt(long, short = "i", parse(from_os_str))]
+        /// the backup dir.
+        backup_dir: PathBuf,
+        #[structopt(long, short = "o", parse(from_os_str))]
+        /// target chain db path, should be empty or not exist. like ~/.starcoin/barnard/starcoindb/db/starcoindb
+        db_path: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        /// target account vault path, the account db is not restored if not set.
+        vault_dir: Option<PathBuf>,
+    },
+    /// Verify the checksums of the files in a backup.
+    #[structopt(name = "verify-backup")]
+    VerifyBackup {
+        #[structopt(long, short = "i", parse(from_os_str))]
+        /// the backup dir.
+        backup_dir: PathBuf,
+    },
+    /// Check the integrity of the chain db, and print a report.
+    /// The db is opened as read only, so it can check the db of a running node if not repair.
+    #[structopt(name = "check", alias = "db-check")]
+    Check {
+        #[structopt(long, short = "i", parse(from_os_str))]
+        /// starcoin node chain db path. like ~/.starcoin/barnard/starcoindb/db/starcoindb
+        db_path: PathBuf,
+        #[structopt(long)]
+        /// traverse the state trees of all the chain heads, it may take a long time.
+        check_state: bool,
+        #[structopt(long)]
+        /// rebuild the broken indexes from blocks, the node should be stopped.
+        repair: bool,
+    },
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn run(opt: DbOpt) -> Result<()> {
+    match opt {
+        DbOpt::Backup {
+            db_path,
+            vault_dir,
+            backup_dir,
+        } => {
+            let chain_db = DBStorage::open_with_cfs(
+                db_path,
+                VEC_PREFIX_NAME.to_vec(),
+                false,
+                RocksdbConfig::default(),
+            )?;
+            let account_db = vault_dir
+                .map(|vault_dir| {
+                    AccountStorage::create_from_path(vault_dir, RocksdbConfig::default())?
+                        .db()
+                        .ok_or_else(|| format_err!("Account storage should have a db."))
+                })
+                .transpose()?;
+            print_json(&backup::backup(
+                &chain_db,
+                account_db.as_deref(),
+                backup_dir.as_path(),
+            )?)
+        }
+        DbOpt::Restore {
+            backup_dir,
+            db_path,
+            vault_dir,
+        } => print_json(&backup::restore(
+            backup_dir.as_path(),
+            db_path.as_path(),
+            vault_dir.as_deref(),
+        )?),
+        DbOpt::VerifyBackup { backup_dir } => {
+            let manifest = BackupManifest::load(backup_dir.join(BACKUP_MANIFEST_FILE).as_path())?;
+            manifest.verify(backup_dir.as_path())?;
+            print_json(&manifest)
+        }
+        DbOpt::Check {
+            db_path,
+            check_state,
+            repair,
+        } => {
+            let db = Arc::new(DBStorage::open_with_cfs(
+                db_path,
+                VEC_PREFIX_NAME.to_vec(),
+                !repair,
+                RocksdbConfig::default(),
+            )?);
+            let storage = Storage::new(StorageInstance::DB { db: db.clone() })?;
+            let report = DBChecker::new(
+                db.as_ref(),
+                &storage,
+                CheckOption {
+                    check_state,
+                    repair,
+                },
+            )
+            .check()?;
+            print_json(&report)?;
+            ensure!(
+                report.is_ok(),
+                "Found {} issues in db.",
+                report.issues.iter().filter(|issue| !issue.repaired).count()
+            );
+            Ok(())
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let _logger = starcoin_logger::init();
+    let opt = DbOpt::from_args();
+    run(opt)
+}
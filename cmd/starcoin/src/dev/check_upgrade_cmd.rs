@@ -0,0 +1,337 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::dev::dev_helper;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
+use starcoin_crypto::hash::PlainCryptoHash;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_client::RemoteStateReader;
+use starcoin_state_api::StateReaderExt;
+use starcoin_types::block::BlockNumber;
+use starcoin_vm_types::access::ModuleAccess;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_config::{
+    ModuleUpgradeStrategy, TwoPhaseUpgradeV2Resource, STRATEGY_ARBITRARY, STRATEGY_FREEZE,
+    STRATEGY_NEW_MODULE, STRATEGY_TWO_PHASE,
+};
+use starcoin_vm_types::compatibility::Compatibility;
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::normalized;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use stdlib::{check_modules_upgrade_compat, ModuleUpgradeCompat};
+use structopt::StructOpt;
+
+/// Check whether a package can be deployed as an upgrade of the modules on chain
+#[derive(Debug, StructOpt)]
+#[structopt(name = "check-upgrade", alias = "check_upgrade")]
+pub struct CheckUpgradeOpt {
+    #[structopt(short = "a", long = "address")]
+    /// address of the deployed modules, default is the package address
+    address: Option<AccountAddress>,
+
+    #[structopt(short = "b", long = "block-number")]
+    /// check against the state of the block, default is the latest block
+    block_number: Option<BlockNumber>,
+
+    #[structopt(name = "mv-or-package-file")]
+    /// move bytecode file path or package binary path
+    mv_or_package_file: PathBuf,
+}
+
+pub struct CheckUpgradeCommand;
+
+impl CommandAction for CheckUpgradeCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = CheckUpgradeOpt;
+    type ReturnItem = CheckUpgradeView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let package = dev_helper::load_package_from_file(opt.mv_or_package_file.as_path())?;
+        let package_hash = package.crypto_hash();
+        let address = opt.address.unwrap_or_else(|| package.package_address());
+
+        let header = match opt.block_number {
+            Some(number) => {
+                client
+                    .chain_get_block_by_number(number, None)?
+                    .ok_or_else(|| format_err!("Can not find block by number: {}", number))?
+                    .header
+            }
+            None => client.chain_info()?.head,
+        };
+        let state_reader = RemoteStateReader::new_with_root(client, header.state_root);
+
+        let strategy = state_reader
+            .get_resource::<ModuleUpgradeStrategy>(address)?
+            .map(|strategy| strategy.strategy())
+            .unwrap_or(STRATEGY_ARBITRARY);
+        let upgrade_plan = state_reader
+            .get_resource::<TwoPhaseUpgradeV2Resource>(address)?
+            .and_then(|resource| {
+                resource.plan().map(|plan| UpgradePlanView {
+                    package_hash: hex::encode(plan.package_hash()),
+                    active_after_time: plan.active_after_time(),
+                    version: plan.version(),
+                    enforced: plan.enforced(),
+                    match_package: plan.package_hash() == package_hash.to_vec().as_slice(),
+                })
+            });
+
+        let new_modules = package
+            .modules()
+            .iter()
+            .map(|module| CompiledModule::deserialize(module.code()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut pre_modules = vec![];
+        for module in &new_modules {
+            if let Some(pre_code) = state_reader.get_code(module.self_id())? {
+                pre_modules.push(CompiledModule::deserialize(pre_code.as_slice())?);
+            }
+        }
+        let (modules, problems) = check_package(
+            address,
+            package_hash,
+            strategy,
+            upgrade_plan.as_ref(),
+            header.timestamp.0,
+            &pre_modules,
+            &new_modules,
+        );
+
+        Ok(CheckUpgradeView {
+            address,
+            package_hash,
+            block_number: header.number.0,
+            upgrade_strategy: strategy_name(strategy),
+            upgrade_plan,
+            modules,
+            upgradable: problems.is_empty(),
+            problems,
+        })
+    }
+}
+
+/// Check whether the `new_modules` of the package can be deployed at `address` over the deployed
+/// `pre_modules`, under the upgrade `strategy` and `upgrade_plan` of the address at a block with
+/// `block_timestamp`. Return the report of every module and the problems which block the upgrade.
+pub(crate) fn check_package(
+    address: AccountAddress,
+    package_hash: HashValue,
+    strategy: u8,
+    upgrade_plan: Option<&UpgradePlanView>,
+    block_timestamp: u64,
+    pre_modules: &[CompiledModule],
+    new_modules: &[CompiledModule],
+) -> (Vec<ModuleCheckView>, Vec<String>) {
+    let mut problems = vec![];
+    match strategy {
+        STRATEGY_FREEZE => {
+            problems.push(format!("Modules under {} are frozen", address));
+        }
+        STRATEGY_TWO_PHASE => match upgrade_plan {
+            None => problems.push("No upgrade plan submitted for the package".to_string()),
+            Some(plan) => {
+                if !plan.match_package {
+                    problems.push(format!(
+                        "Upgrade plan is for package {}, not for this package {}",
+                        plan.package_hash, package_hash
+                    ));
+                }
+                if plan.active_after_time > block_timestamp {
+                    problems.push(format!(
+                        "Upgrade plan is not active until {}, block time is {}",
+                        plan.active_after_time, block_timestamp
+                    ));
+                }
+            }
+        },
+        _ => {}
+    }
+    // the incompatible modules are only allowed by an enforced plan of this package.
+    let enforced = upgrade_plan
+        .map(|plan| plan.enforced && plan.match_package)
+        .unwrap_or(false);
+
+    let pre_modules_by_id = pre_modules
+        .iter()
+        .map(|module| (module.self_id(), module))
+        .collect::<BTreeMap<_, _>>();
+    let mut modules = vec![];
+    for (new_module, (module_id, compat)) in new_modules
+        .iter()
+        .zip(check_modules_upgrade_compat(pre_modules, new_modules))
+    {
+        let view = match (compat, pre_modules_by_id.get(&module_id)) {
+            (ModuleUpgradeCompat::Changed(compat), Some(pre_module)) => {
+                ModuleCheckView::changed(module_id.to_string(), &compat, pre_module, new_module)
+            }
+            (ModuleUpgradeCompat::Unchanged, _) => {
+                ModuleCheckView::unchanged(module_id.to_string())
+            }
+            _ => ModuleCheckView::new_module(module_id.to_string()),
+        };
+        if view.status != ModuleStatus::New && strategy == STRATEGY_NEW_MODULE {
+            problems.push(format!(
+                "Module {} exists, only new modules are allowed",
+                view.module_id
+            ));
+        }
+        if view.status == ModuleStatus::Incompatible && !enforced {
+            problems.push(format!(
+                "Module {} is incompatible with the deployed one",
+                view.module_id
+            ));
+        }
+        modules.push(view);
+    }
+    (modules, problems)
+}
+
+fn strategy_name(strategy: u8) -> String {
+    match strategy {
+        STRATEGY_ARBITRARY => "Arbitrary".to_string(),
+        STRATEGY_TWO_PHASE => "TwoPhase".to_string(),
+        STRATEGY_NEW_MODULE => "NewModule".to_string(),
+        STRATEGY_FREEZE => "Freeze".to_string(),
+        _ => format!("Unknown({})", strategy),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckUpgradeView {
+    pub address: AccountAddress,
+    pub package_hash: HashValue,
+    pub block_number: BlockNumber,
+    pub upgrade_strategy: String,
+    pub upgrade_plan: Option<UpgradePlanView>,
+    pub modules: Vec<ModuleCheckView>,
+    /// Whether the package can be deployed at the block.
+    pub upgradable: bool,
+    /// The reasons why the package can not be deployed.
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpgradePlanView {
+    pub package_hash: String,
+    pub active_after_time: u64,
+    pub version: u64,
+    pub enforced: bool,
+    /// Whether the plan is submitted for the checked package.
+    pub match_package: bool,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum ModuleStatus {
+    New,
+    Unchanged,
+    Compatible,
+    Incompatible,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleCheckView {
+    pub module_id: String,
+    pub status: ModuleStatus,
+    pub struct_layout_compatible: bool,
+    pub linking_compatible: bool,
+    pub struct_changes: Vec<String>,
+    pub function_changes: Vec<String>,
+}
+
+impl ModuleCheckView {
+    fn new_module(module_id: String) -> Self {
+        Self::with_status(module_id, ModuleStatus::New)
+    }
+
+    fn unchanged(module_id: String) -> Self {
+        Self::with_status(module_id, ModuleStatus::Unchanged)
+    }
+
+    fn with_status(module_id: String, status: ModuleStatus) -> Self {
+        Self {
+            module_id,
+            status,
+            struct_layout_compatible: true,
+            linking_compatible: true,
+            struct_changes: vec![],
+            function_changes: vec![],
+        }
+    }
+
+    /// The report of a changed module, `compat` is checked by `check_modules_upgrade_compat`,
+    /// the changes of the structs and public functions are listed to explain it.
+    fn changed(
+        module_id: String,
+        compat: &Compatibility,
+        pre: &CompiledModule,
+        new: &CompiledModule,
+    ) -> Self {
+        let old = normalized::Module::new(pre);
+        let new = normalized::Module::new(new);
+
+        let mut struct_changes = vec![];
+        for (name, old_struct) in &old.structs {
+            match new.structs.get(name) {
+                None => struct_changes.push(format!("struct {} removed", name)),
+                Some(new_struct) if new_struct.abilities != old_struct.abilities => struct_changes
+                    .push(format!(
+                        "struct {} abilities changed: {:?} -> {:?}",
+                        name, old_struct.abilities, new_struct.abilities
+                    )),
+                Some(new_struct)
+                    if new_struct.type_parameters != old_struct.type_parameters
+                        || new_struct.fields != old_struct.fields =>
+                {
+                    struct_changes.push(format!("struct {} layout changed", name))
+                }
+                _ => {}
+            }
+        }
+        for name in new.structs.keys() {
+            if !old.structs.contains_key(name) {
+                struct_changes.push(format!("struct {} added", name));
+            }
+        }
+
+        let mut function_changes = vec![];
+        for (name, old_func) in &old.exposed_functions {
+            match new.exposed_functions.get(name) {
+                None => function_changes.push(format!("public function {} removed", name)),
+                Some(new_func) if new_func != old_func => {
+                    function_changes.push(format!("public function {} signature changed", name))
+                }
+                _ => {}
+            }
+        }
+        for name in new.exposed_functions.keys() {
+            if !old.exposed_functions.contains_key(name) {
+                function_changes.push(format!("public function {} added", name));
+            }
+        }
+
+        Self {
+            module_id,
+            status: if compat.is_fully_compatible() {
+                ModuleStatus::Compatible
+            } else {
+                ModuleStatus::Incompatible
+            },
+            struct_layout_compatible: compat.struct_layout,
+            linking_compatible: compat.struct_and_function_linking,
+            struct_changes,
+            function_changes,
+        }
+    }
+}
@@ -6,14 +6,18 @@ use crate::StarcoinOpt;
 use anyhow::{bail, ensure, format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_config::temp_path;
-use starcoin_move_compiler::shared::Flags;
+use starcoin_move_compiler::error_map::{build_error_map, ERROR_MAP_EXTENSION};
+use starcoin_move_compiler::shared::{AddressBytes, Flags};
 use starcoin_move_compiler::{
-    compile_source_string_no_report, errors, move_compile, MOVE_COMPILED_EXTENSION, MOVE_EXTENSION,
+    compile_source_string_no_report, errors, move_compile, process_source_tpl_file,
+    MOVE_COMPILED_EXTENSION, MOVE_EXTENSION,
 };
 use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::errmap::ErrorMapping;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use stdlib::restore_stdlib_in_dir;
 use structopt::StructOpt;
 
@@ -158,6 +162,39 @@ impl CommandAction for CompileCommand {
                 .map_err(|e| format_err!("write file({:?} error: {:?})", file_path, e))?;
             results.push(file_path.to_string_lossy().to_string());
         }
+
+        // the error maps are used to explain the abort codes of the modules after deployed.
+        let error_map_targets = if source_file_or_dir.is_file() {
+            vec![process_source_tpl_file(
+                temp_path.path(),
+                source_file_or_dir,
+                AddressBytes::new(sender.into()),
+            )?
+            .to_string_lossy()
+            .to_string()]
+        } else {
+            vec![source_file_or_dir.to_string_lossy().to_string()]
+        };
+        save_error_maps(&error_map_targets, &deps, out_dir.as_path())
+            .map_err(|e| format_err!("build error map error: {:?}", e))?;
         Ok(results)
     }
 }
+
+/// Save the error map of each module to `<module name>.errmap` in `out_dir`.
+fn save_error_maps(targets: &[String], deps: &[String], out_dir: &Path) -> Result<()> {
+    let error_map = build_error_map(targets, deps)?;
+    for (module_id, module_error_map) in error_map.module_error_maps {
+        let mut file_path = out_dir.join(module_id.name().as_str());
+        file_path.set_extension(ERROR_MAP_EXTENSION);
+        let mut module_error_maps = BTreeMap::new();
+        module_error_maps.insert(module_id, module_error_map);
+        let module_error_map = ErrorMapping {
+            error_categories: error_map.error_categories.clone(),
+            module_error_maps,
+        };
+        std::fs::write(file_path.as_path(), bcs_ext::to_bytes(&module_error_map)?)
+            .map_err(|e| format_err!("write file({:?}) error: {:?}", file_path, e))?;
+    }
+    Ok(())
+}
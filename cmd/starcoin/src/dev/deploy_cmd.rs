@@ -7,6 +7,9 @@ use crate::view::{ExecuteResultView, TransactionOptions};
 use crate::StarcoinOpt;
 use anyhow::{ensure, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_move_compiler::error_map::ERROR_MAP_EXTENSION;
+use starcoin_rpc_api::types::TransactionStatusView;
+use starcoin_vm_types::errmap::ErrorMapping;
 use starcoin_vm_types::transaction::TransactionPayload;
 use std::path::PathBuf;
 use structopt::StructOpt;
@@ -54,7 +57,34 @@ impl CommandAction for DeployCommand {
             }
         };
 
-        ctx.state()
-            .build_and_execute_transaction(transaction_opts, TransactionPayload::Package(package))
+        let result = ctx.state().build_and_execute_transaction(
+            transaction_opts,
+            TransactionPayload::Package(package),
+        )?;
+
+        // register the error map of the package after the modules are deployed, so the node can
+        // explain the abort codes of them, it requires the node_manager api of the node.
+        // The error map is not published with the package, only the connected node saves it.
+        let error_map_file = opt.mv_or_package_file.with_extension(ERROR_MAP_EXTENSION);
+        let executed = result
+            .get_transaction_info()
+            .map(|txn_info| txn_info.status == TransactionStatusView::Executed)
+            .unwrap_or(false);
+        if executed && error_map_file.is_file() {
+            let error_map: ErrorMapping =
+                bcs_ext::from_bytes(&std::fs::read(error_map_file.as_path())?)?;
+            match ctx.state().client().node_register_error_map(&error_map) {
+                Ok(module_ids) => eprintln!(
+                    "Registered error map of modules to the connected node only: {}",
+                    module_ids
+                        .iter()
+                        .map(|module_id| module_id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Err(e) => eprintln!("Register error map {:?} failed: {}", error_map_file, e),
+            }
+        }
+        Ok(result)
     }
 }
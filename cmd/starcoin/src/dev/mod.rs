@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub use call_contract_cmd::*;
+pub use check_upgrade_cmd::*;
 pub use compile_cmd::*;
 pub use deploy_cmd::*;
 pub use get_coin_cmd::*;
 pub use package_cmd::*;
+pub use prove_cmd::*;
 pub use subscribe_cmd::*;
 pub use upgrade_module_exe_cmd::*;
 pub use upgrade_module_plan_cmd::*;
@@ -15,6 +17,7 @@ pub use upgrade_vm_config_proposal_cmd::*;
 
 pub(crate) mod call_api_cmd;
 mod call_contract_cmd;
+mod check_upgrade_cmd;
 mod compile_cmd;
 mod deploy_cmd;
 pub(crate) mod dev_helper;
@@ -24,6 +27,7 @@ pub(crate) mod log_cmd;
 pub(crate) mod move_explain;
 mod package_cmd;
 pub(crate) mod panic_cmd;
+mod prove_cmd;
 pub(crate) mod resolve_cmd;
 pub(crate) mod sign_txn_helper;
 pub(crate) mod sleep_cmd;
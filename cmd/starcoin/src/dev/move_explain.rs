@@ -5,11 +5,16 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_client::RpcClient;
 use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::errmap::ErrorDescription;
 use starcoin_vm_types::vm_status::AbortLocation;
 use starcoin_vm_types::{identifier::Identifier, language_storage::ModuleId};
+use std::collections::BTreeMap;
 use structopt::StructOpt;
-use vm_status_translator::{explain_move_abort, MoveAbortExplain};
+use vm_status_translator::{
+    explain_move_abort, explain_move_abort_with_resolver, ErrorMapResolver, MoveAbortExplain,
+};
 
 ///Explain Move abort codes. Errors are defined as
 ///a global category + module-specific reason for the error.
@@ -56,7 +61,14 @@ impl CommandAction for MoveExplain {
                     Identifier::new(module_name)?,
                 );
 
-                let explain = explain_move_abort(AbortLocation::Module(module_id), opt.abort_code);
+                let resolver = RemoteErrorMapResolver {
+                    client: ctx.state().client(),
+                };
+                let explain = explain_move_abort_with_resolver(
+                    AbortLocation::Module(module_id),
+                    opt.abort_code,
+                    &resolver,
+                );
 
                 Ok(explain)
             }
@@ -64,3 +76,23 @@ impl CommandAction for MoveExplain {
         }
     }
 }
+
+/// Fetch the error maps of the modules out of stdlib from the node.
+struct RemoteErrorMapResolver<'a> {
+    client: &'a RpcClient,
+}
+
+impl<'a> ErrorMapResolver for RemoteErrorMapResolver<'a> {
+    fn get_module_error_map(
+        &self,
+        module_id: &ModuleId,
+    ) -> Option<BTreeMap<u64, ErrorDescription>> {
+        match self.client.contract_get_error_map(module_id.clone()) {
+            Ok(error_map) => error_map,
+            Err(e) => {
+                eprintln!("Get error map of module {} failed: {}", module_id, e);
+                None
+            }
+        }
+    }
+}
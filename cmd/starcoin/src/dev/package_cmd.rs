@@ -8,8 +8,11 @@ use scmd::{CommandAction, ExecContext};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_crypto::HashValue;
+use starcoin_move_compiler::error_map::ERROR_MAP_EXTENSION;
+use starcoin_move_compiler::MOVE_COMPILED_EXTENSION;
 use starcoin_rpc_api::types::FunctionIdView;
 use starcoin_types::transaction::{parse_transaction_argument, TransactionArgument};
+use starcoin_vm_types::errmap::ErrorMapping;
 use starcoin_vm_types::transaction::ScriptFunction;
 use starcoin_vm_types::transaction::{Module, Package};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
@@ -83,12 +86,27 @@ impl CommandAction for PackageCmd {
             "file {:?} not exist",
             mv_file_or_dir
         );
-        let modules = if mv_file_or_dir.is_file() {
-            vec![read_module(mv_file_or_dir)?]
+        let (modules, error_map_files) = if mv_file_or_dir.is_file() {
+            let error_map_file = mv_file_or_dir.with_extension(ERROR_MAP_EXTENSION);
+            let error_map_files = if error_map_file.is_file() {
+                vec![error_map_file]
+            } else {
+                vec![]
+            };
+            (vec![read_module(mv_file_or_dir)?], error_map_files)
         } else {
-            starcoin_move_compiler::utils::iterate_directory(mv_file_or_dir)
+            let files: Vec<PathBuf> =
+                starcoin_move_compiler::utils::iterate_directory(mv_file_or_dir).collect();
+            let modules = files
+                .iter()
+                .filter(|path| has_extension(path, MOVE_COMPILED_EXTENSION))
                 .map(|path| read_module(path.as_path()))
-                .collect::<Result<Vec<Module>>>()?
+                .collect::<Result<Vec<Module>>>()?;
+            let error_map_files = files
+                .into_iter()
+                .filter(|path| has_extension(path, ERROR_MAP_EXTENSION))
+                .collect();
+            (modules, error_map_files)
         };
 
         let init_script = match &opt.init_script {
@@ -127,14 +145,47 @@ impl CommandAction for PackageCmd {
         };
         file.write_all(&blob)
             .map_err(|e| format_err!("write package file {:?} error:{:?}", output_file, e))?;
+
+        // merge the error maps of the modules, and save it beside the package.
+        let error_map = if error_map_files.is_empty() {
+            None
+        } else {
+            let mut package_error_map = ErrorMapping::default();
+            for error_map_file in error_map_files {
+                let error_map: ErrorMapping = bcs_ext::from_bytes(&std::fs::read(
+                    error_map_file.as_path(),
+                )?)
+                .map_err(|e| format_err!("read error map {:?} error:{:?}", error_map_file, e))?;
+                package_error_map
+                    .error_categories
+                    .extend(error_map.error_categories);
+                package_error_map
+                    .module_error_maps
+                    .extend(error_map.module_error_maps);
+            }
+            let error_map_file = output_file.with_extension(ERROR_MAP_EXTENSION);
+            std::fs::write(
+                error_map_file.as_path(),
+                bcs_ext::to_bytes(&package_error_map)?,
+            )
+            .map_err(|e| format_err!("write error map {:?} error:{:?}", error_map_file, e))?;
+            Some(error_map_file.to_string_lossy().to_string())
+        };
         Ok(PackageResult {
             file: output_file.to_string_lossy().to_string(),
             package_hash,
             hex,
+            error_map,
         })
     }
 }
 
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy() == extension)
+        .unwrap_or(false)
+}
+
 fn read_module(module_file: &Path) -> Result<Module> {
     if !module_file.is_file() {
         bail!("{:?} is not a file", module_file);
@@ -150,4 +201,7 @@ pub struct PackageResult {
     pub package_hash: HashValue,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hex: Option<String>,
+    /// The error map file of the package modules, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_map: Option<String>,
 }
@@ -0,0 +1,128 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::temp_path;
+use starcoin_logger::prelude::LevelFilter;
+use starcoin_move_compiler::MOVE_COMPILED_EXTENSION;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_config::core_code_address;
+use std::path::PathBuf;
+use stdlib::prover::{prove, ProverConfig, ProverResult, PROVER_LOG_TARGETS};
+use stdlib::restore_stdlib_in_dir;
+use structopt::StructOpt;
+
+/// Run the Move prover on module sources, support source dir.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "prove")]
+pub struct ProveOpt {
+    #[structopt(
+        short = "d",
+        name = "dependency_path",
+        long = "dep",
+        help = "path of dependency used to build, support multi deps"
+    )]
+    deps: Option<Vec<String>>,
+
+    #[structopt(
+        long = "onchain-dep",
+        name = "onchain_dep_address",
+        help = "address whose modules deployed on chain are used as dependency, support multi addresses"
+    )]
+    onchain_deps: Option<Vec<AccountAddress>>,
+
+    #[structopt(long = "vc-timeout")]
+    /// timeout in seconds of each verification condition
+    vc_timeout: Option<usize>,
+
+    #[structopt(long = "verbose")]
+    /// print the progress of the prover
+    verbose: bool,
+
+    #[structopt(name = "source_file_or_dir", help = "source file path")]
+    source_file_or_dir: PathBuf,
+}
+
+pub struct ProveCommand;
+
+impl CommandAction for ProveCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ProveOpt;
+    type ReturnItem = ProverResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let source_file_or_dir = opt.source_file_or_dir.as_path();
+        ensure!(
+            source_file_or_dir.exists(),
+            "file {:?} not exist",
+            source_file_or_dir
+        );
+
+        let temp_path = temp_path();
+        let mut deps = restore_stdlib_in_dir(temp_path.path())?;
+        deps.append(&mut opt.deps.clone().unwrap_or_default());
+
+        // the modules deployed on chain are only available as bytecode, so the prover uses the
+        // interfaces generated from them.
+        let onchain_deps = opt.onchain_deps.clone().unwrap_or_default();
+        if !onchain_deps.is_empty() {
+            let bytecode_dir = temp_path.path().join("onchain");
+            std::fs::create_dir_all(bytecode_dir.as_path())?;
+            for address in onchain_deps {
+                ensure!(
+                    address != core_code_address(),
+                    "stdlib modules are always resolved from the local stdlib"
+                );
+                let codes = ctx
+                    .state()
+                    .client()
+                    .state_list_code(address, false, None)?
+                    .codes;
+                ensure!(!codes.is_empty(), "No module deployed at {}", address);
+                for (name, code) in codes {
+                    let mut file_path = bytecode_dir.join(format!("{}_{}", address, name));
+                    file_path.set_extension(MOVE_COMPILED_EXTENSION);
+                    std::fs::write(file_path.as_path(), code.code.0.as_slice())
+                        .map_err(|e| format_err!("write file({:?}) error: {:?}", file_path, e))?;
+                }
+            }
+            deps.extend(starcoin_move_compiler::generate_interface_files(
+                &[bytecode_dir.to_string_lossy().to_string()],
+                Some(temp_path.path().to_string_lossy().to_string()),
+                false,
+            )?);
+        }
+
+        // the prover logs into the cli logger, show its progress only if verbose.
+        let logger_handle = starcoin_logger::init();
+        let prover_log_level = if opt.verbose {
+            LevelFilter::Info
+        } else {
+            LevelFilter::Warn
+        };
+        for target in PROVER_LOG_TARGETS {
+            logger_handle.set_log_level(target.to_string(), prover_log_level);
+        }
+        let config = ProverConfig {
+            vc_timeout: opt.vc_timeout,
+            verbose: opt.verbose,
+        };
+        let result = prove(
+            &[source_file_or_dir.to_string_lossy().to_string()],
+            &deps,
+            &config,
+        )?;
+        if !result.rendered.is_empty() {
+            eprintln!("{}", result.rendered);
+        }
+        Ok(result)
+    }
+}
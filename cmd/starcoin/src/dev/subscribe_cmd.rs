@@ -120,6 +120,28 @@ impl CommandAction for SubscribeNewTxnCommand {
     }
 }
 
+#[derive(Debug, StructOpt)]
+#[structopt(name = "chain_reorg")]
+pub struct SubscribeChainReorgOpt {}
+pub struct SubscribeChainReorgCommand;
+impl CommandAction for SubscribeChainReorgCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeChainReorgOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let event_stream = ctx.state().client().subscribe_chain_reorg()?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
 fn blocking_display_notification<T, F>(
     mut event_stream: impl TryStream<Ok = T, Error = anyhow::Error> + Unpin,
     display: F,
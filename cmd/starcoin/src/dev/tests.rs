@@ -1,7 +1,12 @@
+use super::check_upgrade_cmd::{check_package, ModuleStatus, UpgradePlanView};
 use crate::CliState;
 use anyhow::{format_err, Result};
-use starcoin_config::NodeConfig;
+use starcoin_config::{temp_path, NodeConfig};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
+use starcoin_move_compiler::error_map::build_error_map;
+use starcoin_move_compiler::process_source_tpl_file;
+use starcoin_move_compiler::shared::AddressBytes;
 use starcoin_node::NodeHandle;
 use starcoin_rpc_api::types::{ContractCall, FunctionIdView, TransactionStatusView};
 use starcoin_rpc_client::{RemoteStateReader, RpcClient};
@@ -13,7 +18,10 @@ use starcoin_types::transaction::{
     parse_transaction_argument, ScriptFunction, TransactionArgument,
 };
 use starcoin_vm_types::account_address::AccountAddress;
-use starcoin_vm_types::account_config::core_code_address;
+use starcoin_vm_types::account_config::{
+    core_code_address, STRATEGY_ARBITRARY, STRATEGY_NEW_MODULE, STRATEGY_TWO_PHASE,
+};
+use starcoin_vm_types::file_format::CompiledModule;
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
@@ -30,6 +38,7 @@ use std::convert::TryInto;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::{thread::sleep, time::Duration};
+use stdlib::restore_stdlib_in_dir;
 use test_helper::executor::compile_modules_with_address;
 use test_helper::run_node_by_config;
 
@@ -565,3 +574,240 @@ fn test_only_new_module() {
 
     node_handle.stop().unwrap();
 }
+
+fn compile_test_module(source: &str) -> CompiledModule {
+    let module = compile_modules_with_address(genesis_address(), source)
+        .pop()
+        .expect("should compile a module");
+    CompiledModule::deserialize(module.code()).unwrap()
+}
+
+fn test_upgrade_plan(enforced: bool, match_package: bool) -> UpgradePlanView {
+    UpgradePlanView {
+        package_hash: hex::encode(HashValue::random().to_vec()),
+        active_after_time: 0,
+        version: 1,
+        enforced,
+        match_package,
+    }
+}
+
+#[test]
+fn test_check_upgrade_compat() {
+    let pre_module = compile_test_module(
+        r#"
+        module {{sender}}::CheckUpgrade {
+            struct S has key { a: u64 }
+            public fun f(): u64 { 1 }
+        }
+        "#,
+    );
+    let compatible_module = compile_test_module(
+        r#"
+        module {{sender}}::CheckUpgrade {
+            struct S has key { a: u64 }
+            public fun f(): u64 { 2 }
+            public fun g(): u64 { 3 }
+        }
+        "#,
+    );
+    let incompatible_module = compile_test_module(
+        r#"
+        module {{sender}}::CheckUpgrade {
+            struct S has key { a: u64, b: u64 }
+        }
+        "#,
+    );
+    let check = |strategy, plan: Option<&UpgradePlanView>, new_module: &CompiledModule| {
+        check_package(
+            genesis_address(),
+            HashValue::random(),
+            strategy,
+            plan,
+            0,
+            &[pre_module.clone()],
+            &[new_module.clone()],
+        )
+    };
+
+    // the same module is unchanged.
+    let (modules, problems) = check(STRATEGY_ARBITRARY, None, &pre_module);
+    assert_eq!(modules[0].status, ModuleStatus::Unchanged);
+    assert!(problems.is_empty());
+
+    // a compatible upgrade.
+    let (modules, problems) = check(STRATEGY_ARBITRARY, None, &compatible_module);
+    assert_eq!(modules[0].status, ModuleStatus::Compatible);
+    assert!(modules[0].struct_layout_compatible && modules[0].linking_compatible);
+    assert_eq!(
+        modules[0].function_changes,
+        vec!["public function g added".to_string()]
+    );
+    assert!(problems.is_empty(), "{:?}", problems);
+
+    // an incompatible upgrade without plan.
+    let (modules, problems) = check(STRATEGY_ARBITRARY, None, &incompatible_module);
+    assert_eq!(modules[0].status, ModuleStatus::Incompatible);
+    assert!(!modules[0].struct_layout_compatible);
+    assert!(modules[0]
+        .struct_changes
+        .contains(&"struct S layout changed".to_string()));
+    assert!(modules[0]
+        .function_changes
+        .contains(&"public function f removed".to_string()));
+    assert_eq!(problems.len(), 1);
+
+    // an incompatible upgrade with the non-enforced plan.
+    let plan = test_upgrade_plan(false, true);
+    let (_, problems) = check(STRATEGY_TWO_PHASE, Some(&plan), &incompatible_module);
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("incompatible"));
+
+    // an incompatible upgrade with the enforced plan of the package.
+    let plan = test_upgrade_plan(true, true);
+    let (_, problems) = check(STRATEGY_TWO_PHASE, Some(&plan), &incompatible_module);
+    assert!(problems.is_empty(), "{:?}", problems);
+
+    // the enforced plan of another package does not allow the incompatible upgrade.
+    let plan = test_upgrade_plan(true, false);
+    let (_, problems) = check(STRATEGY_TWO_PHASE, Some(&plan), &incompatible_module);
+    assert_eq!(problems.len(), 2);
+    assert!(problems.iter().any(|p| p.contains("not for this package")));
+    assert!(problems.iter().any(|p| p.contains("incompatible")));
+
+    // a new module.
+    let (modules, problems) = check_package(
+        genesis_address(),
+        HashValue::random(),
+        STRATEGY_NEW_MODULE,
+        None,
+        0,
+        &[],
+        &[compatible_module.clone()],
+    );
+    assert_eq!(modules[0].status, ModuleStatus::New);
+    assert!(problems.is_empty());
+}
+
+#[stest::test(timeout = 300)]
+fn test_explain_user_module_abort() {
+    let node_config = NodeConfig::random_for_test();
+    let config = Arc::new(node_config);
+    let node_handle = run_node_by_config(config.clone()).unwrap();
+    let rpc_service = node_handle.rpc_service().unwrap();
+    let rpc_client = RpcClient::connect_local(rpc_service).unwrap();
+    let node_info = rpc_client.node_info().unwrap();
+    let cli_state = CliState::new(node_info.net, Arc::new(rpc_client), None, None);
+    cli_state
+        .client()
+        .account_unlock(
+            association_address(),
+            "".to_string(),
+            Duration::from_secs(100),
+        )
+        .unwrap();
+    let default_account = cli_state.default_account().unwrap();
+    let _ = create_default_account(&cli_state, &config, &node_handle);
+
+    // 1. build the error map of the module from the doc comments, as the compile cmd does.
+    let source = r#"
+        module {{sender}}::TestAbort {
+            use 0x1::Errors;
+
+            /// The caller is not allowed to do it.
+            const ENOT_ALLOWED: u64 = 101;
+
+            public(script) fun fail() {
+                abort Errors::invalid_argument(ENOT_ALLOWED)
+            }
+        }
+        "#;
+    let source_dir = temp_path();
+    let source_file = source_dir.path().join("TestAbort.move");
+    std::fs::write(source_file.as_path(), source).unwrap();
+    let temp_dir = temp_path();
+    let deps = restore_stdlib_in_dir(temp_dir.path()).unwrap();
+    let target = process_source_tpl_file(
+        temp_dir.path(),
+        source_file.as_path(),
+        AddressBytes::new(default_account.address.into()),
+    )
+    .unwrap();
+    let error_map = build_error_map(&[target.to_string_lossy().to_string()], &deps).unwrap();
+    let module_id = ModuleId::new(
+        default_account.address,
+        Identifier::new("TestAbort").unwrap(),
+    );
+    assert!(error_map.module_error_maps.contains_key(&module_id));
+
+    // 2. deploy the module and register its error map.
+    let module = compile_modules_with_address(default_account.address, source)
+        .pop()
+        .unwrap();
+    let package_txn = _sign_txn_with_default_account_by_rpc_client(
+        &cli_state,
+        1_000_000,
+        1,
+        3_000,
+        TransactionPayload::Package(Package::new_with_module(module).unwrap()),
+    )
+    .unwrap();
+    cli_state
+        .client()
+        .submit_transaction(package_txn.clone())
+        .unwrap();
+    sleep(Duration::from_millis(500));
+    let block = node_handle.generate_block().unwrap();
+    assert!(block.transactions().contains(&package_txn));
+    let module_ids = cli_state
+        .client()
+        .node_register_error_map(&error_map)
+        .unwrap();
+    assert_eq!(module_ids.len(), 1);
+    assert_eq!(module_ids[0].0, module_id);
+
+    // 3. the abort code of the module is explained with the registered error map.
+    let abort_txn = _sign_txn_with_default_account_by_rpc_client(
+        &cli_state,
+        1_000_000,
+        1,
+        3_000,
+        TransactionPayload::ScriptFunction(ScriptFunction::new(
+            module_id.clone(),
+            Identifier::new("fail").unwrap(),
+            vec![],
+            vec![],
+        )),
+    )
+    .unwrap();
+    let abort_txn_id = abort_txn.id();
+    cli_state
+        .client()
+        .submit_transaction(abort_txn.clone())
+        .unwrap();
+    sleep(Duration::from_millis(500));
+    let block = node_handle.generate_block().unwrap();
+    assert!(block.transactions().contains(&abort_txn));
+    let abort_txn_info = cli_state
+        .client()
+        .chain_get_transaction_info(abort_txn_id)
+        .unwrap()
+        .unwrap();
+    match &abort_txn_info.status {
+        TransactionStatusView::MoveAbort { abort_code, .. } => {
+            // the category of `Errors::invalid_argument` is 7.
+            assert_eq!(abort_code.0, (101 << 8) | 7)
+        }
+        status => panic!("unexpected txn status: {:?}", status),
+    }
+    let explain = abort_txn_info.abort_explain.unwrap();
+    assert_eq!(explain.category_name.as_deref(), Some("INVALID_ARGUMENT"));
+    assert_eq!(explain.reason_code, 101);
+    assert_eq!(explain.reason_name.as_deref(), Some("ENOT_ALLOWED"));
+    assert!(explain
+        .reason_description
+        .unwrap()
+        .contains("The caller is not allowed to do it."));
+
+    node_handle.stop().unwrap();
+}
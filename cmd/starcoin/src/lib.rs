@@ -109,7 +109,9 @@ pub fn add_command(
                 .subcommand(dev::GetCoinCommand)
                 .subcommand(dev::move_explain::MoveExplain)
                 .subcommand(dev::CompileCommand)
+                .subcommand(dev::ProveCommand)
                 .subcommand(dev::DeployCommand)
+                .subcommand(dev::CheckUpgradeCommand)
                 .subcommand(dev::UpgradeModuleProposalCommand)
                 .subcommand(dev::UpgradeModulePlanCommand)
                 .subcommand(dev::UpgradeModuleQueueCommand)
@@ -124,7 +126,8 @@ pub fn add_command(
                         .with_about("Subscribe the chain events")
                         .subcommand(dev::SubscribeBlockCommand)
                         .subcommand(dev::SubscribeEventCommand)
-                        .subcommand(dev::SubscribeNewTxnCommand),
+                        .subcommand(dev::SubscribeNewTxnCommand)
+                        .subcommand(dev::SubscribeChainReorgCommand),
                 )
                 .subcommand(
                     Command::with_name("log")
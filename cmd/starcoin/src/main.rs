@@ -68,6 +68,11 @@ fn run() -> Result<()> {
                     let client = RpcClient::connect_websocket(address)?;
                     (client, None)
                 }
+                Connect::Http(address) => {
+                    info!("Try to connect node by http: {:?}", address);
+                    let client = RpcClient::connect_http(address)?;
+                    (client, None)
+                }
             };
 
             let node_info = client.node_info()?;
@@ -5,6 +5,7 @@ use crate::{CliState, StarcoinOpt};
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::BackupManifestView;
 use structopt::StructOpt;
 
 /// Some commands for node manager.
@@ -31,6 +32,26 @@ pub enum NodeManagerOpt {
         #[structopt(name = "block-hash")]
         block_hash: HashValue,
     },
+    /// Switch the main chain to the executed branch of `block-hash`, to confirm a reorg which is
+    /// refused by the max reorg depth limit.
+    #[structopt(name = "confirm-reorg")]
+    ConfirmReorg {
+        #[structopt(name = "block-hash")]
+        block_hash: HashValue,
+    },
+    /// Manually compact the storage column family `cf-name`, wait until the compaction finished.
+    #[structopt(name = "compact-storage")]
+    CompactStorage {
+        #[structopt(name = "cf-name")]
+        cf_name: String,
+    },
+    /// Backup the chain and account databases of the running node to `backup-dir`,
+    /// `backup-dir` is an absolute path on the node machine, and should be empty or not exist.
+    #[structopt(name = "backup")]
+    Backup {
+        #[structopt(name = "backup-dir")]
+        backup_dir: String,
+    },
 }
 
 pub struct NodeManagerCommand;
@@ -39,7 +60,7 @@ impl CommandAction for NodeManagerCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = NodeManagerOpt;
-    type ReturnItem = ();
+    type ReturnItem = Option<BackupManifestView>;
 
     fn run(
         &self,
@@ -47,18 +68,30 @@ impl CommandAction for NodeManagerCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        match opt {
+        let manifest = match opt {
             NodeManagerOpt::DeleteBlock { block_hash } => {
                 client.node_delete_block(*block_hash)?;
+                None
             }
             NodeManagerOpt::ReExecuteBlock { block_hash } => {
                 client.node_re_execute_block(*block_hash)?;
+                None
             }
             NodeManagerOpt::Reset { block_hash } => {
                 client.node_reset(*block_hash)?;
+                None
+            }
+            NodeManagerOpt::ConfirmReorg { block_hash } => {
+                client.node_confirm_reorg(*block_hash)?;
+                None
+            }
+            NodeManagerOpt::CompactStorage { cf_name } => {
+                client.node_compact_storage(cf_name.clone())?;
+                None
             }
-        }
+            NodeManagerOpt::Backup { backup_dir } => Some(client.node_backup(backup_dir.clone())?),
+        };
 
-        Ok(())
+        Ok(manifest)
     }
 }
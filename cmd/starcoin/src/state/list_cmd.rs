@@ -6,7 +6,10 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use serde::{Serialize, Serializer};
-use starcoin_rpc_api::types::{ListCodeView, ListResourceView};
+use starcoin_rpc_api::state::{ListCodeOption, ListResourceOption};
+use starcoin_rpc_api::types::{
+    CodeItemView, Cursor, ListCodeView, ListResourceView, PageOption, PageView, ResourceItemView,
+};
 use starcoin_vm_types::account_address::AccountAddress;
 use structopt::StructOpt;
 
@@ -15,6 +18,7 @@ use structopt::StructOpt;
 ///  ``` shell
 ///  state list code 0x1
 ///  state list resource 0x1
+///  state list resource 0x1 --limit 10
 ///  ```
 #[derive(Debug, StructOpt)]
 #[structopt(name = "list")]
@@ -26,6 +30,14 @@ pub enum ListDataOpt {
         #[structopt(long, short = "n")]
         /// Get state at a special block height.
         block_number: Option<u64>,
+
+        #[structopt(long)]
+        /// Max count of items in the page, list all if both limit and cursor are absent.
+        limit: Option<u64>,
+
+        #[structopt(long)]
+        /// The next_cursor of the previous page.
+        cursor: Option<Cursor>,
     },
     Resource {
         #[structopt(help = "account address")]
@@ -34,6 +46,14 @@ pub enum ListDataOpt {
         #[structopt(long, short = "n")]
         /// Get state at a special block height.
         block_number: Option<u64>,
+
+        #[structopt(long)]
+        /// Max count of items in the page, list all if both limit and cursor are absent.
+        limit: Option<u64>,
+
+        #[structopt(long)]
+        /// The next_cursor of the previous page.
+        cursor: Option<Cursor>,
     },
 }
 
@@ -42,6 +62,8 @@ pub struct ListCmd;
 pub enum ListDataResult {
     Code(ListCodeView),
     Resource(ListResourceView),
+    CodePage(PageView<CodeItemView>),
+    ResourcePage(PageView<ResourceItemView>),
 }
 
 impl Serialize for ListDataResult {
@@ -52,6 +74,8 @@ impl Serialize for ListDataResult {
         match self {
             Self::Code(c) => c.serialize(serializer),
             Self::Resource(r) => r.serialize(serializer),
+            Self::CodePage(c) => c.serialize(serializer),
+            Self::ResourcePage(r) => r.serialize(serializer),
         }
     }
 }
@@ -71,6 +95,8 @@ impl CommandAction for ListCmd {
             ListDataOpt::Code {
                 address,
                 block_number,
+                limit,
+                cursor,
             } => {
                 let state_root = match block_number {
                     Some(block_number) => ctx
@@ -80,15 +106,25 @@ impl CommandAction for ListCmd {
                         .map(|block_view| block_view.header.state_root),
                     None => None,
                 };
-                ListDataResult::Code(
-                    ctx.state()
-                        .client()
-                        .state_list_code(*address, true, state_root)?,
-                )
+                let client = ctx.state().client();
+                if limit.is_none() && cursor.is_none() {
+                    ListDataResult::Code(client.state_list_code(*address, true, state_root)?)
+                } else {
+                    ListDataResult::CodePage(client.state_list_code_page(
+                        *address,
+                        ListCodeOption {
+                            resolve: true,
+                            state_root,
+                        },
+                        Some(PageOption::new(*limit, cursor.clone())),
+                    )?)
+                }
             }
             ListDataOpt::Resource {
                 address,
                 block_number,
+                limit,
+                cursor,
             } => {
                 let state_root = match block_number {
                     Some(block_number) => ctx
@@ -98,11 +134,21 @@ impl CommandAction for ListCmd {
                         .map(|block_view| block_view.header.state_root),
                     None => None,
                 };
-                ListDataResult::Resource(
-                    ctx.state()
-                        .client()
-                        .state_list_resource(*address, true, state_root)?,
-                )
+                let client = ctx.state().client();
+                if limit.is_none() && cursor.is_none() {
+                    ListDataResult::Resource(
+                        client.state_list_resource(*address, true, state_root)?,
+                    )
+                } else {
+                    ListDataResult::ResourcePage(client.state_list_resource_page(
+                        *address,
+                        ListResourceOption {
+                            decode: true,
+                            state_root,
+                        },
+                        Some(PageOption::new(*limit, cursor.clone())),
+                    )?)
+                }
             }
         };
         Ok(result)
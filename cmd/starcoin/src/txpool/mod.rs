@@ -6,6 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::HashValue;
+use starcoin_rpc_api::txpool::GetPendingTxnOption;
 use starcoin_rpc_api::types::SignedUserTransactionView;
 use starcoin_txpool_api::TxPoolStatus;
 use starcoin_vm_types::account_address::AccountAddress;
@@ -32,7 +33,8 @@ impl CommandAction for PendingTxnCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
-        let txn = client.get_pending_txn_by_hash(ctx.opt().hash)?;
+        let txn = client
+            .get_pending_txn_by_hash(ctx.opt().hash, Some(GetPendingTxnOption { decode: true }))?;
 
         Ok(txn)
     }
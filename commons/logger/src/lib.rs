@@ -25,6 +25,9 @@ use std::sync::{Arc, Once};
 
 pub mod structured_log;
 
+/// The log target of audit records, see `LoggerHandle::enable_audit_file`.
+pub const AUDIT_LOGGER: &str = "audit";
+
 /// Logger prelude which includes all logging macros.
 pub mod prelude {
     pub use crate::stacktrace;
@@ -131,6 +134,9 @@ struct LoggerConfigArg {
     max_file_size: u64,
     max_backup: u32,
     pattern: LogPattern,
+    audit_path: Option<PathBuf>,
+    audit_max_file_size: u64,
+    audit_max_backup: u32,
 }
 
 impl LoggerConfigArg {
@@ -161,6 +167,9 @@ impl LoggerConfigArg {
             max_file_size: 0,
             max_backup: 0,
             pattern: pattern.unwrap_or_else(|| LogPattern::by_level(level)),
+            audit_path: None,
+            audit_max_file_size: 0,
+            audit_max_backup: 0,
         }
     }
 }
@@ -205,6 +214,16 @@ impl LoggerHandle {
         self.update_logger(arg);
     }
 
+    /// Write the logs of `AUDIT_LOGGER` to a separate file instead of the main log, it rotates
+    /// by its own size and backup settings.
+    pub fn enable_audit_file(&self, audit_path: PathBuf, max_file_size: u64, max_backup: u32) {
+        let mut arg = self.arg.lock().clone();
+        arg.audit_path = Some(audit_path);
+        arg.audit_max_file_size = max_file_size;
+        arg.audit_max_backup = max_backup;
+        self.update_logger(arg);
+    }
+
     pub fn update_level(&self, level: LevelFilter) {
         let mut arg = self.arg.lock().clone();
         arg.level = level;
@@ -260,6 +279,9 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
         max_file_size,
         max_backup,
         pattern,
+        audit_path,
+        audit_max_file_size,
+        audit_max_backup,
         ..
     } = arg;
     if !enable_stderr && log_path.is_none() {
@@ -302,6 +324,24 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
         }
     }
 
+    if let Some(audit_path) = audit_path {
+        // the audit records are structured, so they are written without decoration.
+        let appender = rolling_file_append(
+            AUDIT_LOGGER,
+            audit_max_file_size,
+            audit_max_backup,
+            LogPattern::Custom("{m}{n}".to_string()),
+            audit_path,
+        )?;
+        builder = builder.appender(appender);
+        builder = builder.logger(
+            Logger::builder()
+                .additive(false)
+                .appender(AUDIT_LOGGER)
+                .build(AUDIT_LOGGER, LevelFilter::Info),
+        );
+    }
+
     builder = builder.loggers(
         module_levels
             .into_iter()
@@ -43,6 +43,8 @@ pub use prometheus::{
 };
 
 pub mod prometheus_export {
+    pub use prometheus::core::{Collector, Desc};
+    pub use prometheus::proto::MetricFamily;
     pub use prometheus::register;
 }
 
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::collector::{FutureTaskSink, SinkError};
+use crate::reorder_stream::ReorderBuffered;
 use crate::task_stream::FutureTaskStream;
 use crate::{CustomErrorHandle, TaskError, TaskEventHandle, TaskResultCollector, TaskState};
 use anyhow::Result;
@@ -99,6 +100,7 @@ where
 {
     init_state: S,
     buffer_size: usize,
+    reorder_buffer_size: usize,
     max_retry_times: u64,
     delay_milliseconds: u64,
     collector: C,
@@ -123,6 +125,7 @@ where
         Self {
             init_state,
             buffer_size,
+            reorder_buffer_size: buffer_size,
             max_retry_times,
             delay_milliseconds: delay_milliseconds_on_error,
             collector,
@@ -130,6 +133,14 @@ where
             custom_error_handle,
         }
     }
+
+    /// The sub tasks run concurrently at most `buffer_size`, and their results are collected in
+    /// order. The running sub tasks and the completed results waiting for the previous sub tasks
+    /// are at most `reorder_buffer_size`, default is `buffer_size`.
+    pub fn with_reorder_buffer_size(mut self, reorder_buffer_size: usize) -> Self {
+        self.reorder_buffer_size = reorder_buffer_size;
+        self
+    }
 }
 
 impl<S, C> Generator for TaskGenerator<S, C>
@@ -153,17 +164,19 @@ where
                 event_handle.clone(),
                 self.custom_error_handle,
             );
-            let mut buffered_stream = stream
-                .buffered(self.buffer_size)
-                .map(|result| {
-                    let items = match result {
-                        Ok(items) => items.into_iter().map(Ok).collect(),
-                        Err(e) => vec![Err(e)],
-                    };
-                    stream::iter(items)
-                })
-                .flatten()
-                .map_err(SinkError::StreamTaskError);
+            // the collector runs in a separate task, it collects the results in order while the
+            // following sub tasks are running.
+            let mut buffered_stream =
+                ReorderBuffered::new(stream, self.buffer_size, self.reorder_buffer_size)
+                    .map(|result| {
+                        let items = match result {
+                            Ok(items) => items.into_iter().map(Ok).collect(),
+                            Err(e) => vec![Err(e)],
+                        };
+                        stream::iter(items)
+                    })
+                    .flatten()
+                    .map_err(SinkError::StreamTaskError);
             let mut sink =
                 FutureTaskSink::new(self.collector, self.buffer_size, event_handle.clone());
             SinkError::map_result(sink.send_all(&mut buffered_stream).await)?;
@@ -10,6 +10,7 @@ use thiserror::Error;
 mod collector;
 mod event;
 mod generator;
+mod reorder_stream;
 mod task_stream;
 
 pub use collector::{CollectorState, CounterCollector, TaskResultCollector};
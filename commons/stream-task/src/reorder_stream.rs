@@ -0,0 +1,113 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::stream::{Fuse, FuturesUnordered};
+use futures::task::{Context, Poll};
+use futures::{Future, Stream, StreamExt};
+use pin_project::pin_project;
+use std::collections::BTreeMap;
+use std::pin::Pin;
+
+#[pin_project]
+struct IndexedFuture<Fut> {
+    index: u64,
+    #[pin]
+    fut: Fut,
+}
+
+impl<Fut> Future for IndexedFuture<Fut>
+where
+    Fut: Future,
+{
+    type Output = (u64, Fut::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let index = *this.index;
+        this.fut.poll(cx).map(|output| (index, output))
+    }
+}
+
+/// Run the futures of the stream concurrently, and output their results in the stream order.
+/// At most `concurrency` futures are running, and the running futures and the completed results
+/// waiting in the reorder buffer are at most `buffer_size`, so a slow future stops new futures
+/// from starting instead of growing the buffer.
+#[pin_project]
+pub(crate) struct ReorderBuffered<St>
+where
+    St: Stream,
+    St::Item: Future,
+{
+    #[pin]
+    stream: Fuse<St>,
+    in_progress: FuturesUnordered<IndexedFuture<St::Item>>,
+    reorder_buffer: BTreeMap<u64, <St::Item as Future>::Output>,
+    next_index: u64,
+    next_output_index: u64,
+    concurrency: usize,
+    buffer_size: usize,
+}
+
+impl<St> ReorderBuffered<St>
+where
+    St: Stream,
+    St::Item: Future,
+{
+    pub fn new(stream: St, concurrency: usize, buffer_size: usize) -> Self {
+        let concurrency = concurrency.max(1);
+        Self {
+            stream: stream.fuse(),
+            in_progress: FuturesUnordered::new(),
+            reorder_buffer: BTreeMap::new(),
+            next_index: 0,
+            next_output_index: 0,
+            concurrency,
+            buffer_size: buffer_size.max(concurrency),
+        }
+    }
+}
+
+impl<St> Stream for ReorderBuffered<St>
+where
+    St: Stream,
+    St::Item: Future,
+{
+    type Item = <St::Item as Future>::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(output) = this.reorder_buffer.remove(this.next_output_index) {
+                *this.next_output_index += 1;
+                return Poll::Ready(Some(output));
+            }
+            while this.in_progress.len() < *this.concurrency
+                && this.in_progress.len() + this.reorder_buffer.len() < *this.buffer_size
+            {
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(fut)) => {
+                        this.in_progress.push(IndexedFuture {
+                            index: *this.next_index,
+                            fut,
+                        });
+                        *this.next_index += 1;
+                    }
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+            match this.in_progress.poll_next_unpin(cx) {
+                Poll::Ready(Some((index, output))) => {
+                    this.reorder_buffer.insert(index, output);
+                }
+                Poll::Ready(None) => {
+                    return if this.stream.is_done() {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Pending
+                    };
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
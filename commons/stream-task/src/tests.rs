@@ -397,3 +397,108 @@ async fn test_collect_enough() {
     //assert!(result.is_ok());
     assert_eq!(result.unwrap(), collector_max);
 }
+
+/// The earlier sub tasks are slower, so the sub tasks complete in reverse order.
+#[derive(Clone)]
+struct ReverseDelayTaskState {
+    state: u64,
+    max: u64,
+    running: Arc<AtomicU64>,
+    max_running: Arc<AtomicU64>,
+}
+
+impl TaskState for ReverseDelayTaskState {
+    type Item = u64;
+
+    fn new_sub_task(self) -> BoxFuture<'static, Result<Vec<Self::Item>>> {
+        async move {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            Delay::new(Duration::from_millis((self.max - self.state) * 5)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(vec![self.state])
+        }
+        .boxed()
+    }
+
+    fn next(&self) -> Option<Self> {
+        if self.state + 1 >= self.max {
+            None
+        } else {
+            Some(Self {
+                state: self.state + 1,
+                ..self.clone()
+            })
+        }
+    }
+}
+
+async fn run_reverse_delay_task(max: u64, concurrency: usize, reorder_buffer_size: usize) -> u64 {
+    let max_running = Arc::new(AtomicU64::new(0));
+    let state = ReverseDelayTaskState {
+        state: 0,
+        max,
+        running: Arc::new(AtomicU64::new(0)),
+        max_running: max_running.clone(),
+    };
+    let result = TaskGenerator::new(
+        state,
+        concurrency,
+        0,
+        0,
+        vec![],
+        Arc::new(TaskEventCounterHandle::new()),
+        Arc::new(DefaultCustomErrorHandle),
+    )
+    .with_reorder_buffer_size(reorder_buffer_size)
+    .generate()
+    .await
+    .unwrap();
+    assert_eq!(result, (0..max).collect::<Vec<_>>());
+    max_running.load(Ordering::SeqCst)
+}
+
+#[stest::test]
+async fn test_reorder_out_of_order_completion() {
+    let max_running = run_reverse_delay_task(20, 5, 10).await;
+    assert!(max_running > 1, "sub tasks should run concurrently");
+    assert!(max_running <= 5);
+}
+
+#[stest::test]
+async fn test_reorder_buffer_bound() {
+    // the first sub task is the slowest, the following sub tasks can not start when the reorder
+    // buffer is full, so the running sub tasks are limited by the reorder buffer.
+    let max_running = run_reverse_delay_task(20, 8, 3).await;
+    assert!(max_running <= 8);
+
+    let max = 20;
+    let concurrency = 2;
+    let buffer_size = 4;
+    let in_flight = Arc::new(AtomicU64::new(0));
+    let max_in_flight = Arc::new(AtomicU64::new(0));
+    let futures = (0..max).map(|i| {
+        let in_flight = in_flight.clone();
+        let max_in_flight = max_in_flight.clone();
+        in_flight.fetch_add(1, Ordering::SeqCst);
+        max_in_flight.fetch_max(in_flight.load(Ordering::SeqCst), Ordering::SeqCst);
+        async move {
+            Delay::new(Duration::from_millis((max - i) * 2)).await;
+            (i, in_flight)
+        }
+    });
+    let outputs: Vec<u64> = crate::reorder_stream::ReorderBuffered::new(
+        futures::stream::iter(futures),
+        concurrency,
+        buffer_size,
+    )
+    .map(|(i, in_flight)| {
+        // the output is consumed, so it leaves the buffer.
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        i
+    })
+    .collect()
+    .await;
+    assert_eq!(outputs, (0..max).collect::<Vec<_>>());
+    assert!(max_in_flight.load(Ordering::SeqCst) <= buffer_size as u64);
+}
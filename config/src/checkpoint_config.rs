@@ -0,0 +1,234 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BuiltinNetworkID;
+use anyhow::{bail, format_err, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_vm_types::genesis_config::ChainId;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A trusted block of the chain, any branch conflict with it will be rejected.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Checkpoint {
+    /// block number of the checkpoint
+    pub number: u64,
+    /// block hash of the checkpoint
+    pub block_hash: HashValue,
+    /// the block accumulator root after the checkpoint block is connected, optional.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_accumulator_root: Option<HashValue>,
+}
+
+impl Checkpoint {
+    pub fn new(number: u64, block_hash: HashValue) -> Self {
+        Self {
+            number,
+            block_hash,
+            block_accumulator_root: None,
+        }
+    }
+
+    pub fn with_block_accumulator_root(mut self, block_accumulator_root: HashValue) -> Self {
+        self.block_accumulator_root = Some(block_accumulator_root);
+        self
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:0x{}", self.number, self.block_hash.to_hex())?;
+        if let Some(block_accumulator_root) = &self.block_accumulator_root {
+            write!(f, ":0x{}", block_accumulator_root.to_hex())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse checkpoint from `number:block_hash` or `number:block_hash:block_accumulator_root`.
+impl FromStr for Checkpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        if parts.len() != 2 && parts.len() != 3 {
+            bail!(
+                "Invalid checkpoint: {}, expect format: number:block_hash[:block_accumulator_root]",
+                s
+            );
+        }
+        let number = parts[0]
+            .parse::<u64>()
+            .map_err(|e| format_err!("Invalid checkpoint number {}: {:?}", parts[0], e))?;
+        let block_hash = HashValue::from_hex_literal(parts[1])?;
+        let block_accumulator_root = match parts.get(2) {
+            Some(root) => Some(HashValue::from_hex_literal(root)?),
+            None => None,
+        };
+        Ok(Self {
+            number,
+            block_hash,
+            block_accumulator_root,
+        })
+    }
+}
+
+/// Checkpoints sorted by block number.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Checkpoints {
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl Checkpoints {
+    pub fn new(checkpoints: Vec<Checkpoint>) -> Result<Self> {
+        let mut result = Self::default();
+        result.extend(checkpoints)?;
+        Ok(result)
+    }
+
+    /// The builtin checkpoints of the network with `chain_id`, empty for a custom network.
+    pub fn builtin(chain_id: ChainId) -> Arc<Self> {
+        BUILTIN_CHECKPOINTS
+            .get(&chain_id.id())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Add checkpoints, return error if a checkpoint conflicts with an exist checkpoint at the same number.
+    pub fn extend(&mut self, checkpoints: Vec<Checkpoint>) -> Result<()> {
+        for checkpoint in checkpoints {
+            match self
+                .checkpoints
+                .binary_search_by_key(&checkpoint.number, |c| c.number)
+            {
+                Ok(idx) => {
+                    let exist = &self.checkpoints[idx];
+                    if exist.block_hash != checkpoint.block_hash
+                        || (exist.block_accumulator_root.is_some()
+                            && checkpoint.block_accumulator_root.is_some()
+                            && exist.block_accumulator_root != checkpoint.block_accumulator_root)
+                    {
+                        bail!("Checkpoint {} conflict with {}", checkpoint, exist);
+                    }
+                    if exist.block_accumulator_root.is_none() {
+                        self.checkpoints[idx] = checkpoint;
+                    }
+                }
+                Err(idx) => self.checkpoints.insert(idx, checkpoint),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Checkpoint> {
+        self.checkpoints.iter()
+    }
+
+    pub fn get(&self, number: u64) -> Option<&Checkpoint> {
+        self.checkpoints
+            .binary_search_by_key(&number, |c| c.number)
+            .ok()
+            .map(|idx| &self.checkpoints[idx])
+    }
+
+    /// The checkpoint with the highest block number.
+    pub fn latest(&self) -> Option<&Checkpoint> {
+        self.checkpoints.last()
+    }
+
+    /// The latest checkpoint which number is less than or equal to `number`.
+    pub fn latest_before(&self, number: u64) -> Option<&Checkpoint> {
+        self.checkpoints.iter().rev().find(|c| c.number <= number)
+    }
+
+    /// Checkpoints in block number range [start, end].
+    pub fn range(&self, start: u64, end: u64) -> impl Iterator<Item = &Checkpoint> {
+        self.checkpoints
+            .iter()
+            .filter(move |c| c.number >= start && c.number <= end)
+    }
+
+    /// Check the block hash at `number` does not conflict with the checkpoint.
+    pub fn check(&self, number: u64, block_hash: HashValue) -> Result<()> {
+        if let Some(checkpoint) = self.get(number) {
+            if checkpoint.block_hash != block_hash {
+                bail!(
+                    "Block {:?} at number {} conflict with checkpoint {}",
+                    block_hash,
+                    number,
+                    checkpoint
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+pub static EMPTY_CHECKPOINTS: Lazy<Vec<Checkpoint>> = Lazy::new(Vec::new);
+
+// The hard-coded trust anchors of the builtin networks, the genesis checkpoints are the ids of the
+// generated genesis blocks in `genesis/generated`, and must be updated when the genesis is
+// regenerated. The later checkpoints are appended when a release is cut.
+pub static HALLEY_CHECKPOINTS: Lazy<Vec<Checkpoint>> = Lazy::new(|| {
+    vec![Checkpoint::new(
+        0,
+        HashValue::from_hex_literal(
+            "0x95acc1c3de1454d8e2f3ff4c929fa083ea27b125680e7706e56b3abadb822bec",
+        )
+        .expect("builtin checkpoint hash must be valid"),
+    )]
+});
+
+pub static PROXIMA_CHECKPOINTS: Lazy<Vec<Checkpoint>> = Lazy::new(|| {
+    vec![Checkpoint::new(
+        0,
+        HashValue::from_hex_literal(
+            "0xa741de4bda5520d0e1acb6a5831c97ac590bbafb0f16570c38d5b6398cbadfe2",
+        )
+        .expect("builtin checkpoint hash must be valid"),
+    )]
+});
+
+pub static BARNARD_CHECKPOINTS: Lazy<Vec<Checkpoint>> = Lazy::new(|| {
+    vec![Checkpoint::new(
+        0,
+        HashValue::from_hex_literal(
+            "0x6819736ab264bcacc468f64b4e35757f24b18d3a9180cba5c5bac14610c5c968",
+        )
+        .expect("builtin checkpoint hash must be valid"),
+    )]
+});
+
+pub static MAIN_CHECKPOINTS: Lazy<Vec<Checkpoint>> = Lazy::new(|| {
+    vec![Checkpoint::new(
+        0,
+        HashValue::from_hex_literal(
+            "0x80848150abee7e9a3bfe9542a019eb0b8b01f124b63b011f9c338fdb935c417d",
+        )
+        .expect("builtin checkpoint hash must be valid"),
+    )]
+});
+
+/// The checkpoints of the builtin networks by chain id, built once and shared by the chains.
+static BUILTIN_CHECKPOINTS: Lazy<HashMap<u8, Arc<Checkpoints>>> = Lazy::new(|| {
+    BuiltinNetworkID::networks()
+        .into_iter()
+        .map(|id| {
+            let checkpoints = Checkpoints::new(id.checkpoints().to_vec())
+                .expect("builtin checkpoints should not conflict.");
+            (id.chain_id().id(), Arc::new(checkpoints))
+        })
+        .collect()
+});
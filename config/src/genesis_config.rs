@@ -1,6 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::checkpoint_config::{
+    Checkpoints, BARNARD_CHECKPOINTS, EMPTY_CHECKPOINTS, HALLEY_CHECKPOINTS, MAIN_CHECKPOINTS,
+    PROXIMA_CHECKPOINTS,
+};
+use crate::Checkpoint;
 use anyhow::{bail, ensure, format_err, Result};
 use network_p2p_types::MultiaddrWithPeerId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -14,6 +19,7 @@ use starcoin_crypto::{
     HashValue, ValidCryptoMaterialStringExt,
 };
 use starcoin_uint::U256;
+use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::event::EventHandle;
 use starcoin_vm_types::gas_schedule::{
@@ -173,6 +179,17 @@ impl BuiltinNetworkID {
         }
     }
 
+    pub fn checkpoints(self) -> &'static [Checkpoint] {
+        match self {
+            BuiltinNetworkID::Test => EMPTY_CHECKPOINTS.as_slice(),
+            BuiltinNetworkID::Dev => EMPTY_CHECKPOINTS.as_slice(),
+            BuiltinNetworkID::Halley => HALLEY_CHECKPOINTS.as_slice(),
+            BuiltinNetworkID::Proxima => PROXIMA_CHECKPOINTS.as_slice(),
+            BuiltinNetworkID::Barnard => BARNARD_CHECKPOINTS.as_slice(),
+            BuiltinNetworkID::Main => MAIN_CHECKPOINTS.as_slice(),
+        }
+    }
+
     pub fn boot_nodes_domain(self) -> String {
         match self {
             BuiltinNetworkID::Test | BuiltinNetworkID::Dev => "localhost".to_string(),
@@ -376,6 +393,13 @@ impl ChainNetworkID {
         }
     }
 
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        match self {
+            Self::Builtin(b) => b.checkpoints(),
+            _ => &[],
+        }
+    }
+
     pub fn as_builtin(&self) -> Option<&BuiltinNetworkID> {
         match self {
             Self::Builtin(net) => Some(net),
@@ -416,6 +440,7 @@ pub struct ChainNetwork {
     id: ChainNetworkID,
     genesis_config: GenesisConfig,
     time_service: Arc<dyn TimeService>,
+    checkpoints: Checkpoints,
 }
 
 impl Display for ChainNetwork {
@@ -433,10 +458,13 @@ impl PartialEq for ChainNetwork {
 impl ChainNetwork {
     pub fn new(id: ChainNetworkID, genesis_config: GenesisConfig) -> Self {
         let time_service = genesis_config.time_service_type.new_time_service();
+        let checkpoints = Checkpoints::new(id.checkpoints().to_vec())
+            .expect("builtin checkpoints should not conflict.");
         Self {
             id,
             genesis_config,
             time_service,
+            checkpoints,
         }
     }
 
@@ -503,6 +531,11 @@ impl ChainNetwork {
         self.id.boot_nodes()
     }
 
+    /// The builtin checkpoints of the network.
+    pub fn checkpoints(&self) -> &Checkpoints {
+        &self.checkpoints
+    }
+
     /// Please ensure network is_ready() before genesis_block_parameter
     pub fn genesis_block_parameter(&self) -> &GenesisBlockParameter {
         &self
@@ -617,6 +650,10 @@ pub struct GenesisConfig {
     pub time_service_type: TimeServiceType,
     /// transaction timeout
     pub transaction_timeout: u64,
+    /// The validators of the proof-of-authority consensus before the on-chain validator set is published,
+    /// only used when the consensus strategy is custom.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authority_validators: Vec<AccountAddress>,
 }
 
 impl GenesisConfig {
@@ -832,6 +869,7 @@ pub static TEST_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        authority_validators: vec![],
     }
 });
 
@@ -881,6 +919,7 @@ pub static DEV_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        authority_validators: vec![],
     }
 });
 
@@ -936,6 +975,7 @@ pub static HALLEY_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        authority_validators: vec![],
     }
 });
 
@@ -992,6 +1032,7 @@ pub static PROXIMA_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 1000, // 1 minute
         },
         transaction_timeout: ONE_DAY,
+        authority_validators: vec![],
     }
 });
 
@@ -1048,6 +1089,7 @@ pub static BARNARD_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        authority_validators: vec![],
     }
 });
 
@@ -1108,5 +1150,6 @@ pub static MAIN_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        authority_validators: vec![],
     }
 });
@@ -3,7 +3,6 @@
 
 use crate::account_vault_config::AccountVaultConfig;
 use crate::helper::{load_config, save_config};
-use crate::sync_config::SyncConfig;
 use anyhow::{ensure, format_err, Result};
 use git_version::git_version;
 use once_cell::sync::Lazy;
@@ -28,6 +27,7 @@ mod account_vault_config;
 mod api_config;
 mod api_quota;
 mod available_port;
+mod checkpoint_config;
 pub mod genesis_config;
 mod helper;
 mod logger_config;
@@ -48,6 +48,7 @@ pub use api_quota::{ApiQuotaConfig, QuotaDuration};
 pub use available_port::{
     get_available_port_from, get_random_available_port, get_random_available_ports,
 };
+pub use checkpoint_config::{Checkpoint, Checkpoints};
 pub use diem_temppath::TempPath;
 pub use genesis_config::{
     BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
@@ -60,12 +61,15 @@ pub use metrics_config::MetricsConfig;
 pub use miner_config::{MinerClientConfig, MinerConfig};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
 pub use rpc_config::{
-    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
-    WsConfiguration,
+    ApiKeyConfig, ApiQuotaConfiguration, AuditLogConfiguration, HttpConfiguration,
+    IpcConfiguration, RpcConfig, TcpConfiguration, WsConfiguration,
 };
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_vm_types::time::{MockTimeService, RealTimeService, TimeService};
-pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
+pub use storage_config::{
+    ColumnFamilyConfig, CompressionType, RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE,
+};
+pub use sync_config::{SyncConfig, MAX_BLOCK_DOWNLOAD_BATCH_SIZE};
 pub use txpool_config::TxPoolConfig;
 
 pub static CRATE_VERSION: &str = crate_version!();
@@ -135,6 +139,8 @@ pub enum Connect {
     IPC(Option<PathBuf>),
     /// Connect by json rpc address.
     WebSocket(String),
+    /// Connect by http(s) json rpc address, subscriptions are not supported.
+    Http(String),
 }
 
 impl Default for Connect {
@@ -152,6 +158,8 @@ impl FromStr for Connect {
         }
         if s.starts_with("ws://") || s.starts_with("wss://") {
             Ok(Connect::WebSocket(s.to_string()))
+        } else if s.starts_with("http://") || s.starts_with("https://") {
+            Ok(Connect::Http(s.to_string()))
         } else {
             Ok(Connect::IPC(Some(PathBuf::from_str(s)?)))
         }
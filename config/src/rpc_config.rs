@@ -5,7 +5,7 @@ use crate::{
     get_available_port_from, get_random_available_ports, parse_key_val, ApiQuotaConfig, ApiSet,
     BaseConfig, ConfigModule, QuotaDuration, StarcoinOpt,
 };
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_logger::prelude::*;
 use std::collections::HashSet;
@@ -14,6 +14,7 @@ use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::num::NonZeroU32;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
 
 //10M
@@ -25,6 +26,19 @@ const DEFAULT_WEB_SOCKET_PORT: u16 = 9870;
 // UNSPECIFIED is 0.0.0.0
 const DEFAULT_RPC_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 const DEFAULT_BLOCK_QUERY_MAX_RANGE: u64 = 32;
+const DEFAULT_AUDIT_LOG_FILE: &str = "rpc_audit.log";
+const DEFAULT_SLOW_CALL_THRESHOLD_MILLIS: u64 = 1000;
+const DEFAULT_AUDIT_LOG_MAX_FILE_SIZE: u64 = 1024 * 1024 * 1024;
+const DEFAULT_AUDIT_LOG_MAX_BACKUP: u32 = 7;
+/// The methods with passwords or private keys in params.
+const DEFAULT_REDACTED_METHODS: &[&str] = &[
+    "account.create",
+    "account.import",
+    "account.export",
+    "account.unlock",
+    "account.change_password",
+    "account.remove",
+];
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct HttpConfiguration {
@@ -339,6 +353,111 @@ impl ApiQuotaConfiguration {
     }
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
+pub struct AuditLogConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-audit-log", long)]
+    /// Write every json rpc call to the audit log file rpc_audit.log in data dir, Default is false
+    pub enable: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-slow-call-threshold", long)]
+    /// The calls slower than the threshold in milliseconds are audited with full params, Default is 1000
+    pub slow_call_threshold: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-audit-redacted-methods", long, use_delimiter = true)]
+    /// Methods whose params are never audited, in addition to the account methods with secrets
+    pub redacted_methods: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-audit-log-max-file-size", long)]
+    /// The audit log file is rotated when it is larger than the size in bytes, Default is 1GB
+    pub max_file_size: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-audit-log-max-backup", long)]
+    /// The max rotated audit log files kept, Default is 7
+    pub max_backup: Option<u32>,
+}
+
+impl AuditLogConfiguration {
+    pub fn enable(&self) -> bool {
+        self.enable.unwrap_or(false)
+    }
+
+    pub fn slow_call_threshold(&self) -> Duration {
+        Duration::from_millis(
+            self.slow_call_threshold
+                .unwrap_or(DEFAULT_SLOW_CALL_THRESHOLD_MILLIS),
+        )
+    }
+
+    pub fn redacted_methods(&self) -> HashSet<String> {
+        DEFAULT_REDACTED_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .chain(self.redacted_methods.clone().unwrap_or_default())
+            .collect()
+    }
+
+    pub fn max_file_size(&self) -> u64 {
+        self.max_file_size
+            .unwrap_or(DEFAULT_AUDIT_LOG_MAX_FILE_SIZE)
+    }
+
+    pub fn max_backup(&self) -> u32 {
+        self.max_backup.unwrap_or(DEFAULT_AUDIT_LOG_MAX_BACKUP)
+    }
+
+    pub fn merge(&mut self, o: &Self) -> Result<()> {
+        if o.enable.is_some() {
+            self.enable = o.enable;
+        }
+        if o.slow_call_threshold.is_some() {
+            self.slow_call_threshold = o.slow_call_threshold;
+        }
+        if o.redacted_methods.is_some() {
+            self.redacted_methods = o.redacted_methods.clone();
+        }
+        if o.max_file_size.is_some() {
+            self.max_file_size = o.max_file_size;
+        }
+        if o.max_backup.is_some() {
+            self.max_backup = o.max_backup;
+        }
+        Ok(())
+    }
+}
+
+/// An api key which grants the caller a set of apis and its own quota, the key is sent by the
+/// http client in the `Authorization: Bearer <key>` or the `X-Api-Key: <key>` header.
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// The name of the key, it is logged and used as the rate limit user `key:<name>`, instead of
+    /// the key itself.
+    pub name: String,
+    pub key: String,
+    /// The apis the key can call, eg: "safe,account,node_manager".
+    pub apis: ApiSet,
+    /// The quota of the key for every api, the default user api quota is used if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ApiQuotaConfig>,
+}
+
+/// The key is redacted, so the config can be logged safely.
+impl std::fmt::Debug for ApiKeyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyConfig")
+            .field("name", &self.name)
+            .field("key", &"<redacted>")
+            .field("apis", &self.apis)
+            .field("quota", &self.quota)
+            .finish()
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct RpcConfig {
@@ -362,6 +481,15 @@ pub struct RpcConfig {
     #[structopt(flatten)]
     pub api_quotas: ApiQuotaConfiguration,
 
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub audit: AuditLogConfiguration,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[structopt(skip)]
+    /// Api keys accepted by the http endpoint, only configurable in the config file.
+    pub api_keys: Vec<ApiKeyConfig>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "rpc-address")]
     /// Rpc address, default is 0.0.0.0
@@ -434,6 +562,10 @@ impl RpcConfig {
         Self::get_ipc_file_by_base(base)
     }
 
+    pub fn get_audit_log_file(&self) -> PathBuf {
+        self.base().data_dir().join(DEFAULT_AUDIT_LOG_FILE)
+    }
+
     pub fn get_http_address(&self) -> Option<ListenAddress> {
         self.http_address.clone()
     }
@@ -512,6 +644,31 @@ impl RpcConfig {
             .join(base.net().id().dir_name())
             .join(DEFAULT_IPC_FILE)
     }
+
+    /// The names and the keys of the api keys should be unique, the key is mapped to its name.
+    pub(crate) fn check_api_keys(&self) -> Result<()> {
+        let mut key_names = HashSet::new();
+        let mut keys = HashSet::new();
+        for api_key in &self.api_keys {
+            ensure!(
+                !api_key.key.is_empty(),
+                "api key {} should not be empty",
+                api_key.name
+            );
+            ensure!(
+                key_names.insert(api_key.name.as_str()),
+                "duplicate api key name: {}",
+                api_key.name
+            );
+            // the key is a secret, so only the name is reported.
+            ensure!(
+                keys.insert(api_key.key.as_str()),
+                "the key of api key {} is used by another api key",
+                api_key.name
+            );
+        }
+        Ok(())
+    }
 }
 
 impl ConfigModule for RpcConfig {
@@ -528,6 +685,8 @@ impl ConfigModule for RpcConfig {
         self.ws.merge(&opt.rpc.ws)?;
         self.ipc.merge(&opt.rpc.ipc)?;
         self.api_quotas.merge(&opt.rpc.api_quotas)?;
+        self.audit.merge(&opt.rpc.audit)?;
+        self.check_api_keys()?;
 
         self.generate_address();
 
@@ -535,6 +694,9 @@ impl ConfigModule for RpcConfig {
         info!("TCP rpc address: {:?}", self.get_tcp_address());
         info!("Websocket rpc address: {:?}", self.get_ws_address());
         info!("Ipc file path: {:?}", self.get_ipc_file());
+        if self.audit.enable() {
+            info!("Rpc audit log path: {:?}", self.get_audit_log_file());
+        }
 
         Ok(())
     }
@@ -5,14 +5,70 @@ use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use structopt::StructOpt;
 
+/// The column family names which have tuned default options, keep same as the names in storage.
+const STATE_NODE_CF_NAME: &str = "state_node";
+const CONTRACT_EVENT_CF_NAME: &str = "contract_event";
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionType {
+    None,
+    Lz4,
+    Zstd,
+}
+
+/// Options of a rocksdb column family, the unset options use the rocksdb default value.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnFamilyConfig {
+    /// Capacity of a dedicated block cache for the column family,
+    /// use the block cache shared by all column families if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_cache_size: Option<u64>,
+    /// Bits per key of the bloom filter, no bloom filter if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bloom_filter_bits: Option<i32>,
+    /// Compression type of every level, default is lz4 for all levels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression_per_level: Option<Vec<CompressionType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_buffer_size: Option<u64>,
+    /// Length of the fixed key prefix extractor, the bloom filter is build on the prefix if set.
+    /// It should be shorter than the keys, which share the prefix are then seeked together.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix_length: Option<usize>,
+}
+
+impl ColumnFamilyConfig {
+    /// Override the options which are set in `o`, keep the others.
+    pub fn merge(&mut self, o: &Self) {
+        if o.block_cache_size.is_some() {
+            self.block_cache_size = o.block_cache_size;
+        }
+        if o.bloom_filter_bits.is_some() {
+            self.bloom_filter_bits = o.bloom_filter_bits;
+        }
+        if o.compression_per_level.is_some() {
+            self.compression_per_level = o.compression_per_level.clone();
+        }
+        if o.write_buffer_size.is_some() {
+            self.write_buffer_size = o.write_buffer_size;
+        }
+        if o.prefix_length.is_some() {
+            self.prefix_length = o.prefix_length;
+        }
+    }
+}
+
 /// Port selected RocksDB options for tuning underlying rocksdb instance of DiemDB.
 /// see https://github.com/facebook/rocksdb/blob/master/include/rocksdb/options.h
 /// for detailed explanations.
-#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(default, deny_unknown_fields)]
 pub struct RocksdbConfig {
     #[structopt(name = "rocksdb-max-open-files", long, help = "rocksdb max open files")]
@@ -23,6 +79,21 @@ pub struct RocksdbConfig {
         help = "rocksdb max total WAL sizes"
     )]
     pub max_total_wal_size: u64,
+    #[structopt(
+        name = "rocksdb-block-cache-size",
+        long,
+        help = "capacity of the rocksdb block cache shared by column families, default is 128M, \
+                it replaces the 8M block cache of every column family of old versions"
+    )]
+    pub block_cache_size: u64,
+    #[structopt(
+        name = "rocksdb-enable-statistics",
+        long,
+        help = "enable rocksdb internal statistics"
+    )]
+    pub enable_statistics: bool,
+    #[structopt(skip)]
+    pub column_families: BTreeMap<String, ColumnFamilyConfig>,
 }
 
 impl RocksdbConfig {
@@ -40,6 +111,38 @@ impl RocksdbConfig {
     fn default_max_open_files() -> i32 {
         256
     }
+
+    /// The state node and contract event are the hottest column families for point lookups.
+    /// They have no prefix extractor, their keys are hashes, so a fixed key prefix groups
+    /// nothing and the bloom filter on the whole key is already the best for point lookups.
+    /// The 128M write buffer of the state node is twice the rocksdb default, so an upgraded
+    /// node uses more memory for memtables, override it in `column_families` if memory is tight.
+    fn default_column_families() -> BTreeMap<String, ColumnFamilyConfig> {
+        let mut column_families = BTreeMap::new();
+        column_families.insert(
+            STATE_NODE_CF_NAME.to_string(),
+            ColumnFamilyConfig {
+                bloom_filter_bits: Some(10),
+                write_buffer_size: Some(128 << 20),
+                ..Default::default()
+            },
+        );
+        column_families.insert(
+            CONTRACT_EVENT_CF_NAME.to_string(),
+            ColumnFamilyConfig {
+                bloom_filter_bits: Some(10),
+                ..Default::default()
+            },
+        );
+        column_families
+    }
+
+    pub fn column_family(&self, cf_name: &str) -> ColumnFamilyConfig {
+        self.column_families
+            .get(cf_name)
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 impl Default for RocksdbConfig {
@@ -51,6 +154,10 @@ impl Default for RocksdbConfig {
             // For now we set the max total WAL size to be 1G. This config can be useful when column
             // families are updated at non-uniform frequencies.
             max_total_wal_size: 1u64 << 30,
+            // Every column family had a 8M block cache before, share a 128M cache by default.
+            block_cache_size: 1u64 << 27,
+            enable_statistics: false,
+            column_families: Self::default_column_families(),
         }
     }
 }
@@ -77,6 +184,45 @@ pub struct StorageConfig {
     #[structopt(name = "cache-sizes", long, help = "cache sizes")]
     pub cache_size: Option<usize>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "enable-write-set",
+        long,
+        help = "save the write sets of executed txns, to query the state diff of txns and blocks"
+    )]
+    pub enable_write_set: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "enable-abort-context",
+        long,
+        help = "save the abort location, function and code offset of failed txns, default is true"
+    )]
+    pub enable_abort_context: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "rocksdb-block-cache-size",
+        long,
+        help = "capacity of the rocksdb block cache shared by column families, default is 128M, \
+                it replaces the 8M block cache of every column family of old versions"
+    )]
+    pub block_cache_size: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "rocksdb-enable-statistics",
+        long,
+        help = "enable rocksdb internal statistics, and export them to metrics"
+    )]
+    pub enable_statistics: Option<bool>,
+
+    /// Options of column families, every option set overrides the default one of the same column
+    /// family.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(skip)]
+    pub column_families: Option<BTreeMap<String, ColumnFamilyConfig>>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -98,11 +244,33 @@ impl StorageConfig {
             max_total_wal_size: self
                 .max_total_wal_size
                 .unwrap_or(default.max_total_wal_size),
+            block_cache_size: self.block_cache_size.unwrap_or(default.block_cache_size),
+            enable_statistics: self.enable_statistics.unwrap_or(default.enable_statistics),
+            column_families: {
+                let mut column_families = default.column_families;
+                if let Some(custom) = self.column_families.as_ref() {
+                    for (cf_name, cf_config) in custom {
+                        column_families
+                            .entry(cf_name.clone())
+                            .or_default()
+                            .merge(cf_config);
+                    }
+                }
+                column_families
+            },
         }
     }
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+
+    pub fn enable_write_set(&self) -> bool {
+        self.enable_write_set.unwrap_or(false)
+    }
+
+    pub fn enable_abort_context(&self) -> bool {
+        self.enable_abort_context.unwrap_or(true)
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -117,6 +285,18 @@ impl ConfigModule for StorageConfig {
         if opt.storage.cache_size.is_some() {
             self.cache_size = opt.storage.cache_size;
         }
+        if opt.storage.enable_write_set.is_some() {
+            self.enable_write_set = opt.storage.enable_write_set;
+        }
+        if opt.storage.enable_abort_context.is_some() {
+            self.enable_abort_context = opt.storage.enable_abort_context;
+        }
+        if opt.storage.block_cache_size.is_some() {
+            self.block_cache_size = opt.storage.block_cache_size;
+        }
+        if opt.storage.enable_statistics.is_some() {
+            self.enable_statistics = opt.storage.enable_statistics;
+        }
         Ok(())
     }
 }
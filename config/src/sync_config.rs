@@ -1,13 +1,17 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use crate::{BaseConfig, Checkpoint, Checkpoints, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
 use network_api::PeerStrategy;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use structopt::StructOpt;
 
+/// The max block or block header count of a download request, the peers reject a request with
+/// more blocks, keep it the same as `MAX_BLOCK_REQUEST_SIZE` of the network rpc api.
+pub const MAX_BLOCK_DOWNLOAD_BATCH_SIZE: u64 = 50;
+
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct SyncConfig {
@@ -28,6 +32,61 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// max concurrent block download requests when sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-download-concurrency",
+        long,
+        help = "max concurrent block download requests when sync, default 8."
+    )]
+    block_download_concurrency: Option<usize>,
+
+    /// block count of every block download request when sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-download-batch-size",
+        long,
+        help = "block count of every block download request when sync, default 10, max 50."
+    )]
+    block_download_batch_size: Option<u64>,
+
+    /// block header count of every block header download request when sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-header-download-batch-size",
+        long,
+        help = "block header count of every block header download request when sync, default 50, max 50."
+    )]
+    block_header_download_batch_size: Option<u64>,
+
+    /// max block batches downloading or waiting for execute when sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-download-buffer-size",
+        long,
+        help = "max block batches downloading or waiting for execute when sync, default 16."
+    )]
+    block_download_buffer_size: Option<usize>,
+
+    /// max reorg depth, a branch which need rollback more blocks than it will be refused
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "max-reorg-depth",
+        long,
+        help = "max reorg depth, a branch which need rollback more blocks than it will be refused until the operator confirms the reorg by `node manager confirm-reorg`, default no limit."
+    )]
+    max_reorg_depth: Option<u64>,
+
+    /// Trusted checkpoints, merged with the network builtin checkpoints.
+    /// Format: number:block_hash[:block_accumulator_root]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[structopt(name = "checkpoint", long, number_of_values = 1)]
+    checkpoints: Vec<Checkpoint>,
+
+    #[serde(skip)]
+    #[structopt(skip)]
+    base: Option<Arc<BaseConfig>>,
 }
 
 impl SyncConfig {
@@ -38,10 +97,43 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn block_download_concurrency(&self) -> usize {
+        self.block_download_concurrency.unwrap_or(8)
+    }
+
+    pub fn block_download_batch_size(&self) -> u64 {
+        self.block_download_batch_size.unwrap_or(10)
+    }
+
+    pub fn block_header_download_batch_size(&self) -> u64 {
+        self.block_header_download_batch_size
+            .unwrap_or(MAX_BLOCK_DOWNLOAD_BATCH_SIZE)
+    }
+
+    pub fn block_download_buffer_size(&self) -> usize {
+        self.block_download_buffer_size.unwrap_or(16)
+    }
+
+    pub fn max_reorg_depth(&self) -> Option<u64> {
+        self.max_reorg_depth
+    }
+
+    fn base(&self) -> &BaseConfig {
+        self.base.as_ref().expect("Config should init.")
+    }
+
+    /// The network builtin checkpoints and the checkpoints in config.
+    pub fn checkpoints(&self) -> Result<Checkpoints> {
+        let mut checkpoints = self.base().net().checkpoints().clone();
+        checkpoints.extend(self.checkpoints.clone())?;
+        Ok(checkpoints)
+    }
 }
 
 impl ConfigModule for SyncConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
         if opt.sync.peer_select_strategy.is_some() {
             self.peer_select_strategy = opt.sync.peer_select_strategy;
         }
@@ -50,6 +142,44 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.block_download_concurrency.is_some() {
+            self.block_download_concurrency = opt.sync.block_download_concurrency;
+        }
+
+        if opt.sync.block_download_batch_size.is_some() {
+            self.block_download_batch_size = opt.sync.block_download_batch_size;
+        }
+
+        if opt.sync.block_header_download_batch_size.is_some() {
+            self.block_header_download_batch_size = opt.sync.block_header_download_batch_size;
+        }
+
+        if opt.sync.block_download_buffer_size.is_some() {
+            self.block_download_buffer_size = opt.sync.block_download_buffer_size;
+        }
+
+        if opt.sync.max_reorg_depth.is_some() {
+            self.max_reorg_depth = opt.sync.max_reorg_depth;
+        }
+
+        if !opt.sync.checkpoints.is_empty() {
+            self.checkpoints = opt.sync.checkpoints.clone();
+        }
+        ensure!(
+            self.block_download_batch_size() <= MAX_BLOCK_DOWNLOAD_BATCH_SIZE,
+            "block_download_batch_size {} is greater than the max download batch size {}",
+            self.block_download_batch_size(),
+            MAX_BLOCK_DOWNLOAD_BATCH_SIZE
+        );
+        ensure!(
+            self.block_header_download_batch_size() <= MAX_BLOCK_DOWNLOAD_BATCH_SIZE,
+            "block_header_download_batch_size {} is greater than the max download batch size {}",
+            self.block_header_download_batch_size(),
+            MAX_BLOCK_DOWNLOAD_BATCH_SIZE
+        );
+        // ensure config checkpoints do not conflict with builtin checkpoints.
+        self.checkpoints()?;
+
         Ok(())
     }
 }
@@ -3,7 +3,9 @@
 
 use super::*;
 use crate::helper::to_toml;
+use starcoin_crypto::HashValue;
 use starcoin_vm_types::gas_schedule::GasAlgebra;
+use starcoin_vm_types::genesis_config::ChainId;
 
 #[test]
 fn test_generate_and_load() -> Result<()> {
@@ -44,6 +46,25 @@ fn test_custom_chain_genesis() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_sync_download_batch_size() -> Result<()> {
+    let temp_path = temp_path();
+    let mut opt = StarcoinOpt {
+        net: Some(BuiltinNetworkID::Test.into()),
+        base_data_dir: Some(temp_path.path().to_path_buf()),
+        ..StarcoinOpt::default()
+    };
+    let config = NodeConfig::load_with_opt(&opt)?;
+    assert_eq!(
+        config.sync.block_header_download_batch_size(),
+        MAX_BLOCK_DOWNLOAD_BATCH_SIZE
+    );
+    // a request with more blocks is rejected by the peers.
+    opt.sync = SyncConfig::from_iter(vec!["sync", "--block-header-download-batch-size", "100"]);
+    assert!(NodeConfig::load_with_opt(&opt).is_err());
+    Ok(())
+}
+
 #[test]
 fn test_genesis_config_save_and_load() -> Result<()> {
     let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
@@ -64,6 +85,47 @@ fn test_api_quota_config() {
     assert_eq!("1000/s", config.to_string().as_str());
 }
 
+#[test]
+fn test_api_key_config() -> Result<()> {
+    let config: RpcConfig = toml::from_str(
+        r#"
+[[api_keys]]
+name = "internal"
+key = "secret"
+apis = "safe,account,node_manager"
+quota = "100/s"
+"#,
+    )?;
+    assert_eq!(config.api_keys.len(), 1);
+    let api_key = &config.api_keys[0];
+    assert!(api_key.apis.list_apis().contains(&Api::NodeManager));
+    assert_eq!(api_key.quota, Some("100/s".parse::<ApiQuotaConfig>()?));
+    assert!(!format!("{:?}", config).contains("secret"));
+    let config2: RpcConfig = toml::from_str(to_toml(&config)?.as_str())?;
+    assert_eq!(config, config2);
+    config.check_api_keys()?;
+
+    // two api keys with the same key are rejected.
+    let config: RpcConfig = toml::from_str(
+        r#"
+[[api_keys]]
+name = "internal"
+key = "secret"
+apis = "safe,account,node_manager"
+
+[[api_keys]]
+name = "public"
+key = "secret"
+apis = "safe"
+"#,
+    )?;
+    let err = config
+        .check_api_keys()
+        .expect_err("duplicate keys should be rejected");
+    assert!(!err.to_string().contains("secret"));
+    Ok(())
+}
+
 #[test]
 fn test_example_config_compact() -> Result<()> {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -183,3 +245,82 @@ fn test_genesis_config_security() {
         );
     }
 }
+
+#[test]
+fn test_checkpoints() -> Result<()> {
+    let hash1 = HashValue::random();
+    let hash2 = HashValue::random();
+    let checkpoint = Checkpoint::from_str(format!("10:0x{}", hash1.to_hex()).as_str())?;
+    assert_eq!(checkpoint, Checkpoint::new(10, hash1));
+    assert_eq!(
+        Checkpoint::from_str(checkpoint.to_string().as_str())?,
+        checkpoint
+    );
+    assert!(Checkpoint::from_str("10").is_err());
+
+    let mut checkpoints = Checkpoints::new(vec![
+        Checkpoint::new(100, hash2),
+        Checkpoint::new(10, hash1),
+    ])?;
+    assert_eq!(checkpoints.latest(), Some(&Checkpoint::new(100, hash2)));
+    assert_eq!(
+        checkpoints.latest_before(99),
+        Some(&Checkpoint::new(10, hash1))
+    );
+    assert!(checkpoints.check(10, hash1).is_ok());
+    assert!(checkpoints.check(10, hash2).is_err());
+    assert!(checkpoints.check(11, hash2).is_ok());
+    assert!(checkpoints
+        .extend(vec![Checkpoint::new(10, hash2)])
+        .is_err());
+
+    // the builtin checkpoints are shared by the chains of the same network.
+    let main_checkpoints = Checkpoints::builtin(BuiltinNetworkID::Main.chain_id());
+    assert!(main_checkpoints.get(0).is_some());
+    assert!(std::sync::Arc::ptr_eq(
+        &main_checkpoints,
+        &Checkpoints::builtin(BuiltinNetworkID::Main.chain_id())
+    ));
+    assert!(Checkpoints::builtin(ChainId::new(123)).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_rocksdb_column_family_config() -> Result<()> {
+    let config: StorageConfig = toml::from_str(
+        r#"
+block_cache_size = 1073741824
+
+[column_families.state_node]
+block_cache_size = 268435456
+compression_per_level = ["none", "none", "lz4", "lz4", "zstd"]
+
+[column_families.block]
+write_buffer_size = 8388608
+"#,
+    )?;
+    let rocksdb_config = config.rocksdb_config();
+    assert_eq!(rocksdb_config.block_cache_size, 1 << 30);
+    let state_node = rocksdb_config.column_family("state_node");
+    assert_eq!(state_node.block_cache_size, Some(1 << 28));
+    assert_eq!(
+        state_node.compression_per_level.as_ref().map(|v| v.len()),
+        Some(5)
+    );
+    // the options not set keep the default options of the column family.
+    assert_eq!(state_node.bloom_filter_bits, Some(10));
+    assert_eq!(state_node.write_buffer_size, Some(128 << 20));
+    let contract_event = rocksdb_config.column_family("contract_event");
+    assert_eq!(contract_event.bloom_filter_bits, Some(10));
+    assert_eq!(
+        rocksdb_config.column_family("block").write_buffer_size,
+        Some(8 << 20)
+    );
+    assert_eq!(
+        rocksdb_config.column_family("transaction"),
+        ColumnFamilyConfig::default()
+    );
+    let config2: StorageConfig = toml::from_str(to_toml(&config)?.as_str())?;
+    assert_eq!(config, config2);
+    Ok(())
+}
@@ -0,0 +1,149 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::consensus::{Consensus, ConsensusVerifyError};
+use anyhow::{ensure, Result};
+use starcoin_chain_api::ChainReader;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_state_api::{ChainStateReader, StateReaderExt, StateView};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{BlockHeader, BlockHeaderExtra};
+use starcoin_types::U256;
+use starcoin_vm_types::account_config::{association_address, CORE_CODE_ADDRESS};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{StructTag, TypeTag};
+use starcoin_vm_types::time::TimeService;
+use std::collections::BTreeSet;
+
+/// The access path of the on-chain validator set, the `0x1::Config::Config<vector<address>>`
+/// published by the association account. The association can update it by `Config::set`.
+pub fn validators_access_path() -> AccessPath {
+    AccessPath::resource_access_path(
+        association_address(),
+        StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new("Config").expect("Config is a valid identifier."),
+            name: Identifier::new("Config").expect("Config is a valid identifier."),
+            type_params: vec![TypeTag::Vector(Box::new(TypeTag::Address))],
+        },
+    )
+}
+
+/// A proof-of-authority consensus for private networks, only the validators can author blocks,
+/// and no PoW is required, the block is produced every block time target.
+/// Every block must be signed by its author, the validator set is read from the chain state
+/// at the parent block, see `validators_access_path`, and the validators in the genesis config
+/// are used before the on-chain validator set is published.
+#[derive(Clone, Debug)]
+pub struct AuthorityConsensus {
+    genesis_validators: BTreeSet<AccountAddress>,
+}
+
+impl AuthorityConsensus {
+    pub fn new(genesis_validators: Vec<AccountAddress>) -> Self {
+        Self {
+            genesis_validators: genesis_validators.into_iter().collect(),
+        }
+    }
+
+    /// Get the validators at the `state`.
+    pub fn validators(&self, state: &dyn ChainStateReader) -> Result<BTreeSet<AccountAddress>> {
+        match state.get(&validators_access_path())? {
+            Some(bytes) => Ok(
+                bcs_ext::from_bytes::<Vec<AccountAddress>>(bytes.as_slice())?
+                    .into_iter()
+                    .collect(),
+            ),
+            None => Ok(self.genesis_validators.clone()),
+        }
+    }
+
+    fn verify_author_signature(reader: &dyn ChainReader, header: &BlockHeader) -> Result<()> {
+        let signed_message = header
+            .author_signature()
+            .ok_or_else(|| anyhow::format_err!("The block has no author signature."))?;
+        ensure!(
+            signed_message.account == header.author(),
+            "The signer {} is not the block author.",
+            signed_message.account
+        );
+        ensure!(
+            signed_message.message == header.author_signing_message(),
+            "The signed message is not the hash of the unsigned header."
+        );
+        ensure!(
+            signed_message.chain_id == header.chain_id(),
+            "The chain id of the signature mismatch."
+        );
+        signed_message.check_signature()?;
+        let account_resource = reader
+            .chain_state_reader()
+            .get_account_resource(header.author())?;
+        signed_message.check_account(header.chain_id(), account_resource.as_ref())
+    }
+}
+
+/// The custom consensus does not use the PoW nonce, wait the `difficulty` as the block interval.
+pub(crate) fn wait_block_interval(difficulty: U256, time_service: &dyn TimeService) -> u32 {
+    debug!(
+        "Custom consensus sleep time in millis second: {}",
+        difficulty.low_u64()
+    );
+    time_service.sleep(difficulty.low_u64());
+    0
+}
+
+impl Consensus for AuthorityConsensus {
+    fn calculate_next_difficulty(&self, chain: &dyn ChainReader) -> Result<U256> {
+        Ok(chain.epoch().block_time_target().into())
+    }
+
+    fn solve_consensus_nonce(
+        &self,
+        _mining_hash: &[u8],
+        difficulty: U256,
+        time_service: &dyn TimeService,
+    ) -> u32 {
+        wait_block_interval(difficulty, time_service)
+    }
+
+    fn verify(&self, reader: &dyn ChainReader, header: &BlockHeader) -> Result<()> {
+        let difficulty = self.calculate_next_difficulty(reader)?;
+        if header.difficulty() != difficulty {
+            return Err(ConsensusVerifyError::VerifyDifficultyError {
+                expect: difficulty,
+                real: header.difficulty(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn verify_author(&self, reader: &dyn ChainReader, header: &BlockHeader) -> Result<()> {
+        let author = header.author();
+        if !self
+            .validators(reader.chain_state_reader())?
+            .contains(&author)
+        {
+            return Err(ConsensusVerifyError::VerifyAuthorityError { author }.into());
+        }
+        Self::verify_author_signature(reader, header).map_err(|e| {
+            ConsensusVerifyError::VerifyAuthorSignatureError {
+                author,
+                reason: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn calculate_pow_hash(
+        &self,
+        _mining_hash: &[u8],
+        _nonce: u32,
+        _extra: &BlockHeaderExtra,
+    ) -> Result<HashValue> {
+        Ok(HashValue::zero())
+    }
+}
@@ -5,6 +5,7 @@ use crate::{difficult_to_target, generate_nonce, ChainReader};
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::BlockHeaderExtra;
 use starcoin_types::{
     block::{Block, BlockHeader, BlockTemplate},
@@ -27,6 +28,13 @@ pub enum ConsensusVerifyError {
         extra: BlockHeaderExtra,
         diff: U256,
     },
+    #[error("Verify Authority Error, author {author} is not a validator")]
+    VerifyAuthorityError { author: AccountAddress },
+    #[error("Verify Author Signature Error, author: {author}, reason: {reason}")]
+    VerifyAuthorSignatureError {
+        author: AccountAddress,
+        reason: String,
+    },
 }
 
 pub trait Consensus {
@@ -61,6 +69,11 @@ pub trait Consensus {
         self.verify_header_difficulty(difficulty, header)
     }
 
+    /// Verify the block author is allowed to author the block, any author is allowed by default.
+    fn verify_author(&self, _reader: &dyn ChainReader, _header: &BlockHeader) -> Result<()> {
+        Ok(())
+    }
+
     /// Calculate the Pow hash for header
     fn calculate_pow_hash(
         &self,
@@ -250,3 +250,22 @@ fn test_next_target_increment_difficulty_compare() {
     assert!(next_target_1 < target0);
     assert!(next_target_2 > target0);
 }
+
+#[stest::test]
+fn test_custom_consensus_registry() {
+    use crate::authority::AuthorityConsensus;
+    use starcoin_types::account_address::AccountAddress;
+    use starcoin_types::block::BlockHeaderExtra;
+    use starcoin_vm_types::genesis_config::{ChainId, ConsensusStrategy};
+    use std::sync::Arc;
+
+    let chain_id = ChainId::new(201);
+    let validator = AccountAddress::random();
+    crate::register_custom_consensus(chain_id, Arc::new(AuthorityConsensus::new(vec![validator])));
+    assert!(crate::custom_consensus(chain_id).is_ok());
+    assert!(crate::custom_consensus(ChainId::new(202)).is_err());
+    let pow_hash = ConsensusStrategy::Custom
+        .calculate_pow_hash(&[0u8; 32], 0, &BlockHeaderExtra::new([0u8; 4]))
+        .unwrap();
+    assert_eq!(pow_hash, starcoin_crypto::HashValue::zero());
+}
@@ -4,15 +4,31 @@ use crate::difficult_to_target;
 use anyhow::{bail, format_err, Result};
 use starcoin_chain_api::ChainReader;
 use starcoin_logger::prelude::*;
-use starcoin_types::block::BlockHeader;
+use starcoin_types::block::{BlockHeader, BlockNumber};
 use starcoin_types::{U256, U512};
+use starcoin_vm_types::on_chain_resource::Epoch;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 
 /// Get the target of next pow work
 pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
-    let epoch = chain.epoch();
-    let current_header = chain.current_header();
+    get_next_work_required_by_headers(chain.epoch(), &chain.current_header(), |n| {
+        chain
+            .get_header_by_number(n)?
+            .ok_or_else(|| format_err!("Can not find header by number {}", n))
+    })
+}
+
+/// Get the target of next pow work after `current_header`, `get_header` returns the header of the
+/// chain by number in the difficulty window of `current_header`.
+pub fn get_next_work_required_by_headers<F>(
+    epoch: &Epoch,
+    current_header: &BlockHeader,
+    get_header: F,
+) -> Result<U256>
+where
+    F: Fn(BlockNumber) -> Result<BlockHeader>,
+{
     if current_header.number() <= 1 {
         return Ok(difficult_to_target(current_header.difficulty()));
     }
@@ -31,12 +47,7 @@ pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
             .checked_add(1)
             .ok_or_else(|| format_err!("block number overflow"))?)
         .rev()
-        .map(|n| {
-            chain
-                .get_header_by_number(n)?
-                .ok_or_else(|| format_err!("Can not find header by number {}", n))
-                .map(|header| header.into())
-        })
+        .map(|n| get_header(n).map(|header| header.into()))
         .collect::<Result<Vec<BlockDiffInfo>>>()?;
     if start_window_num != 0 {
         debug_assert!(
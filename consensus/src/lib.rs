@@ -19,6 +19,7 @@ use starcoin_vm_types::time::TimeService;
 use std::io::Write;
 
 pub mod argon;
+pub mod authority;
 pub mod cn;
 mod consensus;
 #[cfg(test)]
@@ -26,8 +27,10 @@ mod consensus_test;
 pub mod difficulty;
 pub mod dummy;
 pub mod keccak;
+pub mod registry;
 
 pub use consensus::{Consensus, ConsensusVerifyError};
+pub use registry::{custom_consensus, register_custom_consensus, ConsensusRef};
 pub use starcoin_vm_types::time::duration_since_epoch;
 
 pub fn target_to_difficulty(target: U256) -> U256 {
@@ -61,6 +64,8 @@ impl Consensus for ConsensusStrategy {
             ConsensusStrategy::Argon => ARGON.calculate_next_difficulty(reader),
             ConsensusStrategy::Keccak => KECCAK.calculate_next_difficulty(reader),
             ConsensusStrategy::CryptoNight => CRYPTONIGHT.calculate_next_difficulty(reader),
+            ConsensusStrategy::Custom => custom_consensus(reader.current_header().chain_id())?
+                .calculate_next_difficulty(reader),
         }
     }
 
@@ -83,6 +88,9 @@ impl Consensus for ConsensusStrategy {
             ConsensusStrategy::CryptoNight => {
                 CRYPTONIGHT.solve_consensus_nonce(mining_hash, difficulty, time_service)
             }
+            // The custom consensus does not use the PoW nonce, so the nonce is solved without the
+            // registered consensus, which is not available in a standalone miner client.
+            ConsensusStrategy::Custom => authority::wait_block_interval(difficulty, time_service),
         }
     }
 
@@ -92,6 +100,18 @@ impl Consensus for ConsensusStrategy {
             ConsensusStrategy::Argon => ARGON.verify(reader, header),
             ConsensusStrategy::Keccak => KECCAK.verify(reader, header),
             ConsensusStrategy::CryptoNight => CRYPTONIGHT.verify(reader, header),
+            ConsensusStrategy::Custom => {
+                custom_consensus(header.chain_id())?.verify(reader, header)
+            }
+        }
+    }
+
+    fn verify_author(&self, reader: &dyn ChainReader, header: &BlockHeader) -> Result<()> {
+        match self {
+            ConsensusStrategy::Custom => {
+                custom_consensus(header.chain_id())?.verify_author(reader, header)
+            }
+            _ => Ok(()),
         }
     }
 
@@ -108,6 +128,7 @@ impl Consensus for ConsensusStrategy {
             ConsensusStrategy::CryptoNight => {
                 CRYPTONIGHT.calculate_pow_hash(mining_hash, nonce, extra)
             }
+            ConsensusStrategy::Custom => Ok(HashValue::zero()),
         }
     }
 }
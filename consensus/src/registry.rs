@@ -0,0 +1,48 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Registry of the `ConsensusStrategy::Custom` implementations.
+//! The builtin strategies are dispatched by `ConsensusStrategy` directly, a downstream crate can
+//! provide its own consensus for a custom network by registering it with the chain id of the
+//! network before the node starts.
+
+use crate::Consensus;
+use anyhow::{format_err, Result};
+use once_cell::sync::Lazy;
+use starcoin_logger::prelude::*;
+use starcoin_vm_types::genesis_config::ChainId;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+pub type ConsensusRef = Arc<dyn Consensus + Send + Sync>;
+
+static CUSTOM_CONSENSUS: Lazy<RwLock<HashMap<ChainId, ConsensusRef>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Register the consensus implementation of `ConsensusStrategy::Custom` for the chain,
+/// return the previous registered one.
+pub fn register_custom_consensus(
+    chain_id: ChainId,
+    consensus: ConsensusRef,
+) -> Option<ConsensusRef> {
+    info!("Register custom consensus for chain {}.", chain_id);
+    CUSTOM_CONSENSUS
+        .write()
+        .expect("custom consensus lock should not be poisoned.")
+        .insert(chain_id, consensus)
+}
+
+/// Get the registered consensus implementation of `ConsensusStrategy::Custom` for the chain.
+pub fn custom_consensus(chain_id: ChainId) -> Result<ConsensusRef> {
+    CUSTOM_CONSENSUS
+        .read()
+        .expect("custom consensus lock should not be poisoned.")
+        .get(&chain_id)
+        .cloned()
+        .ok_or_else(|| {
+            format_err!(
+                "The custom consensus strategy of chain {} is not registered.",
+                chain_id
+            )
+        })
+}
@@ -5,9 +5,10 @@ use starcoin_crypto::HashValue;
 use starcoin_state_api::ChainState;
 use starcoin_types::error::BlockExecutorError;
 use starcoin_types::error::ExecutorResult;
-use starcoin_types::transaction::TransactionStatus;
+use starcoin_types::transaction::{AbortContext, TransactionStatus};
 use starcoin_types::transaction::{Transaction, TransactionInfo};
 use starcoin_vm_types::contract_event::ContractEvent;
+use starcoin_vm_types::write_set::WriteSet;
 use vm_runtime::metrics::TXN_STATUS_COUNTERS;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,6 +16,11 @@ pub struct BlockExecutedData {
     pub state_root: HashValue,
     pub txn_infos: Vec<TransactionInfo>,
     pub txn_events: Vec<Vec<ContractEvent>>,
+    /// The abort contexts of the failed txns, the same order as `txn_infos`.
+    pub txn_abort_contexts: Vec<Option<AbortContext>>,
+    /// The write sets of the txns, the same order as `txn_infos`, empty unless they are kept by
+    /// `block_execute_with_write_sets`.
+    pub txn_write_sets: Vec<WriteSet>,
 }
 
 impl Default for BlockExecutedData {
@@ -23,6 +29,8 @@ impl Default for BlockExecutedData {
             state_root: HashValue::zero(),
             txn_events: vec![],
             txn_infos: vec![],
+            txn_abort_contexts: vec![],
+            txn_write_sets: vec![],
         }
     }
 }
@@ -31,6 +39,24 @@ pub fn block_execute(
     chain_state: &dyn ChainState,
     txns: Vec<Transaction>,
     block_gas_limit: u64,
+) -> ExecutorResult<BlockExecutedData> {
+    do_block_execute(chain_state, txns, block_gas_limit, false)
+}
+
+/// Execute the block like `block_execute`, and keep the write sets of the txns.
+pub fn block_execute_with_write_sets(
+    chain_state: &dyn ChainState,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+) -> ExecutorResult<BlockExecutedData> {
+    do_block_execute(chain_state, txns, block_gas_limit, true)
+}
+
+fn do_block_execute(
+    chain_state: &dyn ChainState,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+    keep_write_sets: bool,
 ) -> ExecutorResult<BlockExecutedData> {
     let txn_outputs =
         crate::execute_block_transactions(chain_state.as_super(), txns.clone(), block_gas_limit)
@@ -43,6 +69,7 @@ pub fn block_execute(
         .zip(txn_outputs.into_iter())
     {
         let txn_hash = txn.id();
+        let abort_context = output.abort_context().cloned();
         let (write_set, events, gas_used, status) = output.into_inner();
         match status {
             TransactionStatus::Discard(status) => {
@@ -53,6 +80,9 @@ pub fn block_execute(
             }
             TransactionStatus::Keep(status) => {
                 TXN_STATUS_COUNTERS.with_label_values(&["KEEP"]).inc();
+                if keep_write_sets {
+                    executed_data.txn_write_sets.push(write_set.clone());
+                }
                 chain_state
                     .apply_write_set(write_set)
                     .map_err(BlockExecutorError::BlockChainStateErr)?;
@@ -69,6 +99,7 @@ pub fn block_execute(
                     status,
                 ));
                 executed_data.txn_events.push(events);
+                executed_data.txn_abort_contexts.push(abort_context);
             }
         };
     }
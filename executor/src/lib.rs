@@ -4,7 +4,7 @@
 extern crate log;
 
 pub use account::Account;
-pub use block_executor::{block_execute, BlockExecutedData};
+pub use block_executor::{block_execute, block_execute_with_write_sets, BlockExecutedData};
 pub use executor::*;
 pub use starcoin_transaction_builder::{
     build_accept_token_txn, build_batch_transfer_txn, build_transfer_from_association,
@@ -9,15 +9,19 @@ use starcoin_transaction_builder::DEFAULT_MAX_GAS_AMOUNT;
 use starcoin_types::account_config::stc_type_tag;
 use starcoin_types::identifier::Identifier;
 use starcoin_types::language_storage::ModuleId;
+use starcoin_vm_types::access::ModuleAccess;
+use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::account_config::core_code_address;
+use starcoin_vm_types::file_format::{Bytecode, CompiledModule};
 use starcoin_vm_types::move_resource::MoveResource;
 use starcoin_vm_types::on_chain_resource::dao::WithdrawToken;
 use starcoin_vm_types::on_chain_resource::LinearWithdrawCapability;
+use starcoin_vm_types::state_view::StateView;
 use starcoin_vm_types::token::stc::{STCUnit, STC_TOKEN_CODE, STC_TOKEN_CODE_STR};
 use starcoin_vm_types::transaction::{
     RawUserTransaction, ScriptFunction, Transaction, TransactionPayload,
 };
-use starcoin_vm_types::vm_status::KeptVMStatus;
+use starcoin_vm_types::vm_status::{AbortLocation, KeptVMStatus};
 use std::convert::TryInto;
 use test_helper::dao::dao_vote_test;
 use test_helper::executor::{execute_and_apply, prepare_genesis};
@@ -137,5 +141,47 @@ fn test_treasury_withdraw_too_many() -> Result<()> {
         ),
         "expect move abort"
     );
+    let abort_context = output1
+        .abort_context()
+        .expect("abort context should be captured");
+    assert_eq!(abort_context.abort_code, Some(26375));
+    let module_id = ModuleId::new(
+        core_code_address(),
+        Identifier::new("TreasuryWithdrawDaoProposal").unwrap(),
+    );
+    assert_eq!(
+        abort_context.location,
+        AbortLocation::Module(module_id.clone())
+    );
+    assert_eq!(
+        abort_context
+            .function_name
+            .as_ref()
+            .map(|name| name.as_str()),
+        Some("propose_withdraw")
+    );
+    // the code offset points to the abort instruction of the function.
+    let code = chain_state
+        .get(&AccessPath::from(&module_id))?
+        .expect("module should exist");
+    let module = CompiledModule::deserialize(code.as_slice())?;
+    let function_def = &module.function_defs()[abort_context.function as usize];
+    let function_handle = module.function_handle_at(function_def.function);
+    assert_eq!(
+        module.identifier_at(function_handle.name).as_str(),
+        "propose_withdraw"
+    );
+    let code_unit = function_def
+        .code
+        .as_ref()
+        .expect("function should have code");
+    assert_eq!(
+        code_unit.code[abort_context.code_offset as usize],
+        Bytecode::Abort
+    );
+    assert_eq!(
+        abort_context.offsets[0].code_offset,
+        abort_context.code_offset
+    );
     Ok(())
 }
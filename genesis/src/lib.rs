@@ -356,7 +356,11 @@ mod tests {
             if !net.is_ready() {
                 continue;
             }
-            Genesis::load_or_build(&net)?;
+            let genesis = Genesis::load_or_build(&net)?;
+            // the builtin genesis checkpoint must be updated with the generated genesis.
+            if let Some(checkpoint) = net.checkpoints().get(0) {
+                assert_eq!(checkpoint.block_hash, genesis.block().id());
+            }
         }
         Ok(())
     }
@@ -5,9 +5,13 @@ use crate::metrics::MINER_METRICS;
 use crate::task::MintTask;
 use anyhow::Result;
 use consensus::Consensus;
+use futures::channel::oneshot::Receiver;
 use futures::executor::block_on;
 use logger::prelude::*;
+use starcoin_account_api::AccountAsyncService;
+use starcoin_account_service::AccountService;
 use starcoin_config::NodeConfig;
+use starcoin_service_registry::bus::Bus;
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRef,
     ServiceRequest,
@@ -24,7 +28,9 @@ pub use create_block_template::{CreateBlockTemplateRequest, CreateBlockTemplateS
 use crypto::HashValue;
 use std::fmt;
 use thiserror::Error;
+use types::block::Block;
 pub use types::block::BlockHeaderExtra;
+use types::genesis_config::ConsensusStrategy;
 pub use types::system_events::{GenerateBlockEvent, MinedBlock, MintBlockEvent};
 
 #[derive(Debug, Error)]
@@ -52,7 +58,9 @@ pub struct MinerService {
 }
 
 impl ServiceRequest for SubmitSealRequest {
-    type Response = Result<HashValue>;
+    /// The mined block may be signed by the author asynchronously, so the block hash is returned
+    /// by the receiver.
+    type Response = Result<Receiver<Result<HashValue>>>;
 }
 
 #[derive(Clone, Debug)]
@@ -135,7 +143,7 @@ impl ServiceHandler<Self, SubmitSealRequest> for MinerService {
         &mut self,
         req: SubmitSealRequest,
         ctx: &mut ServiceContext<MinerService>,
-    ) -> Result<HashValue> {
+    ) -> Result<Receiver<Result<HashValue>>> {
         self.finish_task(req.nonce, req.extra, req.minting_blob.clone(), ctx)
             .map_err(|e| {
                 warn!(target: "miner", "process seal: {} failed: {}", req, e);
@@ -190,7 +198,7 @@ impl MinerService {
         extra: BlockHeaderExtra,
         minting_blob: Vec<u8>,
         ctx: &mut ServiceContext<MinerService>,
-    ) -> Result<HashValue> {
+    ) -> Result<Receiver<Result<HashValue>>> {
         match self.current_task.as_ref() {
             Some(task) => {
                 if task.minting_blob != minting_blob {
@@ -213,17 +221,46 @@ impl MinerService {
         }
 
         if let Some(task) = self.current_task.take() {
+            let strategy = task.block_template.strategy;
             let block = task.finish(nonce, extra);
-            let block_hash = block.id();
-            info!(target: "miner", "Mint new block: {}", block);
-            ctx.broadcast(MinedBlock(Arc::new(block)));
-            MINER_METRICS.block_mint_count.inc();
-            Ok(block_hash)
+            // the custom consensus requires the block signed by the author.
+            let account_service = if strategy == ConsensusStrategy::Custom {
+                Some(ctx.service_ref::<AccountService>()?.clone())
+            } else {
+                None
+            };
+            let bus = ctx.bus_ref().clone();
+            let fut = async move {
+                let block = match account_service {
+                    Some(account_service) => Self::sign_block(block, account_service).await?,
+                    None => block,
+                };
+                let block_hash = block.id();
+                info!(target: "miner", "Mint new block: {}", block);
+                bus.broadcast(MinedBlock(Arc::new(block)))?;
+                MINER_METRICS.block_mint_count.inc();
+                Ok(block_hash)
+            };
+            Ok(ctx.exec(fut))
         } else {
             Err(MinerError::TaskEmptyError.into())
         }
     }
 
+    async fn sign_block(
+        block: Block,
+        account_service: ServiceRef<AccountService>,
+    ) -> Result<Block> {
+        let Block { header, body } = block;
+        let signed_message = account_service
+            .sign_message(header.author(), header.author_signing_message())
+            .await?;
+        Ok(Block {
+            header: header.with_author_signature(signed_message)?,
+            body,
+        })
+    }
+
     pub fn is_minting(&self) -> bool {
         self.current_task.is_some()
     }
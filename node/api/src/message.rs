@@ -5,6 +5,10 @@ use anyhow::Result;
 use futures::channel::oneshot::Receiver;
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ServiceInfo, ServiceRequest, ServiceStatus};
+use starcoin_storage::backup::BackupManifest;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_vm_types::errmap::ErrorMapping;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub enum NodeRequest {
@@ -16,8 +20,12 @@ pub enum NodeRequest {
     StartPacemaker,
     ShutdownSystem,
     ResetNode(HashValue),
+    ConfirmReorg(HashValue),
     ReExecuteBlock(HashValue),
     DeleteBlock(HashValue),
+    CompactStorage(String),
+    Backup(PathBuf),
+    RegisterErrorMap(ErrorMapping),
 }
 
 #[derive(Debug)]
@@ -25,7 +33,9 @@ pub enum NodeResponse {
     Services(Vec<ServiceInfo>),
     Result(Result<()>),
     AsyncResult(Receiver<Result<()>>),
+    BackupResult(Receiver<Result<BackupManifest>>),
     ServiceStatus(ServiceStatus),
+    ModuleIds(Vec<ModuleId>),
 }
 
 impl ServiceRequest for NodeRequest {
@@ -7,6 +7,10 @@ use starcoin_crypto::HashValue;
 use starcoin_service_registry::{
     ActorService, ServiceHandler, ServiceInfo, ServiceRef, ServiceStatus,
 };
+use starcoin_storage::backup::BackupManifest;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_vm_types::errmap::ErrorMapping;
+use std::path::PathBuf;
 
 #[async_trait::async_trait]
 pub trait NodeAsyncService:
@@ -26,8 +30,12 @@ pub trait NodeAsyncService:
 
     async fn shutdown_system(&self) -> Result<()>;
     async fn reset_node(&self, block_hash: HashValue) -> Result<()>;
+    async fn confirm_reorg(&self, block_hash: HashValue) -> Result<()>;
     async fn re_execute_block(&self, block_hash: HashValue) -> Result<()>;
     async fn delete_block(&self, block_hash: HashValue) -> Result<()>;
+    async fn compact_storage(&self, cf_name: String) -> Result<()>;
+    async fn backup(&self, backup_dir: PathBuf) -> Result<BackupManifest>;
+    async fn register_error_map(&self, error_map: ErrorMapping) -> Result<Vec<ModuleId>>;
 }
 
 #[async_trait::async_trait]
@@ -104,6 +112,14 @@ where
         Ok(())
     }
 
+    async fn confirm_reorg(&self, block_hash: HashValue) -> Result<()> {
+        let response = self.send(NodeRequest::ConfirmReorg(block_hash)).await??;
+        if let NodeResponse::AsyncResult(receiver) = response {
+            return receiver.await?;
+        }
+        Ok(())
+    }
+
     async fn re_execute_block(&self, block_hash: HashValue) -> Result<()> {
         let response = self.send(NodeRequest::ReExecuteBlock(block_hash)).await??;
         if let NodeResponse::AsyncResult(receiver) = response {
@@ -116,4 +132,32 @@ where
         self.try_send(NodeRequest::DeleteBlock(block_hash))?;
         Ok(())
     }
+
+    async fn compact_storage(&self, cf_name: String) -> Result<()> {
+        let response = self.send(NodeRequest::CompactStorage(cf_name)).await??;
+        if let NodeResponse::AsyncResult(receiver) = response {
+            return receiver.await?;
+        }
+        Ok(())
+    }
+
+    async fn backup(&self, backup_dir: PathBuf) -> Result<BackupManifest> {
+        let response = self.send(NodeRequest::Backup(backup_dir)).await??;
+        if let NodeResponse::BackupResult(receiver) = response {
+            receiver.await?
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn register_error_map(&self, error_map: ErrorMapping) -> Result<Vec<ModuleId>> {
+        let response = self
+            .send(NodeRequest::RegisterErrorMap(error_map))
+            .await??;
+        if let NodeResponse::ModuleIds(module_ids) = response {
+            Ok(module_ids)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
 }
@@ -7,7 +7,7 @@ use crate::peer_message_handler::NodePeerMessageHandler;
 use crate::rpc_service_factory::RpcServiceFactory;
 use crate::NodeHandle;
 use actix::prelude::*;
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 use futures::channel::oneshot;
 use futures::executor::block_on;
 use futures_timer::Delay;
@@ -17,6 +17,7 @@ use starcoin_block_relayer::BlockRelayer;
 use starcoin_chain_notify::ChainNotifyHandlerService;
 use starcoin_chain_service::ChainReaderService;
 use starcoin_config::NodeConfig;
+use starcoin_consensus::authority::AuthorityConsensus;
 use starcoin_genesis::{Genesis, GenesisError};
 use starcoin_logger::prelude::*;
 use starcoin_logger::structured_log::set_global_logger;
@@ -36,22 +37,35 @@ use starcoin_service_registry::{
     ActorService, RegistryAsyncService, RegistryService, ServiceContext, ServiceFactory,
     ServiceHandler, ServiceRef,
 };
+use starcoin_state_api::StateView;
 use starcoin_state_service::ChainStateService;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::backup;
 use starcoin_storage::block_info::BlockInfoStore;
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::errors::StorageInitError;
+use starcoin_storage::metrics::RocksdbCollector;
 use starcoin_storage::storage::StorageInstance;
-use starcoin_storage::{BlockStore, Storage};
+use starcoin_storage::{BlockStore, ErrorMapStore, Storage};
 use starcoin_stratum::service::{StratumService, StratumServiceFactory};
 use starcoin_stratum::stratum::{Stratum, StratumFactory};
 use starcoin_sync::announcement::AnnouncementService;
-use starcoin_sync::block_connector::{BlockConnectorService, ExecuteRequest, ResetRequest};
+use starcoin_sync::block_connector::{
+    BlockConnectorService, ConfirmReorgRequest, ExecuteRequest, ResetRequest,
+};
 use starcoin_sync::sync::SyncService;
 use starcoin_sync::txn_sync::TxnSyncService;
 use starcoin_sync::verified_rpc_client::VerifiedRpcClient;
 use starcoin_txpool::TxPoolActorService;
+use starcoin_types::genesis_config::ConsensusStrategy;
+use starcoin_types::language_storage::ModuleId;
 use starcoin_types::system_events::SystemStarted;
+use starcoin_vm_types::access::ModuleAccess;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::errmap::ErrorMapping;
+use starcoin_vm_types::file_format::{CompiledModule, SignatureToken};
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -129,6 +143,17 @@ impl ServiceHandler<Self, NodeRequest> for NodeService {
                 let receiver = ctx.exec(fut);
                 NodeResponse::AsyncResult(receiver)
             }
+            NodeRequest::ConfirmReorg(block_hash) => {
+                let connect_service = ctx.service_ref::<BlockConnectorService>()?.clone();
+                let fut = async move {
+                    info!("Prepare to confirm the reorg to {}", block_hash);
+                    connect_service
+                        .send(ConfirmReorgRequest { block_hash })
+                        .await?
+                };
+                let receiver = ctx.exec(fut);
+                NodeResponse::AsyncResult(receiver)
+            }
             NodeRequest::ReExecuteBlock(block_hash) => {
                 let storage = self
                     .registry
@@ -182,10 +207,109 @@ impl ServiceHandler<Self, NodeRequest> for NodeService {
                         .and_then(|_| storage.delete_block(block_hash)),
                 )
             }
+            NodeRequest::CompactStorage(cf_name) => {
+                let db = self
+                    .registry
+                    .get_shared_sync::<Arc<Storage>>()
+                    .expect("Storage must exist.")
+                    .db()
+                    .ok_or_else(|| format_err!("Storage do not have a rocksdb instance."))?;
+                info!("Prepare to compact storage column family {}", cf_name);
+                let (sender, receiver) = oneshot::channel();
+                // Compaction may take a long time, do not block the node service.
+                std::thread::spawn(move || {
+                    let result = db.compact_cf(cf_name.as_str());
+                    info!(
+                        "Compact storage column family {} result: {:?}",
+                        cf_name, result
+                    );
+                    if sender.send(result).is_err() {
+                        warn!("Send compact storage result failed.");
+                    }
+                });
+                NodeResponse::AsyncResult(receiver)
+            }
+            NodeRequest::Backup(backup_dir) => {
+                let chain_db = self
+                    .registry
+                    .get_shared_sync::<Arc<Storage>>()
+                    .expect("Storage must exist.")
+                    .db()
+                    .ok_or_else(|| format_err!("Storage do not have a rocksdb instance."))?;
+                let account_db = self
+                    .registry
+                    .get_shared_sync::<AccountStorage>()
+                    .expect("AccountStorage must exist.")
+                    .db();
+                info!("Prepare to backup storage to {:?}", backup_dir);
+                let (sender, receiver) = oneshot::channel();
+                std::thread::spawn(move || {
+                    let result = backup::backup(
+                        chain_db.as_ref(),
+                        account_db.as_deref(),
+                        backup_dir.as_path(),
+                    );
+                    if let Err(e) = &result {
+                        error!("Backup storage to {:?} error: {:?}", backup_dir, e);
+                    }
+                    if sender.send(result).is_err() {
+                        warn!("Send backup result failed.");
+                    }
+                });
+                NodeResponse::BackupResult(receiver)
+            }
+            NodeRequest::RegisterErrorMap(error_map) => {
+                let storage = self
+                    .registry
+                    .get_shared_sync::<Arc<Storage>>()
+                    .expect("Storage must exist.");
+                NodeResponse::ModuleIds(register_error_map(storage, error_map)?)
+            }
         })
     }
 }
 
+/// Save the error maps of the modules deployed on the main chain, the reason codes of each map
+/// must be u64 constants of the module, all the maps are checked before any of them is saved.
+/// The error maps are node local, they are not a part of the chain state and are not synced.
+fn register_error_map(storage: Arc<Storage>, error_map: ErrorMapping) -> Result<Vec<ModuleId>> {
+    let head_id = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info should exist."))?
+        .main;
+    let head = storage
+        .get_block_header_by_hash(head_id)?
+        .ok_or_else(|| format_err!("Can not find block header by {}", head_id))?;
+    let state_view = ChainStateDB::new(storage.clone(), Some(head.state_root()));
+    for (module_id, module_error_map) in &error_map.module_error_maps {
+        let code = state_view
+            .get(&AccessPath::from(module_id))?
+            .ok_or_else(|| format_err!("Module {} is not deployed", module_id))?;
+        let module = CompiledModule::deserialize(code.as_slice())?;
+        let reason_codes: BTreeSet<u64> = module
+            .constant_pool()
+            .iter()
+            .filter(|constant| constant.type_ == SignatureToken::U64)
+            .filter_map(|constant| bcs_ext::from_bytes(constant.data.as_slice()).ok())
+            .collect();
+        for reason_code in module_error_map.keys() {
+            ensure!(
+                reason_codes.contains(reason_code),
+                "reason code {} is not a constant of module {}",
+                reason_code,
+                module_id
+            );
+        }
+    }
+    let mut module_ids = vec![];
+    for (module_id, module_error_map) in error_map.module_error_maps {
+        info!("Register error map of module {}", module_id);
+        storage.save_module_error_map(module_id.clone(), module_error_map)?;
+        module_ids.push(module_id);
+    }
+    Ok(module_ids)
+}
+
 impl NodeService {
     pub fn launch(
         config: Arc<NodeConfig>,
@@ -215,6 +339,14 @@ impl NodeService {
             }
         }
 
+        if config.rpc.audit.enable() {
+            logger_handle.enable_audit_file(
+                config.rpc.get_audit_log_file(),
+                config.rpc.audit.max_file_size(),
+                config.rpc.audit.max_backup(),
+            );
+        }
+
         if config.logger.disable_stderr() {
             logger_handle.disable_stderr();
         } else {
@@ -257,6 +389,29 @@ impl NodeService {
         Ok(NodeHandle::new(join_handle, node_service, registry))
     }
 
+    /// Register the proof-of-authority consensus when the network use the custom consensus strategy
+    /// with authority validators in genesis config, unless a downstream crate has registered its own
+    /// consensus for the chain before the node starts.
+    fn register_custom_consensus(config: &NodeConfig) {
+        let net = config.net();
+        let genesis_config = net.genesis_config();
+        if genesis_config.consensus() == ConsensusStrategy::Custom
+            && !genesis_config.authority_validators.is_empty()
+            && starcoin_consensus::custom_consensus(net.chain_id()).is_err()
+        {
+            info!(
+                "Register authority consensus with validators: {:?}",
+                genesis_config.authority_validators
+            );
+            starcoin_consensus::register_custom_consensus(
+                net.chain_id(),
+                Arc::new(AuthorityConsensus::new(
+                    genesis_config.authority_validators.clone(),
+                )),
+            );
+        }
+    }
+
     async fn init_system(
         config: Arc<NodeConfig>,
         logger_handle: Arc<LoggerHandle>,
@@ -267,11 +422,22 @@ impl NodeService {
         registry.put_shared(logger_handle).await?;
 
         let bus = registry.service_ref::<BusService>().await?;
-        let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
-            CacheStorage::new_with_capacity(config.storage.cache_size()),
-            DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
-        ))?);
+        let storage = Arc::new(
+            Storage::new(StorageInstance::new_cache_and_db_instance(
+                CacheStorage::new_with_capacity(config.storage.cache_size()),
+                DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
+            ))?
+            .with_write_set(config.storage.enable_write_set())
+            .with_abort_context(config.storage.enable_abort_context()),
+        );
+        backup::check_restored_storage(storage.as_ref())?;
+        if let Some(db) = storage.db() {
+            if let Err(e) = RocksdbCollector::register(&db) {
+                warn!("Register rocksdb metrics collector failed: {:?}", e);
+            }
+        }
         registry.put_shared(storage.clone()).await?;
+        Self::register_custom_consensus(config.as_ref());
         let (chain_info, genesis) =
             Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
 
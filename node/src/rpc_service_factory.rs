@@ -53,7 +53,7 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                 )
             });
         let txpool_service = ctx.get_shared::<TxPoolService>()?;
-        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone(), storage.clone()));
 
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
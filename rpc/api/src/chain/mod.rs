@@ -4,8 +4,9 @@
 pub use self::gen_client::Client as ChainClient;
 use crate::types::pubsub::EventFilter;
 use crate::types::{
-    BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventResponse, TransactionInfoView, TransactionView,
+    BlockHeaderView, BlockStateDiffView, BlockSummaryView, BlockView, ChainId, ChainInfoView,
+    EpochUncleSummaryView, PageOption, PageView, TransactionEventResponse, TransactionInfoView,
+    TransactionView, TransactionWriteSetView,
 };
 use crate::FutureResult;
 use jsonrpc_core::Result;
@@ -44,6 +45,11 @@ pub trait ChainApi {
         number: Option<BlockNumber>,
         count: u64,
     ) -> FutureResult<Vec<BlockView>>;
+    /// Get a page of blocks in descending order of number, the first page starts at the head block.
+    /// The limit is at most the `block_query_max_range` of the node, the default limit is lowered
+    /// to it if it is smaller.
+    #[rpc(name = "chain.get_blocks_page")]
+    fn get_blocks_page(&self, page: Option<PageOption>) -> FutureResult<PageView<BlockView>>;
     #[rpc(name = "chain.get_block_info_by_number")]
     fn get_block_info_by_number(&self, number: BlockNumber) -> FutureResult<Option<BlockInfo>>;
     /// Get chain transactions
@@ -86,6 +92,34 @@ pub trait ChainApi {
         option: Option<GetEventOption>,
     ) -> FutureResult<Vec<TransactionEventResponse>>;
 
+    /// Get a page of the events matching `filter`, from latest to oldest. The `limit` of the
+    /// filter is ignored, the page limit applies instead. The cursor is only valid for the same
+    /// filter.
+    #[rpc(name = "chain.get_events_page")]
+    fn get_events_page(
+        &self,
+        filter: EventFilter,
+        option: Option<GetEventOption>,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<TransactionEventResponse>>;
+
+    /// Get the state changes of the txn with the old and new values, the node should run with
+    /// `--enable-write-set` when the txn is executed.
+    #[rpc(name = "chain.get_txn_write_set")]
+    fn get_txn_write_set(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> FutureResult<Option<TransactionWriteSetView>>;
+
+    /// Get the state changes of all the txns in the block.
+    #[rpc(name = "chain.get_block_state_diff")]
+    fn get_block_state_diff(
+        &self,
+        block_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> FutureResult<Option<BlockStateDiffView>>;
+
     /// Get current epoch info.
     #[rpc(name = "chain.epoch")]
     fn current_epoch(&self) -> FutureResult<EpochInfo>;
@@ -134,3 +168,10 @@ pub struct GetEventOption {
     #[serde(default)]
     pub decode: bool,
 }
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct GetWriteSetOption {
+    /// Decode the resources with their types.
+    #[serde(default)]
+    pub decode: bool,
+}
@@ -8,8 +8,10 @@ use jsonrpc_derive::rpc;
 use starcoin_abi_decoder::DecodedMoveValue;
 use starcoin_abi_types::{ModuleABI, ScriptFunctionABI, StructABI};
 use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::errmap::ErrorDescription;
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
+use std::collections::BTreeMap;
 
 #[rpc]
 pub trait ContractApi {
@@ -50,4 +52,11 @@ pub trait ContractApi {
     fn resolve_struct(&self, struct_tag: StructTagView) -> FutureResult<StructABI>;
     #[rpc(name = "contract.resolve_module")]
     fn resolve_module(&self, module_id: ModuleIdView) -> FutureResult<ModuleABI>;
+
+    /// get the registered error descriptions of module, keyed by the reason code.
+    #[rpc(name = "contract.get_error_map")]
+    fn get_error_map(
+        &self,
+        module_id: ModuleIdView,
+    ) -> FutureResult<Option<BTreeMap<u64, ErrorDescription>>>;
 }
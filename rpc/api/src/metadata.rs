@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use jsonrpc_pubsub::{PubSubMetadata, Session};
+use starcoin_rpc_middleware::{ApiKeyMetadata, AuditMetadata};
 use std::sync::Arc;
 
 /// RPC methods metadata.
@@ -12,20 +13,39 @@ pub struct Metadata {
     /// Request PubSub Session
     pub session: Option<Arc<Session>>,
     pub user: Option<String>,
+    /// The api key presented by the caller.
+    pub api_key: Option<String>,
+    /// The name of the api key, set after the key is verified.
+    pub key_name: Option<String>,
 }
 
 impl Metadata {
     pub fn new(session: Arc<Session>) -> Self {
         Self {
             session: Some(session),
-            user: None,
+            ..Default::default()
         }
     }
 }
 
 impl jsonrpc_core::Metadata for Metadata {}
+impl ApiKeyMetadata for Metadata {
+    fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    fn set_key_name(&mut self, name: String) {
+        self.key_name = Some(name);
+    }
+}
 impl PubSubMetadata for Metadata {
     fn session(&self) -> Option<Arc<Session>> {
         self.session.clone()
     }
 }
+
+impl AuditMetadata for Metadata {
+    fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as NodeManagerClient;
+use crate::types::{BackupManifestView, ModuleIdView, StrView};
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
 use starcoin_crypto::HashValue;
@@ -26,6 +27,11 @@ pub trait NodeManagerApi {
     #[rpc(name = "node_manager.reset_to_block")]
     fn reset_to_block(&self, block_hash: HashValue) -> FutureResult<()>;
 
+    /// Switch the main chain to the executed branch of `block_hash`, to confirm a reorg which is
+    /// refused by the max reorg depth limit.
+    #[rpc(name = "node_manager.confirm_reorg")]
+    fn confirm_reorg(&self, block_hash: HashValue) -> FutureResult<()>;
+
     /// Re execute the block of `block_id` for fix database
     #[rpc(name = "node_manager.re_execute_block")]
     fn re_execute_block(&self, block_hash: HashValue) -> FutureResult<()>;
@@ -41,4 +47,21 @@ pub trait NodeManagerApi {
     /// Delete block of block_id
     #[rpc(name = "node_manager.delete_block")]
     fn delete_block(&self, block_hash: HashValue) -> FutureResult<()>;
+
+    /// Manually compact the storage column family `cf_name`, return after the compaction finished.
+    #[rpc(name = "node_manager.compact_storage")]
+    fn compact_storage(&self, cf_name: String) -> FutureResult<()>;
+
+    /// Backup the chain and account databases of the running node to `backup_dir`,
+    /// `backup_dir` is an absolute path of the node machine and should be empty or not exist.
+    #[rpc(name = "node_manager.backup")]
+    fn backup(&self, backup_dir: String) -> FutureResult<BackupManifestView>;
+
+    /// Register the error map of modules deployed on chain, so the node can explain the abort
+    /// codes of them, the error_map parameter is the hex of BCS serialized ErrorMapping,
+    /// return the modules registered.
+    /// The error map is only saved in the local node database, it is not published on chain and
+    /// is not synced to other nodes.
+    #[rpc(name = "node_manager.register_error_map")]
+    fn register_error_map(&self, error_map: StrView<Vec<u8>>) -> FutureResult<Vec<ModuleIdView>>;
 }
@@ -3,8 +3,8 @@
 
 pub use self::gen_client::Client as StateClient;
 use crate::types::{
-    AccountStateSetView, CodeView, ListCodeView, ListResourceView, ResourceView,
-    StateWithProofView, StrView,
+    AccountStateSetView, CodeItemView, CodeView, ListCodeView, ListResourceView, PageOption,
+    PageView, ResourceItemView, ResourceView, StateWithProofView, StrView,
 };
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
@@ -76,6 +76,24 @@ pub trait StateApi {
         addr: AccountAddress,
         option: Option<ListCodeOption>,
     ) -> FutureResult<ListCodeView>;
+
+    /// Get a page of the resources of `addr`, in order of the key hash.
+    #[rpc(name = "state.list_resource_page")]
+    fn list_resource_page(
+        &self,
+        addr: AccountAddress,
+        option: Option<ListResourceOption>,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<ResourceItemView>>;
+
+    /// Get a page of the codes of `addr`, in order of the key hash.
+    #[rpc(name = "state.list_code_page")]
+    fn list_code_page(
+        &self,
+        addr: AccountAddress,
+        option: Option<ListCodeOption>,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<CodeItemView>>;
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq)]
@@ -98,7 +116,7 @@ pub struct ListResourceOption {
     pub decode: bool,
     /// The state tree root, default is the latest block state root
     pub state_root: Option<HashValue>,
-    //TODO support filter by type and pagination
+    //TODO support filter by type
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq)]
@@ -107,5 +125,5 @@ pub struct ListCodeOption {
     pub resolve: bool,
     /// The state tree root, default is the latest block state root
     pub state_root: Option<HashValue>,
-    //TODO support filter by type and pagination
+    //TODO support filter by type
 }
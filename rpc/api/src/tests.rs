@@ -1,4 +1,6 @@
-use crate::types::{ContractCall, TransactionArgumentView, TypeTagView};
+use crate::types::{
+    ContractCall, PageOption, PageView, StrView, TransactionArgumentView, TypeTagView,
+};
 use starcoin_vm_types::token::stc::stc_type_tag;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
 
@@ -32,3 +34,21 @@ fn test_deserialize() {
     let v = serde_json::from_str::<ContractCall>(s).unwrap();
     println!("{:?}", v);
 }
+
+#[test]
+fn test_page_view() {
+    let page = PageView::from_items(0u64..10, 4, |i| *i).unwrap();
+    assert_eq!(page.data, vec![0, 1, 2, 3]);
+    let option = PageOption::new(Some(4), page.next_cursor);
+    let start = option.position::<u64>().unwrap().unwrap();
+    assert_eq!(start, 4);
+    let page = PageView::from_items(start..10, option.limit(), |i| *i).unwrap();
+    assert_eq!(page.data, vec![4, 5, 6, 7]);
+    let page = PageView::from_items(8u64..10, option.limit(), |i| *i).unwrap();
+    assert_eq!(page.data, vec![8, 9]);
+    assert!(page.next_cursor.is_none());
+
+    let option = PageOption::new(Some(0), Some(StrView(vec![1])));
+    assert_eq!(option.limit(), 1);
+    assert!(option.position::<u64>().is_err());
+}
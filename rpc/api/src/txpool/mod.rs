@@ -3,10 +3,11 @@
 
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
 use starcoin_types::transaction::SignedUserTransaction;
 
 pub use self::gen_client::Client as TxPoolClient;
-use crate::types::{SignedUserTransactionView, StrView};
+use crate::types::{PageOption, PageView, SignedUserTransactionView, StrView};
 use starcoin_crypto::HashValue;
 use starcoin_txpool_api::TxPoolStatus;
 use starcoin_types::account_address::AccountAddress;
@@ -31,9 +32,21 @@ pub trait TxPoolApi {
         max_len: Option<u32>,
     ) -> FutureResult<Vec<SignedUserTransactionView>>;
 
+    /// get a page of pending txns in txpool of given sender, in order of sequence number.
+    #[rpc(name = "txpool.pending_txns_of_sender_page")]
+    fn pending_txns_page(
+        &self,
+        addr: AccountAddress,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<SignedUserTransactionView>>;
+
     /// get pending txn in txpool by its hash value
     #[rpc(name = "txpool.pending_txn")]
-    fn pending_txn(&self, txn_hash: HashValue) -> FutureResult<Option<SignedUserTransactionView>>;
+    fn pending_txn(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetPendingTxnOption>,
+    ) -> FutureResult<Option<SignedUserTransactionView>>;
 
     /// Returns next valid sequence number for given sender
     /// or `None` if there are no pending transactions from that sender in txpool.
@@ -44,3 +57,9 @@ pub trait TxPoolApi {
     #[rpc(name = "txpool.state")]
     fn state(&self) -> FutureResult<TxPoolStatus>;
 }
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct GetPendingTxnOption {
+    #[serde(default)]
+    pub decode: bool,
+}
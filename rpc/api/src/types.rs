@@ -33,7 +33,9 @@ use starcoin_types::peer_info::{PeerId, PeerInfo};
 use starcoin_types::proof::SparseMerkleProof;
 use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::authenticator::{AuthenticationKey, TransactionAuthenticator};
-use starcoin_types::transaction::{RawUserTransaction, ScriptFunction, TransactionArgument};
+use starcoin_types::transaction::{
+    AbortContext, RawUserTransaction, ScriptFunction, TransactionArgument,
+};
 use starcoin_types::vm_error::AbortLocation;
 use starcoin_types::U256;
 use starcoin_vm_types::access_path::AccessPath;
@@ -109,6 +111,100 @@ pub struct ListCodeView {
     pub codes: BTreeMap<Identifier, CodeView>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ResourceItemView {
+    pub struct_tag: StructTagView,
+    pub resource: ResourceView,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CodeItemView {
+    pub name: Identifier,
+    pub code: CodeView,
+}
+
+/// The position of a page in a list. It is opaque to the client, which passes the `next_cursor`
+/// of a page as the `cursor` of the request for the next page.
+pub type Cursor = StrView<Vec<u8>>;
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 100;
+pub const MAX_PAGE_LIMIT: u64 = 1000;
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq)]
+#[serde(default)]
+pub struct PageOption {
+    /// Max count of items in the page, default is 100, at most 1000.
+    pub limit: Option<u64>,
+    /// The `next_cursor` of the previous page, absent for the first page.
+    pub cursor: Option<Cursor>,
+}
+
+impl PageOption {
+    pub fn new(limit: Option<u64>, cursor: Option<Cursor>) -> Self {
+        Self { limit, cursor }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .max(1)
+            .min(MAX_PAGE_LIMIT) as usize
+    }
+
+    /// Decode the position of the list from the cursor.
+    pub fn position<T: DeserializeOwned>(&self) -> Result<Option<T>, jsonrpc_core::Error> {
+        self.cursor
+            .as_ref()
+            .map(|cursor| {
+                bcs_ext::from_bytes(cursor.0.as_slice())
+                    .map_err(|e| crate::errors::invalid_params("cursor", e))
+            })
+            .transpose()
+    }
+}
+
+/// Encode the position of the list into a cursor.
+pub fn to_cursor<T: Serialize>(position: &T) -> anyhow::Result<Cursor> {
+    Ok(StrView(bcs_ext::to_bytes(position)?))
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PageView<T> {
+    pub data: Vec<T>,
+    /// The cursor of the next page, absent if it is the last page.
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> PageView<T> {
+    /// Take a page of at most `limit` items from `items`, which start at the requested position.
+    /// `position` returns the position of an item, the one of the first item left out is the
+    /// cursor of the next page.
+    pub fn from_items<I, P, F>(items: I, limit: usize, position: F) -> anyhow::Result<Self>
+    where
+        I: IntoIterator<Item = T>,
+        P: Serialize,
+        F: Fn(&T) -> P,
+    {
+        let mut items = items.into_iter();
+        let data: Vec<T> = items.by_ref().take(limit).collect();
+        let next_cursor = items
+            .next()
+            .map(|item| to_cursor(&position(&item)))
+            .transpose()?;
+        Ok(Self { data, next_cursor })
+    }
+
+    pub fn try_map<U, E, F>(self, f: F) -> Result<PageView<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        Ok(PageView {
+            data: self.data.into_iter().map(f).collect::<Result<_, _>>()?,
+            next_cursor: self.next_cursor,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AnnotatedMoveStructView {
     pub abilities: u8,
@@ -805,6 +901,15 @@ pub struct TransactionInfoView {
     /// failures and Move abort's receive more detailed information. But other errors are generally
     /// categorized with no status code or other information
     pub status: TransactionStatusView,
+
+    /// The explanation of the abort code, if the status is `MoveAbort`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abort_explain: Option<MoveAbortExplain>,
+
+    /// The abort location, function and code offset recorded by the executor, if the status is
+    /// `MoveAbort` or `ExecutionFailure`, and the node runs with `--enable-abort-context`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abort_context: Option<AbortContext>,
 }
 
 impl TransactionInfoView {
@@ -827,6 +932,8 @@ impl TransactionInfoView {
             event_root_hash: txn_info.event_root_hash(),
             gas_used: txn_info.gas_used().into(),
             status: TransactionStatusView::from(txn_info.status().clone()),
+            abort_explain: None,
+            abort_context: None,
         })
     }
 }
@@ -963,7 +1070,7 @@ impl TransactionEventView {
 }
 
 use starcoin_vm_types::move_resource::MoveResource;
-pub use vm_status_translator::VmStatusExplainView;
+pub use vm_status_translator::{MoveAbortExplain, VmStatusExplainView};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DryRunOutputView {
@@ -1032,6 +1139,52 @@ pub enum WriteOpView {
     Value,
 }
 
+/// A state change made by a txn.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateChangeView {
+    pub access_path: AccessPath,
+    pub action: WriteOpView,
+    /// The value before the txn, absent if the txn creates it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<WriteOpValueView>,
+    /// The value after the txn, absent if the txn deletes it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<WriteOpValueView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionWriteSetView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    pub transaction_hash: HashValue,
+    pub transaction_index: u32,
+    pub changes: Vec<StateChangeView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockStateDiffView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    /// The state changes of the txns, in order of execution.
+    pub txns: Vec<TransactionWriteSetView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupFileView {
+    pub path: String,
+    pub size: StrView<u64>,
+    pub checksum: HashValue,
+}
+
+/// The manifest of a node database backup.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupManifestView {
+    pub head_block_hash: HashValue,
+    pub head_block_number: StrView<BlockNumber>,
+    pub created_at: StrView<u64>,
+    pub files: Vec<BackupFileView>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UncleSummaryView {
     /// total uncle
@@ -11,7 +11,7 @@ use starcoin_crypto::HashValue;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_types::U256;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use std::convert::TryInto;
@@ -30,6 +30,8 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Main chain reorg, or reorg refused by the max reorg depth limit.
+    ChainReorg,
 }
 
 /// Subscription result.
@@ -41,6 +43,7 @@ pub enum Result {
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventResponse>),
     MintBlock(Box<MintBlockEvent>),
+    ChainReorg(Box<ChainReorgEvent>),
 }
 
 impl Serialize for Result {
@@ -53,6 +56,7 @@ impl Serialize for Result {
             Result::Event(ref evt) => evt.serialize(serializer),
             Result::TransactionHash(ref hash) => hash.serialize(serializer),
             Result::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Result::ChainReorg(ref reorg) => reorg.serialize(serializer),
         }
     }
 }
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::pubsub_client::PubSubClient;
+use crate::{reconnect_delay, ConnectionProvider};
 use actix::prelude::*;
 use actix::AsyncContext;
 use futures::channel::oneshot;
@@ -31,54 +31,218 @@ impl From<BlockView> for ThinHeadBlock {
     }
 }
 
-#[derive(Debug)]
+/// The interval of polling new blocks if the connection does not support subscription.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// The max blocks requested by one `chain.get_blocks_by_number` call while catching up,
+/// the server may still return less blocks than it.
+const CATCH_UP_PAGE_SIZE: u64 = 32;
+
+/// Watches the new blocks to resolve `WatchBlock` and `WatchTxn`, the subscription is
+/// reestablished after the connection is lost, and the missed blocks are fetched, so the watchers
+/// keep pending across reconnects until the watcher is stopped or they time out.
 pub struct ChainWatcher {
+    provider: ConnectionProvider,
     watched_blocks: HashMap<BlockNumber, Vec<Responder>>,
-    watched_txns: HashMap<HashValue, Responder>,
+    watched_txns: HashMap<HashValue, Vec<Responder>>,
+    /// The number of the latest block seen.
+    latest_block: Option<BlockNumber>,
+    catching_up: bool,
 }
+
 impl ChainWatcher {
-    pub fn launch() -> Addr<Self> {
+    pub(crate) fn launch(provider: ConnectionProvider) -> Addr<Self> {
         let actor = Self {
+            provider,
             watched_txns: Default::default(),
             watched_blocks: Default::default(),
+            latest_block: None,
+            catching_up: false,
         };
         actor.start()
     }
 
-    fn start_subscribe(&mut self, client: PubSubClient, ctx: &mut Context<Self>) {
-        let inner_client = client.clone();
-        async move { inner_client.subscribe_new_block().await }
-            .into_actor(self)
-            .then(|res, act, ctx| {
-                match res {
-                    Ok(s) => {
-                        ctx.add_stream(s);
+    fn start_subscribe(&mut self, attempt: usize, ctx: &mut Context<Self>) {
+        let provider = self.provider.clone();
+        async move {
+            let inner = provider.get_client(0).await?;
+            inner.pubsub_client.subscribe_new_block().await
+        }
+        .into_actor(self)
+        .then(move |res, act, ctx| {
+            match res {
+                Ok(s) => {
+                    ctx.add_stream(s);
+                    act.catch_up(ctx);
+                }
+                Err(e) => {
+                    if let RpcError::Other(_) = &e {
+                        act.provider.disconnect();
+                    }
+                    let delay = reconnect_delay(attempt);
+                    // TODO: figure out why this error cannot printed.
+                    error!(
+                        "fail to subscribe new block event, err: {}, retry after {:?}",
+                        &e, delay
+                    );
+                    ctx.notify_later(
+                        StartSubscribe {
+                            attempt: attempt + 1,
+                        },
+                        delay,
+                    );
+                }
+            }
+            async {}.into_actor(act)
+        })
+        .spawn(ctx);
+    }
+
+    /// Fetch the blocks after the latest seen one, only if some blocks or txns are watched.
+    fn catch_up(&mut self, ctx: &mut Context<Self>) {
+        if self.catching_up {
+            return;
+        }
+        self.catching_up = true;
+        let provider = self.provider.clone();
+        let latest_block = self.latest_block;
+        let watching = !self.watched_blocks.is_empty() || !self.watched_txns.is_empty();
+        async move {
+            let inner = provider.get_client(0).await?;
+            let head = inner.chain_client.info().await?.head.number.0;
+            let mut blocks = vec![];
+            if let (Some(latest_block), true) = (latest_block, watching) {
+                // page backward from the head until the whole gap is fetched.
+                let start = latest_block + 1;
+                let mut end = head;
+                while end >= start {
+                    let count = std::cmp::min(end - start + 1, CATCH_UP_PAGE_SIZE);
+                    let page = inner
+                        .chain_client
+                        .get_blocks_by_number(Some(end), count)
+                        .await?;
+                    let lowest = match page.iter().map(|b| b.header.number.0).min() {
+                        Some(lowest) => lowest,
+                        None => break,
+                    };
+                    blocks.extend(
+                        page.into_iter()
+                            .filter(|b| b.header.number.0 >= start)
+                            .map(ThinHeadBlock::from),
+                    );
+                    if lowest <= start {
+                        break;
+                    }
+                    end = lowest - 1;
+                }
+                blocks.sort_by_key(|b| b.header.number.0);
+            }
+            Ok::<_, RpcError>((head, blocks))
+        }
+        .into_actor(self)
+        .then(|res, act, ctx| {
+            act.catching_up = false;
+            match res {
+                Ok((head, blocks)) => {
+                    for block in blocks {
+                        act.notify_watchers(block);
+                    }
+                    act.update_latest_block(head);
+                }
+                Err(e) => {
+                    if let RpcError::Other(_) = &e {
+                        act.provider.disconnect();
                     }
-                    Err(e) => {
-                        // TODO: figure out why this error cannot printed.
-                        error!("fail to subscribe new block event, err: {}", &e);
-                        ctx.notify_later(StartSubscribe { client }, Duration::from_secs(1));
+                    warn!("fail to catch up new blocks, err: {}", &e);
+                    // the polling retries by itself, the subscription needs a retry to fetch the
+                    // blocks missed during the reconnection.
+                    if act.provider.conn_source().support_pubsub() {
+                        ctx.run_later(POLL_INTERVAL, |act, ctx| act.catch_up(ctx));
                     }
                 }
-                async {}.into_actor(act)
-            })
-            .spawn(ctx);
+            }
+            async {}.into_actor(act)
+        })
+        .spawn(ctx);
+    }
+
+    fn notify_watchers(&mut self, b: ThinHeadBlock) {
+        self.update_latest_block(b.header.number.0);
+        if let Some(responders) = self.watched_blocks.remove(&b.header.number.0) {
+            for r in responders {
+                let _ = r.send(Ok(b.clone()));
+            }
+        }
+        for txn in &b.txn_hashes {
+            if let Some(responders) = self.watched_txns.remove(txn) {
+                for r in responders {
+                    let _ = r.send(Ok(b.clone()));
+                }
+            }
+        }
+    }
+
+    /// Fail all the pending watchers, it is only called when the watcher is stopped.
+    fn fail_watchers(&mut self, reason: &str) {
+        for (_, responders) in self.watched_blocks.drain() {
+            for r in responders {
+                let _ = r.send(Err(anyhow::anyhow!(reason.to_string())));
+            }
+        }
+        for (_, responders) in self.watched_txns.drain() {
+            for r in responders {
+                let _ = r.send(Err(anyhow::anyhow!(reason.to_string())));
+            }
+        }
+    }
+
+    /// Remove the watchers whose callers are gone, e.g. timed out.
+    fn remove_canceled_watchers(&mut self) {
+        self.watched_blocks.retain(|_, responders| {
+            responders.retain(|r| !r.is_canceled());
+            !responders.is_empty()
+        });
+        self.watched_txns.retain(|_, responders| {
+            responders.retain(|r| !r.is_canceled());
+            !responders.is_empty()
+        });
+    }
+
+    fn update_latest_block(&mut self, number: BlockNumber) {
+        if self
+            .latest_block
+            .map(|latest| latest < number)
+            .unwrap_or(true)
+        {
+            self.latest_block = Some(number);
+        }
     }
 }
 
 impl Actor for ChainWatcher {
     type Context = Context<Self>;
-    fn started(&mut self, _ctx: &mut Self::Context) {
+    fn started(&mut self, ctx: &mut Self::Context) {
+        if self.provider.conn_source().support_pubsub() {
+            self.start_subscribe(0, ctx);
+        } else {
+            info!(
+                "{:?} does not support subscription, poll new blocks every {:?}",
+                self.provider.conn_source(),
+                POLL_INTERVAL
+            );
+            self.catch_up(ctx);
+            ctx.run_interval(POLL_INTERVAL, |act, ctx| act.catch_up(ctx));
+        }
         info!("ChainWater actor started");
     }
 
     fn stopped(&mut self, _ctx: &mut Self::Context) {
+        self.fail_watchers("chain watcher is stopped");
         info!("ChainWater actor stopped");
     }
 }
 
 pub(crate) struct StartSubscribe {
-    pub(crate) client: PubSubClient,
+    pub(crate) attempt: usize,
 }
 
 impl Message for StartSubscribe {
@@ -89,7 +253,7 @@ impl Handler<StartSubscribe> for ChainWatcher {
     type Result = ();
 
     fn handle(&mut self, msg: StartSubscribe, ctx: &mut Self::Context) {
-        self.start_subscribe(msg.client, ctx)
+        self.start_subscribe(msg.attempt, ctx)
     }
 }
 
@@ -117,6 +281,7 @@ impl Handler<WatchBlock> for ChainWatcher {
 
     /// This method is called for every message received by this actor.
     fn handle(&mut self, msg: WatchBlock, _ctx: &mut Self::Context) -> Self::Result {
+        self.remove_canceled_watchers();
         let (tx, rx) = oneshot::channel();
         self.watched_blocks
             .entry(msg.0)
@@ -131,43 +296,35 @@ impl Handler<WatchTxn> for ChainWatcher {
 
     /// This method is called for every message received by this actor.
     fn handle(&mut self, msg: WatchTxn, _ctx: &mut Self::Context) -> Self::Result {
+        self.remove_canceled_watchers();
         let (tx, rx) = oneshot::channel();
-        self.watched_txns.entry(msg.txn_hash).or_insert(tx);
+        self.watched_txns
+            .entry(msg.txn_hash)
+            .or_insert_with(Vec::new)
+            .push(tx);
         MessageResult(rx)
     }
 }
 
 type BlockEvent = Result<BlockView, RpcError>;
 impl actix::StreamHandler<BlockEvent> for ChainWatcher {
-    fn handle(&mut self, item: BlockEvent, _ctx: &mut Self::Context) {
+    fn handle(&mut self, item: BlockEvent, ctx: &mut Self::Context) {
         match item {
-            Ok(b) => {
-                let b: ThinHeadBlock = b.into();
-                if let Some(responders) = self.watched_blocks.remove(&b.header.number.0) {
-                    for r in responders {
-                        let _ = r.send(Ok(b.clone()));
-                    }
-                }
-                for txn in &b.txn_hashes {
-                    if let Some(r) = self.watched_txns.remove(txn) {
-                        let _ = r.send(Ok(b.clone()));
-                    }
-                }
-            }
+            Ok(b) => self.notify_watchers(b.into()),
+            // the watchers keep pending, fetch the blocks which may be missed because of the error.
             Err(e) => {
-                for (_, responders) in self.watched_blocks.drain() {
-                    for r in responders {
-                        let e = anyhow::format_err!("{}", &e);
-                        let _ = r.send(Err(e));
-                    }
-                }
-                for (_, responder) in self.watched_txns.drain() {
-                    let e = anyhow::format_err!("{}", &e);
-                    let _ = responder.send(Err(e));
-                }
+                warn!("receive new block event error: {}", &e);
+                self.catch_up(ctx);
             }
         }
     }
+
+    /// The subscription stream ends when the connection is lost, so reconnect and resubscribe.
+    fn finished(&mut self, ctx: &mut Self::Context) {
+        warn!("new block subscription is closed, try to resubscribe.");
+        self.provider.disconnect();
+        self.start_subscribe(0, ctx);
+    }
 }
 
 /// Try to stop a actor
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::chain_watcher::{ChainWatcher, StartSubscribe, WatchBlock, WatchTxn};
+use crate::chain_watcher::{ChainWatcher, WatchBlock, WatchTxn};
 use crate::pubsub_client::PubSubClient;
 pub use crate::remote_state_reader::RemoteStateReader;
 use actix::{Addr, System};
@@ -11,7 +11,7 @@ use futures::channel::oneshot;
 use futures::{TryStream, TryStreamExt};
 use jsonrpc_client_transports::RawClient;
 pub use jsonrpc_core::Params;
-use jsonrpc_core_client::{transports::ipc, transports::ws, RpcChannel};
+use jsonrpc_core_client::{transports::http, transports::ipc, transports::ws, RpcChannel};
 use network_api::PeerStrategy;
 use network_p2p_types::network_state::NetworkState;
 use parking_lot::Mutex;
@@ -20,20 +20,25 @@ use starcoin_abi_types::{ModuleABI, ScriptFunctionABI, StructABI};
 use starcoin_account_api::AccountInfo;
 use starcoin_crypto::HashValue;
 use starcoin_logger::{prelude::*, LogPattern};
-use starcoin_rpc_api::chain::{GetBlockOption, GetEventOption, GetTransactionOption};
+use starcoin_rpc_api::chain::{
+    GetBlockOption, GetEventOption, GetTransactionOption, GetWriteSetOption,
+};
 use starcoin_rpc_api::node::NodeInfo;
 use starcoin_rpc_api::service::RpcAsyncService;
 use starcoin_rpc_api::state::{
     GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
 };
+use starcoin_rpc_api::txpool::GetPendingTxnOption;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockSummaryView, BlockView,
-    ChainId, ChainInfoView, CodeView, ContractCall, DecodedMoveValue, DryRunOutputView,
-    DryRunTransactionRequest, EpochUncleSummaryView, FactoryAction, FunctionIdView, ListCodeView,
-    ListResourceView, MintedBlockView, ModuleIdView, PeerInfoView, ResourceView, SignedMessageView,
-    SignedUserTransactionView, StateWithProofView, StrView, StructTagView,
-    TransactionEventResponse, TransactionInfoView, TransactionRequest, TransactionView,
+    AccountStateSetView, AnnotatedMoveStructView, BackupManifestView, BlockHeaderView,
+    BlockStateDiffView, BlockSummaryView, BlockView, ChainId, ChainInfoView, CodeItemView,
+    CodeView, ContractCall, DecodedMoveValue, DryRunOutputView, DryRunTransactionRequest,
+    EpochUncleSummaryView, FactoryAction, FunctionIdView, ListCodeView, ListResourceView,
+    MintedBlockView, ModuleIdView, PageOption, PageView, PeerInfoView, ResourceItemView,
+    ResourceView, SignedMessageView, SignedUserTransactionView, StateWithProofView, StrView,
+    StructTagView, TransactionEventResponse, TransactionInfoView, TransactionRequest,
+    TransactionView, TransactionWriteSetView, MAX_PAGE_LIMIT,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
@@ -51,103 +56,182 @@ use starcoin_types::block::{BlockInfo, BlockNumber};
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::sync_status::SyncStatus;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use starcoin_vm_types::errmap::{ErrorDescription, ErrorMapping};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
 use starcoin_vm_types::token::token_code::TokenCode;
 use starcoin_vm_types::transaction::DryRunTransaction;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread::JoinHandle;
 use std::time::Duration;
-use tokio::runtime::Runtime;
+use tokio::runtime::{Handle, Runtime};
 
 pub mod chain_watcher;
 mod pubsub_client;
 mod remote_state_reader;
 
+/// The delay before the first reconnect attempt, it is doubled after each failed attempt until
+/// `MAX_RECONNECT_DELAY`.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// The reconnect attempts of a rpc call before it fails, the chain watcher retries until the client
+/// is closed.
+const MAX_RECONNECT_RETRIES: usize = 3;
+
+pub(crate) fn reconnect_delay(attempt: usize) -> Duration {
+    INITIAL_RECONNECT_DELAY
+        .checked_mul(1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX))
+        .map(|delay| delay.min(MAX_RECONNECT_DELAY))
+        .unwrap_or(MAX_RECONNECT_DELAY)
+}
+
 #[derive(Clone)]
-enum ConnSource {
+pub(crate) enum ConnSource {
     Ipc(PathBuf),
     WebSocket(String),
+    Http(String),
     Local(Box<RpcChannel>),
 }
 
+impl ConnSource {
+    /// Whether the server can push subscription events through the connection.
+    pub(crate) fn support_pubsub(&self) -> bool {
+        !matches!(self, ConnSource::Http(_))
+    }
+}
+
 impl std::fmt::Debug for ConnSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConnSource::Ipc(path) => write!(f, "Ipc({})", path.as_path().to_string_lossy()),
             ConnSource::WebSocket(url) => write!(f, "WebSocket({})", url),
+            ConnSource::Http(url) => write!(f, "Http({})", url),
             ConnSource::Local(_) => write!(f, "Local"),
         }
     }
 }
 
 pub struct RpcClient {
-    inner: Mutex<Option<RpcClientInner>>,
     provider: ConnectionProvider,
+    runtime: Mutex<Runtime>,
     chain_watcher: Addr<ChainWatcher>,
     //hold the watch thread handle.
     watcher_handle: JoinHandle<()>,
 }
 
-struct ConnectionProvider {
+/// Holds the connection shared by the rpc calls and the chain watcher, and reestablishes it
+/// after it is lost.
+#[derive(Clone)]
+pub(crate) struct ConnectionProvider {
     conn_source: ConnSource,
-    runtime: Mutex<Runtime>,
+    inner: Arc<Mutex<Option<RpcClientInner>>>,
+    // only one caller reconnects at a time, the others wait and reuse the new connection.
+    connecting: Arc<futures::lock::Mutex<()>>,
+    // the transports require the reactor of tokio 0.2, so connections are always made in the
+    // runtime of the client, then the calls can be awaited by any executor.
+    handle: Handle,
 }
 
 impl ConnectionProvider {
-    fn new(conn_source: ConnSource, runtime: Runtime) -> Self {
+    fn new(conn_source: ConnSource, handle: Handle) -> Self {
         Self {
             conn_source,
-            runtime: Mutex::new(runtime),
+            inner: Arc::new(Mutex::new(None)),
+            connecting: Arc::new(futures::lock::Mutex::new(())),
+            handle,
         }
     }
 
-    fn block_on<F>(&self, future: F) -> F::Output
-    where
-        F: futures::Future + std::marker::Send,
-        F::Output: std::marker::Send,
-    {
-        self.runtime.lock().block_on(future)
-    }
-
-    fn get_rpc_channel(&self) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
-        self.block_on(async { self.get_rpc_channel_async().await })
+    pub(crate) fn conn_source(&self) -> &ConnSource {
+        &self.conn_source
     }
 
     async fn get_rpc_channel_async(
         &self,
     ) -> anyhow::Result<RpcChannel, jsonrpc_client_transports::RpcError> {
-        match self.conn_source.clone() {
-            ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
-            ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
-            ConnSource::Local(channel) => Ok(*channel),
+        let conn_source = self.conn_source.clone();
+        self.handle
+            .spawn(async move {
+                match conn_source {
+                    ConnSource::Ipc(sock_path) => ipc::connect(sock_path).await,
+                    ConnSource::WebSocket(url) => ws::try_connect(url.as_str())?.await,
+                    ConnSource::Http(url) => http::connect(url.as_str()).await,
+                    ConnSource::Local(channel) => Ok(*channel),
+                }
+            })
+            .await
+            .map_err(|e| jsonrpc_client_transports::RpcError::Client(e.to_string()))?
+    }
+
+    /// Get the current connection, if it is lost, reconnect with backoff and give up after
+    /// `max_retries` retries.
+    pub(crate) async fn get_client(
+        &self,
+        max_retries: usize,
+    ) -> anyhow::Result<RpcClientInner, jsonrpc_client_transports::RpcError> {
+        if let Some(inner) = self.inner.lock().as_ref().cloned() {
+            return Ok(inner);
+        }
+        let _connecting = self.connecting.lock().await;
+        // another caller may have reconnected while waiting for the lock.
+        if let Some(inner) = self.inner.lock().as_ref().cloned() {
+            return Ok(inner);
         }
+        let mut attempt = 0;
+        let channel = loop {
+            info!("Try to connect node by {:?}", &self.conn_source);
+            match self.get_rpc_channel_async().await {
+                Ok(channel) => break channel,
+                Err(e) if attempt < max_retries => {
+                    let delay = reconnect_delay(attempt);
+                    warn!(
+                        "Reconnect by {:?} failed: {}, retry after {:?}",
+                        &self.conn_source, e, delay
+                    );
+                    futures_timer::Delay::new(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        let new_inner: RpcClientInner = channel.into();
+        *(self.inner.lock()) = Some(new_inner.clone());
+        Ok(new_inner)
+    }
+
+    /// Drop the current connection, the next call reconnects.
+    pub(crate) fn disconnect(&self) {
+        *(self.inner.lock()) = None;
     }
 }
 
 impl RpcClient {
     pub(crate) fn new(conn_source: ConnSource) -> anyhow::Result<Self> {
         let (tx, rx) = oneshot::channel();
-        let provider = ConnectionProvider::new(conn_source, Runtime::new()?);
-        let inner: RpcClientInner = provider.get_rpc_channel().map_err(map_err)?.into(); //Self::create_client_inner(conn_source.clone()).map_err(map_err)?;
-        let pubsub_client = inner.pubsub_client.clone();
+        let runtime = Runtime::new()?;
+        let provider = ConnectionProvider::new(conn_source, runtime.handle().clone());
+        let runtime = Mutex::new(runtime);
+        // connect once without retry, so an unreachable node fails fast.
+        runtime
+            .lock()
+            .block_on(provider.get_client(0))
+            .map_err(map_err)?;
+        let watcher_provider = provider.clone();
         let handle = std::thread::spawn(move || {
             let sys = System::new("client-actix-system");
-            let watcher = ChainWatcher::launch();
+            let watcher = ChainWatcher::launch(watcher_provider);
 
             tx.send(watcher).unwrap();
             let _ = sys.run();
         });
         let watcher = futures::executor::block_on(rx).expect("Init chain watcher fail.");
-        watcher.do_send(StartSubscribe {
-            client: pubsub_client,
-        });
         Ok(Self {
-            inner: Mutex::new(Some(inner)),
             provider,
+            runtime,
             chain_watcher: watcher,
             watcher_handle: handle,
         })
@@ -157,6 +241,12 @@ impl RpcClient {
         Self::new(ConnSource::WebSocket(url.to_string()))
     }
 
+    /// Connect by http or https, the subscriptions are not supported, `watch_txn` and
+    /// `watch_block` poll the new blocks instead.
+    pub fn connect_http(url: &str) -> anyhow::Result<Self> {
+        Self::new(ConnSource::Http(url.to_string()))
+    }
+
     pub fn connect_local<S>(rpc_service: S) -> anyhow::Result<Self>
     where
         S: RpcAsyncService,
@@ -175,37 +265,48 @@ impl RpcClient {
         txn_hash: HashValue,
         timeout: Option<Duration>,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
-        let chain_watcher = self.chain_watcher.clone();
-        let f = async move {
-            let r = chain_watcher.send(WatchTxn { txn_hash }).await?;
-            match timeout {
-                Some(t) => async_std::future::timeout(t, r).await??,
-                None => r.await?,
-            }
-        };
-        futures::executor::block_on(f)
+        futures::executor::block_on(self.watch_txn_async(txn_hash, timeout))
+    }
+
+    pub async fn watch_txn_async(
+        &self,
+        txn_hash: HashValue,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        let r = self.chain_watcher.send(WatchTxn { txn_hash }).await?;
+        match timeout {
+            Some(t) => async_std::future::timeout(t, r).await??,
+            None => r.await?,
+        }
     }
 
     pub fn watch_block(
         &self,
         block_number: BlockNumber,
     ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
-        let chain_watcher = self.chain_watcher.clone();
-        let f = async move {
-            let r = chain_watcher.send(WatchBlock(block_number)).await?;
-            r.await?
-        };
-        futures::executor::block_on(f)
+        futures::executor::block_on(self.watch_block_async(block_number))
+    }
+
+    pub async fn watch_block_async(
+        &self,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<chain_watcher::ThinHeadBlock> {
+        let r = self.chain_watcher.send(WatchBlock(block_number)).await?;
+        r.await?
     }
 
     pub fn node_status(&self) -> anyhow::Result<bool> {
-        self.call_rpc_blocking(|inner| inner.node_client.status())
+        self.block_on(self.node_status_async())
+    }
+
+    pub async fn node_status_async(&self) -> anyhow::Result<bool> {
+        self.call_rpc_async(|inner| inner.node_client.status())
+            .await
             .map_err(map_err)
     }
 
     pub fn node_info(&self) -> anyhow::Result<NodeInfo> {
-        self.call_rpc_blocking(|inner| inner.node_client.info())
-            .map_err(map_err)
+        self.block_on(self.node_info_async())
     }
 
     pub async fn node_info_async(&self) -> anyhow::Result<NodeInfo> {
@@ -215,69 +316,194 @@ impl RpcClient {
     }
 
     pub fn node_metrics(&self) -> anyhow::Result<HashMap<String, String>> {
-        self.call_rpc_blocking(|inner| inner.node_client.metrics())
+        self.block_on(self.node_metrics_async())
+    }
+
+    pub async fn node_metrics_async(&self) -> anyhow::Result<HashMap<String, String>> {
+        self.call_rpc_async(|inner| inner.node_client.metrics())
+            .await
             .map_err(map_err)
     }
 
     pub fn node_peers(&self) -> anyhow::Result<Vec<PeerInfoView>> {
-        self.call_rpc_blocking(|inner| inner.node_client.peers())
+        self.block_on(self.node_peers_async())
+    }
+
+    pub async fn node_peers_async(&self) -> anyhow::Result<Vec<PeerInfoView>> {
+        self.call_rpc_async(|inner| inner.node_client.peers())
+            .await
             .map_err(map_err)
     }
 
     pub fn node_list_service(&self) -> anyhow::Result<Vec<ServiceInfo>> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.list_service())
+        self.block_on(self.node_list_service_async())
+    }
+
+    pub async fn node_list_service_async(&self) -> anyhow::Result<Vec<ServiceInfo>> {
+        self.call_rpc_async(|inner| inner.node_manager_client.list_service())
+            .await
             .map_err(map_err)
     }
 
     pub fn node_start_service(&self, service_name: String) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.start_service(service_name))
+        self.block_on(self.node_start_service_async(service_name))
+    }
+
+    pub async fn node_start_service_async(&self, service_name: String) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.start_service(service_name))
+            .await
             .map_err(map_err)
     }
 
     pub fn node_check_service(&self, service_name: String) -> anyhow::Result<ServiceStatus> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.check_service(service_name))
+        self.block_on(self.node_check_service_async(service_name))
+    }
+
+    pub async fn node_check_service_async(
+        &self,
+        service_name: String,
+    ) -> anyhow::Result<ServiceStatus> {
+        self.call_rpc_async(|inner| inner.node_manager_client.check_service(service_name))
+            .await
             .map_err(map_err)
     }
 
     pub fn node_stop_service(&self, service_name: String) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.stop_service(service_name))
+        self.block_on(self.node_stop_service_async(service_name))
+    }
+
+    pub async fn node_stop_service_async(&self, service_name: String) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.stop_service(service_name))
+            .await
             .map_err(map_err)
     }
 
     pub fn node_shutdown_system(&self) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.shutdown_system())
+        self.block_on(self.node_shutdown_system_async())
+    }
+
+    pub async fn node_shutdown_system_async(&self) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.shutdown_system())
+            .await
             .map_err(map_err)
     }
     pub fn node_reset(&self, block_hash: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.reset_to_block(block_hash))
+        self.block_on(self.node_reset_async(block_hash))
+    }
+
+    pub async fn node_reset_async(&self, block_hash: HashValue) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.reset_to_block(block_hash))
+            .await
+            .map_err(map_err)
+    }
+    pub fn node_confirm_reorg(&self, block_hash: HashValue) -> anyhow::Result<()> {
+        self.block_on(self.node_confirm_reorg_async(block_hash))
+    }
+
+    pub async fn node_confirm_reorg_async(&self, block_hash: HashValue) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.confirm_reorg(block_hash))
+            .await
             .map_err(map_err)
     }
     pub fn node_re_execute_block(&self, block_id: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.re_execute_block(block_id))
+        self.block_on(self.node_re_execute_block_async(block_id))
+    }
+
+    pub async fn node_re_execute_block_async(&self, block_id: HashValue) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.re_execute_block(block_id))
+            .await
             .map_err(map_err)
     }
     pub fn node_delete_block(&self, block_id: HashValue) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.node_manager_client.delete_block(block_id))
+        self.block_on(self.node_delete_block_async(block_id))
+    }
+
+    pub async fn node_delete_block_async(&self, block_id: HashValue) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.delete_block(block_id))
+            .await
+            .map_err(map_err)
+    }
+    pub fn node_compact_storage(&self, cf_name: String) -> anyhow::Result<()> {
+        self.block_on(self.node_compact_storage_async(cf_name))
+    }
+
+    pub async fn node_compact_storage_async(&self, cf_name: String) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.node_manager_client.compact_storage(cf_name))
+            .await
+            .map_err(map_err)
+    }
+    pub fn node_backup(&self, backup_dir: String) -> anyhow::Result<BackupManifestView> {
+        self.block_on(self.node_backup_async(backup_dir))
+    }
+
+    pub async fn node_backup_async(
+        &self,
+        backup_dir: String,
+    ) -> anyhow::Result<BackupManifestView> {
+        self.call_rpc_async(|inner| inner.node_manager_client.backup(backup_dir))
+            .await
+            .map_err(map_err)
+    }
+    pub fn node_register_error_map(
+        &self,
+        error_map: &ErrorMapping,
+    ) -> anyhow::Result<Vec<ModuleIdView>> {
+        self.block_on(self.node_register_error_map_async(error_map))
+    }
+
+    pub async fn node_register_error_map_async(
+        &self,
+        error_map: &ErrorMapping,
+    ) -> anyhow::Result<Vec<ModuleIdView>> {
+        let error_map = StrView(bcs_ext::to_bytes(error_map)?);
+        self.call_rpc_async(|inner| inner.node_manager_client.register_error_map(error_map))
+            .await
             .map_err(map_err)
     }
     pub fn next_sequence_number_in_txpool(
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<u64>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.next_sequence_number(address))
+        self.block_on(self.next_sequence_number_in_txpool_async(address))
+    }
+
+    pub async fn next_sequence_number_in_txpool_async(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Option<u64>> {
+        self.call_rpc_async(|inner| inner.txpool_client.next_sequence_number(address))
+            .await
             .map_err(map_err)
     }
 
     pub fn submit_transaction(&self, txn: SignedUserTransaction) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn))
+        self.block_on(self.submit_transaction_async(txn))
+    }
+
+    pub async fn submit_transaction_async(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_async(|inner| inner.txpool_client.submit_transaction(txn))
+            .await
             .map_err(map_err)
     }
 
     pub fn get_pending_txn_by_hash(
         &self,
         txn_hash: HashValue,
+        option: Option<GetPendingTxnOption>,
+    ) -> anyhow::Result<Option<SignedUserTransactionView>> {
+        self.block_on(self.get_pending_txn_by_hash_async(txn_hash, option))
+    }
+
+    pub async fn get_pending_txn_by_hash_async(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetPendingTxnOption>,
     ) -> anyhow::Result<Option<SignedUserTransactionView>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.pending_txn(txn_hash))
+        self.call_rpc_async(|inner| inner.txpool_client.pending_txn(txn_hash, option))
+            .await
             .map_err(map_err)
     }
 
@@ -286,34 +512,92 @@ impl RpcClient {
         sender: AccountAddress,
         max_len: Option<u32>,
     ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.pending_txns(sender, max_len))
+        self.block_on(self.get_pending_txns_of_sender_async(sender, max_len))
+    }
+
+    pub async fn get_pending_txns_of_sender_async(
+        &self,
+        sender: AccountAddress,
+        max_len: Option<u32>,
+    ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
+        self.call_rpc_async(|inner| inner.txpool_client.pending_txns(sender, max_len))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn get_pending_txns_of_sender_page(
+        &self,
+        sender: AccountAddress,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<SignedUserTransactionView>> {
+        self.block_on(self.get_pending_txns_of_sender_page_async(sender, page))
+    }
+
+    pub async fn get_pending_txns_of_sender_page_async(
+        &self,
+        sender: AccountAddress,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<SignedUserTransactionView>> {
+        self.call_rpc_async(|inner| inner.txpool_client.pending_txns_page(sender, page))
+            .await
             .map_err(map_err)
     }
 
     //TODO should split client for different api ?
     // such as  RpcClient().account().default()
     pub fn account_default(&self) -> anyhow::Result<Option<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.default())
+        self.block_on(self.account_default_async())
+    }
+
+    pub async fn account_default_async(&self) -> anyhow::Result<Option<AccountInfo>> {
+        self.call_rpc_async(|inner| inner.account_client.default())
+            .await
             .map_err(map_err)
     }
 
     pub fn set_default_account(&self, addr: AccountAddress) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.set_default_account(addr))
+        self.block_on(self.set_default_account_async(addr))
+    }
+
+    pub async fn set_default_account_async(
+        &self,
+        addr: AccountAddress,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| inner.account_client.set_default_account(addr))
+            .await
             .map_err(map_err)
     }
 
     pub fn account_create(&self, password: String) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.create(password))
+        self.block_on(self.account_create_async(password))
+    }
+
+    pub async fn account_create_async(&self, password: String) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| inner.account_client.create(password))
+            .await
             .map_err(map_err)
     }
 
     pub fn account_list(&self) -> anyhow::Result<Vec<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.list())
+        self.block_on(self.account_list_async())
+    }
+
+    pub async fn account_list_async(&self) -> anyhow::Result<Vec<AccountInfo>> {
+        self.call_rpc_async(|inner| inner.account_client.list())
+            .await
             .map_err(map_err)
     }
 
     pub fn account_get(&self, address: AccountAddress) -> anyhow::Result<Option<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.get(address))
+        self.block_on(self.account_get_async(address))
+    }
+
+    pub async fn account_get_async(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Option<AccountInfo>> {
+        self.call_rpc_async(|inner| inner.account_client.get(address))
+            .await
             .map_err(map_err)
     }
 
@@ -323,7 +607,16 @@ impl RpcClient {
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> anyhow::Result<SignedUserTransaction> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, signer_address))
+        self.block_on(self.account_sign_multisig_txn_async(raw_txn, signer_address))
+    }
+
+    pub async fn account_sign_multisig_txn_async(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<SignedUserTransaction> {
+        self.call_rpc_async(|inner| inner.account_client.sign_txn(raw_txn, signer_address))
+            .await
             .map_err(map_err)
     }
 
@@ -331,7 +624,15 @@ impl RpcClient {
         &self,
         txn_request: TransactionRequest,
     ) -> anyhow::Result<SignedUserTransaction> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn_request(txn_request))
+        self.block_on(self.account_sign_txn_request_async(txn_request))
+    }
+
+    pub async fn account_sign_txn_request_async(
+        &self,
+        txn_request: TransactionRequest,
+    ) -> anyhow::Result<SignedUserTransaction> {
+        self.call_rpc_async(|inner| inner.account_client.sign_txn_request(txn_request))
+            .await
             .map_err(map_err)
             .and_then(|d: String| {
                 hex::decode(d.as_str().strip_prefix("0x").unwrap_or_else(|| d.as_str()))
@@ -343,9 +644,17 @@ impl RpcClient {
     pub fn account_sign_txn(
         &self,
         raw_txn: RawUserTransaction,
+    ) -> anyhow::Result<SignedUserTransaction> {
+        self.block_on(self.account_sign_txn_async(raw_txn))
+    }
+
+    pub async fn account_sign_txn_async(
+        &self,
+        raw_txn: RawUserTransaction,
     ) -> anyhow::Result<SignedUserTransaction> {
         let signer = raw_txn.sender();
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, signer))
+        self.call_rpc_async(|inner| inner.account_client.sign_txn(raw_txn, signer))
+            .await
             .map_err(map_err)
     }
 
@@ -354,7 +663,16 @@ impl RpcClient {
         signer: AccountAddress,
         message: SigningMessage,
     ) -> anyhow::Result<SignedMessageView> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign(signer, message))
+        self.block_on(self.account_sign_message_async(signer, message))
+    }
+
+    pub async fn account_sign_message_async(
+        &self,
+        signer: AccountAddress,
+        message: SigningMessage,
+    ) -> anyhow::Result<SignedMessageView> {
+        self.call_rpc_async(|inner| inner.account_client.sign(signer, message))
+            .await
             .map_err(map_err)
     }
 
@@ -363,16 +681,30 @@ impl RpcClient {
         address: AccountAddress,
         new_password: String,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.account_change_password_async(address, new_password))
+    }
+
+    pub async fn account_change_password_async(
+        &self,
+        address: AccountAddress,
+        new_password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| {
             inner
                 .account_client
                 .change_account_password(address, new_password)
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn account_lock(&self, address: AccountAddress) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.lock(address))
+        self.block_on(self.account_lock_async(address))
+    }
+
+    pub async fn account_lock_async(&self, address: AccountAddress) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| inner.account_client.lock(address))
+            .await
             .map_err(map_err)
     }
     pub fn account_unlock(
@@ -381,11 +713,21 @@ impl RpcClient {
         password: String,
         duration: std::time::Duration,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.account_unlock_async(address, password, duration))
+    }
+
+    pub async fn account_unlock_async(
+        &self,
+        address: AccountAddress,
+        password: String,
+        duration: std::time::Duration,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| {
             inner
                 .account_client
                 .unlock(address, password, Some(duration.as_secs() as u32))
         })
+        .await
         .map_err(map_err)
     }
     pub fn account_export(
@@ -393,7 +735,16 @@ impl RpcClient {
         address: AccountAddress,
         password: String,
     ) -> anyhow::Result<Vec<u8>> {
-        self.call_rpc_blocking(|inner| inner.account_client.export(address, password))
+        self.block_on(self.account_export_async(address, password))
+    }
+
+    pub async fn account_export_async(
+        &self,
+        address: AccountAddress,
+        password: String,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.call_rpc_async(|inner| inner.account_client.export(address, password))
+            .await
             .map_err(map_err)
     }
     pub fn account_import(
@@ -402,11 +753,21 @@ impl RpcClient {
         private_key: Vec<u8>,
         password: String,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.account_import_async(address, private_key, password))
+    }
+
+    pub async fn account_import_async(
+        &self,
+        address: AccountAddress,
+        private_key: Vec<u8>,
+        password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| {
             inner
                 .account_client
                 .import(address, StrView(private_key), password)
         })
+        .await
         .map_err(map_err)
     }
 
@@ -415,11 +776,20 @@ impl RpcClient {
         address: AccountAddress,
         public_key: Vec<u8>,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.account_import_readonly_async(address, public_key))
+    }
+
+    pub async fn account_import_readonly_async(
+        &self,
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| {
             inner
                 .account_client
                 .import_readonly(address, StrView(public_key))
         })
+        .await
         .map_err(map_err)
     }
 
@@ -427,7 +797,15 @@ impl RpcClient {
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Vec<TokenCode>> {
-        self.call_rpc_blocking(|inner| inner.account_client.accepted_tokens(address))
+        self.block_on(self.account_accepted_tokens_async(address))
+    }
+
+    pub async fn account_accepted_tokens_async(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Vec<TokenCode>> {
+        self.call_rpc_async(|inner| inner.account_client.accepted_tokens(address))
+            .await
             .map_err(map_err)
     }
 
@@ -436,13 +814,27 @@ impl RpcClient {
         address: AccountAddress,
         password: Option<String>,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.remove(address, password))
+        self.block_on(self.account_remove_async(address, password))
+    }
+
+    pub async fn account_remove_async(
+        &self,
+        address: AccountAddress,
+        password: Option<String>,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_async(|inner| inner.account_client.remove(address, password))
+            .await
             .map_err(map_err)
     }
 
     pub fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
+        self.block_on(self.get_code_async(module_id))
+    }
+
+    pub async fn get_code_async(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
         let result: Option<StrView<Vec<u8>>> = self
-            .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
+            .call_rpc_async(|inner| inner.contract_client.get_code(StrView(module_id)))
+            .await
             .map_err(map_err)?;
         Ok(result.map(|s| s.to_string()))
     }
@@ -452,16 +844,33 @@ impl RpcClient {
         addr: AccountAddress,
         resource_type: StructTag,
     ) -> anyhow::Result<Option<AnnotatedMoveStructView>> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.get_resource_async(addr, resource_type))
+    }
+
+    pub async fn get_resource_async(
+        &self,
+        addr: AccountAddress,
+        resource_type: StructTag,
+    ) -> anyhow::Result<Option<AnnotatedMoveStructView>> {
+        self.call_rpc_async(|inner| {
             inner
                 .contract_client
                 .get_resource(addr, StrView(resource_type))
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn state_get(&self, access_path: AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get(access_path))
+        self.block_on(self.state_get_async(access_path))
+    }
+
+    pub async fn state_get_async(
+        &self,
+        access_path: AccessPath,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        self.call_rpc_async(|inner| inner.state_client.get(access_path))
+            .await
             .map_err(map_err)
     }
 
@@ -469,7 +878,15 @@ impl RpcClient {
         &self,
         access_path: AccessPath,
     ) -> anyhow::Result<StateWithProofView> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_with_proof(access_path))
+        self.block_on(self.state_get_with_proof_async(access_path))
+    }
+
+    pub async fn state_get_with_proof_async(
+        &self,
+        access_path: AccessPath,
+    ) -> anyhow::Result<StateWithProofView> {
+        self.call_rpc_async(|inner| inner.state_client.get_with_proof(access_path))
+            .await
             .map_err(map_err)
     }
 
@@ -478,16 +895,30 @@ impl RpcClient {
         access_path: AccessPath,
         state_root: HashValue,
     ) -> anyhow::Result<StateWithProofView> {
-        self.call_rpc_blocking(|inner| {
-            inner
-                .state_client
+        self.block_on(self.state_get_with_proof_by_root_async(access_path, state_root))
+    }
+
+    pub async fn state_get_with_proof_by_root_async(
+        &self,
+        access_path: AccessPath,
+        state_root: HashValue,
+    ) -> anyhow::Result<StateWithProofView> {
+        self.call_rpc_async(|inner| {
+            inner
+                .state_client
                 .get_with_proof_by_root(access_path, state_root)
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn state_get_state_root(&self) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_state_root())
+        self.block_on(self.state_get_state_root_async())
+    }
+
+    pub async fn state_get_state_root_async(&self) -> anyhow::Result<HashValue> {
+        self.call_rpc_async(|inner| inner.state_client.get_state_root())
+            .await
             .map_err(map_err)
     }
 
@@ -495,7 +926,15 @@ impl RpcClient {
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<AccountState>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_account_state(address))
+        self.block_on(self.state_get_account_state_async(address))
+    }
+
+    pub async fn state_get_account_state_async(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<Option<AccountState>> {
+        self.call_rpc_async(|inner| inner.state_client.get_account_state(address))
+            .await
             .map_err(map_err)
     }
 
@@ -504,11 +943,20 @@ impl RpcClient {
         address: AccountAddress,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<AccountStateSetView>> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.state_get_account_state_set_async(address, state_root))
+    }
+
+    pub async fn state_get_account_state_set_async(
+        &self,
+        address: AccountAddress,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<AccountStateSetView>> {
+        self.call_rpc_async(|inner| {
             inner
                 .state_client
                 .get_account_state_set(address, state_root)
         })
+        .await
         .map_err(map_err)
     }
 
@@ -519,27 +967,74 @@ impl RpcClient {
         decode: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<ResourceView>> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.state_get_resource_async(address, resource_type, decode, state_root))
+    }
+
+    pub async fn state_get_resource_async(
+        &self,
+        address: AccountAddress,
+        resource_type: StructTag,
+        decode: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<ResourceView>> {
+        self.call_rpc_async(|inner| {
             inner.state_client.get_resource(
                 address,
                 StrView(resource_type),
                 Some(GetResourceOption { decode, state_root }),
             )
         })
+        .await
         .map_err(map_err)
     }
 
+    /// List all the resources of `address`, page by page.
     pub fn state_list_resource(
         &self,
         address: AccountAddress,
         decode: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<ListResourceView> {
-        self.call_rpc_blocking(|inner| {
+        let option = ListResourceOption { decode, state_root };
+        let mut page = PageOption::new(Some(MAX_PAGE_LIMIT), None);
+        let mut result = ListResourceView::default();
+        loop {
+            let resources =
+                self.state_list_resource_page(address, option.clone(), Some(page.clone()))?;
+            result.resources.extend(
+                resources
+                    .data
+                    .into_iter()
+                    .map(|item| (item.struct_tag, item.resource)),
+            );
+            match resources.next_cursor {
+                Some(cursor) => page.cursor = Some(cursor),
+                None => return Ok(result),
+            }
+        }
+    }
+
+    pub fn state_list_resource_page(
+        &self,
+        address: AccountAddress,
+        option: ListResourceOption,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<ResourceItemView>> {
+        self.block_on(self.state_list_resource_page_async(address, option, page))
+    }
+
+    pub async fn state_list_resource_page_async(
+        &self,
+        address: AccountAddress,
+        option: ListResourceOption,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<ResourceItemView>> {
+        self.call_rpc_async(|inner| {
             inner
                 .state_client
-                .list_resource(address, Some(ListResourceOption { decode, state_root }))
+                .list_resource_page(address, Some(option), page)
         })
+        .await
         .map_err(map_err)
     }
 
@@ -549,7 +1044,16 @@ impl RpcClient {
         resolve: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<CodeView>> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.state_get_code_async(module_id, resolve, state_root))
+    }
+
+    pub async fn state_get_code_async(
+        &self,
+        module_id: ModuleId,
+        resolve: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<CodeView>> {
+        self.call_rpc_async(|inner| {
             inner.state_client.get_code(
                 StrView(module_id),
                 Some(GetCodeOption {
@@ -558,29 +1062,69 @@ impl RpcClient {
                 }),
             )
         })
+        .await
         .map_err(map_err)
     }
 
+    /// List all the codes of `address`, page by page.
     pub fn state_list_code(
         &self,
         address: AccountAddress,
         resolve: bool,
         state_root: Option<HashValue>,
     ) -> anyhow::Result<ListCodeView> {
-        self.call_rpc_blocking(|inner| {
-            inner.state_client.list_code(
-                address,
-                Some(ListCodeOption {
-                    resolve,
-                    state_root,
-                }),
-            )
+        let option = ListCodeOption {
+            resolve,
+            state_root,
+        };
+        let mut page = PageOption::new(Some(MAX_PAGE_LIMIT), None);
+        let mut result = ListCodeView::default();
+        loop {
+            let codes = self.state_list_code_page(address, option.clone(), Some(page.clone()))?;
+            result
+                .codes
+                .extend(codes.data.into_iter().map(|item| (item.name, item.code)));
+            match codes.next_cursor {
+                Some(cursor) => page.cursor = Some(cursor),
+                None => return Ok(result),
+            }
+        }
+    }
+
+    pub fn state_list_code_page(
+        &self,
+        address: AccountAddress,
+        option: ListCodeOption,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<CodeItemView>> {
+        self.block_on(self.state_list_code_page_async(address, option, page))
+    }
+
+    pub async fn state_list_code_page_async(
+        &self,
+        address: AccountAddress,
+        option: ListCodeOption,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<CodeItemView>> {
+        self.call_rpc_async(|inner| {
+            inner
+                .state_client
+                .list_code_page(address, Some(option), page)
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn contract_call(&self, call: ContractCall) -> anyhow::Result<Vec<DecodedMoveValue>> {
-        self.call_rpc_blocking(|inner| inner.contract_client.call_v2(call))
+        self.block_on(self.contract_call_async(call))
+    }
+
+    pub async fn contract_call_async(
+        &self,
+        call: ContractCall,
+    ) -> anyhow::Result<Vec<DecodedMoveValue>> {
+        self.call_rpc_async(|inner| inner.contract_client.call_v2(call))
+            .await
             .map_err(map_err)
     }
 
@@ -588,17 +1132,57 @@ impl RpcClient {
         &self,
         function_id: FunctionIdView,
     ) -> anyhow::Result<ScriptFunctionABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_function(function_id))
+        self.block_on(self.contract_resolve_function_async(function_id))
+    }
+
+    pub async fn contract_resolve_function_async(
+        &self,
+        function_id: FunctionIdView,
+    ) -> anyhow::Result<ScriptFunctionABI> {
+        self.call_rpc_async(|inner| inner.contract_client.resolve_function(function_id))
+            .await
             .map_err(map_err)
     }
 
     pub fn contract_resolve_struct(&self, struct_tag: StructTagView) -> anyhow::Result<StructABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_struct(struct_tag))
+        self.block_on(self.contract_resolve_struct_async(struct_tag))
+    }
+
+    pub async fn contract_resolve_struct_async(
+        &self,
+        struct_tag: StructTagView,
+    ) -> anyhow::Result<StructABI> {
+        self.call_rpc_async(|inner| inner.contract_client.resolve_struct(struct_tag))
+            .await
             .map_err(map_err)
     }
 
     pub fn contract_resolve_module(&self, module_id: ModuleIdView) -> anyhow::Result<ModuleABI> {
-        self.call_rpc_blocking(|inner| inner.contract_client.resolve_module(module_id))
+        self.block_on(self.contract_resolve_module_async(module_id))
+    }
+
+    pub async fn contract_resolve_module_async(
+        &self,
+        module_id: ModuleIdView,
+    ) -> anyhow::Result<ModuleABI> {
+        self.call_rpc_async(|inner| inner.contract_client.resolve_module(module_id))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn contract_get_error_map(
+        &self,
+        module_id: ModuleId,
+    ) -> anyhow::Result<Option<BTreeMap<u64, ErrorDescription>>> {
+        self.block_on(self.contract_get_error_map_async(module_id))
+    }
+
+    pub async fn contract_get_error_map_async(
+        &self,
+        module_id: ModuleId,
+    ) -> anyhow::Result<Option<BTreeMap<u64, ErrorDescription>>> {
+        self.call_rpc_async(|inner| inner.contract_client.get_error_map(StrView(module_id)))
+            .await
             .map_err(map_err)
     }
 
@@ -607,51 +1191,106 @@ impl RpcClient {
         logger_name: Option<String>,
         level: Level,
     ) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.debug_set_log_level_async(logger_name, level))
+    }
+
+    pub async fn debug_set_log_level_async(
+        &self,
+        logger_name: Option<String>,
+        level: Level,
+    ) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| {
             inner
                 .debug_client
                 .set_log_level(logger_name, level.to_string())
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn debug_set_log_pattern(&self, pattern: LogPattern) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.debug_client.set_log_pattern(pattern))
+        self.block_on(self.debug_set_log_pattern_async(pattern))
+    }
+
+    pub async fn debug_set_log_pattern_async(&self, pattern: LogPattern) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.debug_client.set_log_pattern(pattern))
+            .await
             .map_err(map_err)
     }
 
     pub fn debug_panic(&self) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.debug_client.panic())
+        self.block_on(self.debug_panic_async())
+    }
+
+    pub async fn debug_panic_async(&self) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.debug_client.panic())
+            .await
             .map_err(map_err)
     }
 
     pub fn debug_txfactory_status(&self, action: FactoryAction) -> anyhow::Result<bool> {
-        self.call_rpc_blocking(|inner| inner.debug_client.txfactory_status(action))
+        self.block_on(self.debug_txfactory_status_async(action))
+    }
+
+    pub async fn debug_txfactory_status_async(
+        &self,
+        action: FactoryAction,
+    ) -> anyhow::Result<bool> {
+        self.call_rpc_async(|inner| inner.debug_client.txfactory_status(action))
+            .await
             .map_err(map_err)
     }
 
     pub fn sleep(&self, time: u64) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.debug_client.sleep(time))
+        self.block_on(self.sleep_async(time))
+    }
+
+    pub async fn sleep_async(&self, time: u64) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.debug_client.sleep(time))
+            .await
             .map_err(map_err)
     }
 
     pub fn chain_id(&self) -> anyhow::Result<ChainId> {
-        self.call_rpc_blocking(|inner| inner.chain_client.id())
+        self.block_on(self.chain_id_async())
+    }
+
+    pub async fn chain_id_async(&self) -> anyhow::Result<ChainId> {
+        self.call_rpc_async(|inner| inner.chain_client.id())
+            .await
             .map_err(map_err)
     }
 
     pub fn chain_info(&self) -> anyhow::Result<ChainInfoView> {
-        self.call_rpc_blocking(|inner| inner.chain_client.info())
+        self.block_on(self.chain_info_async())
+    }
+
+    pub async fn chain_info_async(&self) -> anyhow::Result<ChainInfoView> {
+        self.call_rpc_async(|inner| inner.chain_client.info())
+            .await
             .map_err(map_err)
     }
 
     pub fn epoch_info(&self) -> anyhow::Result<EpochInfo> {
-        self.call_rpc_blocking(|inner| inner.chain_client.current_epoch())
+        self.block_on(self.epoch_info_async())
+    }
+
+    pub async fn epoch_info_async(&self) -> anyhow::Result<EpochInfo> {
+        self.call_rpc_async(|inner| inner.chain_client.current_epoch())
+            .await
             .map_err(map_err)
     }
 
     pub fn get_epoch_info_by_number(&self, number: BlockNumber) -> anyhow::Result<EpochInfo> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_epoch_info_by_number(number))
+        self.block_on(self.get_epoch_info_by_number_async(number))
+    }
+
+    pub async fn get_epoch_info_by_number_async(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<EpochInfo> {
+        self.call_rpc_async(|inner| inner.chain_client.get_epoch_info_by_number(number))
+            .await
             .map_err(map_err)
     }
 
@@ -659,7 +1298,15 @@ impl RpcClient {
         &self,
         number: BlockNumber,
     ) -> anyhow::Result<Vec<BlockSummaryView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_epoch_uncles_by_number(number))
+        self.block_on(self.get_epoch_uncles_by_number_async(number))
+    }
+
+    pub async fn get_epoch_uncles_by_number_async(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<Vec<BlockSummaryView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_epoch_uncles_by_number(number))
+            .await
             .map_err(map_err)
     }
 
@@ -667,7 +1314,15 @@ impl RpcClient {
         &self,
         number: BlockNumber,
     ) -> anyhow::Result<EpochUncleSummaryView> {
-        self.call_rpc_blocking(|inner| inner.chain_client.epoch_uncle_summary_by_number(number))
+        self.block_on(self.epoch_uncle_summary_by_number_async(number))
+    }
+
+    pub async fn epoch_uncle_summary_by_number_async(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<EpochUncleSummaryView> {
+        self.call_rpc_async(|inner| inner.chain_client.epoch_uncle_summary_by_number(number))
+            .await
             .map_err(map_err)
     }
 
@@ -675,7 +1330,15 @@ impl RpcClient {
         &self,
         block_hashes: Vec<HashValue>,
     ) -> anyhow::Result<Vec<BlockHeaderView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_headers(block_hashes))
+        self.block_on(self.get_headers_async(block_hashes))
+    }
+
+    pub async fn get_headers_async(
+        &self,
+        block_hashes: Vec<HashValue>,
+    ) -> anyhow::Result<Vec<BlockHeaderView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_headers(block_hashes))
+            .await
             .map_err(map_err)
     }
 
@@ -683,7 +1346,15 @@ impl RpcClient {
         &self,
         number: BlockNumber,
     ) -> anyhow::Result<GlobalTimeOnChain> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_global_time_by_number(number))
+        self.block_on(self.get_global_time_by_number_async(number))
+    }
+
+    pub async fn get_global_time_by_number_async(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<GlobalTimeOnChain> {
+        self.call_rpc_async(|inner| inner.chain_client.get_global_time_by_number(number))
+            .await
             .map_err(map_err)
     }
 
@@ -692,7 +1363,16 @@ impl RpcClient {
         hash: HashValue,
         option: Option<GetBlockOption>,
     ) -> anyhow::Result<Option<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_hash(hash, option))
+        self.block_on(self.chain_get_block_by_hash_async(hash, option))
+    }
+
+    pub async fn chain_get_block_by_hash_async(
+        &self,
+        hash: HashValue,
+        option: Option<GetBlockOption>,
+    ) -> anyhow::Result<Option<BlockView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_block_by_hash(hash, option))
+            .await
             .map_err(map_err)
     }
 
@@ -701,7 +1381,16 @@ impl RpcClient {
         number: BlockNumber,
         option: Option<GetBlockOption>,
     ) -> anyhow::Result<Option<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_number(number, option))
+        self.block_on(self.chain_get_block_by_number_async(number, option))
+    }
+
+    pub async fn chain_get_block_by_number_async(
+        &self,
+        number: BlockNumber,
+        option: Option<GetBlockOption>,
+    ) -> anyhow::Result<Option<BlockView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_block_by_number(number, option))
+            .await
             .map_err(map_err)
     }
 
@@ -709,7 +1398,15 @@ impl RpcClient {
         &self,
         number: BlockNumber,
     ) -> anyhow::Result<Option<BlockInfo>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_info_by_number(number))
+        self.block_on(self.chain_get_block_info_by_number_async(number))
+    }
+
+    pub async fn chain_get_block_info_by_number_async(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<Option<BlockInfo>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_block_info_by_number(number))
+            .await
             .map_err(map_err)
     }
 
@@ -718,7 +1415,32 @@ impl RpcClient {
         number: Option<BlockNumber>,
         count: u64,
     ) -> anyhow::Result<Vec<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_blocks_by_number(number, count))
+        self.block_on(self.chain_get_blocks_by_number_async(number, count))
+    }
+
+    pub async fn chain_get_blocks_by_number_async(
+        &self,
+        number: Option<BlockNumber>,
+        count: u64,
+    ) -> anyhow::Result<Vec<BlockView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_blocks_by_number(number, count))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_blocks_page(
+        &self,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<BlockView>> {
+        self.block_on(self.chain_get_blocks_page_async(page))
+    }
+
+    pub async fn chain_get_blocks_page_async(
+        &self,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<BlockView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_blocks_page(page))
+            .await
             .map_err(map_err)
     }
 
@@ -727,7 +1449,16 @@ impl RpcClient {
         txn_id: HashValue,
         option: Option<GetTransactionOption>,
     ) -> anyhow::Result<Option<TransactionView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction(txn_id, option))
+        self.block_on(self.chain_get_transaction_async(txn_id, option))
+    }
+
+    pub async fn chain_get_transaction_async(
+        &self,
+        txn_id: HashValue,
+        option: Option<GetTransactionOption>,
+    ) -> anyhow::Result<Option<TransactionView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_transaction(txn_id, option))
+            .await
             .map_err(map_err)
     }
 
@@ -735,7 +1466,15 @@ impl RpcClient {
         &self,
         txn_hash: HashValue,
     ) -> anyhow::Result<Option<TransactionInfoView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction_info(txn_hash))
+        self.block_on(self.chain_get_transaction_info_async(txn_hash))
+    }
+
+    pub async fn chain_get_transaction_info_async(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<TransactionInfoView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_transaction_info(txn_hash))
+            .await
             .map_err(map_err)
     }
 
@@ -744,7 +1483,90 @@ impl RpcClient {
         txn_hash: HashValue,
         option: Option<GetEventOption>,
     ) -> anyhow::Result<Vec<TransactionEventResponse>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_events_by_txn_hash(txn_hash, option))
+        self.block_on(self.chain_get_events_by_txn_hash_async(txn_hash, option))
+    }
+
+    pub async fn chain_get_events_by_txn_hash_async(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetEventOption>,
+    ) -> anyhow::Result<Vec<TransactionEventResponse>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_events_by_txn_hash(txn_hash, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_events(
+        &self,
+        filter: EventFilter,
+        option: Option<GetEventOption>,
+    ) -> anyhow::Result<Vec<TransactionEventResponse>> {
+        self.block_on(self.chain_get_events_async(filter, option))
+    }
+
+    pub async fn chain_get_events_async(
+        &self,
+        filter: EventFilter,
+        option: Option<GetEventOption>,
+    ) -> anyhow::Result<Vec<TransactionEventResponse>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_events(filter, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_events_page(
+        &self,
+        filter: EventFilter,
+        option: Option<GetEventOption>,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<TransactionEventResponse>> {
+        self.block_on(self.chain_get_events_page_async(filter, option, page))
+    }
+
+    pub async fn chain_get_events_page_async(
+        &self,
+        filter: EventFilter,
+        option: Option<GetEventOption>,
+        page: Option<PageOption>,
+    ) -> anyhow::Result<PageView<TransactionEventResponse>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_events_page(filter, option, page))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_txn_write_set(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> anyhow::Result<Option<TransactionWriteSetView>> {
+        self.block_on(self.chain_get_txn_write_set_async(txn_hash, option))
+    }
+
+    pub async fn chain_get_txn_write_set_async(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> anyhow::Result<Option<TransactionWriteSetView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_txn_write_set(txn_hash, option))
+            .await
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_block_state_diff(
+        &self,
+        block_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> anyhow::Result<Option<BlockStateDiffView>> {
+        self.block_on(self.chain_get_block_state_diff_async(block_hash, option))
+    }
+
+    pub async fn chain_get_block_state_diff_async(
+        &self,
+        block_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> anyhow::Result<Option<BlockStateDiffView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_block_state_diff(block_hash, option))
+            .await
             .map_err(map_err)
     }
 
@@ -752,7 +1574,15 @@ impl RpcClient {
         &self,
         block_id: HashValue,
     ) -> anyhow::Result<Vec<TransactionInfoView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_txn_infos(block_id))
+        self.block_on(self.chain_get_block_txn_infos_async(block_id))
+    }
+
+    pub async fn chain_get_block_txn_infos_async(
+        &self,
+        block_id: HashValue,
+    ) -> anyhow::Result<Vec<TransactionInfoView>> {
+        self.call_rpc_async(|inner| inner.chain_client.get_block_txn_infos(block_id))
+            .await
             .map_err(map_err)
     }
 
@@ -761,29 +1591,54 @@ impl RpcClient {
         block_id: HashValue,
         idx: u64,
     ) -> anyhow::Result<Option<TransactionInfoView>> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.chain_get_txn_info_by_block_and_index_async(block_id, idx))
+    }
+
+    pub async fn chain_get_txn_info_by_block_and_index_async(
+        &self,
+        block_id: HashValue,
+        idx: u64,
+    ) -> anyhow::Result<Option<TransactionInfoView>> {
+        self.call_rpc_async(|inner| {
             inner
                 .chain_client
                 .get_txn_info_by_block_and_index(block_id, idx)
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<DryRunOutputView> {
-        self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
+        self.block_on(self.dry_run_async(txn))
+    }
+
+    pub async fn dry_run_async(
+        &self,
+        txn: DryRunTransactionRequest,
+    ) -> anyhow::Result<DryRunOutputView> {
+        self.call_rpc_async(|inner| inner.contract_client.dry_run(txn))
+            .await
             .map_err(map_err)
     }
     pub fn dry_run_raw(&self, txn: DryRunTransaction) -> anyhow::Result<DryRunOutputView> {
+        self.block_on(self.dry_run_raw_async(txn))
+    }
+
+    pub async fn dry_run_raw_async(
+        &self,
+        txn: DryRunTransaction,
+    ) -> anyhow::Result<DryRunOutputView> {
         let DryRunTransaction {
             raw_txn,
             public_key,
         } = txn;
         let raw_txn_str = hex::encode(raw_txn.encode()?);
-        self.call_rpc_blocking(|inner| {
+        self.call_rpc_async(|inner| {
             inner
                 .contract_client
                 .dry_run_raw(raw_txn_str, StrView(public_key))
         })
+        .await
         .map_err(map_err)
     }
     pub fn miner_submit(
@@ -792,8 +1647,7 @@ impl RpcClient {
         nonce: u32,
         extra: String,
     ) -> anyhow::Result<MintedBlockView> {
-        self.call_rpc_blocking(|inner| inner.miner_client.submit(minting_blob, nonce, extra))
-            .map_err(map_err)
+        self.block_on(self.miner_submit_async(minting_blob, nonce, extra))
     }
     pub async fn miner_submit_async(
         &self,
@@ -807,7 +1661,12 @@ impl RpcClient {
     }
 
     pub fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.state())
+        self.block_on(self.txpool_status_async())
+    }
+
+    pub async fn txpool_status_async(&self) -> anyhow::Result<TxPoolStatus> {
+        self.call_rpc_async(|inner| inner.txpool_client.state())
+            .await
             .map_err(map_err)
     }
 
@@ -816,38 +1675,74 @@ impl RpcClient {
         filter: EventFilter,
         decode: bool,
     ) -> anyhow::Result<impl TryStream<Ok = TransactionEventView, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
+        self.block_on(self.subscribe_events_async(filter, decode))
+    }
+
+    pub async fn subscribe_events_async(
+        &self,
+        filter: EventFilter,
+        decode: bool,
+    ) -> anyhow::Result<impl TryStream<Ok = TransactionEventView, Error = anyhow::Error>> {
+        self.call_rpc_async(|inner| async move {
             let res = inner.pubsub_client.subscribe_events(filter, decode).await;
             res.map(|s| s.map_err(map_err))
         })
+        .await
         .map_err(map_err)
     }
     pub fn subscribe_new_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = BlockView, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
+        self.block_on(self.subscribe_new_blocks_async())
+    }
+
+    pub async fn subscribe_new_blocks_async(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = BlockView, Error = anyhow::Error>> {
+        self.call_rpc_async(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_block().await;
             res.map(|s| s.map_err(map_err))
         })
+        .await
         .map_err(map_err)
     }
     pub fn subscribe_new_transactions(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = Vec<HashValue>, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
+        self.block_on(self.subscribe_new_transactions_async())
+    }
+
+    pub async fn subscribe_new_transactions_async(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = Vec<HashValue>, Error = anyhow::Error>> {
+        self.call_rpc_async(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_transactions().await;
             res.map(|s| s.map_err(map_err))
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn subscribe_new_mint_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlockEvent, Error = anyhow::Error>> {
-        self.call_rpc_blocking(|inner| async move {
-            let res = inner.pubsub_client.subscribe_new_mint_block().await;
+        self.block_on(self.subscribe_new_mint_blocks_async())
+    }
+
+    pub fn subscribe_chain_reorg(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = ChainReorgEvent, Error = anyhow::Error>> {
+        self.block_on(self.subscribe_chain_reorg_async())
+    }
+
+    pub async fn subscribe_chain_reorg_async(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = ChainReorgEvent, Error = anyhow::Error>> {
+        self.call_rpc_async(|inner| async move {
+            let res = inner.pubsub_client.subscribe_chain_reorg().await;
             res.map(|s| s.map_err(map_err))
         })
+        .await
         .map_err(map_err)
     }
 
@@ -862,16 +1757,9 @@ impl RpcClient {
         .map_err(map_err)
     }
 
-    fn call_rpc_blocking<F, T>(
-        &self,
-        f: impl FnOnce(RpcClientInner) -> F + Send,
-    ) -> Result<T, jsonrpc_client_transports::RpcError>
-    where
-        T: Send,
-        F: std::future::Future<Output = Result<T, jsonrpc_client_transports::RpcError>> + Send,
-    {
-        self.provider
-            .block_on(async { self.call_rpc_async(f).await })
+    /// Run an async client call to completion on the client's runtime.
+    fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
+        self.runtime.lock().block_on(future)
     }
 
     async fn call_rpc_async<F, T>(
@@ -881,46 +1769,42 @@ impl RpcClient {
     where
         F: std::future::Future<Output = Result<T, jsonrpc_client_transports::RpcError>> + Send,
     {
-        let inner_opt = self.inner.lock().as_ref().cloned();
-        let inner = match inner_opt {
-            Some(inner) => inner,
-            None => {
-                info!(
-                    "Connection is lost, try reconnect by {:?}",
-                    &self.provider.conn_source
-                );
-                let new_inner: RpcClientInner = self
-                    .provider
-                    .get_rpc_channel_async()
-                    .await
-                    .map(|c| c.into())?;
-                *(self.inner.lock()) = Some(new_inner.clone());
-                self.chain_watcher.do_send(StartSubscribe {
-                    client: new_inner.pubsub_client.clone(),
-                });
-                new_inner
-            }
-        };
+        let inner = self.provider.get_client(MAX_RECONNECT_RETRIES).await?;
         let result = f(inner).await;
         if let Err(jsonrpc_client_transports::RpcError::Other(e)) = &result {
             error!("rpc error due to {}", e);
-            *(self.inner.lock()) = None;
+            self.provider.disconnect();
         }
         result
     }
 
     pub fn sync_status(&self) -> anyhow::Result<SyncStatus> {
-        self.call_rpc_blocking(|inner| inner.sync_client.status())
+        self.block_on(self.sync_status_async())
+    }
+
+    pub async fn sync_status_async(&self) -> anyhow::Result<SyncStatus> {
+        self.call_rpc_async(|inner| inner.sync_client.status())
+            .await
             .map_err(map_err)
     }
 
     pub fn sync_progress(&self) -> anyhow::Result<Option<SyncProgressReport>> {
-        self.call_rpc_blocking(|inner| inner.sync_client.progress())
+        self.block_on(self.sync_progress_async())
+    }
+
+    pub async fn sync_progress_async(&self) -> anyhow::Result<Option<SyncProgressReport>> {
+        self.call_rpc_async(|inner| inner.sync_client.progress())
+            .await
             .map_err(map_err)
     }
 
     pub fn sync_peer_score(&self) -> anyhow::Result<PeerScoreResponse> {
-        self.call_rpc_blocking(|inner| inner.sync_client.peer_score())
+        self.block_on(self.sync_peer_score_async())
+    }
+
+    pub async fn sync_peer_score_async(&self) -> anyhow::Result<PeerScoreResponse> {
+        self.call_rpc_async(|inner| inner.sync_client.peer_score())
+            .await
             .map_err(map_err)
     }
 
@@ -931,36 +1815,75 @@ impl RpcClient {
         skip_pow_verify: bool,
         strategy: Option<PeerStrategy>,
     ) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.sync_start_async(force, peers, skip_pow_verify, strategy))
+    }
+
+    pub async fn sync_start_async(
+        &self,
+        force: bool,
+        peers: Vec<PeerId>,
+        skip_pow_verify: bool,
+        strategy: Option<PeerStrategy>,
+    ) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| {
             inner
                 .sync_client
                 .start(force, peers, skip_pow_verify, strategy)
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn sync_cancel(&self) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.sync_client.cancel())
+        self.block_on(self.sync_cancel_async())
+    }
+
+    pub async fn sync_cancel_async(&self) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.sync_client.cancel())
+            .await
             .map_err(map_err)
     }
 
     pub fn network_known_peers(&self) -> anyhow::Result<Vec<PeerId>> {
-        self.call_rpc_blocking(|inner| inner.network_client.known_peers())
+        self.block_on(self.network_known_peers_async())
+    }
+
+    pub async fn network_known_peers_async(&self) -> anyhow::Result<Vec<PeerId>> {
+        self.call_rpc_async(|inner| inner.network_client.known_peers())
+            .await
             .map_err(map_err)
     }
 
     pub fn network_state(&self) -> anyhow::Result<NetworkState> {
-        self.call_rpc_blocking(|inner| inner.network_client.state())
+        self.block_on(self.network_state_async())
+    }
+
+    pub async fn network_state_async(&self) -> anyhow::Result<NetworkState> {
+        self.call_rpc_async(|inner| inner.network_client.state())
+            .await
             .map_err(map_err)
     }
 
     pub fn network_get_address(&self, peer_id: String) -> anyhow::Result<Vec<Multiaddr>> {
-        self.call_rpc_blocking(|inner| inner.network_client.get_address(peer_id))
+        self.block_on(self.network_get_address_async(peer_id))
+    }
+
+    pub async fn network_get_address_async(
+        &self,
+        peer_id: String,
+    ) -> anyhow::Result<Vec<Multiaddr>> {
+        self.call_rpc_async(|inner| inner.network_client.get_address(peer_id))
+            .await
             .map_err(map_err)
     }
 
     pub fn network_add_peer(&self, peer: String) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.network_client.add_peer(peer))
+        self.block_on(self.network_add_peer_async(peer))
+    }
+
+    pub async fn network_add_peer_async(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc_async(|inner| inner.network_client.add_peer(peer))
+            .await
             .map_err(map_err)
     }
 
@@ -970,16 +1893,31 @@ impl RpcClient {
         rpc_method: String,
         message: StrView<Vec<u8>>,
     ) -> anyhow::Result<StrView<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| {
+        self.block_on(self.network_call_peer_async(peer_id, rpc_method, message))
+    }
+
+    pub async fn network_call_peer_async(
+        &self,
+        peer_id: String,
+        rpc_method: String,
+        message: StrView<Vec<u8>>,
+    ) -> anyhow::Result<StrView<Vec<u8>>> {
+        self.call_rpc_async(|inner| {
             inner
                 .network_client
                 .call_peer(peer_id, rpc_method.into(), message)
         })
+        .await
         .map_err(map_err)
     }
 
     pub fn call_raw_api(&self, api: &str, params: Params) -> anyhow::Result<Value> {
-        self.call_rpc_blocking(|inner| inner.raw_client.call_method(api, params))
+        self.block_on(self.call_raw_api_async(api, params))
+    }
+
+    pub async fn call_raw_api_async(&self, api: &str, params: Params) -> anyhow::Result<Value> {
+        self.call_rpc_async(|inner| inner.raw_client.call_method(api, params))
+            .await
             .map_err(map_err)
     }
 
@@ -5,7 +5,7 @@ use jsonrpc_core_client::*;
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::pubsub::EventParams;
 use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventView};
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 
 const STARCOIN_SUBSCRIPTION: &str = "starcoin_subscription";
 const STARCOIN_SUBSCRIBE: &str = "starcoin_subscribe";
@@ -76,4 +76,15 @@ impl PubSubClient {
             "MintBlockEvent",
         )
     }
+    pub async fn subscribe_chain_reorg(
+        &self,
+    ) -> Result<TypedSubscriptionStream<ChainReorgEvent>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::ChainReorg],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "ChainReorgEvent",
+        )
+    }
 }
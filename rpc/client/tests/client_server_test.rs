@@ -5,8 +5,17 @@ use anyhow::Result;
 use futures::{StreamExt, TryStreamExt};
 use starcoin_config::NodeConfig;
 use starcoin_logger::prelude::*;
+use starcoin_rpc_api::chain::{
+    GetBlockOption, GetEventOption, GetTransactionOption, GetWriteSetOption,
+};
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::{
+    to_cursor, BlockTransactionsView, PageOption, ResourceView, TransactionEventResponse,
+    TransactionPayloadView, WriteOpValueView,
+};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_vm_types::account_config::genesis_address;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -22,6 +31,7 @@ fn do_client_test() -> Result<()> {
     let config = Arc::new(node_config);
     let ipc_file = config.rpc.get_ipc_file();
     let url = config.rpc.get_ws_address().unwrap();
+    let http_url = config.rpc.get_http_address().unwrap();
     debug!("url:{}", url);
     debug!("data_dir:{:?}", config.data_dir());
 
@@ -43,9 +53,15 @@ fn do_client_test() -> Result<()> {
         RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail.");
     let status = ws_client.node_info()?;
     info!("ws_client node_status: {:?}", status);
+
+    let http_client =
+        RpcClient::connect_http(http_url.to_string().as_str()).expect("connect http fail.");
+    let status = http_client.node_info()?;
+    info!("http_client node_status: {:?}", status);
     local_client.close();
     ipc_client.close();
     ws_client.close();
+    http_client.close();
     if let Err(e) = node_handle.stop() {
         error!("node stop error: {:?}", e)
     }
@@ -78,10 +94,7 @@ fn test_client_reconnect() -> Result<()> {
 
     let node_handle = test_helper::run_node_by_config(config)?;
     std::thread::sleep(Duration::from_millis(300));
-    //first call after lost connection will return error
-    let result = ws_client.node_info();
-    assert!(result.is_err());
-    //second call will return ok
+    //the lost connection is reestablished by the chain watcher or the first call.
     let result = ws_client.node_info();
     assert!(result.is_ok());
 
@@ -117,9 +130,8 @@ fn test_client_reconnect_subscribe() -> Result<()> {
 
     let node_handle = test_helper::run_node_by_config(config)?;
     std::thread::sleep(Duration::from_millis(300));
-    //first call after lost connection will return error
     let result = ws_client.node_info();
-    assert!(result.is_err());
+    assert!(result.is_ok());
 
     let stream2 = ws_client.subscribe_new_mint_blocks()?;
     let handle2 = async_std::task::spawn(async move {
@@ -139,3 +151,264 @@ fn test_client_reconnect_subscribe() -> Result<()> {
     assert_ne!(events2.len(), 0);
     Ok(())
 }
+
+#[stest::test(timeout = 120)]
+fn test_client_watch_block_across_reconnect() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let url = config.rpc.get_ws_address().unwrap();
+    debug!("url:{}", url);
+
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let ws_client =
+        RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail.");
+    let head = ws_client.chain_info()?.head.number.0;
+    // restart the node while the block is watched, the watcher keeps pending across the reconnect.
+    let restart_handle = std::thread::spawn(move || -> Result<_> {
+        std::thread::sleep(Duration::from_millis(500));
+        let _e = node_handle.stop();
+        let node_handle = test_helper::run_node_by_config(config)?;
+        std::thread::sleep(Duration::from_millis(300));
+        let block = node_handle.generate_block()?;
+        Ok((node_handle, block))
+    });
+    let block = ws_client.watch_block(head + 1)?;
+    assert_eq!(block.header.number.0, head + 1);
+
+    let (node_handle, generated) = restart_handle.join().expect("restart node fail.")?;
+    assert_eq!(generated.header().id(), block.header.block_hash);
+    ws_client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_http_client_watch_block() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let url = config.rpc.get_http_address().unwrap();
+    debug!("url:{}", url);
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let http_client =
+        RpcClient::connect_http(url.to_string().as_str()).expect("connect http fail.");
+    let head = http_client.chain_info()?.head.number.0;
+    let generate_handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(2));
+        let result = node_handle.generate_block();
+        (node_handle, result)
+    });
+    let block = http_client.watch_block(head + 1)?;
+    assert_eq!(block.header.number.0, head + 1);
+
+    let (node_handle, result) = generate_handle.join().expect("generate block fail.");
+    assert_eq!(result?.header().id(), block.header.block_hash);
+    http_client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_block_state_diff() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.storage.enable_write_set = Some(true);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config)?;
+    let client = RpcClient::connect_local(node_handle.rpc_service()?)?;
+
+    let block = node_handle.generate_block()?;
+    let diff = client
+        .chain_get_block_state_diff(block.id(), Some(GetWriteSetOption { decode: true }))?
+        .expect("block should exist");
+    assert_eq!(diff.block_hash, block.id());
+    // the block metadata txn at least updates the block of the chain.
+    let txn_write_set = diff.txns.first().expect("block should have txns");
+    assert!(!txn_write_set.changes.is_empty());
+    // the resources of the chain exist before the block, so both the old and new values are set.
+    assert!(txn_write_set.changes.iter().any(|change| matches!(
+        (&change.old_value, &change.new_value),
+        (
+            Some(WriteOpValueView::Resource(_)),
+            Some(WriteOpValueView::Resource(ResourceView {
+                json: Some(_),
+                ..
+            }))
+        )
+    )));
+
+    let write_set = client
+        .chain_get_txn_write_set(txn_write_set.transaction_hash, None)?
+        .expect("txn should exist");
+    assert_eq!(write_set.changes.len(), txn_write_set.changes.len());
+    client.close();
+    node_handle.stop()?;
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_decode_txn_payload_and_events() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_local(node_handle.rpc_service()?)?;
+
+    // the txn creates a new account, its events are of the types published before the txn.
+    let (_account, txn) =
+        test_helper::txn::create_account_with_txpool(config.net(), &node_handle.txpool(), 1)
+            .pop()
+            .expect("should create a txn");
+    let txn_hash = client.submit_transaction(txn)?;
+    let block = node_handle.generate_block()?;
+
+    let txn_view = client
+        .chain_get_transaction(txn_hash, Some(GetTransactionOption { decode: true }))?
+        .expect("txn should exist");
+    assert_eq!(txn_view.block_hash, block.id());
+    let decoded_payload = txn_view
+        .user_transaction
+        .expect("txn should be a user txn")
+        .raw_txn
+        .decoded_payload;
+    match decoded_payload {
+        Some(TransactionPayloadView::ScriptFunction(function)) => {
+            assert_eq!(
+                function.function.as_str(),
+                "create_account_with_initial_amount"
+            );
+            assert_eq!(function.args.len(), 3);
+        }
+        _ => panic!("unexpected decoded payload: {:?}", decoded_payload),
+    }
+
+    let block_view = client
+        .chain_get_block_by_hash(block.id(), Some(GetBlockOption { decode: true }))?
+        .expect("block should exist");
+    match block_view.body {
+        BlockTransactionsView::Full(txns) => {
+            assert!(txns.iter().all(|txn| txn.raw_txn.decoded_payload.is_some()));
+        }
+        _ => panic!("the block should have full txns"),
+    }
+
+    let events =
+        client.chain_get_events_by_txn_hash(txn_hash, Some(GetEventOption { decode: true }))?;
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|e| e.decode_event_data.is_some()));
+
+    client.close();
+    node_handle.stop()?;
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_get_events_page() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_local(node_handle.rpc_service()?)?;
+
+    // the txn emits several events in one block, so a page may end in the middle of a block.
+    let (_account, txn) =
+        test_helper::txn::create_account_with_txpool(config.net(), &node_handle.txpool(), 1)
+            .pop()
+            .expect("should create a txn");
+    client.submit_transaction(txn)?;
+    node_handle.generate_block()?;
+    node_handle.generate_block()?;
+
+    let filter = EventFilter {
+        from_block: Some(0),
+        to_block: None,
+        event_keys: None,
+        addrs: None,
+        type_tags: None,
+        limit: None,
+    };
+    let all_events = client.chain_get_events(
+        EventFilter {
+            limit: Some(1000),
+            ..filter.clone()
+        },
+        None,
+    )?;
+    assert!(all_events.len() > 3);
+
+    for limit in 1..=3 {
+        let mut events = vec![];
+        let mut cursor = None;
+        let mut cursor_in_block = false;
+        loop {
+            let page = client.chain_get_events_page(
+                filter.clone(),
+                None,
+                Some(PageOption::new(Some(limit), cursor)),
+            )?;
+            assert!(page.data.len() <= limit as usize);
+            if let (Some(last), Some(first)) = (events.last(), page.data.first()) {
+                let last: &TransactionEventResponse = last;
+                cursor_in_block |= last.event.block_number == first.event.block_number;
+            }
+            events.extend(page.data);
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        assert_eq!(events, all_events);
+        assert!(cursor_in_block);
+    }
+
+    // a cursor which points to no event of the block is rejected.
+    let first = &all_events[0].event;
+    let position = (
+        first.block_number.unwrap().0,
+        first.transaction_index.unwrap(),
+        u64::MAX,
+    );
+    let cursor = to_cursor(&position)?;
+    assert!(client
+        .chain_get_events_page(filter, None, Some(PageOption::new(Some(1), Some(cursor))))
+        .is_err());
+
+    client.close();
+    node_handle.stop()?;
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_list_resource_page() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config)?;
+    let client = RpcClient::connect_local(node_handle.rpc_service()?)?;
+
+    let address = genesis_address();
+    let all_resources = client.state_list_resource(address, false, None)?.resources;
+    assert!(all_resources.len() > 3);
+
+    let mut resources = vec![];
+    let mut cursor = None;
+    loop {
+        let page = client.state_list_resource_page(
+            address,
+            Default::default(),
+            Some(PageOption::new(Some(3), cursor)),
+        )?;
+        assert!(page.data.len() <= 3);
+        resources.extend(page.data);
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+    assert_eq!(resources.len(), all_resources.len());
+    for item in resources {
+        assert_eq!(
+            all_resources.get(&item.struct_tag).map(|r| &r.raw),
+            Some(&item.resource.raw)
+        );
+    }
+
+    client.close();
+    node_handle.stop()?;
+    Ok(())
+}
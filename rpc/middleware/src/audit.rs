@@ -0,0 +1,153 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use crate::{id_to_string, output_to_code, ApiKeyMetadata};
+use futures::{future::Either, Future, FutureExt};
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::serde_json::{self, json};
+use jsonrpc_core::{Call, FutureResponse, Middleware, Output, Params};
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_logger::AUDIT_LOGGER;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const REDACTED: &str = "<redacted>";
+
+/// Metadata which identifies the caller for the audit log.
+pub trait AuditMetadata: ApiKeyMetadata {
+    /// The caller, e.g. the ip of the client.
+    fn user(&self) -> Option<&str>;
+}
+
+/// Writes a record of every call to the `AUDIT_LOGGER`: method, params digest, caller, latency
+/// and result code. The params of slow calls are written in full, except the redacted methods.
+#[derive(Clone, Debug)]
+pub struct AuditMiddleware {
+    enable: bool,
+    slow_call_threshold: Duration,
+    redacted_methods: Arc<HashSet<String>>,
+    /// Names of the api keys indexed by the key, the key itself is never written.
+    api_key_names: Arc<HashMap<String, String>>,
+}
+
+impl AuditMiddleware {
+    pub fn new(
+        slow_call_threshold: Duration,
+        redacted_methods: HashSet<String>,
+        api_key_names: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            enable: true,
+            slow_call_threshold,
+            redacted_methods: Arc::new(redacted_methods),
+            api_key_names: Arc::new(api_key_names),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enable: false,
+            slow_call_threshold: Duration::from_secs(0),
+            redacted_methods: Default::default(),
+            api_key_names: Default::default(),
+        }
+    }
+}
+
+struct AuditRecord {
+    id: String,
+    method: String,
+    params: Option<Params>,
+    redacted: bool,
+    user: Option<String>,
+    api_key: Option<String>,
+    start: Instant,
+}
+
+impl AuditRecord {
+    fn end(self, code: i64, slow_call_threshold: Duration) {
+        let latency = self.start.elapsed();
+        let slow = latency >= slow_call_threshold;
+        let params_digest = if self.redacted {
+            json!(REDACTED)
+        } else {
+            json!(self.params.as_ref().map(|params| {
+                let params = serde_json::to_vec(params).unwrap_or_default();
+                HashValue::sha3_256_of(params.as_slice()).to_string()
+            }))
+        };
+        if slow {
+            warn!(
+                "slow rpc call {} id: {} took {:?}",
+                self.method, self.id, latency
+            );
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let mut record = json!({
+            "timestamp": timestamp,
+            "id": self.id,
+            "method": self.method,
+            "params_digest": params_digest,
+            "user": self.user,
+            "api_key": self.api_key,
+            "latency_ms": latency.as_millis() as u64,
+            "code": code,
+            "slow": slow,
+        });
+        if slow && !self.redacted {
+            record["params"] = json!(self.params);
+        }
+        info!(target: AUDIT_LOGGER, "{}", record);
+    }
+}
+
+impl<M: AuditMetadata> Middleware<M> for AuditMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: M, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, M) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        if !self.enable {
+            return Either::Right(next(call, meta));
+        }
+        let (id, method, params) = match &call {
+            Call::MethodCall(m) => (
+                id_to_string(&m.id),
+                m.method.clone(),
+                Some(m.params.clone()),
+            ),
+            Call::Notification(n) => ("".to_owned(), n.method.clone(), Some(n.params.clone())),
+            Call::Invalid { id } => (id_to_string(id), "".to_owned(), None),
+        };
+        let api_key = meta.api_key().map(|key| {
+            self.api_key_names
+                .get(key)
+                .cloned()
+                .unwrap_or_else(|| "<invalid>".to_owned())
+        });
+        let record = AuditRecord {
+            id,
+            redacted: self.redacted_methods.contains(&method),
+            method,
+            params,
+            user: meta.user().map(ToOwned::to_owned),
+            api_key,
+            start: Instant::now(),
+        };
+        let slow_call_threshold = self.slow_call_threshold;
+        let fut = next(call, meta).map(move |output| {
+            record.end(output_to_code(output.as_ref()), slow_call_threshold);
+            output
+        });
+        let box_fut: Self::CallFuture = Box::pin(fut);
+        Either::Left(box_fut)
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use futures::{future::Either, Future};
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{
+    Call, Error, ErrorCode, Failure, FutureResponse, Id, Metadata, Middleware, Output,
+};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// The error code returned when the api key is invalid or the method is out of the scopes.
+pub const UNAUTHORIZED_ERROR_CODE: i64 = -10001;
+
+/// Metadata which carries the api key of the caller.
+pub trait ApiKeyMetadata: Metadata {
+    /// The api key presented by the caller, e.g. the bearer token of a http request.
+    fn api_key(&self) -> Option<&str>;
+    /// Record the name of the api key after it is verified.
+    fn set_key_name(&mut self, name: String);
+}
+
+#[derive(Clone, Debug)]
+pub struct ApiKey<Scope> {
+    pub name: String,
+    pub scopes: HashSet<Scope>,
+}
+
+/// Checks the caller is allowed to call the method: the callers with an api key are allowed to
+/// call the methods in the scopes of the key, others are allowed to call the anonymous scopes.
+#[derive(Debug)]
+pub struct ApiKeyMiddleware<Scope> {
+    anonymous_scopes: HashSet<Scope>,
+    /// Api keys indexed by the key.
+    api_keys: HashMap<String, ApiKey<Scope>>,
+    method_scopes: HashMap<String, Scope>,
+}
+
+impl<Scope> ApiKeyMiddleware<Scope>
+where
+    Scope: Clone + Eq + Hash,
+{
+    pub fn new(
+        anonymous_scopes: HashSet<Scope>,
+        api_keys: HashMap<String, ApiKey<Scope>>,
+        method_scopes: HashMap<String, Scope>,
+    ) -> Self {
+        Self {
+            anonymous_scopes,
+            api_keys,
+            method_scopes,
+        }
+    }
+
+    fn check<M: ApiKeyMetadata>(&self, method: &str, meta: &mut M) -> Result<(), String> {
+        let scope = match self.method_scopes.get(method) {
+            Some(scope) => scope,
+            // let the io handler report the method is not found.
+            None => return Ok(()),
+        };
+        let api_key = match meta.api_key() {
+            Some(key) => Some(
+                self.api_keys
+                    .get(key)
+                    .ok_or_else(|| "invalid api key".to_string())?,
+            ),
+            None => None,
+        };
+        let scopes = match api_key {
+            Some(api_key) => {
+                meta.set_key_name(api_key.name.clone());
+                &api_key.scopes
+            }
+            None => &self.anonymous_scopes,
+        };
+        if scopes.contains(scope) {
+            Ok(())
+        } else {
+            Err(format!("method {} is not allowed", method))
+        }
+    }
+}
+
+impl<M, Scope> Middleware<M> for ApiKeyMiddleware<Scope>
+where
+    M: ApiKeyMetadata,
+    Scope: Clone + Eq + Hash + Send + Sync + 'static,
+{
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, mut meta: M, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, M) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let method = match &call {
+            Call::MethodCall(m) => Some((m.method.clone(), m.jsonrpc, m.id.clone())),
+            Call::Notification(n) => Some((n.method.clone(), n.jsonrpc, Id::Null)),
+            Call::Invalid { .. } => None,
+        };
+        if let Some((m, json_version, id)) = method {
+            match self.check(m.as_str(), &mut meta) {
+                Ok(_) => Either::Right(next(call, meta)),
+                Err(e) => {
+                    let output = Output::Failure(Failure {
+                        jsonrpc: json_version,
+                        error: Error {
+                            code: ErrorCode::ServerError(UNAUTHORIZED_ERROR_CODE),
+                            message: e,
+                            data: None,
+                        },
+                        id,
+                    });
+                    Either::Left(Box::pin(futures::future::ready(Some(output))))
+                }
+            }
+        } else {
+            Either::Right(next(call, meta))
+        }
+    }
+}
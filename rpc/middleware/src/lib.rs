@@ -7,8 +7,12 @@ use starcoin_logger::prelude::*;
 use starcoin_metrics::HistogramTimer;
 use std::fmt;
 
+mod audit;
+mod auth;
 mod metrics;
 
+pub use audit::*;
+pub use auth::*;
 use jsonrpc_core::middleware::NoopCallFuture;
 pub use metrics::*;
 
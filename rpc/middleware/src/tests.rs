@@ -1,8 +1,10 @@
 use super::*;
 use futures::executor::block_on;
+use jsonrpc_core::serde_json;
 use jsonrpc_core::{MetaIoHandler, Params, Value};
 use rand::Rng;
 use starcoin_metrics::get_all_metrics;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 #[stest::test]
@@ -29,3 +31,136 @@ fn test_middleware() {
     }
     info!("metrics: {:?}", get_all_metrics());
 }
+
+#[derive(Clone, Default)]
+struct KeyMeta {
+    api_key: Option<String>,
+}
+
+impl Metadata for KeyMeta {}
+
+impl ApiKeyMetadata for KeyMeta {
+    fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    fn set_key_name(&mut self, _name: String) {}
+}
+
+impl AuditMetadata for KeyMeta {
+    fn user(&self) -> Option<&str> {
+        Some("127.0.0.1")
+    }
+}
+
+#[stest::test]
+fn test_api_key_middleware() {
+    let method_scopes: HashMap<String, &'static str> = vec![
+        ("chain.info".to_string(), "chain"),
+        ("account.list".to_string(), "account"),
+    ]
+    .into_iter()
+    .collect();
+    let api_keys = vec![(
+        "secret".to_string(),
+        ApiKey {
+            name: "internal".to_string(),
+            scopes: vec!["chain", "account"].into_iter().collect(),
+        },
+    )]
+    .into_iter()
+    .collect();
+    let anonymous_scopes: HashSet<&'static str> = vec!["chain"].into_iter().collect();
+    let mut io_handler = MetaIoHandler::with_middleware(ApiKeyMiddleware::new(
+        anonymous_scopes,
+        api_keys,
+        method_scopes,
+    ));
+    io_handler.add_method_with_meta("chain.info", |_params: Params, _meta: KeyMeta| async {
+        Ok(Value::Bool(true))
+    });
+    io_handler.add_method_with_meta("account.list", |_params: Params, _meta: KeyMeta| async {
+        Ok(Value::Bool(true))
+    });
+    let call = |method: &str, api_key: Option<&str>| {
+        let request = format!(
+            r#"{{"jsonrpc":"2.0","method":"{}","params":[],"id":1}}"#,
+            method
+        );
+        let meta = KeyMeta {
+            api_key: api_key.map(ToString::to_string),
+        };
+        let response = block_on(io_handler.handle_request(request.as_str(), meta)).unwrap();
+        !response.contains("error")
+    };
+    assert!(call("chain.info", None));
+    assert!(!call("account.list", None));
+    assert!(call("account.list", Some("secret")));
+    assert!(!call("chain.info", Some("invalid")));
+}
+
+#[stest::test]
+fn test_audit_middleware() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let audit_path = tmp_dir.path().join("rpc_audit.log");
+    starcoin_logger::init_for_test().enable_audit_file(audit_path.clone(), 1024 * 1024, 1);
+    let redacted_methods = vec!["account.unlock".to_string()].into_iter().collect();
+    let api_key_names = vec![("secret".to_string(), "internal".to_string())]
+        .into_iter()
+        .collect();
+    let mut io_handler = MetaIoHandler::with_middleware(AuditMiddleware::new(
+        Duration::from_millis(10),
+        redacted_methods,
+        api_key_names,
+    ));
+    io_handler.add_method_with_meta("chain.info", |_params: Params, _meta: KeyMeta| async {
+        std::thread::sleep(Duration::from_millis(20));
+        Ok(Value::Bool(true))
+    });
+    io_handler.add_method_with_meta("account.unlock", |_params: Params, _meta: KeyMeta| async {
+        Ok(Value::Bool(true))
+    });
+    let call = |method: &str, api_key: Option<&str>| {
+        let request = format!(
+            r#"{{"jsonrpc":"2.0","method":"{}","params":["password"],"id":1}}"#,
+            method
+        );
+        let meta = KeyMeta {
+            api_key: api_key.map(ToString::to_string),
+        };
+        block_on(io_handler.handle_request(request.as_str(), meta)).unwrap()
+    };
+    assert!(!call("chain.info", Some("secret")).contains("error"));
+    assert!(!call("account.unlock", None).contains("error"));
+    assert!(call("node.info", Some("invalid")).contains("error"));
+
+    let content = std::fs::read_to_string(audit_path).unwrap();
+    // only the params of the slow call are written in full, the redacted ones never are.
+    assert_eq!(content.matches("password").count(), 1);
+    let records = content
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .map(|record| (record["method"].as_str().unwrap().to_string(), record))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(records.len(), 3);
+
+    let slow_call = &records["chain.info"];
+    assert_eq!(slow_call["api_key"], "internal");
+    assert_eq!(slow_call["user"], "127.0.0.1");
+    assert_eq!(slow_call["code"], 0);
+    assert_eq!(slow_call["slow"], true);
+    assert_eq!(slow_call["params"], serde_json::json!(["password"]));
+    assert!(slow_call["params_digest"].is_string());
+
+    let redacted_call = &records["account.unlock"];
+    assert_eq!(redacted_call["api_key"], Value::Null);
+    assert_eq!(redacted_call["code"], 0);
+    assert_eq!(redacted_call["slow"], false);
+    assert_eq!(redacted_call["params_digest"], "<redacted>");
+    assert!(redacted_call.get("params").is_none());
+
+    let invalid_call = &records["node.info"];
+    assert_eq!(invalid_call["api_key"], "<invalid>");
+    assert_eq!(invalid_call["code"], -32601);
+    assert!(invalid_call.get("params").is_none());
+}
@@ -3,23 +3,36 @@
 
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
 use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
-use starcoin_config::{Api, ApiQuotaConfiguration};
+use starcoin_config::{Api, ApiKeyConfig, ApiQuotaConfiguration, AuditLogConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
-use starcoin_rpc_middleware::MetricMiddleware;
-use std::collections::HashMap;
+use starcoin_rpc_middleware::{ApiKey, ApiKeyMiddleware, AuditMiddleware, MetricMiddleware};
+use std::collections::{HashMap, HashSet};
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    MetricMiddleware,
+    AuditMiddleware,
+    ApiKeyMiddleware<Api>,
+    JsonApiRateLimitMiddleware,
+);
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
     quotas: ApiQuotaConfiguration,
+    api_keys: Vec<ApiKeyConfig>,
+    audit: AuditLogConfiguration,
 }
 
 impl ApiRegistry {
-    pub fn new(api_quotas: ApiQuotaConfiguration) -> ApiRegistry {
+    pub fn new(
+        api_quotas: ApiQuotaConfiguration,
+        api_keys: Vec<ApiKeyConfig>,
+        audit: AuditLogConfiguration,
+    ) -> ApiRegistry {
         Self {
             apis: Default::default(),
             quotas: api_quotas,
+            api_keys,
+            audit,
         }
     }
 
@@ -27,29 +40,47 @@ impl ApiRegistry {
     where
         F: IntoIterator<Item = (String, RemoteProcedure<Metadata>)>,
     {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
-        let io_handler = self.apis.entry(api_type).or_insert_with(|| {
-            MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                MetricMiddleware,
-                rate_limit_middleware,
-            ))
-        });
-        io_handler.extend_with(apis);
+        if !self.apis.contains_key(&api_type) {
+            let io_handler = self.new_io_handler(HashSet::new(), false);
+            self.apis.insert(api_type, io_handler);
+        }
+        if let Some(io_handler) = self.apis.get_mut(&api_type) {
+            io_handler.extend_with(apis);
+        }
     }
 
+    /// Get the io handler serves `api_types` to every caller.
     pub fn get_apis(
         &self,
         api_types: impl IntoIterator<Item = Api>,
     ) -> MetaIoHandler<Metadata, Middlewares> {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
+        self.build_io_handler(api_types.into_iter().collect(), false)
+    }
+
+    /// Same as `get_apis`, but the callers with an api key can also call the apis of the key.
+    pub fn get_apis_with_api_keys(
+        &self,
+        api_types: impl IntoIterator<Item = Api>,
+    ) -> MetaIoHandler<Metadata, Middlewares> {
+        self.build_io_handler(api_types.into_iter().collect(), true)
+    }
+
+    fn build_io_handler(
+        &self,
+        anonymous_apis: HashSet<Api>,
+        accept_api_keys: bool,
+    ) -> MetaIoHandler<Metadata, Middlewares> {
+        let mut api_types = anonymous_apis.clone();
+        if accept_api_keys {
+            for api_key in &self.api_keys {
+                api_types.extend(api_key.apis.list_apis());
+            }
+        }
         api_types
             .into_iter()
             .map(|api_type| self.apis.get(&api_type))
             .fold(
-                MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                    MetricMiddleware,
-                    rate_limit_middleware,
-                )),
+                self.new_io_handler(anonymous_apis, accept_api_keys),
                 |mut init, apis| {
                     if let Some(apis) = apis {
                         init.extend_with(apis.iter().map(|(k, v)| (k.clone(), v.clone())));
@@ -58,4 +89,55 @@ impl ApiRegistry {
                 },
             )
     }
+
+    fn new_io_handler(
+        &self,
+        anonymous_apis: HashSet<Api>,
+        accept_api_keys: bool,
+    ) -> MetaIoHandler<Metadata, Middlewares> {
+        let api_keys: &[ApiKeyConfig] = if accept_api_keys { &self.api_keys } else { &[] };
+        let method_scopes = self
+            .apis
+            .iter()
+            .flat_map(|(api_type, apis)| apis.iter().map(move |(k, _)| (k.clone(), *api_type)))
+            .collect();
+        let api_key_middleware = ApiKeyMiddleware::new(
+            anonymous_apis,
+            api_keys
+                .iter()
+                .map(|api_key| {
+                    (
+                        api_key.key.clone(),
+                        ApiKey {
+                            name: api_key.name.clone(),
+                            scopes: api_key.apis.list_apis(),
+                        },
+                    )
+                })
+                .collect(),
+            method_scopes,
+        );
+        let rate_limit_middleware =
+            JsonApiRateLimitMiddleware::from_config(self.quotas.clone(), api_keys);
+        // the audit middleware runs before the api key middleware, so the rejected calls are
+        // audited too.
+        let audit_middleware = if self.audit.enable() {
+            AuditMiddleware::new(
+                self.audit.slow_call_threshold(),
+                self.audit.redacted_methods(),
+                api_keys
+                    .iter()
+                    .map(|api_key| (api_key.key.clone(), api_key.name.clone()))
+                    .collect(),
+            )
+        } else {
+            AuditMiddleware::disabled()
+        };
+        MetaIoHandler::<Metadata, Middlewares>::with_middleware((
+            MetricMiddleware,
+            audit_middleware,
+            api_key_middleware,
+            rate_limit_middleware,
+        ))
+    }
 }
@@ -7,6 +7,9 @@ use starcoin_rpc_api::metadata::Metadata;
 use std::net::IpAddr;
 use std::sync::Arc;
 
+/// The http header carries the api key, alternative to `Authorization: Bearer <key>`.
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
 /// Common HTTP & IPC & TCP metadata extractor.
 #[derive(Default)]
 pub struct RpcExtractor {
@@ -27,9 +30,23 @@ impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
             }
         }
 
+        let api_key = _req
+            .headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .or_else(|| {
+                _req.headers()
+                    .get(API_KEY_HEADER)
+                    .and_then(|v| v.to_str().ok())
+            })
+            .map(|key| key.trim().to_string());
+
         Metadata {
             session: None,
             user: client_ip.map(|ip| ip.to_string()),
+            api_key,
+            key_name: None,
         }
     }
 }
@@ -37,7 +54,7 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
     fn extract(&self, req: &jsonrpc_ipc_server::RequestContext) -> Metadata {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
-            user: None,
+            ..Default::default()
         }
     }
 }
@@ -46,6 +63,7 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            ..Default::default()
         }
     }
 }
@@ -56,7 +74,7 @@ impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
         let session = Some(Arc::new(Session::new(req.sender())));
         Metadata {
             session,
-            user: None,
+            ..Default::default()
         }
     }
 }
@@ -1,26 +1,30 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::helpers::{
+    block_write_set_views, decode_txn_events, fill_txn_info_ext, try_decode_block_txns,
+    try_decode_txn_payload, txn_pre_state_root,
+};
 use crate::module::map_err;
 use futures::future::{FutureExt, TryFutureExt};
-use starcoin_abi_decoder::decode_txn_payload;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
-use starcoin_logger::prelude::*;
-use starcoin_resource_viewer::MoveValueAnnotator;
-use starcoin_rpc_api::chain::{ChainApi, GetBlockOption, GetEventOption, GetTransactionOption};
+use starcoin_rpc_api::chain::{
+    ChainApi, GetBlockOption, GetEventOption, GetTransactionOption, GetWriteSetOption,
+};
+use starcoin_rpc_api::errors::invalid_params;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
-    BlockHeaderView, BlockSummaryView, BlockTransactionsView, BlockView, ChainId, ChainInfoView,
-    EpochUncleSummaryView, SignedUserTransactionView, TransactionEventResponse,
-    TransactionInfoView, TransactionView,
+    to_cursor, BlockHeaderView, BlockStateDiffView, BlockSummaryView, BlockView, ChainId,
+    ChainInfoView, EpochUncleSummaryView, PageOption, PageView, TransactionEventResponse,
+    TransactionInfoView, TransactionView, TransactionWriteSetView,
 };
 use starcoin_rpc_api::FutureResult;
-use starcoin_state_api::StateView;
 use starcoin_statedb::ChainStateDB;
 use starcoin_storage::Storage;
 use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
 use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::TransactionInfo;
@@ -88,12 +92,8 @@ where
             let result = service.get_block_by_hash(hash).await?;
             let mut block: Option<BlockView> = result.map(|b| b.try_into()).transpose()?;
             if decode {
-                let state = ChainStateDB::new(
-                    storage,
-                    Some(service.main_head_header().await?.state_root()),
-                );
                 if let Some(block) = block.as_mut() {
-                    try_decode_block_txns(&state, block)?;
+                    try_decode_block_txns(storage, block)?;
                 }
             }
             Ok(block)
@@ -116,12 +116,8 @@ where
             let result = service.main_block_by_number(number).await?;
             let mut block: Option<BlockView> = result.map(|b| b.try_into()).transpose()?;
             if decode {
-                let state = ChainStateDB::new(
-                    storage,
-                    Some(service.main_head_header().await?.state_root()),
-                );
                 if let Some(block) = block.as_mut() {
-                    try_decode_block_txns(&state, block)?;
+                    try_decode_block_txns(storage, block)?;
                 }
             }
             Ok(block)
@@ -173,6 +169,46 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_blocks_page(&self, page: Option<PageOption>) -> FutureResult<PageView<BlockView>> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let page = page.unwrap_or_default();
+            // the cursor is the number of the first block in the page.
+            let start_block_number = match page.position::<BlockNumber>()? {
+                Some(number) => number,
+                None => service.clone().main_head_header().await?.number(),
+            };
+            // the blocks page is also bounded by the block query range.
+            let max_limit = config.rpc.block_query_max_range();
+            let limit = match page.limit {
+                Some(limit) if limit > max_limit => {
+                    return Err(invalid_params(
+                        "limit",
+                        format!("max limit of blocks page is {}", max_limit),
+                    )
+                    .into());
+                }
+                Some(_) => page.limit() as u64,
+                None => (page.limit() as u64).min(max_limit),
+            };
+            // fetch one more block to know whether there is a next page.
+            let blocks = service
+                .main_blocks_by_number(
+                    Some(start_block_number),
+                    limit
+                        .saturating_add(1)
+                        .min(start_block_number.saturating_add(1)),
+                )
+                .await?;
+            PageView::from_items(blocks, limit as usize, |blk| blk.header().number())?
+                .try_map(|blk| BlockView::try_from_block(blk, true))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_transaction(
         &self,
         transaction_hash: HashValue,
@@ -198,9 +234,14 @@ where
 
                     let mut txn = TransactionView::new(t, &block)?;
                     if decode_payload {
+                        // resolve the payload against the state before the txn is executed.
                         let state = ChainStateDB::new(
-                            storage,
-                            Some(service.main_head_header().await?.state_root()),
+                            storage.clone(),
+                            txn_pre_state_root(
+                                storage.as_ref(),
+                                txn.block_hash,
+                                txn.transaction_index as u64,
+                            )?,
                         );
                         if let Some(txn) = txn.user_transaction.as_mut() {
                             try_decode_txn_payload(&state, txn)?;
@@ -220,6 +261,7 @@ where
         transaction_hash: HashValue,
     ) -> FutureResult<Option<TransactionInfoView>> {
         let service = self.service.clone();
+        let storage = self.storage.clone();
         let fut = async move {
             let txn_info = {
                 let info = service.get_transaction_info(transaction_hash).await?;
@@ -240,8 +282,11 @@ where
                     )
                 })?;
 
-            TransactionInfoView::new(Into::<(_, TransactionInfo)>::into(txn_info).1, &block)
-                .map(Some)
+            let txn_info_id = txn_info.id();
+            let mut txn_info =
+                TransactionInfoView::new(Into::<(_, TransactionInfo)>::into(txn_info).1, &block)?;
+            fill_txn_info_ext(storage.as_ref(), txn_info_id, &mut txn_info)?;
+            Ok(Some(txn_info))
         }
         .map_err(map_err);
 
@@ -250,6 +295,7 @@ where
 
     fn get_block_txn_infos(&self, block_hash: HashValue) -> FutureResult<Vec<TransactionInfoView>> {
         let service = self.service.clone();
+        let storage = self.storage.clone();
         let fut = async move {
             let txn_infos = service.get_block_txn_infos(block_hash).await?;
             let block = service.get_block_by_hash(block_hash).await?;
@@ -258,7 +304,12 @@ where
                 Some(block) => txn_infos
                     .into_iter()
                     .map(|info| {
+                        let txn_info_id = info.id();
                         TransactionInfoView::new(Into::<(_, TransactionInfo)>::into(info).1, &block)
+                            .and_then(|mut txn_info| {
+                                fill_txn_info_ext(storage.as_ref(), txn_info_id, &mut txn_info)?;
+                                Ok(txn_info)
+                            })
                     })
                     .collect::<Result<Vec<_>, _>>(),
             }
@@ -274,6 +325,7 @@ where
         idx: u64,
     ) -> FutureResult<Option<TransactionInfoView>> {
         let service = self.service.clone();
+        let storage = self.storage.clone();
         let fut = async move {
             let block = service.get_block_by_hash(block_hash).await?;
             match block {
@@ -284,10 +336,15 @@ where
                         .await?;
                     txn_info
                         .map(|info| {
+                            let txn_info_id = info.id();
                             TransactionInfoView::new(
                                 Into::<(_, TransactionInfo)>::into(info).1,
                                 &block,
                             )
+                            .and_then(|mut txn_info| {
+                                fill_txn_info_ext(storage.as_ref(), txn_info_id, &mut txn_info)?;
+                                Ok(txn_info)
+                            })
                         })
                         .transpose()
                 }
@@ -307,12 +364,6 @@ where
         let storage = self.storage.clone();
         let fut = async move {
             let events = service.get_events_by_txn_hash(txn_hash).await?;
-            let state_root = if event_option.decode {
-                Some(service.main_head_header().await?.state_root())
-            } else {
-                None
-            };
-
             let mut resp_data: Vec<_> = events
                 .into_iter()
                 .map(|e| TransactionEventResponse {
@@ -321,16 +372,8 @@ where
                 })
                 .collect();
 
-            if let Some(state_root) = state_root {
-                let state = ChainStateDB::new(storage, Some(state_root));
-                let annotator = MoveValueAnnotator::new(&state);
-                for elem in resp_data.iter_mut() {
-                    elem.decode_event_data = Some(
-                        annotator
-                            .view_value(&elem.event.type_tag.0, elem.event.data.0.as_slice())?
-                            .into(),
-                    );
-                }
+            if event_option.decode {
+                decode_txn_events(storage, resp_data.as_mut_slice())?;
             }
             Ok(resp_data)
         }
@@ -356,27 +399,8 @@ where
             }
 
             let filter: Filter = filter.try_into()?;
+            check_block_range(&filter, config.rpc.block_query_max_range())?;
 
-            let max_block_range = config.rpc.block_query_max_range();
-            // if the from~to range is bigger than what we configured, return invalid param error.
-            if filter
-                .to_block
-                .checked_sub(filter.from_block)
-                .filter(|r| *r > max_block_range)
-                .is_some()
-            {
-                return Err(jsonrpc_core::Error::invalid_params(format!(
-                    "from_block is too far, max block range is {} ",
-                    max_block_range
-                ))
-                .into());
-            }
-
-            let state_root = if event_option.decode {
-                Some(service.main_head_header().await?.state_root())
-            } else {
-                None
-            };
             let mut data: Vec<_> = service
                 .main_events(filter)
                 .await?
@@ -386,16 +410,8 @@ where
                     decode_event_data: None,
                 })
                 .collect();
-            if let Some(state_root) = state_root {
-                let state = ChainStateDB::new(storage, Some(state_root));
-                let annotator = MoveValueAnnotator::new(&state);
-                for elem in data.iter_mut() {
-                    elem.decode_event_data = Some(
-                        annotator
-                            .view_value(&elem.event.type_tag.0, elem.event.data.0.as_slice())?
-                            .into(),
-                    );
-                }
+            if event_option.decode {
+                decode_txn_events(storage, data.as_mut_slice())?;
             }
             Ok(data)
         }
@@ -404,6 +420,154 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_events_page(
+        &self,
+        mut filter: EventFilter,
+        option: Option<GetEventOption>,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<TransactionEventResponse>> {
+        let event_option = option.unwrap_or_default();
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let fut = async move {
+            let page = page.unwrap_or_default();
+            if filter.to_block.is_none() {
+                let header_block_number = service.main_head_header().await?.number();
+                filter.to_block = Some(header_block_number);
+            }
+
+            let mut filter: Filter = filter.try_into()?;
+            check_block_range(&filter, config.rpc.block_query_max_range())?;
+
+            let limit = page.limit();
+            // the cursor is the position of the first event of the page, see `event_positions`.
+            let mut events = match page.position::<EventPosition>()? {
+                Some(position) => {
+                    let (block_number, _, _) = position;
+                    if block_number < filter.from_block || block_number > filter.to_block {
+                        return Err(
+                            invalid_params("cursor", "cursor is out of the block range").into()
+                        );
+                    }
+                    // all the events of the cursor block, the events before the cursor are dropped.
+                    let mut block_filter = filter.clone();
+                    block_filter.from_block = block_number;
+                    block_filter.to_block = block_number;
+                    block_filter.limit = None;
+                    let block_events = service.main_events(block_filter).await?;
+                    let start = event_positions(&block_events)
+                        .into_iter()
+                        .position(|p| p == position)
+                        .ok_or_else(|| invalid_params("cursor", "cursor event is not found"))?;
+                    let mut events = block_events.into_iter().skip(start).collect::<Vec<_>>();
+                    // fetch one more event to know whether there is a next page.
+                    if events.len() <= limit && block_number > filter.from_block {
+                        filter.to_block = block_number - 1;
+                        filter.limit = Some(limit + 1 - events.len());
+                        events.extend(service.main_events(filter).await?);
+                    }
+                    events
+                }
+                None => {
+                    // fetch one more event to know whether there is a next page.
+                    filter.limit = Some(limit + 1);
+                    service.main_events(filter).await?
+                }
+            };
+            let next_cursor = match event_positions(&events).get(limit) {
+                Some(position) => Some(to_cursor(position)?),
+                None => None,
+            };
+            events.truncate(limit);
+            let mut data: Vec<_> = events
+                .into_iter()
+                .map(|e| TransactionEventResponse {
+                    event: e.into(),
+                    decode_event_data: None,
+                })
+                .collect();
+            if event_option.decode {
+                decode_txn_events(storage, data.as_mut_slice())?;
+            }
+            Ok(PageView { data, next_cursor })
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_txn_write_set(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> FutureResult<Option<TransactionWriteSetView>> {
+        let decode = option.unwrap_or_default().decode;
+        let service = self.service.clone();
+        let storage = self.storage.clone();
+        let fut = async move {
+            let txn_info = match service.get_transaction_info(txn_hash).await? {
+                Some(txn_info) => txn_info,
+                None => return Ok(None),
+            };
+            let block = service
+                .get_block_by_hash(txn_info.block_id())
+                .await?
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "cannot find the block {} which include txn {}",
+                        txn_info.block_id(),
+                        txn_hash
+                    )
+                })?;
+            // the old values are read from the state after the previous txn in the block.
+            let txn_infos = service.get_block_txn_infos(block.id()).await?;
+            let txn_index = txn_infos
+                .iter()
+                .position(|info| info.id() == txn_info.id())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("cannot find txn {} in block {}", txn_hash, block.id())
+                })?;
+            let mut views = block_write_set_views(
+                storage,
+                &block,
+                txn_infos.as_slice(),
+                Some(txn_index),
+                decode,
+            )?;
+            Ok(views.pop())
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_block_state_diff(
+        &self,
+        block_hash: HashValue,
+        option: Option<GetWriteSetOption>,
+    ) -> FutureResult<Option<BlockStateDiffView>> {
+        let decode = option.unwrap_or_default().decode;
+        let service = self.service.clone();
+        let storage = self.storage.clone();
+        let fut = async move {
+            let block = match service.get_block_by_hash(block_hash).await? {
+                Some(block) => block,
+                None => return Ok(None),
+            };
+            let txn_infos = service.get_block_txn_infos(block_hash).await?;
+            let txns = block_write_set_views(storage, &block, txn_infos.as_slice(), None, decode)?;
+            Ok(Some(BlockStateDiffView {
+                block_hash,
+                block_number: block.header().number().into(),
+                txns,
+            }))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn current_epoch(&self) -> FutureResult<EpochInfo> {
         let service = self.service.clone();
         let fut = async move { service.epoch_info().await };
@@ -465,31 +629,39 @@ where
     }
 }
 
-fn try_decode_block_txns(state: &dyn StateView, block: &mut BlockView) -> anyhow::Result<()> {
-    if let BlockTransactionsView::Full(txns) = &mut block.body {
-        for txn in txns.iter_mut() {
-            try_decode_txn_payload(state, txn)?;
-        }
+/// The position of an event: block number, transaction index, and the index of the event
+/// among the matched events of the transaction.
+type EventPosition = (BlockNumber, u32, u64);
+
+/// Positions of `events`, which are returned by the same filter and start at a block boundary.
+fn event_positions(events: &[ContractEventInfo]) -> Vec<EventPosition> {
+    let mut positions: Vec<EventPosition> = Vec::with_capacity(events.len());
+    for e in events {
+        let event_index = match positions.last() {
+            Some((block_number, txn_index, event_index))
+                if *block_number == e.block_number && *txn_index == e.transaction_index =>
+            {
+                event_index + 1
+            }
+            _ => 0,
+        };
+        positions.push((e.block_number, e.transaction_index, event_index));
     }
-    Ok(())
+    positions
 }
 
-fn try_decode_txn_payload(
-    state: &dyn StateView,
-    txn: &mut SignedUserTransactionView,
-) -> anyhow::Result<()> {
-    let txn_payload = bcs_ext::from_bytes(txn.raw_txn.payload.0.as_slice())?;
-    match decode_txn_payload(state, &txn_payload) {
-        // ignore decode failure, as txns may has invalid payload here.
-        Err(e) => {
-            debug!(
-                "decode payload of txn {} failure, {:?}",
-                txn.transaction_hash, e
-            );
-        }
-        Ok(d) => {
-            txn.raw_txn.decoded_payload = Some(d.into());
-        }
+/// If the from~to range is bigger than what we configured, return invalid param error.
+fn check_block_range(filter: &Filter, max_block_range: u64) -> Result<(), jsonrpc_core::Error> {
+    if filter
+        .to_block
+        .checked_sub(filter.from_block)
+        .filter(|r| *r > max_block_range)
+        .is_some()
+    {
+        return Err(jsonrpc_core::Error::invalid_params(format!(
+            "from_block is too far, max block range is {} ",
+            max_block_range
+        )));
     }
     Ok(())
 }
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::helpers::TransactionRequestFiller;
+use crate::module::helpers::{StorageErrorMapResolver, TransactionRequestFiller};
 use crate::module::map_err;
 use anyhow::format_err;
 use futures::future::TryFutureExt;
@@ -22,18 +22,21 @@ use starcoin_rpc_api::types::{
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::ChainStateAsyncService;
 use starcoin_statedb::ChainStateDB;
-use starcoin_storage::Storage;
+use starcoin_storage::{ErrorMapStore, Storage};
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::language_storage::{ModuleId, StructTag};
 use starcoin_types::transaction::{DryRunTransaction, RawUserTransaction, TransactionPayload};
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::errmap::ErrorDescription;
 use starcoin_vm_types::file_format::CompiledModule;
 use starcoin_vm_types::state_view::StateView;
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
 use starcoin_vm_types::transaction::TransactionArgument;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use vm_status_translator::ErrorMapResolver;
 
 pub struct ContractRpcImpl<Account, Pool, State> {
     pub(crate) account: Option<Account>,
@@ -217,13 +220,14 @@ where
             } = txn;
 
             let txn = txn_builder.fill_transaction(transaction).await?;
-            let state_view = ChainStateDB::new(storage, Some(state_root));
+            let state_view = ChainStateDB::new(storage.clone(), Some(state_root));
             dry_run(
                 &state_view,
                 DryRunTransaction {
                     raw_txn: txn,
                     public_key: sender_public_key.0,
                 },
+                &StorageErrorMapResolver::new(storage.as_ref()),
             )
         }
         .map_err(map_err);
@@ -240,13 +244,14 @@ where
         let f = async move {
             let state_root = service.state_root().await?;
             let raw_txn = RawUserTransaction::from_str(raw_txn.as_str())?;
-            let state_view = ChainStateDB::new(storage, Some(state_root));
+            let state_view = ChainStateDB::new(storage.clone(), Some(state_root));
             dry_run(
                 &state_view,
                 DryRunTransaction {
                     raw_txn,
                     public_key: sender_public_key.0,
                 },
+                &StorageErrorMapResolver::new(storage.as_ref()),
             )
         }
         .map_err(map_err);
@@ -286,14 +291,25 @@ where
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn get_error_map(
+        &self,
+        module_id: ModuleIdView,
+    ) -> FutureResult<Option<BTreeMap<u64, ErrorDescription>>> {
+        let storage = self.storage.clone();
+        let fut = async move { storage.get_module_error_map(&module_id.0) }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
 
 pub fn dry_run(
     state_view: &dyn StateView,
     txn: DryRunTransaction,
+    error_map_resolver: &dyn ErrorMapResolver,
 ) -> anyhow::Result<DryRunOutputView> {
     let (vm_status, output) = starcoin_dev::playground::dry_run(state_view, txn.clone())?;
-    let vm_status_explain = vm_status_translator::explain_vm_status(state_view, vm_status)?;
+    let vm_status_explain =
+        vm_status_translator::explain_vm_status(state_view, vm_status, error_map_resolver)?;
     let mut txn_output: TransactionOutputView = output.into();
 
     let resolver = {
@@ -1,11 +1,34 @@
+use starcoin_abi_decoder::{decode_move_value, decode_txn_payload, DecodedMoveValue};
+use starcoin_abi_resolver::ABIResolver;
 use starcoin_account_api::AccountAsyncService;
 use starcoin_config::NodeConfig;
-use starcoin_rpc_api::types::TransactionRequest;
+use starcoin_crypto::HashValue;
+use starcoin_dev::playground::view_resource;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::{
+    BlockTransactionsView, BlockView, ResourceView, SignedUserTransactionView, StateChangeView,
+    StrView, TransactionEventResponse, TransactionEventView, TransactionInfoView,
+    TransactionRequest, TransactionStatusView, TransactionWriteSetView, WriteOpValueView,
+    WriteOpView,
+};
 use starcoin_state_api::ChainStateAsyncService;
+use starcoin_state_api::StateView;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::error_map::ModuleErrorMap;
+use starcoin_storage::{
+    BlockStore, BlockTransactionInfoStore, ErrorMapStore, Storage, Store, WriteSetStore,
+};
 use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::access_path::{AccessPath, DataPath};
 use starcoin_types::account_config::AccountResource;
-use starcoin_types::transaction::{Module, Package, RawUserTransaction, TransactionPayload};
+use starcoin_types::block::Block;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_types::transaction::{
+    BlockTransactionInfo, Module, Package, RawUserTransaction, TransactionPayload,
+};
+use starcoin_types::write_set::WriteOp;
 use std::sync::Arc;
+use vm_status_translator::{explain_move_abort_with_resolver, ErrorMapResolver};
 
 #[derive(Clone)]
 pub(crate) struct TransactionRequestFiller<Account, Pool, State> {
@@ -103,3 +126,271 @@ where
         Ok(raw_txn)
     }
 }
+
+/// Decode the event data to json through the ABI of the event's type tag.
+pub(crate) fn decode_event_data(
+    resolver: &ABIResolver,
+    event: &TransactionEventView,
+) -> anyhow::Result<DecodedMoveValue> {
+    let abi = resolver.resolve_type_tag(&event.type_tag.0)?;
+    Ok(decode_move_value(&abi, event.data.0.as_slice())?)
+}
+
+/// Decode the data of all `events` at `state`, the modules resolved are cached across the events.
+pub(crate) fn decode_events(
+    state: &dyn StateView,
+    events: &mut [TransactionEventResponse],
+) -> anyhow::Result<()> {
+    let resolver = ABIResolver::new(state);
+    for elem in events.iter_mut() {
+        elem.decode_event_data = Some(decode_event_data(&resolver, &elem.event)?);
+    }
+    Ok(())
+}
+
+/// Decode the data of the `events`, every event is decoded at the state before its txn is executed.
+pub(crate) fn decode_txn_events(
+    storage: Arc<Storage>,
+    events: &mut [TransactionEventResponse],
+) -> anyhow::Result<()> {
+    let mut start = 0;
+    while start < events.len() {
+        let event = &events[start].event;
+        let (block_hash, txn_index) = event
+            .block_hash
+            .zip(event.transaction_index)
+            .ok_or_else(|| anyhow::anyhow!("the event has no txn to decode it at"))?;
+        let len = events[start..]
+            .iter()
+            .take_while(|e| {
+                e.event.block_hash == Some(block_hash)
+                    && e.event.transaction_index == Some(txn_index)
+            })
+            .count();
+        let state = ChainStateDB::new(
+            storage.clone(),
+            txn_pre_state_root(storage.as_ref(), block_hash, txn_index as u64)?,
+        );
+        decode_events(&state, &mut events[start..start + len])?;
+        start += len;
+    }
+    Ok(())
+}
+
+/// The state root before the txn at `txn_index` of the block is executed, the payload and the
+/// events of the txn are resolved at it, since the txn itself may publish or upgrade modules.
+pub(crate) fn txn_pre_state_root(
+    storage: &Storage,
+    block_hash: HashValue,
+    txn_index: u64,
+) -> anyhow::Result<Option<HashValue>> {
+    if txn_index > 0 {
+        let txn_info = storage
+            .get_transaction_info_by_block_and_index(block_hash, txn_index - 1)?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "cannot find the txn info {} of block {}",
+                    txn_index - 1,
+                    block_hash
+                )
+            })?;
+        return Ok(Some(txn_info.state_root_hash()));
+    }
+    let header = storage
+        .get_block_header_by_hash(block_hash)?
+        .ok_or_else(|| anyhow::anyhow!("cannot find the block {}", block_hash))?;
+    if header.is_genesis() {
+        return Ok(None);
+    }
+    let parent = storage
+        .get_block_header_by_hash(header.parent_hash())?
+        .ok_or_else(|| anyhow::anyhow!("cannot find the parent of block {}", block_hash))?;
+    Ok(Some(parent.state_root()))
+}
+
+/// Decode the payloads of the user txns in `block`, every payload is decoded at the state before
+/// its txn is executed.
+pub(crate) fn try_decode_block_txns(
+    storage: Arc<Storage>,
+    block: &mut BlockView,
+) -> anyhow::Result<()> {
+    if let BlockTransactionsView::Full(txns) = &mut block.body {
+        // the first txn info is of the block metadata txn, so the txn info before the user txn
+        // at `idx` is at `idx` too.
+        let txn_infos = storage.get_block_transaction_infos(block.header.block_hash)?;
+        for (idx, txn) in txns.iter_mut().enumerate() {
+            let txn_info = txn_infos.get(idx).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "cannot find the txn info {} of block {}",
+                    idx,
+                    block.header.block_hash
+                )
+            })?;
+            let state = ChainStateDB::new(storage.clone(), Some(txn_info.state_root_hash()));
+            try_decode_txn_payload(&state, txn)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn try_decode_txn_payload(
+    state: &dyn StateView,
+    txn: &mut SignedUserTransactionView,
+) -> anyhow::Result<()> {
+    let txn_payload = bcs_ext::from_bytes(txn.raw_txn.payload.0.as_slice())?;
+    match decode_txn_payload(state, &txn_payload) {
+        // ignore decode failure, as txns may has invalid payload here.
+        Err(e) => {
+            debug!(
+                "decode payload of txn {} failure, {:?}",
+                txn.transaction_hash, e
+            );
+        }
+        Ok(d) => {
+            txn.raw_txn.decoded_payload = Some(d.into());
+        }
+    }
+    Ok(())
+}
+
+/// Fill the fields of `txn_info` out of the txn info itself: the abort context recorded by the
+/// executor, and the explanation of the abort code with the error maps of stdlib and the ones
+/// registered to the node.
+pub(crate) fn fill_txn_info_ext(
+    storage: &Storage,
+    txn_info_id: HashValue,
+    txn_info: &mut TransactionInfoView,
+) -> anyhow::Result<()> {
+    txn_info.abort_context = storage.get_transaction_abort_context(txn_info_id)?;
+    if let TransactionStatusView::MoveAbort {
+        location,
+        abort_code,
+    } = &txn_info.status
+    {
+        txn_info.abort_explain = Some(explain_move_abort_with_resolver(
+            location.clone(),
+            abort_code.0,
+            &StorageErrorMapResolver::new(storage),
+        ));
+    }
+    Ok(())
+}
+
+/// Build the state changes of the txns in `block` from their write sets, the old values are read
+/// from the state before each txn. Only the txn at `txn_index` is built if it is given.
+pub(crate) fn block_write_set_views(
+    storage: Arc<Storage>,
+    block: &Block,
+    txn_infos: &[BlockTransactionInfo],
+    txn_index: Option<usize>,
+    decode: bool,
+) -> anyhow::Result<Vec<TransactionWriteSetView>> {
+    let parent_state_root = if block.header().is_genesis() {
+        None
+    } else {
+        let parent = storage
+            .get_block_header_by_hash(block.header().parent_hash())?
+            .ok_or_else(|| anyhow::anyhow!("cannot find the parent of block {}", block.id()))?;
+        Some(parent.state_root())
+    };
+    let mut views = vec![];
+    for (idx, txn_info) in txn_infos.iter().enumerate() {
+        if txn_index.filter(|txn_index| *txn_index != idx).is_some() {
+            continue;
+        }
+        let write_set = storage
+            .get_transaction_write_set(txn_info.id())?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "the write set of txn {} is not recorded, it requires the node to run with --enable-write-set",
+                    txn_info.transaction_hash()
+                )
+            })?;
+        let pre_state_root = match idx {
+            0 => parent_state_root,
+            _ => Some(txn_infos[idx - 1].state_root_hash()),
+        };
+        let pre_state = ChainStateDB::new(storage.clone(), pre_state_root);
+        let post_state = ChainStateDB::new(storage.clone(), Some(txn_info.state_root_hash()));
+        let changes = write_set
+            .into_iter()
+            .map(|(access_path, write_op)| {
+                let old_value = pre_state
+                    .get(&access_path)?
+                    .map(|value| write_op_value_view(&pre_state, &access_path, value, decode))
+                    .transpose()?;
+                let (action, new_value) = match write_op {
+                    WriteOp::Deletion => (WriteOpView::Deletion, None),
+                    WriteOp::Value(value) => (
+                        WriteOpView::Value,
+                        Some(write_op_value_view(
+                            &post_state,
+                            &access_path,
+                            value,
+                            decode,
+                        )?),
+                    ),
+                };
+                Ok(StateChangeView {
+                    access_path,
+                    action,
+                    old_value,
+                    new_value,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        views.push(TransactionWriteSetView {
+            block_hash: block.id(),
+            block_number: block.header().number().into(),
+            transaction_hash: txn_info.transaction_hash(),
+            transaction_index: idx as u32,
+            changes,
+        });
+    }
+    Ok(views)
+}
+
+fn write_op_value_view(
+    state: &dyn StateView,
+    access_path: &AccessPath,
+    value: Vec<u8>,
+    decode: bool,
+) -> anyhow::Result<WriteOpValueView> {
+    Ok(match &access_path.path {
+        DataPath::Resource(struct_tag) => {
+            let json = if decode {
+                Some(view_resource(state, struct_tag.clone(), value.as_slice())?.into())
+            } else {
+                None
+            };
+            WriteOpValueView::Resource(ResourceView {
+                raw: StrView(value),
+                json,
+            })
+        }
+        DataPath::Code(_) => WriteOpValueView::Code(value.into()),
+    })
+}
+
+/// Resolve the error maps of the modules out of stdlib from the ones registered to the node.
+pub(crate) struct StorageErrorMapResolver<'a> {
+    storage: &'a dyn ErrorMapStore,
+}
+
+impl<'a> StorageErrorMapResolver<'a> {
+    pub(crate) fn new(storage: &'a dyn ErrorMapStore) -> Self {
+        Self { storage }
+    }
+}
+
+impl<'a> ErrorMapResolver for StorageErrorMapResolver<'a> {
+    fn get_module_error_map(&self, module_id: &ModuleId) -> Option<ModuleErrorMap> {
+        match self.storage.get_module_error_map(module_id) {
+            Ok(error_map) => error_map,
+            Err(e) => {
+                debug!("get error map of module {} failure, {:?}", module_id, e);
+                None
+            }
+        }
+    }
+}
@@ -38,13 +38,14 @@ impl MinerApi for MinerRpcImpl {
                     .map_err(|_| anyhow::anyhow!("Invalid length of extra"))
             })?;
             let extra = BlockHeaderExtra::new(*e);
-            let block_hash = miner_service
+            let receiver = miner_service
                 .send(SubmitSealRequest {
                     nonce,
                     extra,
                     minting_blob,
                 })
                 .await??;
+            let block_hash = receiver.await??;
             Ok(MintedBlockView { block_hash })
         }
         .map_err(map_err);
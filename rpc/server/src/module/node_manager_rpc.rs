@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::map_err;
+use anyhow::ensure;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use starcoin_crypto::HashValue;
 use starcoin_node_api::node_service::NodeAsyncService;
 use starcoin_rpc_api::node_manager::NodeManagerApi;
+use starcoin_rpc_api::types::{BackupFileView, BackupManifestView, ModuleIdView, StrView};
 use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use std::path::PathBuf;
 
 pub struct NodeManagerRpcImpl<S>
 where
@@ -86,6 +89,16 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn confirm_reorg(&self, block_hash: HashValue) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move {
+            service.confirm_reorg(block_hash).await?;
+            Ok(())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn re_execute_block(&self, block_hash: HashValue) -> FutureResult<()> {
         let service = self.service.clone();
         let fut = async move {
@@ -105,4 +118,54 @@ where
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn compact_storage(&self, cf_name: String) -> FutureResult<()> {
+        let service = self.service.clone();
+        let fut = async move {
+            service.compact_storage(cf_name).await?;
+            Ok(())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn backup(&self, backup_dir: String) -> FutureResult<BackupManifestView> {
+        let service = self.service.clone();
+        let fut = async move {
+            let backup_dir = PathBuf::from(backup_dir);
+            ensure!(
+                backup_dir.is_absolute(),
+                "Backup dir {:?} should be an absolute path.",
+                backup_dir
+            );
+            let manifest = service.backup(backup_dir).await?;
+            Ok(BackupManifestView {
+                head_block_hash: manifest.head_block_hash,
+                head_block_number: manifest.head_block_number.into(),
+                created_at: manifest.created_at.into(),
+                files: manifest
+                    .files
+                    .into_iter()
+                    .map(|file| BackupFileView {
+                        path: file.path,
+                        size: file.size.into(),
+                        checksum: file.checksum,
+                    })
+                    .collect(),
+            })
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn register_error_map(&self, error_map: StrView<Vec<u8>>) -> FutureResult<Vec<ModuleIdView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let error_map = bcs_ext::from_bytes(error_map.0.as_slice())?;
+            let module_ids = service.register_error_map(error_map).await?;
+            Ok(module_ids.into_iter().map(StrView).collect())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
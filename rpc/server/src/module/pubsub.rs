@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::helpers::decode_txn_events;
 use crate::module::map_err;
 use anyhow::Result;
 use futures::channel::mpsc;
@@ -9,8 +10,6 @@ use futures::StreamExt;
 use jsonrpc_pubsub::typed::Subscriber;
 use jsonrpc_pubsub::SubscriptionId;
 use parking_lot::RwLock;
-use starcoin_abi_decoder::decode_move_value;
-use starcoin_abi_resolver::ABIResolver;
 use starcoin_chain_notify::message::{ContractEventNotification, Notification, ThinBlock};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
@@ -22,12 +21,11 @@ use starcoin_service_registry::{
     ActorService, EventHandler as ActorEventHandler, ServiceContext, ServiceFactory,
     ServiceHandler, ServiceRef, ServiceRequest,
 };
-use starcoin_statedb::ChainStateDB;
 use starcoin_storage::Storage;
 use starcoin_txpool::TxPoolService;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
@@ -144,6 +142,23 @@ impl PubSubImpl {
                         msg,
                     )
                 }),
+            (pubsub::Kind::ChainReorg, None) => self
+                .service
+                .try_send(SubscribeChainReorg(subscriber))
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.0,
+                            TrySendError::Full(t) => t.0,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::ChainReorg, _) => Err((
+                subscriber,
+                errors::invalid_params("chainReorg", "Expected no parameters."),
+            )),
         }
     }
 }
@@ -206,6 +221,7 @@ pub struct PubSubService {
     new_event_subscribers:
         HashMap<SubscriptionId, mpsc::UnboundedSender<ContractEventNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
+    chain_reorg_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<ChainReorgEvent>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
 }
 
@@ -224,6 +240,7 @@ impl PubSubService {
             new_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
+            chain_reorg_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
         }
     }
@@ -242,6 +259,7 @@ impl ActorService for PubSubService {
         ctx.subscribe::<NewHeadNotification>();
         ctx.subscribe::<ContractEventNotification>();
         ctx.subscribe::<MintBlockEvent>();
+        ctx.subscribe::<ChainReorgEvent>();
 
         Ok(())
     }
@@ -269,6 +287,12 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
     }
 }
 
+impl ActorEventHandler<Self, ChainReorgEvent> for PubSubService {
+    fn handle_event(&mut self, msg: ChainReorgEvent, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(&mut self.chain_reorg_subscribers, msg);
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeNewHeads(Subscriber<pubsub::Result>);
 
@@ -333,6 +357,29 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeChainReorg(Subscriber<pubsub::Result>);
+
+impl ServiceRequest for SubscribeChainReorg {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeChainReorg> for PubSubService {
+    fn handle(&mut self, msg: SubscribeChainReorg, ctx: &mut ServiceContext<Self>) {
+        let SubscribeChainReorg(sink) = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.chain_reorg_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            sink,
+            ChainReorgHandler,
+        ));
+    }
+}
+
 #[derive(Debug)]
 struct SubscribeEvents {
     subscriber: Subscriber<pubsub::Result>,
@@ -418,6 +465,7 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         self.new_header_subscribers.remove(&msg.0);
         self.new_event_subscribers.remove(&msg.0);
         self.mint_block_subscribers.remove(&msg.0);
+        self.chain_reorg_subscribers.remove(&msg.0);
         self.miner_service.do_send(UpdateSubscriberNumRequest {
             number: Some(self.mint_block_subscribers.len() as u32),
         });
@@ -512,6 +560,15 @@ impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct ChainReorgHandler;
+
+impl EventHandler<ChainReorgEvent> for ChainReorgHandler {
+    fn handle(&self, msg: ChainReorgEvent) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        vec![Ok(pubsub::Result::ChainReorg(Box::new(msg)))]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ContractEventHandler {
     filter: Filter,
@@ -521,7 +578,7 @@ pub struct ContractEventHandler {
 
 impl EventHandler<ContractEventNotification> for ContractEventHandler {
     fn handle(&self, msg: ContractEventNotification) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
-        let Notification((state_root, events)) = msg;
+        let Notification((_state_root, events)) = msg;
         let filtered = events
             .as_ref()
             .iter()
@@ -535,37 +592,27 @@ impl EventHandler<ContractEventNotification> for ContractEventHandler {
             }
         };
 
-        let state = if self.decode {
-            Some(ChainStateDB::new(self.storage.clone(), Some(state_root)))
-        } else {
-            None
-        };
-        filtered_events
+        let mut events: Vec<_> = filtered_events
             .into_iter()
-            .map(|e| {
-                let decoded_data = match &state {
-                    Some(s) => {
-                        let abi =
-                            ABIResolver::new(s).resolve_type_tag(e.contract_event.type_tag())?;
-                        Some(decode_move_value(&abi, e.contract_event.event_data())?)
-                    }
-                    None => None,
-                };
-                Ok(TransactionEventResponse {
-                    event: TransactionEventView::new(
-                        Some(e.block_hash),
-                        Some(e.block_number),
-                        Some(e.transaction_hash),
-                        e.transaction_index,
-                        &e.contract_event,
-                    ),
-                    decode_event_data: decoded_data,
-                })
-            })
-            .map(|e| {
-                e.map(|d| pubsub::Result::Event(Box::new(d)))
-                    .map_err(map_err)
+            .map(|e| TransactionEventResponse {
+                event: TransactionEventView::new(
+                    Some(e.block_hash),
+                    Some(e.block_number),
+                    Some(e.transaction_hash),
+                    e.transaction_index,
+                    &e.contract_event,
+                ),
+                decode_event_data: None,
             })
+            .collect();
+        if self.decode {
+            if let Err(e) = decode_txn_events(self.storage.clone(), events.as_mut_slice()) {
+                return vec![Err(map_err(e))];
+            }
+        }
+        events
+            .into_iter()
+            .map(|e| Ok(pubsub::Result::Event(Box::new(e))))
             .collect()
     }
 }
@@ -13,17 +13,19 @@ use starcoin_rpc_api::state::{
     GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption, StateApi,
 };
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, CodeView, ListCodeView, ListResourceView,
-    ResourceView, StateWithProofView, StrView, StructTagView,
+    to_cursor, AccountStateSetView, AnnotatedMoveStructView, CodeItemView, CodeView, ListCodeView,
+    ListResourceView, PageOption, PageView, ResourceItemView, ResourceView, StateWithProofView,
+    StrView, StructTagView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::{ChainStateAsyncService, StateView};
-use starcoin_state_tree::StateNodeStore;
+use starcoin_state_tree::{RawKey, StateNodeStore, StateTree};
 use starcoin_statedb::{ChainStateDB, ChainStateReader};
 use starcoin_types::language_storage::ModuleId;
 use starcoin_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
 };
+use starcoin_vm_types::access_path::ModuleName;
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::StructTag;
 use std::collections::BTreeMap;
@@ -316,4 +318,106 @@ where
         };
         Box::pin(fut.map_err(map_err).boxed())
     }
+
+    fn list_resource_page(
+        &self,
+        addr: AccountAddress,
+        option: Option<ListResourceOption>,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<ResourceItemView>> {
+        let state_service = self.service.clone();
+        let db = self.state_store.clone();
+        let option = option.unwrap_or_default();
+        let page = page.unwrap_or_default();
+        let fut = async move {
+            let state_root = option
+                .state_root
+                .unwrap_or(state_service.state_root().await?);
+            let statedb = ChainStateDB::new(db.clone(), Some(state_root));
+            let resource_root =
+                ChainStateReader::get_account_state(&statedb, &addr)?.map(|s| s.resource_root());
+            page_of_state_tree::<StructTag>(db, resource_root, &page)?
+                .try_map::<_, anyhow::Error, _>(|(k, v)| {
+                    let struct_tag = StructTag::decode(k.as_slice())?;
+                    let decoded = if option.decode {
+                        Some(view_resource(&statedb, struct_tag.clone(), v.as_slice())?.into())
+                    } else {
+                        None
+                    };
+                    Ok(ResourceItemView {
+                        struct_tag: StrView(struct_tag),
+                        resource: ResourceView {
+                            raw: StrView(v),
+                            json: decoded,
+                        },
+                    })
+                })
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn list_code_page(
+        &self,
+        addr: AccountAddress,
+        option: Option<ListCodeOption>,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<CodeItemView>> {
+        let state_service = self.service.clone();
+        let db = self.state_store.clone();
+        let option = option.unwrap_or_default();
+        let page = page.unwrap_or_default();
+        let fut = async move {
+            let state_root = option
+                .state_root
+                .unwrap_or(state_service.state_root().await?);
+            let statedb = ChainStateDB::new(db.clone(), Some(state_root));
+            let code_root =
+                ChainStateReader::get_account_state(&statedb, &addr)?.and_then(|s| s.code_root());
+            page_of_state_tree::<ModuleName>(db, code_root, &page)?.try_map::<_, anyhow::Error, _>(
+                |(k, v)| {
+                    let name = Identifier::decode(k.as_slice())?;
+                    let module_id = ModuleId::new(addr, name.clone());
+                    let abi = if option.resolve {
+                        Some(ABIResolver::new(&statedb).resolve_module(&module_id)?)
+                    } else {
+                        None
+                    };
+                    Ok(CodeItemView {
+                        name,
+                        code: CodeView {
+                            code: StrView(v),
+                            abi,
+                        },
+                    })
+                },
+            )
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+}
+
+/// Take a page of an account state tree in order of the key hash, the cursor is the key hash to
+/// start at.
+fn page_of_state_tree<K: RawKey>(
+    store: Arc<dyn StateNodeStore>,
+    root: Option<HashValue>,
+    page: &PageOption,
+) -> anyhow::Result<PageView<(Vec<u8>, Vec<u8>)>> {
+    let tree = match root {
+        Some(root) => StateTree::<K>::new(store, Some(root)),
+        None => {
+            return Ok(PageView {
+                data: vec![],
+                next_cursor: None,
+            })
+        }
+    };
+    let start = page
+        .position::<HashValue>()?
+        .unwrap_or_else(HashValue::zero);
+    let (states, next) = tree.dump_page(start, page.limit())?;
+    Ok(PageView {
+        data: states.iter().cloned().collect(),
+        next_cursor: next.map(|next| to_cursor(&next)).transpose()?,
+    })
 }
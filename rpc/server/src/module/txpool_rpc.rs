@@ -1,17 +1,24 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::helpers::try_decode_txn_payload;
 use crate::module::{convert_to_rpc_error, map_err};
+use anyhow::format_err;
 use bcs_ext::BCSCodec;
+use futures::future::TryFutureExt;
+use futures::FutureExt;
 use starcoin_crypto::HashValue;
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
-use starcoin_rpc_api::types::{SignedUserTransactionView, StrView};
+use starcoin_rpc_api::types::{PageOption, PageView, SignedUserTransactionView, StrView};
 use starcoin_rpc_api::{txpool::TxPoolApi, FutureResult};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{BlockStore, Storage};
 use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::transaction::SignedUserTransaction;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::sync::Arc;
 
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
@@ -21,14 +28,15 @@ where
     S: TxPoolSyncService + 'static,
 {
     service: S,
+    storage: Arc<Storage>,
 }
 
 impl<S> TxPoolRpcImpl<S>
 where
     S: TxPoolSyncService,
 {
-    pub fn new(service: S) -> Self {
-        Self { service }
+    pub fn new(service: S, storage: Arc<Storage>) -> Self {
+        Self { service, storage }
     }
 }
 
@@ -84,14 +92,59 @@ where
         Box::pin(futures::future::ready(txns.map_err(map_err)))
     }
 
-    fn pending_txn(&self, txn_hash: HashValue) -> FutureResult<Option<SignedUserTransactionView>> {
-        let txn = self
-            .service
-            .find_txn(&txn_hash)
-            .map(TryInto::try_into)
-            .transpose()
-            .map_err(map_err);
-        Box::pin(futures::future::ready(txn))
+    fn pending_txns_page(
+        &self,
+        addr: AccountAddress,
+        page: Option<PageOption>,
+    ) -> FutureResult<PageView<SignedUserTransactionView>> {
+        let page = page.unwrap_or_default();
+        let mut txns = self.service.txns_of_sender(&addr, None);
+        let fut = async move {
+            // the cursor is the sequence number of the first txn in the page.
+            let start_sequence_number = page.position::<u64>()?.unwrap_or_default();
+            txns.sort_by_key(|txn| txn.sequence_number());
+            PageView::from_items(
+                txns.into_iter()
+                    .filter(|txn| txn.sequence_number() >= start_sequence_number),
+                page.limit(),
+                |txn| txn.sequence_number(),
+            )?
+            .try_map(SignedUserTransactionView::try_from)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn pending_txn(
+        &self,
+        txn_hash: HashValue,
+        option: Option<GetPendingTxnOption>,
+    ) -> FutureResult<Option<SignedUserTransactionView>> {
+        let decode = option.unwrap_or_default().decode;
+        let txn = self.service.find_txn(&txn_hash);
+        let storage = self.storage.clone();
+        let fut = async move {
+            let mut txn: Option<SignedUserTransactionView> =
+                txn.map(TryInto::try_into).transpose()?;
+            if decode {
+                if let Some(txn) = txn.as_mut() {
+                    // pending txn is not included in any block yet, resolve its payload against the head state.
+                    let startup_info = storage
+                        .get_startup_info()?
+                        .ok_or_else(|| format_err!("Startup info should exist."))?;
+                    let head = storage
+                        .get_block_header_by_hash(startup_info.main)?
+                        .ok_or_else(|| {
+                            format_err!("Can not find head block header {}", startup_info.main)
+                        })?;
+                    let state = ChainStateDB::new(storage, Some(head.state_root()));
+                    try_decode_txn_payload(&state, txn)?;
+                }
+            }
+            Ok(txn)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
     }
 
     fn next_sequence_number(&self, address: AccountAddress) -> FutureResult<Option<u64>> {
@@ -110,6 +163,7 @@ mod tests {
     use super::*;
     use futures::executor::block_on;
     use jsonrpc_core::IoHandler;
+    use starcoin_storage::storage::StorageInstance;
     use starcoin_txpool_mock_service::MockTxPoolService;
 
     #[test]
@@ -121,7 +175,8 @@ mod tests {
 
         let mut io = IoHandler::new();
         let txpool_service = MockTxPoolService::new();
-        io.extend_with(TxPoolRpcImpl::new(txpool_service).to_delegate());
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        io.extend_with(TxPoolRpcImpl::new(txpool_service, storage).to_delegate());
         let txn = SignedUserTransaction::mock();
         let txn_hash = txn.id();
         let prefix = r#"{"jsonrpc":"2.0","method":"txpool.submit_transaction","params":["#;
@@ -6,9 +6,13 @@ type MethodName = String;
 
 use api_limiter::ApiLimiters;
 pub use api_limiter::Quota;
+use governor::clock::DefaultClock;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::RateLimiter;
 use jsonrpc_core::middleware::NoopCallFuture;
-use starcoin_config::{ApiQuotaConfig, ApiQuotaConfiguration, QuotaDuration};
+use starcoin_config::{ApiKeyConfig, ApiQuotaConfig, ApiQuotaConfiguration, QuotaDuration};
 use starcoin_rpc_api::metadata::Metadata;
+use std::collections::HashMap;
 
 struct QuotaWrapper(Quota);
 
@@ -23,13 +27,17 @@ impl From<ApiQuotaConfig> for QuotaWrapper {
     }
 }
 
+type DirectRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
 #[derive(Debug)]
 pub struct JsonApiRateLimitMiddleware {
     limiters: ApiLimiters<MethodName, String>,
+    /// Limiters of the api keys with their own quota, indexed by the key name.
+    api_key_limiters: HashMap<String, DirectRateLimiter>,
 }
 
 impl JsonApiRateLimitMiddleware {
-    pub fn from_config(quotas: ApiQuotaConfiguration) -> Self {
+    pub fn from_config(quotas: ApiQuotaConfiguration, api_keys: &[ApiKeyConfig]) -> Self {
         let limiters = ApiLimiters::new(
             Into::<QuotaWrapper>::into(quotas.default_global_api_quota()).0,
             quotas
@@ -44,7 +52,38 @@ impl JsonApiRateLimitMiddleware {
                 .map(|(k, v)| (k, Into::<QuotaWrapper>::into(v).0))
                 .collect(),
         );
-        Self { limiters }
+        let api_key_limiters = api_keys
+            .iter()
+            .filter_map(|api_key| {
+                api_key.quota.clone().map(|quota| {
+                    (
+                        api_key.name.clone(),
+                        DirectRateLimiter::direct(Into::<QuotaWrapper>::into(quota).0),
+                    )
+                })
+            })
+            .collect();
+        Self {
+            limiters,
+            api_key_limiters,
+        }
+    }
+
+    fn check(&self, method: &MethodName, meta: &Metadata) -> anyhow::Result<()> {
+        match &meta.key_name {
+            Some(key_name) => match self.api_key_limiters.get(key_name) {
+                Some(limiter) => {
+                    limiter.check().map_err(|e| anyhow::anyhow!("{}", &e))?;
+                    self.limiters.check(method, None)
+                }
+                // the callers of the same key share the user quota, the user is prefixed to not
+                // share the quota with an ip address.
+                None => self
+                    .limiters
+                    .check(method, Some(&format!("key:{}", key_name))),
+            },
+            None => self.limiters.check(method, meta.user.as_ref()),
+        }
     }
 }
 
@@ -64,7 +103,7 @@ impl Middleware<Metadata> for JsonApiRateLimitMiddleware {
             Call::Invalid { .. } => None,
         };
         if let Some((m, json_version, id)) = method {
-            match self.limiters.check(&m, meta.user.as_ref()) {
+            match self.check(&m, &meta) {
                 Ok(_) => Either::Right(next(call, meta)),
                 Err(e) => {
                     let output = Output::Failure(Failure {
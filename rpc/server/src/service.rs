@@ -98,7 +98,11 @@ impl RpcService {
         M: MinerApi,
         Contract: ContractApi,
     {
-        let mut api_registry = ApiRegistry::new(config.rpc.api_quotas.clone());
+        let mut api_registry = ApiRegistry::new(
+            config.rpc.api_quotas.clone(),
+            config.rpc.api_keys.clone(),
+            config.rpc.audit.clone(),
+        );
 
         api_registry.register(Api::Node, NodeApi::to_delegate(node_api));
         if let Some(node_manager_api) = node_manager_api {
@@ -167,7 +171,7 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_http_address() {
             let address = addr.into();
             let apis = self.config.rpc.http.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self.api_registry.get_apis_with_api_keys(apis);
             let http = jsonrpc_http_server::ServerBuilder::new(io_handler)
                 .meta_extractor(RpcExtractor {
                     http_ip_headers: self.config.rpc.http.ip_headers(),
@@ -7,5 +7,6 @@ mod state_tree;
 #[cfg(test)]
 mod state_tree_test;
 
+pub use forkable_jellyfish_merkle::RawKey;
 pub use starcoin_state_store_api::{StateNode, StateNodeStore};
 pub use state_tree::StateTree;
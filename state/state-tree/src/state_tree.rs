@@ -221,6 +221,34 @@ where
         Ok(StateSet::new(states))
     }
 
+    /// Dump at most `limit` states in order of the key hash, starting at the key hash `start`.
+    /// Only the tree nodes of the page are read, the key hash of the state after the page is
+    /// returned if there is one.
+    pub fn dump_page(
+        &self,
+        start: HashValue,
+        limit: usize,
+    ) -> Result<(StateSet, Option<HashValue>)> {
+        let cur_root_hash = self.root_hash();
+        let mut cache_guard = self.cache.lock();
+        let cache = cache_guard.deref_mut();
+        let reader = CachedTreeReader {
+            store: self.storage.as_ref(),
+            cache,
+        };
+        let mut iterator = JellyfishMerkleIterator::new(&reader, cur_root_hash, start)?;
+        let mut states = vec![];
+        for item in iterator.by_ref().take(limit) {
+            let item: (K, Blob) = item?;
+            states.push((item.0.encode_key()?, item.1.into()));
+        }
+        let next = iterator
+            .next()
+            .transpose()?
+            .map(|(key, _): (K, Blob)| key.key_hash());
+        Ok((StateSet::new(states), next))
+    }
+
     /// passing None value with a key means delete the key
     fn updates(&self, updates: Vec<(K, Option<Blob>)>) -> Result<HashValue> {
         let cur_root_hash = self.root_hash();
@@ -166,6 +166,30 @@ pub fn test_state_dump() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_state_dump_page() -> Result<()> {
+    let s = MockStateNodeStore::new();
+    let state = StateTree::new(Arc::new(s), None);
+    for i in 0..5u8 {
+        state.put(HashValueKey(HashValue::random()), vec![i]);
+    }
+    state.commit()?;
+    let all = state.dump()?;
+    let mut start = HashValue::zero();
+    let mut pages = vec![];
+    loop {
+        let (page, next) = state.dump_page(start, 2)?;
+        assert!(page.len() <= 2);
+        pages.extend(page.iter().cloned());
+        match next {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+    assert_eq!(all.iter().cloned().collect::<Vec<_>>(), pages);
+    Ok(())
+}
+
 #[test]
 pub fn test_repeat_commit() -> Result<()> {
     let s = MockStateNodeStore::new();
@@ -0,0 +1,306 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Online backup and restore of the chain and account databases by RocksDB checkpoints.
+//! A backup directory looks like:
+//! ```text
+//! backup_dir
+//! ├── manifest.json
+//! ├── chain
+//! └── account
+//! ```
+
+use crate::block_info::BlockInfoStore;
+use crate::chain_info::ChainInfoStorage;
+use crate::db_storage::DBStorage;
+use crate::storage::{InnerStore, StorageInstance};
+use crate::{BlockStore, Storage, CHAIN_INFO_PREFIX_NAME, VEC_PREFIX_NAME};
+use anyhow::{ensure, format_err, Result};
+use crypto::hash::DefaultHasher;
+use crypto::HashValue;
+use logger::prelude::*;
+use serde::{Deserialize, Serialize};
+use starcoin_config::RocksdbConfig;
+use starcoin_types::startup_info::StartupInfo;
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+/// The manifest copied into the restored chain db dir, checked and removed at node startup.
+pub const RESTORE_MANIFEST_FILE: &str = "restore_manifest.json";
+pub const CHAIN_DB_BACKUP_DIR: &str = "chain";
+pub const ACCOUNT_DB_BACKUP_DIR: &str = "account";
+/// Max attempts to take the chain db and the account db checkpoints at the same head block.
+const MAX_BACKUP_ATTEMPTS: usize = 3;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BackupFile {
+    /// Path relative to the backup dir.
+    pub path: String,
+    pub size: u64,
+    pub checksum: HashValue,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BackupManifest {
+    /// The main chain head block of the chain db checkpoint, the account db checkpoint is taken
+    /// at the same head.
+    pub head_block_hash: HashValue,
+    pub head_block_number: u64,
+    /// Backup create time in milliseconds.
+    pub created_at: u64,
+    pub files: Vec<BackupFile>,
+}
+
+impl BackupManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .map_err(|e| format_err!("Open backup manifest {:?} error: {:?}", path, e))?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    /// Check the size and checksum of every file in the `backup_dir`.
+    pub fn verify(&self, backup_dir: &Path) -> Result<()> {
+        for file in &self.files {
+            let path = backup_dir.join(file.path.as_str());
+            let (size, checksum) = file_checksum(path.as_path())?;
+            ensure!(
+                size == file.size && checksum == file.checksum,
+                "Backup file {:?} is broken, expect size: {}, checksum: {}, got size: {}, checksum: {}",
+                path,
+                file.size,
+                file.checksum,
+                size,
+                checksum
+            );
+        }
+        Ok(())
+    }
+}
+
+fn file_checksum(path: &Path) -> Result<(u64, HashValue)> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = DefaultHasher::new(b"BackupFile");
+    let mut buf = vec![0u8; 1 << 20];
+    let mut size = 0u64;
+    loop {
+        let len = reader.read(&mut buf)?;
+        if len == 0 {
+            break;
+        }
+        hasher.update(&buf[..len]);
+        size += len as u64;
+    }
+    Ok((size, hasher.finish()))
+}
+
+/// List the files of a checkpoint dir, the checkpoint dir is flat.
+fn list_backup_files(backup_dir: &Path, sub_dir: &str) -> Result<Vec<BackupFile>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(backup_dir.join(sub_dir))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let path = format!("{}/{}", sub_dir, entry.file_name().to_string_lossy());
+        let (size, checksum) = file_checksum(entry.path().as_path())?;
+        files.push(BackupFile {
+            path,
+            size,
+            checksum,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn ensure_empty_dir(dir: &Path) -> Result<()> {
+    if dir.exists() {
+        ensure!(
+            dir.is_dir() && std::fs::read_dir(dir)?.next().is_none(),
+            "{:?} is not an empty dir.",
+            dir
+        );
+    }
+    Ok(())
+}
+
+/// Backup the chain db and the account db to `backup_dir`, the dbs can be used by a running node.
+/// The chain db checkpoint is a consistent snapshot, its main chain head is recorded in the manifest.
+/// The account db checkpoint is taken while the main chain head stays at the recorded head, the
+/// checkpoints are taken again if the head moved in between.
+pub fn backup(
+    chain_db: &DBStorage,
+    account_db: Option<&DBStorage>,
+    backup_dir: &Path,
+) -> Result<BackupManifest> {
+    ensure_empty_dir(backup_dir)?;
+    let chain_dir = backup_dir.join(CHAIN_DB_BACKUP_DIR);
+    let mut attempts = 0;
+    let (storage, head_block_hash) = loop {
+        attempts += 1;
+        std::fs::create_dir_all(backup_dir)?;
+        let live_head_block_hash = get_head_block_hash(chain_db)?;
+        if let Some(account_db) = account_db {
+            account_db.create_checkpoint(backup_dir.join(ACCOUNT_DB_BACKUP_DIR).as_path())?;
+        }
+        chain_db.create_checkpoint(chain_dir.as_path())?;
+        let storage = open_readonly_storage(chain_dir.as_path())?;
+        let head_block_hash = storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info not found in chain db, can not backup."))?
+            .main;
+        if account_db.is_none() || head_block_hash == live_head_block_hash {
+            break (storage, head_block_hash);
+        }
+        ensure!(
+            attempts < MAX_BACKUP_ATTEMPTS,
+            "The main chain head moved during every backup attempt, please try again later."
+        );
+        warn!(
+            "The main chain head moved from {} to {} during backup, backup again.",
+            live_head_block_hash, head_block_hash
+        );
+        drop(storage);
+        std::fs::remove_dir_all(backup_dir)?;
+    };
+    let mut files = list_backup_files(backup_dir, CHAIN_DB_BACKUP_DIR)?;
+    if account_db.is_some() {
+        files.extend(list_backup_files(backup_dir, ACCOUNT_DB_BACKUP_DIR)?);
+    }
+
+    let head_block_number = storage
+        .get_block_header_by_hash(head_block_hash)?
+        .ok_or_else(|| format_err!("Head block header {} not found.", head_block_hash))?
+        .number();
+    let manifest = BackupManifest {
+        head_block_hash,
+        head_block_number,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Get time since epoch should success.")
+            .as_millis() as u64,
+        files,
+    };
+    manifest.save(backup_dir.join(BACKUP_MANIFEST_FILE).as_path())?;
+    info!(
+        "Backup to {:?} at block {}:{}",
+        backup_dir, head_block_number, head_block_hash
+    );
+    Ok(manifest)
+}
+
+/// Get the main chain head of a running chain db.
+fn get_head_block_hash(chain_db: &DBStorage) -> Result<HashValue> {
+    let startup_info: StartupInfo = chain_db
+        .get(
+            CHAIN_INFO_PREFIX_NAME,
+            ChainInfoStorage::STARTUP_INFO_KEY.as_bytes().to_vec(),
+        )?
+        .ok_or_else(|| format_err!("Startup info not found in chain db, can not backup."))?
+        .try_into()?;
+    Ok(startup_info.main)
+}
+
+fn open_readonly_storage(chain_db_dir: &Path) -> Result<Storage> {
+    let db = DBStorage::open_with_cfs(
+        chain_db_dir,
+        VEC_PREFIX_NAME.to_vec(),
+        true,
+        RocksdbConfig::default(),
+    )?;
+    Storage::new(StorageInstance::new_db_instance(db))
+}
+
+/// Restore the backup in `backup_dir` to the chain db dir and the account db dir,
+/// the target dirs must be empty. The node checks the restored chain db at startup.
+pub fn restore(
+    backup_dir: &Path,
+    chain_db_dir: &Path,
+    account_db_dir: Option<&Path>,
+) -> Result<BackupManifest> {
+    let manifest = BackupManifest::load(backup_dir.join(BACKUP_MANIFEST_FILE).as_path())?;
+    manifest.verify(backup_dir)?;
+    ensure_empty_dir(chain_db_dir)?;
+    if let Some(account_db_dir) = account_db_dir {
+        ensure_empty_dir(account_db_dir)?;
+    }
+    for file in &manifest.files {
+        let target_dir = match file.path.split('/').next() {
+            Some(CHAIN_DB_BACKUP_DIR) => chain_db_dir,
+            Some(ACCOUNT_DB_BACKUP_DIR) => match account_db_dir {
+                Some(account_db_dir) => account_db_dir,
+                None => continue,
+            },
+            _ => return Err(format_err!("Unexpect backup file: {}", file.path)),
+        };
+        let source = backup_dir.join(file.path.as_str());
+        let file_name = source
+            .file_name()
+            .ok_or_else(|| format_err!("Invalid backup file: {}", file.path))?;
+        std::fs::create_dir_all(target_dir)?;
+        std::fs::copy(source.as_path(), target_dir.join(file_name))?;
+    }
+    manifest.save(chain_db_dir.join(RESTORE_MANIFEST_FILE).as_path())?;
+    info!(
+        "Restore backup {:?} at block {}:{}",
+        backup_dir, manifest.head_block_number, manifest.head_block_hash
+    );
+    Ok(manifest)
+}
+
+/// Check the chain info of a restored storage with the restore manifest, and remove the manifest
+/// after check passed. Do nothing if the storage is not restored from a backup.
+pub fn check_restored_storage(storage: &Storage) -> Result<()> {
+    let manifest_path: PathBuf = match storage.db() {
+        Some(db) => db.path().join(RESTORE_MANIFEST_FILE),
+        None => return Ok(()),
+    };
+    if !manifest_path.exists() {
+        return Ok(());
+    }
+    let manifest = BackupManifest::load(manifest_path.as_path())?;
+    let startup_info = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info not found in restored storage."))?;
+    ensure!(
+        startup_info.main == manifest.head_block_hash,
+        "Restored storage head {} not match the backup manifest head {}",
+        startup_info.main,
+        manifest.head_block_hash
+    );
+    let header = storage
+        .get_block_header_by_hash(manifest.head_block_hash)?
+        .ok_or_else(|| {
+            format_err!(
+                "Restored head block {} not found.",
+                manifest.head_block_hash
+            )
+        })?;
+    ensure!(
+        header.number() == manifest.head_block_number,
+        "Restored head block number {} not match the backup manifest {}",
+        header.number(),
+        manifest.head_block_number
+    );
+    ensure!(
+        storage.get_block_info(manifest.head_block_hash)?.is_some(),
+        "Restored head block info {} not found.",
+        manifest.head_block_hash
+    );
+    std::fs::remove_file(manifest_path)?;
+    info!(
+        "Check restored storage at block {}:{} success.",
+        manifest.head_block_number, manifest.head_block_hash
+    );
+    Ok(())
+}
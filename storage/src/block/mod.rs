@@ -254,4 +254,8 @@ impl BlockStorage {
             None => Ok(None),
         }
     }
+
+    pub fn delete_failed_block(&self, block_id: HashValue) -> Result<()> {
+        self.failed_block_storage.remove(block_id)
+    }
 }
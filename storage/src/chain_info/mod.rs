@@ -23,7 +23,7 @@ impl ColumnFamily for ChainInfoColumnFamily {
 pub type ChainInfoStorage = InnerStorage<ChainInfoColumnFamily>;
 
 impl ChainInfoStorage {
-    const STARTUP_INFO_KEY: &'static str = "startup_info";
+    pub(crate) const STARTUP_INFO_KEY: &'static str = "startup_info";
     const GENESIS_KEY: &'static str = "genesis";
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
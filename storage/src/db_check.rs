@@ -0,0 +1,609 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integrity check of the chain database, used to find the broken data after a crash or disk
+//! issue. The checker walks the block, block info, transaction info, accumulator and state node
+//! column families, and can repair the indexes which can be rebuilt from the blocks.
+
+use crate::block_info::BlockInfoStore;
+use crate::db_storage::DBStorage;
+use crate::storage::ValueCodec;
+use crate::{
+    BlockStore, BlockTransactionInfoStore, Storage, Store, BLOCK_HEADER_PREFIX_NAME,
+    BLOCK_INFO_PREFIX_NAME, BLOCK_PREFIX_NAME, BLOCK_TRANSACTIONS_PREFIX_NAME,
+    STATE_NODE_PREFIX_NAME, TRANSACTION_INFO_HASH_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
+};
+use anyhow::Result;
+use crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
+use crypto::HashValue;
+use forkable_jellyfish_merkle::node_type::Node;
+use forkable_jellyfish_merkle::RawKey;
+use logger::prelude::*;
+use lru::LruCache;
+use serde::Serialize;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_state_store_api::StateNodeStore;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::{Block, BlockHeader, BlockInfo};
+use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+/// The max state nodes remembered as visited, the subtrees shared by the state trees of the heads
+/// and accounts are traversed once if they are still remembered.
+const MAX_VISITED_STATE_NODES: usize = 1 << 20;
+
+/// The raw key of a state tree leaf, the key type of different state trees are different, but the
+/// key hash is always the hash of the encoded key.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct RawBytesKey(Vec<u8>);
+
+impl RawKey for RawBytesKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.0.clone())
+    }
+
+    fn decode_key(bytes: &[u8]) -> Result<Self> {
+        Ok(Self(bytes.to_vec()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CheckOption {
+    /// Traverse the state trees of all the chain heads, it may take a long time.
+    pub check_state: bool,
+    /// Rebuild the broken indexes from blocks, the db should not be used by a running node.
+    pub repair: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckIssue {
+    pub cf_name: String,
+    pub key: String,
+    pub message: String,
+    pub repaired: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CheckReport {
+    pub head_block_hash: Option<HashValue>,
+    pub head_block_number: Option<u64>,
+    pub blocks: u64,
+    pub block_headers: u64,
+    pub block_infos: u64,
+    pub transaction_infos: u64,
+    pub main_chain_blocks: u64,
+    /// The blocks without child, their state trees are traversed if `check_state` is set.
+    pub heads: u64,
+    pub state_nodes: u64,
+    pub issues: Vec<CheckIssue>,
+}
+
+impl CheckReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.iter().all(|issue| issue.repaired)
+    }
+
+    fn issue(&mut self, cf_name: &str, key: impl ToString, message: String, repaired: bool) {
+        warn!("[db-check] {} {}: {}", cf_name, key.to_string(), message);
+        self.issues.push(CheckIssue {
+            cf_name: cf_name.to_string(),
+            key: key.to_string(),
+            message,
+            repaired,
+        });
+    }
+}
+
+pub struct DBChecker<'a> {
+    db: &'a DBStorage,
+    storage: &'a Storage,
+    option: CheckOption,
+    report: CheckReport,
+}
+
+impl<'a> DBChecker<'a> {
+    pub fn new(db: &'a DBStorage, storage: &'a Storage, option: CheckOption) -> Self {
+        Self {
+            db,
+            storage,
+            option,
+            report: CheckReport::default(),
+        }
+    }
+
+    pub fn check(mut self) -> Result<CheckReport> {
+        self.check_blocks()?;
+        let heads = self.check_block_headers()?;
+        self.check_block_infos()?;
+        self.check_transaction_infos()?;
+        self.check_main_chain()?;
+        if self.option.check_state {
+            let mut visited = LruCache::new(MAX_VISITED_STATE_NODES);
+            for (head_id, state_root) in heads {
+                self.check_state_tree(head_id, state_root, &mut visited)?;
+            }
+        }
+        Ok(self.report)
+    }
+
+    /// Iterate all the records of the column family, and decode the key and the value.
+    fn for_each<V, F>(&mut self, cf_name: &str, mut f: F) -> Result<u64>
+    where
+        V: ValueCodec,
+        F: FnMut(&mut Self, HashValue, V) -> Result<()>,
+    {
+        let db = self.db;
+        let mut iter = db.iter(cf_name)?;
+        iter.seek_to_first();
+        let mut count = 0;
+        for item in iter {
+            let (key, value) = item?;
+            count += 1;
+            let key = match HashValue::from_slice(key.as_slice()) {
+                Ok(key) => key,
+                Err(e) => {
+                    self.report
+                        .issue(cf_name, format!("{:?}", key), format!("{:?}", e), false);
+                    continue;
+                }
+            };
+            match V::decode_value(value.as_slice()) {
+                Ok(value) => f(self, key, value)?,
+                Err(e) => self
+                    .report
+                    .issue(cf_name, key, format!("decode error: {:?}", e), false),
+            }
+        }
+        Ok(count)
+    }
+
+    fn check_blocks(&mut self) -> Result<()> {
+        self.report.blocks = self.for_each(BLOCK_PREFIX_NAME, |checker, key, block: Block| {
+            if key != block.id() {
+                checker.report.issue(
+                    BLOCK_PREFIX_NAME,
+                    key,
+                    format!("key not match the block hash {}", block.id()),
+                    false,
+                );
+            }
+            if block.header().body_hash() != block.body.hash() {
+                checker.report.issue(
+                    BLOCK_PREFIX_NAME,
+                    key,
+                    "body hash not match the header".to_string(),
+                    false,
+                );
+            }
+            if checker
+                .storage
+                .get_block_header_by_hash(block.id())?
+                .is_none()
+            {
+                let repaired = checker.option.repair;
+                if repaired {
+                    checker.storage.commit_block(block)?;
+                }
+                checker.report.issue(
+                    BLOCK_HEADER_PREFIX_NAME,
+                    key,
+                    "header of the block is missing".to_string(),
+                    repaired,
+                );
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Check the block headers, return the id and state root of the blocks without child.
+    fn check_block_headers(&mut self) -> Result<Vec<(HashValue, HashValue)>> {
+        let mut parents = HashSet::new();
+        let mut blocks = vec![];
+        self.report.block_headers = self.for_each(
+            BLOCK_HEADER_PREFIX_NAME,
+            |checker, key, header: BlockHeader| {
+                if key != header.id() {
+                    checker.report.issue(
+                        BLOCK_HEADER_PREFIX_NAME,
+                        key,
+                        format!("key not match the header hash {}", header.id()),
+                        false,
+                    );
+                }
+                if !header.is_genesis() {
+                    parents.insert(header.parent_hash());
+                }
+                blocks.push((key, header.state_root()));
+                Ok(())
+            },
+        )?;
+        let heads: Vec<_> = blocks
+            .into_iter()
+            .filter(|(block_id, _)| !parents.contains(block_id))
+            .collect();
+        self.report.heads = heads.len() as u64;
+        Ok(heads)
+    }
+
+    fn check_block_infos(&mut self) -> Result<()> {
+        self.report.block_infos =
+            self.for_each(BLOCK_INFO_PREFIX_NAME, |checker, key, info: BlockInfo| {
+                if key != info.block_id {
+                    checker.report.issue(
+                        BLOCK_INFO_PREFIX_NAME,
+                        key,
+                        format!("key not match the block id {}", info.block_id),
+                        false,
+                    );
+                }
+                if checker.storage.get_block_header_by_hash(key)?.is_none() {
+                    checker.report.issue(
+                        BLOCK_INFO_PREFIX_NAME,
+                        key,
+                        "block header of the block info is missing".to_string(),
+                        false,
+                    );
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    fn check_transaction_infos(&mut self) -> Result<()> {
+        self.report.transaction_infos = self.for_each(
+            TRANSACTION_INFO_PREFIX_NAME,
+            |checker, key, info: BlockTransactionInfo| {
+                if key != info.id() {
+                    checker.report.issue(
+                        TRANSACTION_INFO_PREFIX_NAME,
+                        key,
+                        format!("key not match the transaction info hash {}", info.id()),
+                        false,
+                    );
+                }
+                let info_ids = checker
+                    .storage
+                    .get_transaction_info_ids_by_hash(info.transaction_hash())?;
+                if !info_ids.contains(&key) {
+                    let repaired = checker.option.repair;
+                    if repaired {
+                        checker.storage.save_transaction_infos(vec![info])?;
+                    }
+                    checker.report.issue(
+                        TRANSACTION_INFO_HASH_PREFIX_NAME,
+                        key,
+                        "transaction hash index is missing".to_string(),
+                        repaired,
+                    );
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Walk the main chain from the head to genesis, check the blocks, the txns and the
+    /// accumulators of every block.
+    fn check_main_chain(&mut self) -> Result<()> {
+        let head_id = match self.storage.get_startup_info()? {
+            Some(startup_info) => startup_info.main,
+            None => {
+                self.report.issue(
+                    "chain_info",
+                    "startup_info",
+                    "startup info is missing".to_string(),
+                    false,
+                );
+                return Ok(());
+            }
+        };
+        let head = match self.storage.get_block_header_by_hash(head_id)? {
+            Some(head) => head,
+            None => {
+                self.report.issue(
+                    BLOCK_HEADER_PREFIX_NAME,
+                    head_id,
+                    "head block header is missing".to_string(),
+                    false,
+                );
+                return Ok(());
+            }
+        };
+        let head_info = match self.storage.get_block_info(head_id)? {
+            Some(head_info) => head_info,
+            None => {
+                self.report.issue(
+                    BLOCK_INFO_PREFIX_NAME,
+                    head_id,
+                    "head block info is missing".to_string(),
+                    false,
+                );
+                return Ok(());
+            }
+        };
+        self.report.head_block_hash = Some(head_id);
+        self.report.head_block_number = Some(head.number());
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            head_info.block_accumulator_info.clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+
+        let mut current = Some((head, head_info));
+        while let Some((header, info)) = current.take() {
+            self.report.main_chain_blocks += 1;
+            let block_id = header.id();
+            if header.txn_accumulator_root() != info.txn_accumulator_info.accumulator_root {
+                self.report.issue(
+                    BLOCK_INFO_PREFIX_NAME,
+                    block_id,
+                    "txn accumulator root not match the header".to_string(),
+                    false,
+                );
+            }
+            match block_accumulator.get_leaf(header.number()) {
+                Ok(Some(leaf)) if leaf == block_id => {}
+                result => self.report.issue(
+                    BLOCK_INFO_PREFIX_NAME,
+                    block_id,
+                    format!("block accumulator leaf not match: {:?}", result),
+                    false,
+                ),
+            }
+            let parent = if header.is_genesis() {
+                None
+            } else {
+                let parent_id = header.parent_hash();
+                let parent_header = self.storage.get_block_header_by_hash(parent_id)?;
+                let parent_info = self.storage.get_block_info(parent_id)?;
+                match (parent_header, parent_info) {
+                    (Some(parent_header), Some(parent_info)) => {
+                        if header.block_accumulator_root()
+                            != parent_info.block_accumulator_info.accumulator_root
+                        {
+                            self.report.issue(
+                                BLOCK_INFO_PREFIX_NAME,
+                                parent_id,
+                                "block accumulator root not match the child header".to_string(),
+                                false,
+                            );
+                        }
+                        Some((parent_header, parent_info))
+                    }
+                    _ => {
+                        self.report.issue(
+                            BLOCK_INFO_PREFIX_NAME,
+                            parent_id,
+                            "parent block header or info is missing, stop walking the main chain"
+                                .to_string(),
+                            false,
+                        );
+                        None
+                    }
+                }
+            };
+            self.check_accumulator_root(
+                AccumulatorStoreType::Block,
+                block_id,
+                parent
+                    .as_ref()
+                    .map(|(_, parent_info)| parent_info.block_accumulator_info.clone()),
+                &[block_id],
+                info.block_accumulator_info.accumulator_root,
+            )?;
+            self.check_block_txns(&header, &info, parent.as_ref())?;
+            current = parent;
+        }
+        Ok(())
+    }
+
+    /// Recompute the accumulator root by appending the `leaves` of the block to the accumulator
+    /// of its parent, and check it matches the root in the block info.
+    fn check_accumulator_root(
+        &mut self,
+        store_type: AccumulatorStoreType,
+        block_id: HashValue,
+        parent_accumulator_info: Option<AccumulatorInfo>,
+        leaves: &[HashValue],
+        expect_root: HashValue,
+    ) -> Result<()> {
+        let store = self.storage.get_accumulator_store(store_type);
+        let accumulator = match parent_accumulator_info {
+            Some(parent_accumulator_info) => {
+                MerkleAccumulator::new_with_info(parent_accumulator_info, store)
+            }
+            None => MerkleAccumulator::new_empty(store),
+        };
+        // the appended nodes stay in memory, the accumulator is never flushed.
+        match accumulator.append(leaves) {
+            Ok(root) if root == expect_root => {}
+            result => self.report.issue(
+                BLOCK_INFO_PREFIX_NAME,
+                block_id,
+                format!(
+                    "recomputed {:?} accumulator root not match: {:?}",
+                    store_type, result
+                ),
+                false,
+            ),
+        }
+        Ok(())
+    }
+
+    fn check_block_txns(
+        &mut self,
+        header: &BlockHeader,
+        info: &BlockInfo,
+        parent: Option<&(BlockHeader, BlockInfo)>,
+    ) -> Result<()> {
+        let block_id = header.id();
+        let block = match self.storage.get_block_by_hash(block_id)? {
+            Some(block) => block,
+            None => {
+                self.report.issue(
+                    BLOCK_PREFIX_NAME,
+                    block_id,
+                    "main chain block is missing".to_string(),
+                    false,
+                );
+                return Ok(());
+            }
+        };
+        // genesis block do not have BlockMetadata transaction.
+        let mut txn_ids: Vec<HashValue> = match parent {
+            Some((parent_header, _)) => {
+                vec![Transaction::BlockMetadata(block.to_metadata(parent_header.gas_used())).id()]
+            }
+            None => vec![],
+        };
+        txn_ids.extend(block.transactions().iter().map(|txn| txn.id()));
+
+        let saved_txn_ids = self.storage.block_storage.get_transactions(block_id).ok();
+        if saved_txn_ids.as_ref() != Some(&txn_ids) {
+            let repaired = self.option.repair;
+            if repaired {
+                self.storage
+                    .save_block_transaction_ids(block_id, txn_ids.clone())?;
+            }
+            self.report.issue(
+                BLOCK_TRANSACTIONS_PREFIX_NAME,
+                block_id,
+                "block transaction ids are missing or not match the block".to_string(),
+                repaired,
+            );
+        }
+
+        let txn_info_ids = self.storage.get_block_txn_info_ids(block_id)?;
+        if txn_info_ids.len() != txn_ids.len() {
+            self.report.issue(
+                BLOCK_INFO_PREFIX_NAME,
+                block_id,
+                format!(
+                    "block has {} txns, but {} txn infos",
+                    txn_ids.len(),
+                    txn_info_ids.len()
+                ),
+                false,
+            );
+            return Ok(());
+        }
+        for (txn_id, txn_info_id) in txn_ids.iter().zip(txn_info_ids.iter()) {
+            match self.storage.get_transaction_info(*txn_info_id)? {
+                Some(txn_info)
+                    if txn_info.transaction_hash() == *txn_id
+                        && txn_info.block_id() == block_id => {}
+                Some(_) => self.report.issue(
+                    TRANSACTION_INFO_PREFIX_NAME,
+                    txn_info_id,
+                    format!("transaction info not match the txn {} of block", txn_id),
+                    false,
+                ),
+                None => self.report.issue(
+                    TRANSACTION_INFO_PREFIX_NAME,
+                    txn_info_id,
+                    format!("transaction info of txn {} is missing", txn_id),
+                    false,
+                ),
+            }
+        }
+
+        let parent_num_leaves = parent
+            .map(|(_, parent_info)| parent_info.txn_accumulator_info.num_leaves)
+            .unwrap_or_default();
+        let num_leaves = info.txn_accumulator_info.num_leaves;
+        if num_leaves != parent_num_leaves + txn_info_ids.len() as u64 {
+            self.report.issue(
+                BLOCK_INFO_PREFIX_NAME,
+                block_id,
+                "txn accumulator leaves not match the txn count".to_string(),
+                false,
+            );
+        } else {
+            self.check_accumulator_root(
+                AccumulatorStoreType::Transaction,
+                block_id,
+                parent.map(|(_, parent_info)| parent_info.txn_accumulator_info.clone()),
+                txn_info_ids.as_slice(),
+                info.txn_accumulator_info.accumulator_root,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Traverse the global state tree and the account storage trees from the state root of the
+    /// head, check every node exists and its hash matches the key. The nodes in `visited` are
+    /// skipped, they are checked from the other heads or accounts.
+    fn check_state_tree(
+        &mut self,
+        head_id: HashValue,
+        state_root: HashValue,
+        visited: &mut LruCache<HashValue, ()>,
+    ) -> Result<()> {
+        // (node hash, is the node in the global state tree)
+        let mut pending = vec![(state_root, true)];
+        while let Some((hash, global)) = pending.pop() {
+            if hash == *SPARSE_MERKLE_PLACEHOLDER_HASH || visited.put(hash, ()).is_some() {
+                continue;
+            }
+            self.report.state_nodes += 1;
+            let state_node = match StateNodeStore::get(self.storage, &hash)? {
+                Some(state_node) => state_node,
+                None => {
+                    self.report.issue(
+                        STATE_NODE_PREFIX_NAME,
+                        hash,
+                        format!("state node of head {} is unreachable", head_id),
+                        false,
+                    );
+                    continue;
+                }
+            };
+            let node = match Node::<RawBytesKey>::decode(state_node.0.as_slice()) {
+                Ok(node) => node,
+                Err(e) => {
+                    self.report.issue(
+                        STATE_NODE_PREFIX_NAME,
+                        hash,
+                        format!("decode error: {:?}", e),
+                        false,
+                    );
+                    continue;
+                }
+            };
+            if node.hash() != hash {
+                self.report.issue(
+                    STATE_NODE_PREFIX_NAME,
+                    hash,
+                    format!("key not match the node hash {}", node.hash()),
+                    false,
+                );
+            }
+            match node {
+                Node::Internal(internal) => {
+                    pending.extend(internal.all_child().into_iter().map(|h| (h, global)));
+                }
+                Node::Leaf(leaf) if global => match AccountState::try_from(leaf.blob().as_ref()) {
+                    Ok(account_state) => pending.extend(
+                        account_state
+                            .storage_roots()
+                            .iter()
+                            .flatten()
+                            .map(|root| (*root, false)),
+                    ),
+                    Err(e) => self.report.issue(
+                        STATE_NODE_PREFIX_NAME,
+                        hash,
+                        format!("decode account state error: {:?}", e),
+                        false,
+                    ),
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
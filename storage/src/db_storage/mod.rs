@@ -7,8 +7,12 @@ use crate::metrics::{record_metrics, STORAGE_ITER_BYTES};
 use crate::storage::{ColumnFamilyName, InnerStore, WriteOp};
 use crate::{DEFAULT_PREFIX_NAME, VEC_PREFIX_NAME};
 use anyhow::{ensure, format_err, Error, Result};
-use rocksdb::{Options, ReadOptions, WriteBatch as DBWriteBatch, WriteOptions, DB};
-use starcoin_config::RocksdbConfig;
+use rocksdb::checkpoint::Checkpoint;
+use rocksdb::{
+    BlockBasedOptions, Cache, DBCompressionType, Options, ReadOptions, SliceTransform,
+    WriteBatch as DBWriteBatch, WriteOptions, DB,
+};
+use starcoin_config::{CompressionType, RocksdbConfig};
 use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::path::Path;
@@ -17,8 +21,21 @@ use std::path::Path;
 pub struct DBStorage {
     db: DB,
     cfs: Vec<ColumnFamilyName>,
+    db_opts: Options,
+    readonly: bool,
 }
 
+/// The int properties of every column family exported to metrics.
+pub const ROCKSDB_PROPERTIES: &[&str] = &[
+    "rocksdb.estimate-num-keys",
+    "rocksdb.estimate-live-data-size",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.cur-size-all-mem-tables",
+    "rocksdb.block-cache-usage",
+    "rocksdb.estimate-pending-compaction-bytes",
+    "rocksdb.num-running-compactions",
+];
+
 impl DBStorage {
     pub fn new<P: AsRef<Path> + Clone>(
         db_root_path: P,
@@ -79,12 +96,19 @@ impl DBStorage {
         } else {
             rocksdb_opts.create_if_missing(true);
             rocksdb_opts.create_missing_column_families(true);
-            Self::open_inner(&rocksdb_opts, path, column_families.clone())?
+            Self::open_inner(
+                &rocksdb_opts,
+                path,
+                column_families.clone(),
+                &rocksdb_config,
+            )?
         };
 
         Ok(DBStorage {
             db,
             cfs: column_families,
+            db_opts: rocksdb_opts,
+            readonly,
         })
     }
 
@@ -92,19 +116,68 @@ impl DBStorage {
         opts: &Options,
         path: impl AsRef<Path>,
         column_families: Vec<ColumnFamilyName>,
+        rocksdb_config: &RocksdbConfig,
     ) -> Result<DB> {
-        let inner = rocksdb::DB::open_cf_descriptors(
-            opts,
-            path,
-            column_families.iter().map(|cf_name| {
-                let mut cf_opts = rocksdb::Options::default();
-                cf_opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
-                rocksdb::ColumnFamilyDescriptor::new((*cf_name).to_string(), cf_opts)
-            }),
-        )?;
+        let shared_cache = Cache::new_lru_cache(rocksdb_config.block_cache_size as usize)?;
+        let cf_descriptors = column_families
+            .iter()
+            .map(|cf_name| {
+                let cf_opts = Self::gen_cf_options(cf_name, rocksdb_config, &shared_cache)?;
+                Ok(rocksdb::ColumnFamilyDescriptor::new(
+                    (*cf_name).to_string(),
+                    cf_opts,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let inner = rocksdb::DB::open_cf_descriptors(opts, path, cf_descriptors)?;
         Ok(inner)
     }
 
+    fn gen_cf_options(
+        cf_name: &str,
+        rocksdb_config: &RocksdbConfig,
+        shared_cache: &Cache,
+    ) -> Result<Options> {
+        let cf_config = rocksdb_config.column_family(cf_name);
+        let mut cf_opts = Options::default();
+        let mut table_opts = BlockBasedOptions::default();
+        match cf_config.block_cache_size {
+            Some(block_cache_size) => {
+                table_opts.set_block_cache(&Cache::new_lru_cache(block_cache_size as usize)?)
+            }
+            None => table_opts.set_block_cache(shared_cache),
+        }
+        if let Some(bits_per_key) = cf_config.bloom_filter_bits {
+            table_opts.set_bloom_filter(bits_per_key, false);
+        }
+        cf_opts.set_block_based_table_factory(&table_opts);
+        match cf_config.compression_per_level {
+            Some(compression_per_level) => {
+                let level_types: Vec<_> = compression_per_level
+                    .into_iter()
+                    .map(Self::db_compression_type)
+                    .collect();
+                cf_opts.set_compression_per_level(&level_types);
+            }
+            None => cf_opts.set_compression_type(DBCompressionType::Lz4),
+        }
+        if let Some(write_buffer_size) = cf_config.write_buffer_size {
+            cf_opts.set_write_buffer_size(write_buffer_size as usize);
+        }
+        if let Some(prefix_length) = cf_config.prefix_length {
+            cf_opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(prefix_length));
+        }
+        Ok(cf_opts)
+    }
+
+    fn db_compression_type(compression_type: CompressionType) -> DBCompressionType {
+        match compression_type {
+            CompressionType::None => DBCompressionType::None,
+            CompressionType::Lz4 => DBCompressionType::Lz4,
+            CompressionType::Zstd => DBCompressionType::Zstd,
+        }
+    }
+
     fn open_readonly(
         db_opts: &Options,
         path: impl AsRef<Path>,
@@ -137,6 +210,84 @@ impl DBStorage {
         Ok(())
     }
 
+    /// Manually compact all the data of the column family `cf_name`.
+    pub fn compact_cf(&self, cf_name: &str) -> Result<()> {
+        let cf_handle = self.get_cf_handle(cf_name)?;
+        self.db
+            .compact_range_cf(cf_handle, None::<&[u8]>, None::<&[u8]>);
+        Ok(())
+    }
+
+    /// Create a RocksDB checkpoint of the db in `checkpoint_dir`, the dir should not exist.
+    /// The checkpoint is a consistent snapshot of all column families.
+    pub fn create_checkpoint(&self, checkpoint_dir: &Path) -> Result<()> {
+        Checkpoint::new(&self.db)?.create_checkpoint(checkpoint_dir)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        self.db.path()
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Writes the batches of different column families atomically.
+    pub fn write_batches(&self, batches: Vec<(ColumnFamilyName, WriteBatch)>) -> Result<()> {
+        record_metrics("db", "batch", "multi_cf").end_with(|| {
+            let mut db_batch = DBWriteBatch::default();
+            for (prefix_name, batch) in &batches {
+                let cf_handle = self.get_cf_handle(prefix_name)?;
+                for (key, write_op) in &batch.rows {
+                    match write_op {
+                        WriteOp::Value(value) => db_batch.put_cf(cf_handle, key, value),
+                        WriteOp::Deletion => db_batch.delete_cf(cf_handle, key),
+                    };
+                }
+            }
+            self.db
+                .write_opt(db_batch, &Self::default_write_options())?;
+            Ok(())
+        })
+    }
+
+    pub fn column_families(&self) -> &[ColumnFamilyName] {
+        &self.cfs
+    }
+
+    /// Get the int property `property` of every column family, see `ROCKSDB_PROPERTIES`.
+    pub fn property_values(&self, property: &str) -> Result<Vec<(ColumnFamilyName, u64)>> {
+        let mut values = Vec::with_capacity(self.cfs.len());
+        for cf_name in &self.cfs {
+            let cf_handle = self.get_cf_handle(cf_name)?;
+            if let Some(value) = self.db.property_int_value_cf(cf_handle, property)? {
+                values.push((*cf_name, value));
+            }
+        }
+        Ok(values)
+    }
+
+    /// Get the tickers of rocksdb internal statistics, return empty if statistics is not enabled.
+    pub fn statistics_tickers(&self) -> Vec<(String, u64)> {
+        // The ticker line format is `rocksdb.block.cache.miss COUNT : 42`,
+        // the histogram lines are ignored.
+        self.db_opts
+            .get_statistics()
+            .map(|statistics| {
+                statistics
+                    .lines()
+                    .filter_map(|line| {
+                        let mut parts = line.split(" COUNT : ");
+                        let name = parts.next()?;
+                        let value = parts.next()?.trim().parse::<u64>().ok()?;
+                        Some((name.to_string(), value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// List cf
     pub fn list_cf(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
         Ok(rocksdb::DB::list_cf(&rocksdb::Options::default(), path)?)
@@ -166,6 +317,9 @@ impl DBStorage {
         let mut db_opts = Options::default();
         db_opts.set_max_open_files(config.max_open_files);
         db_opts.set_max_total_wal_size(config.max_total_wal_size);
+        if config.enable_statistics {
+            db_opts.enable_statistics();
+        }
         db_opts
     }
     fn iter_with_direction(
@@ -174,9 +328,11 @@ impl DBStorage {
         direction: ScanDirection,
     ) -> Result<SchemaIterator> {
         let cf_handle = self.get_cf_handle(prefix_name)?;
+        // Iterate the whole column family, even if it has a prefix extractor.
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_total_order_seek(true);
         Ok(SchemaIterator::new(
-            self.db
-                .raw_iterator_cf_opt(cf_handle, ReadOptions::default()),
+            self.db.raw_iterator_cf_opt(cf_handle, read_opts),
             direction,
         ))
     }
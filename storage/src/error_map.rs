@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::define_storage;
+use crate::storage::{CodecKVStore, KeyCodec, ValueCodec};
+use crate::{ErrorMapStore, ERROR_MAP_PREFIX_NAME};
+use anyhow::Result;
+use bcs_ext::BCSCodec;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_vm_types::errmap::ErrorDescription;
+use std::collections::BTreeMap;
+
+/// Error descriptions of a module, keyed by the reason code.
+/// The error maps are registered to the local node only, they are not a part of the chain data.
+pub type ModuleErrorMap = BTreeMap<u64, ErrorDescription>;
+
+define_storage!(
+    ErrorMapStorage,
+    ModuleId,
+    ModuleErrorMap,
+    ERROR_MAP_PREFIX_NAME
+);
+
+impl KeyCodec for ModuleId {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+impl ValueCodec for ModuleErrorMap {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+impl ErrorMapStore for ErrorMapStorage {
+    fn save_module_error_map(&self, module_id: ModuleId, error_map: ModuleErrorMap) -> Result<()> {
+        self.put(module_id, error_map)
+    }
+
+    fn get_module_error_map(&self, module_id: &ModuleId) -> Result<Option<ModuleErrorMap>> {
+        self.get(module_id.clone())
+    }
+}
@@ -8,10 +8,15 @@ use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStorage, BlockInfoStore};
 use crate::chain_info::ChainInfoStorage;
 use crate::contract_event::ContractEventStorage;
+use crate::db_storage::DBStorage;
+use crate::error_map::{ErrorMapStorage, ModuleErrorMap};
 use crate::state_node::StateStorage;
 use crate::storage::{CodecKVStore, CodecWriteBatch, ColumnFamilyName, StorageInstance};
 use crate::transaction::TransactionStorage;
-use crate::transaction_info::{TransactionInfoHashStorage, TransactionInfoStorage};
+use crate::transaction_info::{
+    TransactionInfoExtStorage, TransactionInfoHashStorage, TransactionInfoStorage,
+};
+use crate::write_set::WriteSetStorage;
 use anyhow::{bail, format_err, Error, Result};
 use crypto::HashValue;
 use once_cell::sync::Lazy;
@@ -19,9 +24,11 @@ use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorTreeStore;
 use starcoin_state_store_api::{StateNode, StateNodeStore};
 use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::language_storage::ModuleId;
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
-use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+use starcoin_types::transaction::{AbortContext, BlockTransactionInfo, Transaction};
+use starcoin_types::write_set::WriteSet;
 use starcoin_types::{
     block::{Block, BlockBody, BlockHeader, BlockInfo},
     startup_info::StartupInfo,
@@ -31,21 +38,26 @@ use std::fmt::{Debug, Display, Formatter};
 use std::sync::Arc;
 
 pub mod accumulator;
+pub mod backup;
 pub mod batch;
 pub mod block;
 pub mod block_info;
 pub mod cache_storage;
 pub mod chain_info;
 pub mod contract_event;
+pub mod db_check;
 pub mod db_storage;
+pub mod error_map;
 pub mod errors;
-mod metrics;
+pub mod metrics;
+pub mod migration;
 pub mod state_node;
 pub mod storage;
 #[cfg(test)]
 mod tests;
 pub mod transaction;
 pub mod transaction_info;
+pub mod write_set;
 
 #[macro_use]
 pub mod storage_macros;
@@ -63,8 +75,11 @@ pub const CHAIN_INFO_PREFIX_NAME: ColumnFamilyName = "chain_info";
 pub const TRANSACTION_PREFIX_NAME: ColumnFamilyName = "transaction";
 pub const TRANSACTION_INFO_PREFIX_NAME: ColumnFamilyName = "transaction_info";
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
+pub const TRANSACTION_INFO_EXT_PREFIX_NAME: ColumnFamilyName = "transaction_info_ext";
+pub const WRITE_SET_PREFIX_NAME: ColumnFamilyName = "write_set";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
 pub const FAILED_BLOCK_PREFIX_NAME: ColumnFamilyName = "failed_block";
+pub const ERROR_MAP_PREFIX_NAME: ColumnFamilyName = "error_map";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -85,6 +100,9 @@ pub static VEC_PREFIX_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TRANSACTION_INFO_HASH_PREFIX_NAME,
         CONTRACT_EVENT_PREFIX_NAME,
         FAILED_BLOCK_PREFIX_NAME,
+        ERROR_MAP_PREFIX_NAME,
+        TRANSACTION_INFO_EXT_PREFIX_NAME,
+        WRITE_SET_PREFIX_NAME,
     ]
 });
 
@@ -141,6 +159,8 @@ pub trait BlockStore {
         &self,
         block_id: HashValue,
     ) -> Result<Option<(Block, Option<PeerId>, String)>>;
+
+    fn delete_failed_block(&self, block_id: HashValue) -> Result<()>;
 }
 
 pub trait BlockTransactionInfoStore {
@@ -153,6 +173,15 @@ pub trait BlockTransactionInfoStore {
     /// if not transaction info match with the `txn_hash`, return empty Vec.
     fn get_transaction_info_ids_by_hash(&self, txn_hash: HashValue) -> Result<Vec<HashValue>>;
     fn save_transaction_infos(&self, vec_txn_info: Vec<BlockTransactionInfo>) -> Result<()>;
+    /// Save the abort contexts of the failed txns, keyed by the txn info id.
+    fn save_transaction_abort_contexts(
+        &self,
+        abort_contexts: Vec<(HashValue, AbortContext)>,
+    ) -> Result<()>;
+    /// Get the abort context by `txn_info_id`, `None` if the txn is executed, or the context is
+    /// not recorded, e.g. the block is executed by an old version node.
+    fn get_transaction_abort_context(&self, txn_info_id: HashValue)
+        -> Result<Option<AbortContext>>;
 }
 pub trait ContractEventStore {
     /// Save events by key `txn_info_id`.
@@ -169,6 +198,27 @@ pub trait ContractEventStore {
     fn get_contract_events(&self, txn_info_id: HashValue) -> Result<Option<Vec<ContractEvent>>>;
 }
 
+/// Write sets of the txns, the state changes can be queried without diffing the state trees.
+pub trait WriteSetStore {
+    /// Whether the write sets are saved, the executor only keeps them if so.
+    fn write_set_enabled(&self) -> bool;
+
+    /// Save the write sets by key `txn_info_id`, it does nothing if the write set storage is
+    /// disabled.
+    fn save_transaction_write_sets(&self, write_sets: Vec<(HashValue, WriteSet)>) -> Result<()>;
+
+    /// Get the write set by `txn_info_id`, `None` if the write set storage is disabled when the
+    /// txn is executed.
+    fn get_transaction_write_set(&self, txn_info_id: HashValue) -> Result<Option<WriteSet>>;
+}
+
+/// Error maps of the modules out of stdlib, used to explain their abort codes.
+pub trait ErrorMapStore {
+    fn save_module_error_map(&self, module_id: ModuleId, error_map: ModuleErrorMap) -> Result<()>;
+
+    fn get_module_error_map(&self, module_id: &ModuleId) -> Result<Option<ModuleErrorMap>>;
+}
+
 pub trait TransactionStore {
     fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<Transaction>>;
     fn save_transaction(&self, txn_info: Transaction) -> Result<()>;
@@ -180,6 +230,7 @@ pub trait TransactionStore {
 pub struct Storage {
     transaction_info_storage: TransactionInfoStorage,
     transaction_info_hash_storage: TransactionInfoHashStorage,
+    transaction_info_ext_storage: TransactionInfoExtStorage,
     transaction_storage: TransactionStorage,
     block_storage: BlockStorage,
     state_node_storage: StateStorage,
@@ -188,13 +239,28 @@ pub struct Storage {
     block_info_storage: BlockInfoStorage,
     event_storage: ContractEventStorage,
     chain_info_storage: ChainInfoStorage,
+    error_map_storage: ErrorMapStorage,
+    write_set_storage: WriteSetStorage,
+    write_set_enabled: bool,
+    abort_context_enabled: bool,
+    db: Option<Arc<DBStorage>>,
 }
 
 impl Storage {
+    /// Create the storage, and run the migrations if the db schema version is old.
     pub fn new(instance: StorageInstance) -> Result<Self> {
+        let db = instance.db();
+        if let Some(db) = db.as_ref() {
+            migration::migrate(
+                db.as_ref(),
+                migration::MIGRATIONS.as_slice(),
+                migration::STORAGE_VERSION,
+            )?;
+        }
         Ok(Self {
             transaction_info_storage: TransactionInfoStorage::new(instance.clone()),
             transaction_info_hash_storage: TransactionInfoHashStorage::new(instance.clone()),
+            transaction_info_ext_storage: TransactionInfoExtStorage::new(instance.clone()),
             transaction_storage: TransactionStorage::new(instance.clone()),
             block_storage: BlockStorage::new(instance.clone()),
             state_node_storage: StateStorage::new(instance.clone()),
@@ -205,10 +271,32 @@ impl Storage {
                 AccumulatorStorage::new_transaction_accumulator_storage(instance.clone()),
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
-            chain_info_storage: ChainInfoStorage::new(instance),
+            chain_info_storage: ChainInfoStorage::new(instance.clone()),
+            error_map_storage: ErrorMapStorage::new(instance.clone()),
+            write_set_storage: WriteSetStorage::new(instance),
+            write_set_enabled: false,
+            abort_context_enabled: true,
+            db,
         })
     }
 
+    /// The underlying rocksdb storage, None if the storage is cache only.
+    pub fn db(&self) -> Option<Arc<DBStorage>> {
+        self.db.clone()
+    }
+
+    /// Save the write sets of the executed txns or not, default is not.
+    pub fn with_write_set(mut self, enable: bool) -> Self {
+        self.write_set_enabled = enable;
+        self
+    }
+
+    /// Save the abort contexts of the failed txns or not, default is true.
+    pub fn with_abort_context(mut self, enable: bool) -> Self {
+        self.abort_context_enabled = enable;
+        self
+    }
+
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
         self.block_accumulator_storage.clone()
     }
@@ -356,6 +444,10 @@ impl BlockStore for Storage {
     ) -> Result<Option<(Block, Option<PeerId>, String)>> {
         self.block_storage.get_failed_block_by_id(block_id)
     }
+
+    fn delete_failed_block(&self, block_id: HashValue) -> Result<()> {
+        self.block_storage.delete_failed_block(block_id)
+    }
 }
 
 impl BlockInfoStore for Storage {
@@ -405,6 +497,24 @@ impl BlockTransactionInfoStore for Storage {
         self.transaction_info_storage
             .save_transaction_infos(vec_txn_info)
     }
+
+    fn save_transaction_abort_contexts(
+        &self,
+        abort_contexts: Vec<(HashValue, AbortContext)>,
+    ) -> Result<(), Error> {
+        if !self.abort_context_enabled {
+            return Ok(());
+        }
+        self.transaction_info_ext_storage
+            .save_abort_contexts(abort_contexts)
+    }
+
+    fn get_transaction_abort_context(
+        &self,
+        txn_info_id: HashValue,
+    ) -> Result<Option<AbortContext>, Error> {
+        self.transaction_info_ext_storage.get(txn_info_id)
+    }
 }
 
 impl ContractEventStore for Storage {
@@ -424,6 +534,34 @@ impl ContractEventStore for Storage {
     }
 }
 
+impl WriteSetStore for Storage {
+    fn write_set_enabled(&self) -> bool {
+        self.write_set_enabled
+    }
+
+    fn save_transaction_write_sets(&self, write_sets: Vec<(HashValue, WriteSet)>) -> Result<()> {
+        if !self.write_set_enabled {
+            return Ok(());
+        }
+        self.write_set_storage.save_write_sets(write_sets)
+    }
+
+    fn get_transaction_write_set(&self, txn_info_id: HashValue) -> Result<Option<WriteSet>> {
+        self.write_set_storage.get(txn_info_id)
+    }
+}
+
+impl ErrorMapStore for Storage {
+    fn save_module_error_map(&self, module_id: ModuleId, error_map: ModuleErrorMap) -> Result<()> {
+        self.error_map_storage
+            .save_module_error_map(module_id, error_map)
+    }
+
+    fn get_module_error_map(&self, module_id: &ModuleId) -> Result<Option<ModuleErrorMap>> {
+        self.error_map_storage.get_module_error_map(module_id)
+    }
+}
+
 impl TransactionStore for Storage {
     fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<Transaction>, Error> {
         self.transaction_storage.get(txn_hash)
@@ -446,6 +584,8 @@ pub trait Store:
     + TransactionStore
     + BlockTransactionInfoStore
     + ContractEventStore
+    + ErrorMapStore
+    + WriteSetStore
     + IntoSuper<dyn StateNodeStore>
 {
     fn get_transaction_info_by_block_and_index(
@@ -1,11 +1,15 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
+use crate::db_storage::{DBStorage, ROCKSDB_PROPERTIES};
 use anyhow::Result;
+use logger::prelude::*;
 use once_cell::sync::Lazy;
+use starcoin_metrics::prometheus_export::{Collector, Desc, MetricFamily};
 use starcoin_metrics::{
     self, register_histogram_vec, register_int_counter_vec, register_uint_gauge, HistogramTimer,
-    HistogramVec, IntCounterVec, UIntGauge,
+    HistogramVec, IntCounterVec, Opts, UIntGauge, UIntGaugeVec,
 };
+use std::sync::{Arc, Weak};
 
 pub static STORAGE_COUNTERS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -146,3 +150,77 @@ pub fn record_metrics<'a>(
 ) -> MetricsRecord<'a> {
     MetricsRecord::new(storage_type, key_type, method)
 }
+
+/// Export the rocksdb column family properties and internal statistics to metrics,
+/// the values are read from rocksdb when the metrics are collected.
+pub struct RocksdbCollector {
+    db: Weak<DBStorage>,
+    properties: UIntGaugeVec,
+    tickers: UIntGaugeVec,
+}
+
+impl RocksdbCollector {
+    pub fn new(db: &Arc<DBStorage>) -> Result<Self> {
+        let properties = UIntGaugeVec::new(
+            Opts::new(
+                "starcoin_rocksdb_property",
+                "Int properties of rocksdb column family",
+            ),
+            &["cf_name", "property"],
+        )?;
+        let tickers = UIntGaugeVec::new(
+            Opts::new(
+                "starcoin_rocksdb_ticker",
+                "Tickers of rocksdb internal statistics",
+            ),
+            &["ticker"],
+        )?;
+        Ok(Self {
+            db: Arc::downgrade(db),
+            properties,
+            tickers,
+        })
+    }
+
+    /// Register the collector to the default metrics registry.
+    pub fn register(db: &Arc<DBStorage>) -> Result<()> {
+        starcoin_metrics::prometheus_export::register(Box::new(Self::new(db)?))?;
+        Ok(())
+    }
+}
+
+impl Collector for RocksdbCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        let mut descs = self.properties.desc();
+        descs.extend(self.tickers.desc());
+        descs
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        // The db has been dropped.
+        let db = match self.db.upgrade() {
+            Some(db) => db,
+            None => return vec![],
+        };
+        for property in ROCKSDB_PROPERTIES {
+            match db.property_values(property) {
+                Ok(values) => {
+                    for (cf_name, value) in values {
+                        self.properties
+                            .with_label_values(&[cf_name, property])
+                            .set(value);
+                    }
+                }
+                Err(e) => warn!("Get rocksdb property {} error: {:?}", property, e),
+            }
+        }
+        for (ticker, value) in db.statistics_tickers() {
+            self.tickers
+                .with_label_values(&[ticker.as_str()])
+                .set(value);
+        }
+        let mut mfs = self.properties.collect();
+        mfs.extend(self.tickers.collect());
+        mfs
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage schema version and migrations.
+//! The schema version is saved in the `default` column family, the migrations whose version is
+//! greater than the db version are run in order when the storage is opened.
+//! A migration is run batch by batch, the writes of a batch and the progress are saved atomically,
+//! so a migration interrupted by a crash resumes from the last saved batch at the next startup.
+
+use crate::batch::WriteBatch;
+use crate::db_storage::DBStorage;
+use crate::storage::{ColumnFamilyName, InnerStore, ValueCodec};
+use crate::{DEFAULT_PREFIX_NAME, TRANSACTION_INFO_HASH_PREFIX_NAME};
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use crypto::HashValue;
+use logger::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// The schema version of the storage in code.
+/// Version 0 is the db created before the schema version is introduced, which has the same
+/// layout as version 1.
+/// Version 2 removes the duplicated ids in the transaction info hash index.
+pub const STORAGE_VERSION: u64 = 2;
+
+const STORAGE_VERSION_KEY: &str = "storage_version";
+const MIGRATION_PROGRESS_KEY: &str = "migration_progress";
+
+/// The migrations ordered by version, a migration must be added here when the layout or the
+/// codec of a column family is changed, and the `STORAGE_VERSION` bumped to its version.
+pub static MIGRATIONS: Lazy<Vec<Box<dyn Migration>>> =
+    Lazy::new(|| vec![Box::new(DedupTransactionInfoHashMigration) as Box<dyn Migration>]);
+
+const DEDUP_TRANSACTION_INFO_HASH_BATCH_SIZE: usize = 10000;
+
+/// The output of a migration batch.
+#[derive(Debug, Default)]
+pub struct MigrationBatch {
+    /// The writes of the batch, grouped by column family.
+    pub writes: Vec<(ColumnFamilyName, WriteBatch)>,
+    /// The count of the records migrated by the batch.
+    pub migrated: u64,
+    /// The cursor of the next batch, `None` if the migration is finished.
+    pub next_cursor: Option<Vec<u8>>,
+}
+
+pub trait Migration: Send + Sync {
+    /// The schema version after the migration.
+    fn version(&self) -> u64;
+
+    fn name(&self) -> &str;
+
+    /// Migrate the next batch of records from the `cursor`, `None` for the first batch.
+    /// The migration should not write to db directly, the returned writes are saved together
+    /// with the progress.
+    fn migrate_batch(&self, db: &DBStorage, cursor: Option<Vec<u8>>) -> Result<MigrationBatch>;
+}
+
+/// The transaction info hash index pushed the id of a transaction info again when the info was
+/// saved twice, e.g. by a repair of the db check, remove the duplicated ids and keep the order.
+pub struct DedupTransactionInfoHashMigration;
+
+impl Migration for DedupTransactionInfoHashMigration {
+    fn version(&self) -> u64 {
+        2
+    }
+
+    fn name(&self) -> &str {
+        "dedup_transaction_info_hash"
+    }
+
+    fn migrate_batch(&self, db: &DBStorage, cursor: Option<Vec<u8>>) -> Result<MigrationBatch> {
+        let mut iter = db.iter(TRANSACTION_INFO_HASH_PREFIX_NAME)?;
+        match cursor {
+            Some(cursor) => iter.seek(cursor)?,
+            None => iter.seek_to_first(),
+        }
+        let mut batch = WriteBatch::new();
+        let mut scanned = 0;
+        let mut migrated = 0;
+        let mut next_cursor = None;
+        for item in iter {
+            let (key, value) = item?;
+            if scanned == DEDUP_TRANSACTION_INFO_HASH_BATCH_SIZE {
+                next_cursor = Some(key);
+                break;
+            }
+            scanned += 1;
+            let ids = Vec::<HashValue>::decode_value(value.as_slice())?;
+            let mut deduped = Vec::with_capacity(ids.len());
+            for id in ids.iter() {
+                if !deduped.contains(id) {
+                    deduped.push(*id);
+                }
+            }
+            if deduped.len() != ids.len() {
+                batch.put(key, deduped.encode_value()?)?;
+                migrated += 1;
+            }
+        }
+        Ok(MigrationBatch {
+            writes: vec![(TRANSACTION_INFO_HASH_PREFIX_NAME, batch)],
+            migrated,
+            next_cursor,
+        })
+    }
+}
+
+/// The progress of the running migration, saved after every batch.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MigrationProgress {
+    pub version: u64,
+    pub cursor: Option<Vec<u8>>,
+    pub migrated: u64,
+}
+
+/// Get the schema version of the db, `None` if the version is not saved.
+pub fn get_storage_version(db: &DBStorage) -> Result<Option<u64>> {
+    db.get(DEFAULT_PREFIX_NAME, STORAGE_VERSION_KEY.as_bytes().to_vec())?
+        .map(|bytes| u64::decode(bytes.as_slice()))
+        .transpose()
+}
+
+/// Get the progress of the interrupted migration.
+pub fn get_migration_progress(db: &DBStorage) -> Result<Option<MigrationProgress>> {
+    db.get(
+        DEFAULT_PREFIX_NAME,
+        MIGRATION_PROGRESS_KEY.as_bytes().to_vec(),
+    )?
+    .map(|bytes| MigrationProgress::decode(bytes.as_slice()))
+    .transpose()
+}
+
+/// A db is empty if all the column families except `default` are empty.
+fn is_empty_db(db: &DBStorage) -> Result<bool> {
+    for cf_name in db.column_families() {
+        if *cf_name == DEFAULT_PREFIX_NAME {
+            continue;
+        }
+        let mut iter = db.iter(cf_name)?;
+        iter.seek_to_first();
+        if iter.next().is_some() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Run the `migrations` to upgrade the db to `target_version`, return the version of the db.
+/// A new db is marked as `target_version` directly, a readonly db is not migrated.
+pub fn migrate(
+    db: &DBStorage,
+    migrations: &[Box<dyn Migration>],
+    target_version: u64,
+) -> Result<u64> {
+    let db_version = match get_storage_version(db)? {
+        Some(version) => version,
+        None if db.is_readonly() => 0,
+        None if is_empty_db(db)? => {
+            save_version(db, vec![], target_version)?;
+            info!("Init storage version {}", target_version);
+            return Ok(target_version);
+        }
+        None => 0,
+    };
+    ensure!(
+        db_version <= target_version,
+        "Storage version {} is newer than the version {} of the node, please upgrade the node.",
+        db_version,
+        target_version
+    );
+    if db_version == target_version {
+        return Ok(db_version);
+    }
+    if db.is_readonly() {
+        warn!(
+            "Storage version {} is older than {}, readonly storage is not migrated.",
+            db_version, target_version
+        );
+        return Ok(db_version);
+    }
+    let mut version = db_version;
+    for migration in migrations.iter().filter(|migration| {
+        migration.version() > db_version && migration.version() <= target_version
+    }) {
+        ensure!(
+            migration.version() > version,
+            "Migration {} version {} is not in order.",
+            migration.name(),
+            migration.version()
+        );
+        run_migration(db, migration.as_ref())?;
+        version = migration.version();
+    }
+    if version < target_version {
+        save_version(db, vec![], target_version)?;
+    }
+    info!(
+        "Storage is migrated from version {} to {}",
+        db_version, target_version
+    );
+    Ok(target_version)
+}
+
+fn run_migration(db: &DBStorage, migration: &dyn Migration) -> Result<()> {
+    let mut progress = match get_migration_progress(db)? {
+        Some(progress) if progress.version == migration.version() => {
+            info!(
+                "Resume migration {} to version {}, {} records migrated.",
+                migration.name(),
+                progress.version,
+                progress.migrated
+            );
+            progress
+        }
+        Some(progress) => {
+            return Err(format_err!(
+                "Unexpect migration progress of version {}, expect version {}.",
+                progress.version,
+                migration.version()
+            ))
+        }
+        None => {
+            info!(
+                "Start migration {} to version {}.",
+                migration.name(),
+                migration.version()
+            );
+            MigrationProgress {
+                version: migration.version(),
+                cursor: None,
+                migrated: 0,
+            }
+        }
+    };
+    loop {
+        let batch = migration.migrate_batch(db, progress.cursor.clone())?;
+        progress.migrated += batch.migrated;
+        match batch.next_cursor {
+            Some(cursor) => {
+                progress.cursor = Some(cursor);
+                let mut writes = batch.writes;
+                let mut progress_batch = WriteBatch::new();
+                progress_batch.put(
+                    MIGRATION_PROGRESS_KEY.as_bytes().to_vec(),
+                    progress.encode()?,
+                )?;
+                writes.push((DEFAULT_PREFIX_NAME, progress_batch));
+                db.write_batches(writes)?;
+                info!(
+                    "Migration {} to version {}, {} records migrated.",
+                    migration.name(),
+                    progress.version,
+                    progress.migrated
+                );
+            }
+            None => {
+                save_version(db, batch.writes, migration.version())?;
+                info!(
+                    "Migration {} to version {} finished, {} records migrated.",
+                    migration.name(),
+                    progress.version,
+                    progress.migrated
+                );
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Save the version with the `writes`, and remove the migration progress.
+fn save_version(
+    db: &DBStorage,
+    mut writes: Vec<(ColumnFamilyName, WriteBatch)>,
+    version: u64,
+) -> Result<()> {
+    let mut batch = WriteBatch::new();
+    batch.put(STORAGE_VERSION_KEY.as_bytes().to_vec(), version.encode()?)?;
+    batch.delete(MIGRATION_PROGRESS_KEY.as_bytes().to_vec())?;
+    writes.push((DEFAULT_PREFIX_NAME, batch));
+    db.write_batches(writes)
+}
@@ -0,0 +1,109 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::backup::{
+    self, BackupManifest, ACCOUNT_DB_BACKUP_DIR, BACKUP_MANIFEST_FILE, RESTORE_MANIFEST_FILE,
+};
+use crate::block_info::BlockInfoStore;
+use crate::cache_storage::CacheStorage;
+use crate::db_storage::DBStorage;
+use crate::storage::{InnerStore, StorageInstance};
+use crate::{BlockStore, Storage, DEFAULT_PREFIX_NAME, VEC_PREFIX_NAME};
+use anyhow::Result;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
+use starcoin_config::RocksdbConfig;
+use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockInfo};
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_uint::U256;
+
+fn open_storage(chain_db_dir: &std::path::Path) -> Result<Storage> {
+    Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::open_with_cfs(
+            chain_db_dir,
+            VEC_PREFIX_NAME.to_vec(),
+            false,
+            RocksdbConfig::default(),
+        )?,
+    ))
+}
+
+#[test]
+fn test_backup_and_restore() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let chain_db_dir = tmpdir.path().join("chain");
+    let storage = open_storage(chain_db_dir.as_path())?;
+    let header = BlockHeader::random();
+    let block_id = header.id();
+    storage.commit_block(Block::new(header.clone(), BlockBody::new_empty()))?;
+    storage.save_block_info(BlockInfo::new(
+        block_id,
+        U256::zero(),
+        AccumulatorInfo::default(),
+        AccumulatorInfo::default(),
+    ))?;
+    storage.save_startup_info(StartupInfo::new(block_id))?;
+
+    let account_db = DBStorage::open_with_cfs(
+        tmpdir.path().join("account"),
+        vec![DEFAULT_PREFIX_NAME],
+        false,
+        RocksdbConfig::default(),
+    )?;
+    account_db.put(DEFAULT_PREFIX_NAME, b"key".to_vec(), b"value".to_vec())?;
+
+    let backup_dir = tmpdir.path().join("backup");
+    let manifest = backup::backup(
+        storage.db().expect("db must exist").as_ref(),
+        Some(&account_db),
+        backup_dir.as_path(),
+    )?;
+    assert_eq!(manifest.head_block_hash, block_id);
+    assert!(manifest
+        .files
+        .iter()
+        .any(|file| file.path.starts_with(ACCOUNT_DB_BACKUP_DIR)));
+    assert_eq!(manifest.head_block_number, header.number());
+    assert_eq!(
+        BackupManifest::load(backup_dir.join(BACKUP_MANIFEST_FILE).as_path())?,
+        manifest
+    );
+    manifest.verify(backup_dir.as_path())?;
+    // backup to a not empty dir should fail.
+    assert!(backup::backup(storage.db().unwrap().as_ref(), None, backup_dir.as_path()).is_err());
+
+    let restore_dir = tmpdir.path().join("restore");
+    let account_restore_dir = tmpdir.path().join("account_restore");
+    backup::restore(
+        backup_dir.as_path(),
+        restore_dir.as_path(),
+        Some(account_restore_dir.as_path()),
+    )?;
+    let restored_account_db = DBStorage::open_with_cfs(
+        account_restore_dir.as_path(),
+        vec![DEFAULT_PREFIX_NAME],
+        true,
+        RocksdbConfig::default(),
+    )?;
+    assert_eq!(
+        restored_account_db.get(DEFAULT_PREFIX_NAME, b"key".to_vec())?,
+        Some(b"value".to_vec())
+    );
+    assert!(restore_dir.join(RESTORE_MANIFEST_FILE).exists());
+    let restored = open_storage(restore_dir.as_path())?;
+    backup::check_restored_storage(&restored)?;
+    assert!(!restore_dir.join(RESTORE_MANIFEST_FILE).exists());
+    assert_eq!(restored.get_block_header_by_hash(block_id)?, Some(header));
+
+    // a broken backup file should fail the verify.
+    let broken_file = manifest
+        .files
+        .iter()
+        .find(|file| file.path.ends_with("CURRENT"))
+        .expect("CURRENT file must exist");
+    std::fs::write(backup_dir.join(broken_file.path.as_str()), b"broken")?;
+    assert!(manifest.verify(backup_dir.as_path()).is_err());
+    let restore_dir2 = tmpdir.path().join("restore2");
+    assert!(backup::restore(backup_dir.as_path(), restore_dir2.as_path(), None).is_err());
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::db_check::{CheckOption, DBChecker};
+use crate::db_storage::DBStorage;
+use crate::storage::{InnerStore, StorageInstance};
+use crate::{BlockStore, Storage, BLOCK_HEADER_PREFIX_NAME, VEC_PREFIX_NAME};
+use anyhow::Result;
+use starcoin_config::RocksdbConfig;
+use starcoin_types::block::{Block, BlockBody, BlockHeaderBuilder};
+
+#[test]
+fn test_db_check_and_repair() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    // use db instance only, so the records removed from db are not read from cache.
+    let storage = Storage::new(StorageInstance::new_db_instance(DBStorage::open_with_cfs(
+        tmpdir.path(),
+        VEC_PREFIX_NAME.to_vec(),
+        false,
+        RocksdbConfig::default(),
+    )?))?;
+    let db = storage.db().expect("db must exist");
+    let body = BlockBody::new_empty();
+    let header = BlockHeaderBuilder::random()
+        .with_body_hash(body.hash())
+        .build();
+    let block_id = header.id();
+    storage.commit_block(Block::new(header, body))?;
+
+    let report = DBChecker::new(db.as_ref(), &storage, CheckOption::default()).check()?;
+    assert_eq!(report.blocks, 1);
+    assert_eq!(report.block_headers, 1);
+    // the startup info is missing, so the main chain is not checked.
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.head_block_hash.is_none());
+
+    db.remove(BLOCK_HEADER_PREFIX_NAME, block_id.to_vec())?;
+    let report = DBChecker::new(db.as_ref(), &storage, CheckOption::default()).check()?;
+    assert_eq!(report.block_headers, 0);
+    assert!(report
+        .issues
+        .iter()
+        .any(|issue| issue.cf_name == BLOCK_HEADER_PREFIX_NAME && !issue.repaired));
+
+    let report = DBChecker::new(
+        db.as_ref(),
+        &storage,
+        CheckOption {
+            check_state: false,
+            repair: true,
+        },
+    )
+    .check()?;
+    assert!(report
+        .issues
+        .iter()
+        .any(|issue| issue.cf_name == BLOCK_HEADER_PREFIX_NAME && issue.repaired));
+    assert!(db
+        .get(BLOCK_HEADER_PREFIX_NAME, block_id.to_vec())?
+        .is_some());
+    Ok(())
+}
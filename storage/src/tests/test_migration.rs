@@ -0,0 +1,168 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::batch::WriteBatch;
+use crate::db_storage::DBStorage;
+use crate::migration::{
+    get_migration_progress, get_storage_version, migrate, DedupTransactionInfoHashMigration,
+    Migration, MigrationBatch, STORAGE_VERSION,
+};
+use crate::storage::{InnerStore, ValueCodec};
+use crate::{
+    TRANSACTION_INFO_HASH_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME, TRANSACTION_PREFIX_NAME,
+    VEC_PREFIX_NAME,
+};
+use anyhow::{bail, Result};
+use crypto::HashValue;
+use starcoin_config::RocksdbConfig;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const BATCH_SIZE: usize = 3;
+
+/// Append a suffix to the values of the transaction column family, fail at the second batch once.
+struct AppendSuffixMigration {
+    fail_once: AtomicBool,
+}
+
+impl Migration for AppendSuffixMigration {
+    fn version(&self) -> u64 {
+        STORAGE_VERSION + 1
+    }
+
+    fn name(&self) -> &str {
+        "append_suffix"
+    }
+
+    fn migrate_batch(&self, db: &DBStorage, cursor: Option<Vec<u8>>) -> Result<MigrationBatch> {
+        if cursor.is_some() && self.fail_once.swap(false, Ordering::SeqCst) {
+            bail!("migration crashed");
+        }
+        let mut iter = db.iter(TRANSACTION_PREFIX_NAME)?;
+        match cursor {
+            Some(cursor) => iter.seek(cursor)?,
+            None => iter.seek_to_first(),
+        }
+        let mut batch = WriteBatch::new();
+        let mut migrated = 0;
+        let mut next_cursor = None;
+        for item in iter {
+            let (key, mut value) = item?;
+            if migrated == BATCH_SIZE {
+                next_cursor = Some(key);
+                break;
+            }
+            value.extend_from_slice(b"_v2");
+            batch.put(key, value)?;
+            migrated += 1;
+        }
+        Ok(MigrationBatch {
+            writes: vec![(TRANSACTION_PREFIX_NAME, batch)],
+            migrated: migrated as u64,
+            next_cursor,
+        })
+    }
+}
+
+fn open_db(path: &std::path::Path) -> Result<DBStorage> {
+    DBStorage::open_with_cfs(
+        path,
+        VEC_PREFIX_NAME.to_vec(),
+        false,
+        RocksdbConfig::default(),
+    )
+}
+
+#[test]
+fn test_migrate_new_and_legacy_db() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let db = open_db(tmpdir.path().join("new").as_path())?;
+    assert_eq!(get_storage_version(&db)?, None);
+    assert_eq!(migrate(&db, &[], STORAGE_VERSION)?, STORAGE_VERSION);
+    assert_eq!(get_storage_version(&db)?, Some(STORAGE_VERSION));
+    // the db is newer than the node.
+    assert!(migrate(&db, &[], STORAGE_VERSION - 1).is_err());
+
+    // a db with any record but no version is a legacy db.
+    let legacy_db = open_db(tmpdir.path().join("legacy").as_path())?;
+    legacy_db.put(
+        TRANSACTION_INFO_PREFIX_NAME,
+        b"txn_info".to_vec(),
+        b"txn_info".to_vec(),
+    )?;
+    let migrations: Vec<Box<dyn Migration>> = vec![Box::new(AppendSuffixMigration {
+        fail_once: AtomicBool::new(false),
+    })];
+    // the migration of a higher version is not run.
+    assert_eq!(
+        migrate(&legacy_db, migrations.as_slice(), STORAGE_VERSION)?,
+        STORAGE_VERSION
+    );
+    assert_eq!(get_storage_version(&legacy_db)?, Some(STORAGE_VERSION));
+    Ok(())
+}
+
+#[test]
+fn test_migration_resume() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let db = open_db(tmpdir.path())?;
+    migrate(&db, &[], STORAGE_VERSION)?;
+    for i in 0..10u8 {
+        db.put(TRANSACTION_PREFIX_NAME, vec![i], vec![i])?;
+    }
+    let migrations: Vec<Box<dyn Migration>> = vec![Box::new(AppendSuffixMigration {
+        fail_once: AtomicBool::new(true),
+    })];
+    let target_version = STORAGE_VERSION + 1;
+    assert!(migrate(&db, migrations.as_slice(), target_version).is_err());
+    assert_eq!(get_storage_version(&db)?, Some(STORAGE_VERSION));
+    let progress = get_migration_progress(&db)?.expect("progress must exist");
+    assert_eq!(progress.version, target_version);
+    assert_eq!(progress.migrated, BATCH_SIZE as u64);
+    assert_eq!(progress.cursor, Some(vec![BATCH_SIZE as u8]));
+
+    assert_eq!(
+        migrate(&db, migrations.as_slice(), target_version)?,
+        target_version
+    );
+    assert_eq!(get_storage_version(&db)?, Some(target_version));
+    assert_eq!(get_migration_progress(&db)?, None);
+    for i in 0..10u8 {
+        let mut expect = vec![i];
+        expect.extend_from_slice(b"_v2");
+        assert_eq!(db.get(TRANSACTION_PREFIX_NAME, vec![i])?, Some(expect));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_dedup_transaction_info_hash_migration() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let db = open_db(tmpdir.path())?;
+    let id1 = HashValue::random();
+    let id2 = HashValue::random();
+    let duplicated_key = HashValue::random();
+    let key = HashValue::random();
+    db.put(
+        TRANSACTION_INFO_HASH_PREFIX_NAME,
+        duplicated_key.to_vec(),
+        vec![id1, id2, id1, id2].encode_value()?,
+    )?;
+    db.put(
+        TRANSACTION_INFO_HASH_PREFIX_NAME,
+        key.to_vec(),
+        vec![id2, id1].encode_value()?,
+    )?;
+    let migrations: Vec<Box<dyn Migration>> = vec![Box::new(DedupTransactionInfoHashMigration)];
+    // the legacy db is migrated from version 0.
+    assert_eq!(migrate(&db, migrations.as_slice(), 2)?, 2);
+    assert_eq!(get_storage_version(&db)?, Some(2));
+    let get_ids = |key: HashValue| -> Result<Vec<HashValue>> {
+        let value = db
+            .get(TRANSACTION_INFO_HASH_PREFIX_NAME, key.to_vec())?
+            .expect("ids must exist");
+        Vec::<HashValue>::decode_value(value.as_slice())
+    };
+    assert_eq!(get_ids(duplicated_key)?, vec![id1, id2]);
+    assert_eq!(get_ids(key)?, vec![id2, id1]);
+    Ok(())
+}
@@ -5,16 +5,25 @@ extern crate chrono;
 
 use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
+use crate::error_map::ModuleErrorMap;
 use crate::storage::{CodecKVStore, InnerStore, StorageInstance, ValueCodec, CACHE_NONE_OBJECT};
 use crate::{
-    BlockTransactionInfoStore, Storage, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
-    VEC_PREFIX_NAME,
+    BlockTransactionInfoStore, ErrorMapStore, Storage, WriteSetStore, DEFAULT_PREFIX_NAME,
+    STATE_NODE_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME, VEC_PREFIX_NAME,
 };
 use anyhow::Result;
 use crypto::HashValue;
 use starcoin_config::RocksdbConfig;
-use starcoin_types::transaction::{BlockTransactionInfo, TransactionInfo};
-use starcoin_types::vm_error::KeptVMStatus;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::identifier::Identifier;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_types::transaction::{
+    AbortContext, BlockTransactionInfo, ErrorOffset, TransactionInfo,
+};
+use starcoin_types::vm_error::{AbortLocation, KeptVMStatus, StatusCode};
+use starcoin_types::write_set::{WriteOp, WriteSetMut};
+use starcoin_vm_types::errmap::ErrorDescription;
 
 #[test]
 fn test_reopen() {
@@ -61,6 +70,39 @@ fn test_open_read_only() {
     assert_eq!(result, Some(value.to_vec()));
 }
 
+#[test]
+fn test_compact_and_statistics() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let rocksdb_config = RocksdbConfig {
+        enable_statistics: true,
+        ..Default::default()
+    };
+    let db = DBStorage::new(tmpdir.path(), rocksdb_config)?;
+    let mut keys: Vec<_> = (0..100).map(|_| HashValue::random().to_vec()).collect();
+    for key in &keys {
+        db.put(STATE_NODE_PREFIX_NAME, key.clone(), key.clone())?;
+    }
+    db.flush_all()?;
+    db.compact_cf(STATE_NODE_PREFIX_NAME)?;
+    assert!(db.compact_cf("not_exist_cf").is_err());
+
+    // the prefix extractor of state_node should not break the iterator.
+    let mut iter = db.iter(STATE_NODE_PREFIX_NAME)?;
+    iter.seek_to_first();
+    let iter_keys = iter
+        .map(|item| item.map(|(k, _)| k))
+        .collect::<Result<Vec<_>>>()?;
+    keys.sort();
+    assert_eq!(keys, iter_keys);
+
+    let values = db.property_values("rocksdb.estimate-num-keys")?;
+    assert!(values
+        .iter()
+        .any(|(cf_name, value)| *cf_name == STATE_NODE_PREFIX_NAME && *value > 0));
+    assert!(!db.statistics_tickers().is_empty());
+    Ok(())
+}
+
 #[test]
 fn test_storage() {
     let tmpdir = starcoin_config::temp_path();
@@ -218,3 +260,107 @@ fn test_missing_key_handle() -> Result<()> {
     assert_eq!(contains, false);
     Ok(())
 }
+
+#[test]
+fn test_error_map_storage() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap(),
+    ))
+    .unwrap();
+    let module_id = ModuleId::new(AccountAddress::random(), Identifier::new("Test")?);
+    assert!(storage.get_module_error_map(&module_id)?.is_none());
+
+    let mut error_map = ModuleErrorMap::new();
+    error_map.insert(
+        1,
+        ErrorDescription {
+            code_name: "ETEST".to_string(),
+            code_description: "test error".to_string(),
+        },
+    );
+    storage.save_module_error_map(module_id.clone(), error_map)?;
+    let description = storage
+        .get_module_error_map(&module_id)?
+        .and_then(|error_map| error_map.get(&1).cloned())
+        .unwrap();
+    assert_eq!(description.code_name, "ETEST");
+    Ok(())
+}
+
+#[test]
+fn test_transaction_abort_context() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap(),
+    ))
+    .unwrap();
+    let txn_info_id = HashValue::random();
+    assert!(storage
+        .get_transaction_abort_context(txn_info_id)?
+        .is_none());
+
+    let location = AbortLocation::Module(ModuleId::new(
+        AccountAddress::random(),
+        Identifier::new("Test")?,
+    ));
+    let offset = ErrorOffset {
+        function: 1,
+        function_name: Some(Identifier::new("test")?),
+        code_offset: 5,
+    };
+    let abort_context = AbortContext {
+        status_code: StatusCode::ABORTED,
+        abort_code: Some(1),
+        location,
+        function: offset.function,
+        function_name: offset.function_name.clone(),
+        code_offset: offset.code_offset,
+        offsets: vec![offset],
+        message: None,
+    };
+    storage.save_transaction_abort_contexts(vec![(txn_info_id, abort_context.clone())])?;
+    assert_eq!(
+        storage.get_transaction_abort_context(txn_info_id)?,
+        Some(abort_context.clone())
+    );
+
+    // the abort contexts are not saved if disabled.
+    let storage = storage.with_abort_context(false);
+    let other_txn_info_id = HashValue::random();
+    storage.save_transaction_abort_contexts(vec![(other_txn_info_id, abort_context)])?;
+    assert!(storage
+        .get_transaction_abort_context(other_txn_info_id)?
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn test_transaction_write_set() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap(),
+    ))
+    .unwrap();
+    let write_set = WriteSetMut::new(vec![
+        (AccessPath::random_resource(), WriteOp::Value(vec![1, 2, 3])),
+        (AccessPath::random_code(), WriteOp::Deletion),
+    ])
+    .freeze()?;
+
+    // the write set storage is disabled by default.
+    let txn_info_id = HashValue::random();
+    storage.save_transaction_write_sets(vec![(txn_info_id, write_set.clone())])?;
+    assert!(storage.get_transaction_write_set(txn_info_id)?.is_none());
+
+    let storage = storage.with_write_set(true);
+    storage.save_transaction_write_sets(vec![(txn_info_id, write_set.clone())])?;
+    assert_eq!(
+        storage.get_transaction_write_set(txn_info_id)?,
+        Some(write_set)
+    );
+    Ok(())
+}
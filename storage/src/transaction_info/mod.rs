@@ -3,12 +3,15 @@
 
 use crate::define_storage;
 use crate::storage::{CodecKVStore, CodecWriteBatch, ValueCodec};
+use crate::TRANSACTION_INFO_EXT_PREFIX_NAME;
 use crate::TRANSACTION_INFO_HASH_PREFIX_NAME;
 use crate::TRANSACTION_INFO_PREFIX_NAME;
 use anyhow::{Error, Result};
 use bcs_ext::BCSCodec;
 use crypto::HashValue;
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::transaction::{AbortContext, BlockTransactionInfo};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 
 define_storage!(
     TransactionInfoStorage,
@@ -24,6 +27,13 @@ define_storage!(
     TRANSACTION_INFO_HASH_PREFIX_NAME
 );
 
+define_storage!(
+    TransactionInfoExtStorage,
+    HashValue,
+    AbortContext,
+    TRANSACTION_INFO_EXT_PREFIX_NAME
+);
+
 impl ValueCodec for BlockTransactionInfo {
     fn encode_value(&self) -> Result<Vec<u8>> {
         self.encode()
@@ -34,6 +44,16 @@ impl ValueCodec for BlockTransactionInfo {
     }
 }
 
+impl ValueCodec for AbortContext {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
 impl TransactionInfoHashStorage {
     pub(crate) fn get_transaction_info_ids_by_hash(
         &self,
@@ -50,15 +70,24 @@ impl TransactionInfoHashStorage {
         &self,
         vec_txn_info: Vec<BlockTransactionInfo>,
     ) -> Result<(), Error> {
-        let mut batch = CodecWriteBatch::new();
+        // the infos of a txn in the same batch are merged, and a saved id is not pushed again.
+        let mut id_vecs: HashMap<HashValue, Vec<HashValue>> = HashMap::new();
         for txn_info in vec_txn_info {
-            if let Ok(Some(mut id_vec)) = self.get(txn_info.transaction_hash()) {
+            let txn_hash = txn_info.transaction_hash();
+            let id_vec = match id_vecs.entry(txn_hash) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(self.get_transaction_info_ids_by_hash(txn_hash)?)
+                }
+            };
+            if !id_vec.contains(&txn_info.id()) {
                 id_vec.push(txn_info.id());
-                batch.put(txn_info.transaction_hash(), id_vec)?;
-            } else {
-                batch.put(txn_info.transaction_hash(), vec![txn_info.id()])?;
             }
         }
+        let mut batch = CodecWriteBatch::new();
+        for (txn_hash, id_vec) in id_vecs {
+            batch.put(txn_hash, id_vec)?;
+        }
         self.write_batch(batch)
     }
 }
@@ -80,3 +109,16 @@ impl TransactionInfoStorage {
         self.write_batch(batch)
     }
 }
+
+impl TransactionInfoExtStorage {
+    pub(crate) fn save_abort_contexts(
+        &self,
+        abort_contexts: Vec<(HashValue, AbortContext)>,
+    ) -> Result<(), Error> {
+        let mut batch = CodecWriteBatch::new();
+        for (txn_info_id, abort_context) in abort_contexts {
+            batch.put(txn_info_id, abort_context)?;
+        }
+        self.write_batch(batch)
+    }
+}
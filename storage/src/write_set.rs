@@ -0,0 +1,32 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::define_storage;
+use crate::storage::{CodecKVStore, CodecWriteBatch, ValueCodec};
+use crate::WRITE_SET_PREFIX_NAME;
+use anyhow::Result;
+use bcs_ext::BCSCodec;
+use crypto::HashValue;
+use starcoin_types::write_set::WriteSet;
+
+define_storage!(WriteSetStorage, HashValue, WriteSet, WRITE_SET_PREFIX_NAME);
+
+impl ValueCodec for WriteSet {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+impl WriteSetStorage {
+    pub(crate) fn save_write_sets(&self, write_sets: Vec<(HashValue, WriteSet)>) -> Result<()> {
+        let mut batch = CodecWriteBatch::new();
+        for (txn_info_id, write_set) in write_sets {
+            batch.put(txn_info_id, write_set)?;
+        }
+        self.write_batch(batch)
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block_connector::{ExecuteRequest, ResetRequest, WriteBlockChainService};
+use crate::block_connector::{
+    ConfirmReorgRequest, ExecuteRequest, ResetRequest, WriteBlockChainService,
+};
 use crate::sync::{CheckSyncEvent, SyncService};
 use crate::tasks::BlockConnectedEvent;
 use anyhow::{format_err, Result};
@@ -135,22 +137,24 @@ impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
                         }
                         e => {
                             warn!("BlockConnector fail: {:?}, peer_id:{:?}", e, peer_id);
-                            if let Err(err) = self
-                                .chain_service
-                                .get_main()
-                                .get_storage()
-                                .save_failed_block(
-                                    msg.get_block().id(),
-                                    msg.get_block().clone(),
-                                    Some(peer_id.clone()),
-                                    format!("{:?}", e),
-                                )
-                            {
-                                warn!(
-                                    "Save FailedBlock err: {:?}, block_id:{:?}.",
-                                    err,
-                                    msg.get_block().id()
-                                );
+                            if e.is_failed_block() {
+                                if let Err(err) = self
+                                    .chain_service
+                                    .get_main()
+                                    .get_storage()
+                                    .save_failed_block(
+                                        msg.get_block().id(),
+                                        msg.get_block().clone(),
+                                        Some(peer_id.clone()),
+                                        format!("{:?}", e),
+                                    )
+                                {
+                                    warn!(
+                                        "Save FailedBlock err: {:?}, block_id:{:?}.",
+                                        err,
+                                        msg.get_block().id()
+                                    );
+                                }
                             }
 
                             if let Err(e1) = ctx
@@ -178,6 +182,16 @@ impl ServiceHandler<Self, ResetRequest> for BlockConnectorService {
     }
 }
 
+impl ServiceHandler<Self, ConfirmReorgRequest> for BlockConnectorService {
+    fn handle(
+        &mut self,
+        msg: ConfirmReorgRequest,
+        _ctx: &mut ServiceContext<BlockConnectorService>,
+    ) -> Result<()> {
+        self.chain_service.confirm_reorg(msg.block_hash)
+    }
+}
+
 impl ServiceHandler<Self, ExecuteRequest> for BlockConnectorService {
     fn handle(
         &mut self,
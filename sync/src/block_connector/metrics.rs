@@ -19,6 +19,10 @@ pub struct ChainMetrics {
     pub exe_block_time: HistogramVec,
     pub rollback_block_size: IntGauge,
     pub current_head_number: IntGauge,
+    pub reorg_count: UIntCounterVec,
+    pub reorg_depth: HistogramVec,
+    pub reorg_retracted_txns: UIntCounterVec,
+    pub last_reorg_depth: IntGauge,
 }
 
 impl ChainMetrics {
@@ -55,11 +59,53 @@ impl ChainMetrics {
 
         default_registry().register(Box::new(block_connect_count.clone()))?;
 
+        let reorg_count = UIntCounterVec::new(
+            Opts::new(
+                format!("{}{}", PREFIX, "reorg_count"),
+                "chain reorg count, accepted or refused by max reorg depth".to_string(),
+            )
+            .namespace(SC_NS),
+            &["type"],
+        )?;
+
+        default_registry().register(Box::new(reorg_count.clone()))?;
+
+        let reorg_depth = register_histogram_vec!(
+            HistogramOpts::new(
+                format!("{}{}", PREFIX, "reorg_depth"),
+                "chain reorg depth".to_string()
+            )
+            .namespace(SC_NS)
+            .buckets(vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0, 256.0]),
+            &["type"]
+        )?;
+
+        let reorg_retracted_txns = UIntCounterVec::new(
+            Opts::new(
+                format!("{}{}", PREFIX, "reorg_retracted_txns"),
+                "user transaction count in the retracted blocks of chain reorg".to_string(),
+            )
+            .namespace(SC_NS),
+            &["type"],
+        )?;
+
+        default_registry().register(Box::new(reorg_retracted_txns.clone()))?;
+
+        let last_reorg_depth = register_int_gauge!(Opts::new(
+            format!("{}{}", PREFIX, "last_reorg_depth"),
+            "the depth of latest chain reorg".to_string()
+        )
+        .namespace(SC_NS))?;
+
         Ok(Self {
             exe_block_time,
             rollback_block_size,
             current_head_number,
             block_connect_count,
+            reorg_count,
+            reorg_depth,
+            reorg_retracted_txns,
+            last_reorg_depth,
         })
     }
 }
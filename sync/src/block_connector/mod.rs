@@ -32,6 +32,17 @@ impl ServiceRequest for ResetRequest {
     type Response = anyhow::Result<()>;
 }
 
+/// Switch the main chain to the branch of the block, to confirm a reorg which is refused by the
+/// max reorg depth limit.
+#[derive(Debug, Clone)]
+pub struct ConfirmReorgRequest {
+    pub block_hash: HashValue,
+}
+
+impl ServiceRequest for ConfirmReorgRequest {
+    type Response = anyhow::Result<()>;
+}
+
 #[derive(Debug, Clone)]
 pub struct ExecuteRequest {
     pub block: Block,
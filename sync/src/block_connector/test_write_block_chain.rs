@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(clippy::integer_arithmetic)]
 use crate::block_connector::WriteBlockChainService;
-use config::NodeConfig;
+use config::{NodeConfig, StarcoinOpt};
 use consensus::Consensus;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_chain_service::WriteableChainService;
+use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis as StarcoinGenesis;
 use starcoin_service_registry::bus::BusService;
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
@@ -16,6 +17,7 @@ use starcoin_types::block::Block;
 use starcoin_types::startup_info::StartupInfo;
 use starcoin_vm_types::time::TimeService;
 use std::sync::Arc;
+use structopt::StructOpt;
 
 pub async fn create_writeable_block_chain() -> (
     WriteBlockChainService<MockTxPoolService>,
@@ -23,8 +25,16 @@ pub async fn create_writeable_block_chain() -> (
     Arc<dyn Store>,
 ) {
     let node_config = NodeConfig::random_for_test();
-    let node_config = Arc::new(node_config);
+    create_writeable_block_chain_with_config(Arc::new(node_config)).await
+}
 
+pub async fn create_writeable_block_chain_with_config(
+    node_config: Arc<NodeConfig>,
+) -> (
+    WriteBlockChainService<MockTxPoolService>,
+    Arc<NodeConfig>,
+    Arc<dyn Store>,
+) {
     let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
         .expect("init storage by genesis fail.");
     let registry = RegistryService::launch();
@@ -59,6 +69,9 @@ pub fn gen_blocks(
             );
             writeable_block_chain_service.try_connect(block).unwrap();
         }
+        Some(parent_id)
+    } else {
+        None
     }
 }
 
@@ -106,7 +119,7 @@ fn gen_fork_block_chain(
     node_config: Arc<NodeConfig>,
     times: u64,
     writeable_block_chain_service: &mut WriteBlockChainService<MockTxPoolService>,
-) {
+) -> Option<HashValue> {
     let miner_account = AccountInfo::random();
     if let Some(block_header) = writeable_block_chain_service
         .get_main()
@@ -133,6 +146,9 @@ fn gen_fork_block_chain(
 
             writeable_block_chain_service.try_connect(block).unwrap();
         }
+        Some(parent_id)
+    } else {
+        None
     }
 }
 
@@ -240,3 +256,53 @@ async fn test_block_chain_reset() -> anyhow::Result<()> {
         .is_some());
     Ok(())
 }
+
+#[stest::test]
+async fn test_block_chain_reorg_depth_limit() -> anyhow::Result<()> {
+    let times = 10;
+    let opt =
+        StarcoinOpt::from_iter_safe(vec!["starcoin", "-n", "test", "--max-reorg-depth", "3"])?;
+    let node_config = Arc::new(NodeConfig::load_with_opt(&opt)?);
+    let (mut writeable_block_chain_service, node_config, _) =
+        create_writeable_block_chain_with_config(node_config).await;
+    let net = node_config.net();
+    gen_blocks(
+        times,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let main_head = writeable_block_chain_service
+        .get_main()
+        .current_header()
+        .id();
+
+    // the branch has more total difficulty, but need rollback 5 blocks.
+    let branch_head =
+        gen_fork_block_chain(5, node_config, times, &mut writeable_block_chain_service).unwrap();
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        main_head
+    );
+
+    // the refused branch is marked as failed, so the sync task does not sync to it again.
+    let storage = writeable_block_chain_service.get_main().get_storage();
+    assert!(storage.get_failed_block_by_id(branch_head)?.is_some());
+    // reset only works for the blocks in main chain.
+    assert!(writeable_block_chain_service.reset(branch_head).is_err());
+
+    // the operator confirm the reorg manually.
+    writeable_block_chain_service.confirm_reorg(branch_head)?;
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        branch_head
+    );
+    // the confirmed branch head is not failed any more.
+    assert!(storage.get_failed_block_by_id(branch_head)?.is_none());
+    Ok(())
+}
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::metrics::WRITE_BLOCK_CHAIN_METRICS;
-use anyhow::{format_err, Result};
-use config::NodeConfig;
+use anyhow::{ensure, format_err, Result};
+use config::{Checkpoints, NodeConfig};
 use logger::prelude::*;
+use starcoin_chain::verifier::CheckpointVerifier;
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, WriteableChainService};
 use starcoin_crypto::HashValue;
@@ -12,11 +13,11 @@ use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
 use starcoin_storage::Store;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::block::BlockInfo;
+use starcoin_types::block::{BlockIdAndNumber, BlockInfo};
 use starcoin_types::{
     block::{Block, BlockHeader, ExecutedBlock},
     startup_info::StartupInfo,
-    system_events::{NewBranch, NewHeadBlock},
+    system_events::{ChainReorgEvent, NewBranch, NewHeadBlock},
 };
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::sync::Arc;
@@ -33,6 +34,7 @@ where
     storage: Arc<dyn Store>,
     txpool: P,
     bus: ServiceRef<BusService>,
+    checkpoints: Checkpoints,
 }
 
 impl<P> WriteableChainService for WriteBlockChainService<P>
@@ -56,7 +58,9 @@ where
         bus: ServiceRef<BusService>,
     ) -> Result<Self> {
         let net = config.net();
-        let main = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
+        let checkpoints = config.sync.checkpoints()?;
+        let main = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?
+            .with_checkpoints(&checkpoints)?;
         Ok(Self {
             config,
             startup_info,
@@ -64,6 +68,7 @@ where
             storage,
             txpool,
             bus,
+            checkpoints,
         })
     }
 
@@ -81,26 +86,26 @@ where
             if self.is_main_head(&header.parent_hash()) {
                 None
             } else {
-                let net = self.config.net();
-                Some(BlockChain::new(
-                    net.time_service(),
-                    block_id,
-                    self.storage.clone(),
-                )?)
+                Some(self.new_chain(block_id)?)
             }
         } else if self.block_exist(header.parent_hash())? {
-            let net = self.config.net();
-            Some(BlockChain::new(
-                net.time_service(),
-                header.parent_hash(),
-                self.storage.clone(),
-            )?)
+            Some(self.new_chain(header.parent_hash())?)
         } else {
             None
         };
         Ok((block_info, block_chain))
     }
 
+    /// Create a chain with the `head_id`, which verifies blocks with the checkpoints.
+    fn new_chain(&self, head_id: HashValue) -> Result<BlockChain> {
+        BlockChain::new(
+            self.config.net().time_service(),
+            head_id,
+            self.storage.clone(),
+        )?
+        .with_checkpoints(&self.checkpoints)
+    }
+
     fn block_exist(&self, block_id: HashValue) -> Result<bool> {
         Ok(matches!(self.storage.get_block_info(block_id)?, Some(_)))
     }
@@ -116,21 +121,13 @@ where
         let parent_is_main_head = self.is_main_head(&executed_block.header().parent_hash());
 
         if branch_total_difficulty > main_total_difficulty {
-            let (enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
-                if !parent_is_main_head {
-                    self.find_ancestors_from_accumulator(&new_branch)?
-                } else {
-                    (1, vec![executed_block.block.clone()], 0, vec![])
-                };
-            self.main = new_branch;
-
-            self.do_new_head(
-                executed_block,
-                enacted_count,
-                enacted_blocks,
-                retracted_count,
-                retracted_blocks,
-            )?;
+            if parent_is_main_head {
+                self.main = new_branch;
+                let enacted_blocks = vec![executed_block.block.clone()];
+                self.do_new_head(executed_block, 1, enacted_blocks, 0, vec![])?;
+            } else {
+                self.switch_branch(new_branch, true)?;
+            }
         } else {
             //send new branch event
             self.broadcast_new_branch(executed_block);
@@ -138,6 +135,73 @@ where
         Ok(())
     }
 
+    /// Switch main chain to the `new_branch`, if `check_reorg_depth` is true and the reorg depth
+    /// exceeds the max reorg depth, the branch is refused.
+    fn switch_branch(&mut self, new_branch: BlockChain, check_reorg_depth: bool) -> Result<()> {
+        let executed_block = new_branch.head_block();
+        let (
+            ancestor,
+            enacted_count,
+            enacted_blocks,
+            retracted_count,
+            retracted_txns,
+            retracted_blocks,
+        ) = self.find_ancestors_from_accumulator(&new_branch)?;
+        let mut reorg_event = ChainReorgEvent {
+            old_head: self.main.current_header().id(),
+            old_head_number: self.main.current_header().number(),
+            new_head: executed_block.header().id(),
+            new_head_number: executed_block.header().number(),
+            ancestor: ancestor.id,
+            depth: retracted_count,
+            enacted_blocks: enacted_count,
+            retracted_txns,
+            refused: false,
+        };
+        if let Some(max_reorg_depth) = self.config.sync.max_reorg_depth() {
+            if check_reorg_depth && retracted_count > max_reorg_depth {
+                reorg_event.refused = true;
+                let reason = format!(
+                    "reorg depth {} exceeds the max reorg depth {}",
+                    retracted_count, max_reorg_depth
+                );
+                warn!("[chain] Refuse to switch to branch {}({}), {}, confirm the reorg manually if it is expected.", reorg_event.new_head, reorg_event.new_head_number, reason);
+                // mark the branch head as failed, so the sync task does not sync to the branch again.
+                self.storage.save_failed_block(
+                    executed_block.header().id(),
+                    executed_block.block().clone(),
+                    None,
+                    reason,
+                )?;
+                self.broadcast_reorg(reorg_event);
+                self.broadcast_new_branch(executed_block);
+                return Ok(());
+            }
+        }
+        self.main = new_branch;
+        self.do_new_head(
+            executed_block,
+            enacted_count,
+            enacted_blocks,
+            retracted_count,
+            retracted_blocks,
+        )?;
+        // the branch is the descendant of the main head, no block is retracted.
+        if reorg_event.depth > 0 {
+            info!(
+                "[chain] Chain reorg, old head: {}({}), new head: {}({}), depth: {}, retracted txns: {}",
+                reorg_event.old_head,
+                reorg_event.old_head_number,
+                reorg_event.new_head,
+                reorg_event.new_head_number,
+                reorg_event.depth,
+                reorg_event.retracted_txns
+            );
+            self.broadcast_reorg(reorg_event);
+        }
+        Ok(())
+    }
+
     fn do_new_head(
         &mut self,
         executed_block: ExecutedBlock,
@@ -168,17 +232,32 @@ where
         Ok(())
     }
 
+    /// Switch main chain to the executed branch of `block_id`, it is used to confirm a reorg
+    /// which is refused by the max reorg depth limit.
+    pub fn confirm_reorg(&mut self, block_id: HashValue) -> Result<()> {
+        ensure!(
+            self.main.get_block(block_id)?.is_none(),
+            "Block {} is in main chain already.",
+            block_id
+        );
+        ensure!(
+            self.block_exist(block_id)?,
+            "Can not find executed block {} in branches",
+            block_id
+        );
+        let new_branch = self.new_chain(block_id)?;
+        self.switch_branch(new_branch, false)?;
+        // the refused branch head is accepted now, so it is not a failed block any more.
+        self.storage.delete_failed_block(block_id)
+    }
+
     /// Reset the node to `block_id`, and replay blocks after the block
     pub fn reset(&mut self, block_id: HashValue) -> Result<()> {
         let new_head_block = self
             .main
             .get_block(block_id)?
             .ok_or_else(|| format_err!("Can not find block {} in main chain", block_id,))?;
-        let new_branch = BlockChain::new(
-            self.config.net().time_service(),
-            block_id,
-            self.storage.clone(),
-        )?;
+        let new_branch = self.new_chain(block_id)?;
 
         // delete block since from block.number + 1 to latest.
         let start = new_head_block.header().number().saturating_add(1);
@@ -210,11 +289,7 @@ where
 
     ///Directly execute the block and save result, do not try to connect.
     pub fn execute(&mut self, block: Block) -> Result<ExecutedBlock> {
-        let chain = BlockChain::new(
-            self.config.net().time_service(),
-            block.header().parent_hash(),
-            self.storage.clone(),
-        )?;
+        let chain = self.new_chain(block.header().parent_hash())?;
         let verify_block = chain.verify(block)?;
         chain.execute(verify_block)
     }
@@ -240,7 +315,7 @@ where
     fn find_ancestors_from_accumulator(
         &self,
         new_branch: &BlockChain,
-    ) -> Result<(u64, Vec<Block>, u64, Vec<Block>)> {
+    ) -> Result<(BlockIdAndNumber, u64, Vec<Block>, u64, u64, Vec<Block>)> {
         let ancestor = self.main.find_ancestor(new_branch)?.ok_or_else(|| {
             format_err!(
                 "Can not find ancestors between main chain: {:?} and branch: {:?}",
@@ -265,10 +340,29 @@ where
             .checked_sub(ancestor_block.header().number())
             .ok_or_else(|| format_err!("current_header number should > ancestor_block number."))?;
 
+        // count the retracted txns by the txn accumulator leaves, every retracted block has a
+        // block metadata txn besides the user txns.
+        let ancestor_txn_leaves = self
+            .main
+            .get_block_info(Some(ancestor.id))?
+            .ok_or_else(|| format_err!("Can not find block info by id:{}", ancestor.id))?
+            .get_txn_accumulator_info()
+            .get_num_leaves();
+        let retracted_txns = self
+            .main
+            .status()
+            .info()
+            .get_txn_accumulator_info()
+            .get_num_leaves()
+            .checked_sub(ancestor_txn_leaves)
+            .and_then(|txns| txns.checked_sub(retracted_count))
+            .ok_or_else(|| format_err!("current txn num should > ancestor txn num."))?;
+
         let block_enacted = new_branch.current_header().id();
         let block_retracted = self.main.current_header().id();
 
         let enacted = self.find_blocks_until(block_enacted, ancestor.id, MAX_ROLL_BACK_BLOCK)?;
+        // only the latest retracted blocks are committed back to the txpool.
         let retracted =
             self.find_blocks_until(block_retracted, ancestor.id, MAX_ROLL_BACK_BLOCK)?;
 
@@ -276,7 +370,14 @@ where
             "Commit block count:{}, rollback block count:{}",
             enacted_count, retracted_count,
         );
-        Ok((enacted_count, enacted, retracted_count, retracted))
+        Ok((
+            ancestor,
+            enacted_count,
+            enacted,
+            retracted_count,
+            retracted_txns,
+            retracted,
+        ))
     }
 
     fn find_blocks_until(
@@ -306,6 +407,30 @@ where
         Ok(blocks)
     }
 
+    fn broadcast_reorg(&self, event: ChainReorgEvent) {
+        let reorg_type = if event.refused { "refused" } else { "accepted" };
+        WRITE_BLOCK_CHAIN_METRICS
+            .reorg_count
+            .with_label_values(&[reorg_type])
+            .inc();
+        WRITE_BLOCK_CHAIN_METRICS
+            .reorg_depth
+            .with_label_values(&[reorg_type])
+            .observe(event.depth as f64);
+        WRITE_BLOCK_CHAIN_METRICS
+            .reorg_retracted_txns
+            .with_label_values(&[reorg_type])
+            .inc_by(event.retracted_txns);
+        if !event.refused {
+            WRITE_BLOCK_CHAIN_METRICS
+                .last_reorg_depth
+                .set(event.depth as i64);
+        }
+        if let Err(e) = self.bus.broadcast(event) {
+            error!("Broadcast ChainReorgEvent error: {:?}", e);
+        }
+    }
+
     fn broadcast_new_head(&self, block: ExecutedBlock) {
         if let Err(e) = self.bus.broadcast(NewHeadBlock(Arc::new(block))) {
             error!("Broadcast NewHeadBlock error: {:?}", e);
@@ -324,6 +449,15 @@ where
             debug!("Repeat connect, current header is {} already.", block_id);
             return Ok(());
         }
+        // the chain verifies new blocks with the checkpoints, but an exist block is connected
+        // without verify, so check it here.
+        CheckpointVerifier::verify_header(&self.checkpoints, block.header()).map_err(|e| {
+            WRITE_BLOCK_CHAIN_METRICS
+                .block_connect_count
+                .with_label_values(&["verify_failed"])
+                .inc();
+            e
+        })?;
         if self.main.current_header().id() == block.header().parent_hash()
             && !self.block_exist(block_id)?
         {
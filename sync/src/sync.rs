@@ -3,7 +3,7 @@
 
 use crate::block_connector::BlockConnectorService;
 use crate::sync_metrics::SYNC_METRICS;
-use crate::tasks::{full_sync_task, AncestorEvent, SyncFetcher};
+use crate::tasks::{full_sync_task, AncestorEvent, BlockDownloadOption, SyncFetcher};
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
 use config::NodeConfig;
@@ -199,6 +199,8 @@ impl SyncService {
                     self_ref.clone(),
                     network.clone(),
                     config.sync.max_retry_times(),
+                    BlockDownloadOption::from(&config.sync),
+                    config.sync.checkpoints()?,
                 )?;
 
                 self_ref.notify(SyncBeginEvent {
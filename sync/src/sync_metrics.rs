@@ -1,7 +1,7 @@
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
-    default_registry, register_histogram_vec, HistogramOpts, HistogramVec, Opts, PrometheusError,
-    UIntCounterVec,
+    default_registry, register_histogram_vec, register_int_gauge, HistogramOpts, HistogramVec,
+    IntGauge, Opts, PrometheusError, UIntCounterVec,
 };
 
 const SC_NS: &str = "starcoin";
@@ -14,6 +14,40 @@ pub const LABEL_STATE: &str = "state";
 pub const LABEL_TXN_INFO: &str = "txn_info";
 pub const LABEL_ACCUMULATOR: &str = "accumulator";
 
+pub const LABEL_STAGE_DOWNLOAD: &str = "download";
+pub const LABEL_STAGE_EXECUTE: &str = "execute";
+
+/// Increase the gauge when created and decrease it when dropped, so the gauge stays accurate
+/// when the guarded item is dropped by an error or a cancelled task.
+pub struct IntGaugeGuard {
+    gauge: IntGauge,
+}
+
+impl IntGaugeGuard {
+    pub fn new(gauge: IntGauge) -> Self {
+        gauge.inc();
+        Self { gauge }
+    }
+}
+
+impl Clone for IntGaugeGuard {
+    fn clone(&self) -> Self {
+        Self::new(self.gauge.clone())
+    }
+}
+
+impl std::fmt::Debug for IntGaugeGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IntGaugeGuard({})", self.gauge.get())
+    }
+}
+
+impl Drop for IntGaugeGuard {
+    fn drop(&mut self) {
+        self.gauge.dec();
+    }
+}
+
 pub static SYNC_METRICS: Lazy<SyncMetrics> = Lazy::new(|| SyncMetrics::register().unwrap());
 
 #[derive(Clone)]
@@ -22,6 +56,9 @@ pub struct SyncMetrics {
     pub sync_apply_block_time: HistogramVec,
     pub sync_times: UIntCounterVec,
     pub sync_break_times: UIntCounterVec,
+    pub sync_block_download_time: HistogramVec,
+    pub sync_block_stage_items: UIntCounterVec,
+    pub sync_block_buffer_depth: IntGauge,
 }
 
 impl SyncMetrics {
@@ -59,14 +96,39 @@ impl SyncMetrics {
             .namespace(SC_NS),
             &["type"],
         )?;
+        let sync_block_download_time = register_histogram_vec!(
+            HistogramOpts::new(
+                format!("{}{}", PREFIX, "sync_block_download_time"),
+                "sync block download time".to_string()
+            )
+            .namespace(SC_NS),
+            &["sync_block_download_time"]
+        )?;
+        let sync_block_stage_items = UIntCounterVec::new(
+            Opts::new(
+                format!("{}{}", PREFIX, "sync_block_stage_items"),
+                "sync block items processed by every pipeline stage".to_string(),
+            )
+            .namespace(SC_NS),
+            &["stage"],
+        )?;
+        let sync_block_buffer_depth = register_int_gauge!(Opts::new(
+            format!("{}{}", PREFIX, "sync_block_buffer_depth"),
+            "sync downloaded blocks wait for execute in reorder buffer".to_string()
+        )
+        .namespace(SC_NS))?;
         default_registry().register(Box::new(sync_times.clone()))?;
         default_registry().register(Box::new(sync_break_times.clone()))?;
+        default_registry().register(Box::new(sync_block_stage_items.clone()))?;
 
         Ok(Self {
             sync_get_block_ids_time,
             sync_apply_block_time,
             sync_times,
             sync_break_times,
+            sync_block_download_time,
+            sync_block_stage_items,
+            sync_block_buffer_depth,
         })
     }
 }
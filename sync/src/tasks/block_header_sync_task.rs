@@ -0,0 +1,284 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tasks::BlockHeaderFetcher;
+use crate::verified_rpc_client::RpcVerifyError;
+use anyhow::{ensure, format_err, Result};
+use consensus::difficulty::get_next_work_required_by_headers;
+use consensus::{target_to_difficulty, Consensus};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use logger::prelude::*;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_chain::BlockChain;
+use starcoin_chain_api::ChainReader;
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::block::{BlockHeader, BlockIdAndNumber, BlockNumber};
+use starcoin_types::U256;
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use stream_task::{CollectorState, TaskResultCollector, TaskState};
+
+/// Download the block headers between ancestor and target, the block headers are checked with the
+/// block accumulator which has been synced from the target peers.
+#[derive(Clone)]
+pub struct BlockHeaderSyncTask {
+    accumulator: Arc<MerkleAccumulator>,
+    start_number: BlockNumber,
+    fetcher: Arc<dyn BlockHeaderFetcher>,
+    batch_size: u64,
+}
+
+impl BlockHeaderSyncTask {
+    pub fn new<F>(
+        accumulator: MerkleAccumulator,
+        ancestor: BlockIdAndNumber,
+        fetcher: F,
+        batch_size: u64,
+    ) -> Self
+    where
+        F: BlockHeaderFetcher + 'static,
+    {
+        //start_number is include, so start from ancestor.number + 1
+        let start_number = ancestor.number.saturating_add(1);
+        info!(
+            "[sync] Start sync block header, ancestor: {:?}, start_number: {}, target_number: {}",
+            ancestor,
+            start_number,
+            accumulator.num_leaves().saturating_sub(1)
+        );
+        Self {
+            accumulator: Arc::new(accumulator),
+            start_number,
+            fetcher: Arc::new(fetcher),
+            batch_size,
+        }
+    }
+}
+
+impl TaskState for BlockHeaderSyncTask {
+    type Item = BlockHeader;
+
+    fn new_sub_task(self) -> BoxFuture<'static, Result<Vec<Self::Item>>> {
+        async move {
+            let block_ids =
+                self.accumulator
+                    .get_leaves(self.start_number, false, self.batch_size)?;
+            if block_ids.is_empty() {
+                return Ok(vec![]);
+            }
+            let headers = self
+                .fetcher
+                .fetch_block_headers(self.start_number, block_ids.len() as u64)
+                .await?;
+            ensure!(
+                headers.len() == block_ids.len(),
+                "Expect {} block headers from number {}, but got {}",
+                block_ids.len(),
+                self.start_number,
+                headers.len()
+            );
+            for (block_id, header) in block_ids.iter().zip(headers.iter()) {
+                ensure!(
+                    *block_id == header.id(),
+                    "Block header {:?} at number {} mismatch with block id {:?} in accumulator",
+                    header.id(),
+                    header.number(),
+                    block_id
+                );
+            }
+            Ok(headers)
+        }
+        .boxed()
+    }
+
+    fn next(&self) -> Option<Self> {
+        let next_start_number = self.start_number.saturating_add(self.batch_size);
+        if next_start_number > self.accumulator.num_leaves() {
+            None
+        } else {
+            Some(Self {
+                accumulator: self.accumulator.clone(),
+                start_number: next_start_number,
+                fetcher: self.fetcher.clone(),
+                batch_size: self.batch_size,
+            })
+        }
+    }
+
+    fn total_items(&self) -> Option<u64> {
+        Some(
+            self.accumulator
+                .num_leaves()
+                .saturating_sub(self.start_number),
+        )
+    }
+}
+
+/// Verify the parent linkage, difficulty and PoW of the synced block headers, and accumulate the
+/// total difficulty of the header chain. The block bodies are only downloaded after the header
+/// chain reaches the target with the total difficulty claimed by the target peers.
+pub struct BlockHeaderCollector {
+    ancestor: BlockIdAndNumber,
+    accumulator: MerkleAccumulator,
+    target: SyncTarget,
+    epoch: Epoch,
+    // the latest headers in the difficulty window, the latest header at the front.
+    headers: VecDeque<BlockHeader>,
+    total_difficulty: U256,
+    skip_pow_verify: bool,
+    // the headers below the latest checkpoint are verified by the checkpoint, skip their PoW.
+    checkpoint_number: Option<BlockNumber>,
+}
+
+impl BlockHeaderCollector {
+    /// `chain` is the block chain init by ancestor.
+    pub fn new(
+        ancestor: BlockIdAndNumber,
+        accumulator: MerkleAccumulator,
+        chain: &BlockChain,
+        target: SyncTarget,
+        skip_pow_verify: bool,
+    ) -> Result<Self> {
+        let epoch = chain.epoch().clone();
+        let ancestor_header = chain.current_header();
+        let start_window_num = ancestor_header
+            .number()
+            .saturating_add(1)
+            .saturating_sub(epoch.block_difficulty_window());
+        let headers = (start_window_num..=ancestor_header.number())
+            .rev()
+            .map(|n| {
+                chain
+                    .get_header_by_number(n)?
+                    .ok_or_else(|| format_err!("Can not find header by number {}", n))
+            })
+            .collect::<Result<VecDeque<BlockHeader>>>()?;
+        let total_difficulty = chain.get_total_difficulty()?;
+        // the target's block accumulator has been verified with the checkpoints before sync headers.
+        let checkpoint_number = chain
+            .checkpoints()
+            .latest_before(target.target_id.number())
+            .map(|checkpoint| checkpoint.number);
+        Ok(Self {
+            ancestor,
+            accumulator,
+            target,
+            epoch,
+            headers,
+            total_difficulty,
+            skip_pow_verify,
+            checkpoint_number,
+        })
+    }
+
+    fn verify_error(&self, msg: String) -> anyhow::Error {
+        RpcVerifyError::new_with_peers(self.target.peers.clone(), msg).into()
+    }
+
+    /// Calculate the next block's difficulty by the headers in the difficulty window.
+    fn next_difficulty(&self) -> Result<U256> {
+        let current_header = self.headers.front().expect("headers must not be empty.");
+        let target = get_next_work_required_by_headers(&self.epoch, current_header, |number| {
+            current_header
+                .number()
+                .checked_sub(number)
+                .and_then(|idx| self.headers.get(idx as usize))
+                .cloned()
+                .ok_or_else(|| format_err!("Can not find header by number {}", number))
+        })?;
+        Ok(target_to_difficulty(target))
+    }
+
+    fn verify_header(&self, header: &BlockHeader) -> Result<()> {
+        let parent = self.headers.front().expect("headers must not be empty.");
+        if header.parent_hash() != parent.id() || header.number() != parent.number() + 1 {
+            return Err(self.verify_error(format!(
+                "Block header {:?}({}) is not the child of {:?}({})",
+                header.id(),
+                header.number(),
+                parent.id(),
+                parent.number()
+            )));
+        }
+        if header.timestamp() <= parent.timestamp() {
+            return Err(self.verify_error(format!(
+                "Block header {:?} timestamp {} is not greater than parent's timestamp {}",
+                header.id(),
+                header.timestamp(),
+                parent.timestamp()
+            )));
+        }
+        let below_checkpoint = self
+            .checkpoint_number
+            .map(|number| header.number() <= number)
+            .unwrap_or(false);
+        let strategy = self.epoch.strategy();
+        // Dummy and custom consensus do not verify difficulty and nonce by PoW.
+        if self.skip_pow_verify
+            || below_checkpoint
+            || matches!(
+                strategy,
+                ConsensusStrategy::Dummy | ConsensusStrategy::Custom
+            )
+        {
+            return Ok(());
+        }
+        let verify_result = if header.number() <= self.epoch.end_block_number() {
+            self.next_difficulty()
+                .and_then(|difficulty| strategy.verify_header_difficulty(difficulty, header))
+        } else {
+            // the next epoch's config is on chain, so only verify the PoW with the header's difficulty.
+            strategy.verify_blob(
+                header.as_pow_header_blob(),
+                header.nonce(),
+                *header.extra(),
+                header.difficulty(),
+            )
+        };
+        verify_result.map_err(|e| {
+            self.verify_error(format!(
+                "Verify block header {:?}({}) failed: {:?}",
+                header.id(),
+                header.number(),
+                e
+            ))
+        })
+    }
+}
+
+impl TaskResultCollector<BlockHeader> for BlockHeaderCollector {
+    type Output = (BlockIdAndNumber, MerkleAccumulator);
+
+    fn collect(&mut self, item: BlockHeader) -> Result<CollectorState> {
+        self.verify_header(&item)?;
+        self.total_difficulty = self.total_difficulty + item.difficulty();
+        let number = item.number();
+        self.headers.push_front(item);
+        self.headers
+            .truncate(self.epoch.block_difficulty_window() as usize);
+        if number == self.target.target_id.number() {
+            Ok(CollectorState::Enough)
+        } else {
+            Ok(CollectorState::Need)
+        }
+    }
+
+    fn finish(self) -> Result<Self::Output> {
+        let latest_header = self.headers.front().expect("headers must not be empty.");
+        if latest_header.id() != self.target.target_id.id()
+            || self.total_difficulty != self.target.block_info.total_difficulty
+        {
+            return Err(self.verify_error(format!(
+                "Verify target error, expect target: {:?}, total_difficulty: {}, but header chain's latest header: {:?}, total_difficulty: {}",
+                self.target.target_id,
+                self.target.block_info.total_difficulty,
+                latest_header.id(),
+                self.total_difficulty
+            )));
+        }
+        Ok((self.ancestor, self.accumulator))
+    }
+}
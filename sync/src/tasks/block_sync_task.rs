@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sync_metrics::SYNC_METRICS;
+use crate::sync_metrics::{IntGaugeGuard, LABEL_STAGE_DOWNLOAD, LABEL_STAGE_EXECUTE, SYNC_METRICS};
 use crate::tasks::{BlockConnectedEvent, BlockConnectedEventHandle, BlockFetcher, BlockLocalStore};
 use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::{format_err, Result};
@@ -12,6 +12,7 @@ use network_api::PeerProvider;
 use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::{verifier::BasicVerifier, BlockChain};
 use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, ExecutedBlock};
+use starcoin_crypto::HashValue;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
@@ -25,6 +26,8 @@ pub struct SyncBlockData {
     pub(crate) block: Block,
     pub(crate) info: Option<BlockInfo>,
     pub(crate) peer_id: Option<PeerId>,
+    // keep the buffer depth gauge counting the block until it is executed or dropped.
+    buffer_guard: Option<IntGaugeGuard>,
 }
 
 impl SyncBlockData {
@@ -33,8 +36,17 @@ impl SyncBlockData {
             block,
             info: block_info,
             peer_id,
+            buffer_guard: None,
         }
     }
+
+    /// Count the block in the sync block buffer depth gauge until it is dropped.
+    pub(crate) fn with_buffer_guard(mut self) -> Self {
+        self.buffer_guard = Some(IntGaugeGuard::new(
+            SYNC_METRICS.sync_block_buffer_depth.clone(),
+        ));
+        self
+    }
 }
 
 #[allow(clippy::from_over_into)]
@@ -83,6 +95,62 @@ impl BlockSyncTask {
             batch_size,
         }
     }
+
+    async fn fetch_blocks(&self, block_ids: Vec<HashValue>) -> Result<Vec<SyncBlockData>> {
+        if self.check_local_store {
+            let block_with_info = self.local_store.get_block_with_info(block_ids.clone())?;
+            let (no_exist_block_ids, result_map) =
+                block_ids.clone().into_iter().zip(block_with_info).fold(
+                    (vec![], HashMap::new()),
+                    |(mut no_exist_block_ids, mut result_map), (block_id, block_with_info)| {
+                        match block_with_info {
+                            Some(block_data) => {
+                                result_map.insert(block_id, block_data);
+                            }
+                            None => {
+                                no_exist_block_ids.push(block_id);
+                            }
+                        }
+                        (no_exist_block_ids, result_map)
+                    },
+                );
+            debug!(
+                "[sync] get_block_with_info from local store, ids: {}, found: {}",
+                block_ids.len(),
+                result_map.len()
+            );
+            let mut result_map = if no_exist_block_ids.is_empty() {
+                result_map
+            } else {
+                self.fetcher
+                    .fetch_blocks(no_exist_block_ids)
+                    .await?
+                    .into_iter()
+                    .fold(result_map, |mut result_map, (block, peer_id)| {
+                        result_map.insert(block.id(), SyncBlockData::new(block, None, peer_id));
+                        result_map
+                    })
+            };
+            //ensure return block's order same as request block_id's order.
+            let result: Result<Vec<SyncBlockData>> = block_ids
+                .iter()
+                .map(|block_id| {
+                    result_map
+                        .remove(block_id)
+                        .ok_or_else(|| format_err!("Get block by id {:?} failed", block_id))
+                })
+                .collect();
+            result
+        } else {
+            Ok(self
+                .fetcher
+                .fetch_blocks(block_ids)
+                .await?
+                .into_iter()
+                .map(|(block, peer_id)| SyncBlockData::new(block, None, peer_id))
+                .collect())
+        }
+    }
 }
 
 impl TaskState for BlockSyncTask {
@@ -96,59 +164,20 @@ impl TaskState for BlockSyncTask {
             if block_ids.is_empty() {
                 return Ok(vec![]);
             }
-            if self.check_local_store {
-                let block_with_info = self.local_store.get_block_with_info(block_ids.clone())?;
-                let (no_exist_block_ids, result_map) =
-                    block_ids.clone().into_iter().zip(block_with_info).fold(
-                        (vec![], HashMap::new()),
-                        |(mut no_exist_block_ids, mut result_map), (block_id, block_with_info)| {
-                            match block_with_info {
-                                Some(block_data) => {
-                                    result_map.insert(block_id, block_data);
-                                }
-                                None => {
-                                    no_exist_block_ids.push(block_id);
-                                }
-                            }
-                            (no_exist_block_ids, result_map)
-                        },
-                    );
-                debug!(
-                    "[sync] get_block_with_info from local store, ids: {}, found: {}",
-                    block_ids.len(),
-                    result_map.len()
-                );
-                let mut result_map = if no_exist_block_ids.is_empty() {
-                    result_map
-                } else {
-                    self.fetcher
-                        .fetch_blocks(no_exist_block_ids)
-                        .await?
-                        .into_iter()
-                        .fold(result_map, |mut result_map, (block, peer_id)| {
-                            result_map.insert(block.id(), SyncBlockData::new(block, None, peer_id));
-                            result_map
-                        })
-                };
-                //ensure return block's order same as request block_id's order.
-                let result: Result<Vec<SyncBlockData>> = block_ids
-                    .iter()
-                    .map(|block_id| {
-                        result_map
-                            .remove(block_id)
-                            .ok_or_else(|| format_err!("Get block by id {:?} failed", block_id))
-                    })
-                    .collect();
-                result
-            } else {
-                Ok(self
-                    .fetcher
-                    .fetch_blocks(block_ids)
-                    .await?
-                    .into_iter()
-                    .map(|(block, peer_id)| SyncBlockData::new(block, None, peer_id))
-                    .collect())
-            }
+            let _timer = SYNC_METRICS
+                .sync_block_download_time
+                .with_label_values(&["time"])
+                .start_timer();
+            let blocks = self.fetch_blocks(block_ids).await?;
+            SYNC_METRICS
+                .sync_block_stage_items
+                .with_label_values(&[LABEL_STAGE_DOWNLOAD])
+                .inc_by(blocks.len() as u64);
+            // downloaded blocks wait in the reorder buffer until all previous blocks are executed.
+            Ok(blocks
+                .into_iter()
+                .map(SyncBlockData::with_buffer_guard)
+                .collect())
         }
         .boxed()
     }
@@ -187,6 +216,8 @@ pub struct BlockCollector<N, H> {
     event_handle: H,
     peer_provider: N,
     skip_pow_verify: bool,
+    // blocks below the checkpoint are trusted, so skip pow verify for them.
+    checkpoint_number: Option<BlockNumber>,
 }
 
 impl<N, H> BlockCollector<N, H>
@@ -202,6 +233,11 @@ where
         peer_provider: N,
         skip_pow_verify: bool,
     ) -> Self {
+        // the target's block accumulator has been verified with the checkpoints before sync blocks.
+        let checkpoint_number = chain
+            .checkpoints()
+            .latest_before(target.target_id.number())
+            .map(|checkpoint| checkpoint.number);
         Self {
             current_block_info,
             target,
@@ -209,6 +245,7 @@ where
             event_handle,
             peer_provider,
             skip_pow_verify,
+            checkpoint_number,
         }
     }
 
@@ -222,24 +259,12 @@ where
             .sync_apply_block_time
             .with_label_values(&["time"])
             .start_timer();
-        if let Some((_failed_block, pre_peer_id, err)) = self
-            .chain
-            .get_storage()
-            .get_failed_block_by_id(block.id())?
-        {
-            warn!(
-                "[sync] apply a previous failed block: {}, previous_peer_id:{:?}, err: {}",
-                block.id(),
-                pre_peer_id,
-                err
-            );
-            if let Some(peer) = peer_id {
-                self.peer_provider
-                    .report_peer(peer, ConnectBlockError::REP_VERIFY_BLOCK_FAILED);
-            }
-            return Err(format_err!("collect previous failed block:{}", block.id()));
-        }
-        let apply_result = if self.skip_pow_verify {
+        let below_checkpoint = self
+            .checkpoint_number
+            .map(|number| block.header().number() <= number)
+            .unwrap_or(false);
+        // the chain verifies the block and its block info with the checkpoints.
+        let apply_result = if self.skip_pow_verify || below_checkpoint {
             self.chain
                 .apply_with_verifier::<BasicVerifier>(block.clone())
         } else {
@@ -257,12 +282,14 @@ where
                         Err(ConnectBlockError::FutureBlock(block).into())
                     }
                     e => {
-                        self.chain.get_storage().save_failed_block(
-                            block.id(),
-                            block,
-                            peer_id.clone(),
-                            error_msg,
-                        )?;
+                        if e.is_failed_block() {
+                            self.chain.get_storage().save_failed_block(
+                                block.id(),
+                                block,
+                                peer_id.clone(),
+                                error_msg,
+                            )?;
+                        }
                         if let Some(peer) = peer_id {
                             self.peer_provider.report_peer(peer, e.reputation());
                         }
@@ -286,9 +313,35 @@ where
     type Output = BlockChain;
 
     fn collect(&mut self, item: SyncBlockData) -> Result<CollectorState> {
+        SYNC_METRICS
+            .sync_block_stage_items
+            .with_label_values(&[LABEL_STAGE_EXECUTE])
+            .inc();
         let (block, block_info, peer_id) = item.into();
         let block_id = block.id();
         let timestamp = block.header().timestamp();
+        // a previous failed block or the head of a refused reorg branch, which may has been
+        // executed, break the sync to the target peers' branch.
+        if let Some((_failed_block, pre_peer_id, err)) =
+            self.chain.get_storage().get_failed_block_by_id(block_id)?
+        {
+            warn!(
+                "[sync] collect a previous failed block: {}, previous_peer_id:{:?}, err: {}",
+                block_id, pre_peer_id, err
+            );
+            if let Some(peer) = peer_id {
+                self.peer_provider
+                    .report_peer(peer, ConnectBlockError::REP_VERIFY_BLOCK_FAILED);
+            }
+            return Err(TaskError::BreakError(
+                RpcVerifyError::new_with_peers(
+                    self.target.peers.clone(),
+                    format!("collect previous failed block:{}, err: {}", block_id, err),
+                )
+                .into(),
+            )
+            .into());
+        }
         let block_info = match block_info {
             Some(block_info) => {
                 //If block_info exists, it means that this block was already executed and try connect in the previous sync, but the sync task was interrupted.
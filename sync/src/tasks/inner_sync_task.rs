@@ -1,10 +1,14 @@
 use crate::tasks::{
     AccumulatorCollector, BlockAccumulatorSyncTask, BlockCollector, BlockConnectedEventHandle,
-    BlockFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
+    BlockDownloadOption, BlockFetcher, BlockHeaderCollector, BlockHeaderFetcher,
+    BlockHeaderSyncTask, BlockIdFetcher, BlockSyncTask, PeerOperator,
 };
+use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::format_err;
+use config::Checkpoints;
 use network_api::PeerProvider;
 use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::BlockChain;
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
@@ -19,7 +23,7 @@ use stream_task::{
 pub struct InnerSyncTask<H, F, N>
 where
     H: BlockConnectedEventHandle + Sync + 'static,
-    F: BlockIdFetcher + BlockFetcher + PeerOperator + 'static,
+    F: BlockIdFetcher + BlockFetcher + BlockHeaderFetcher + PeerOperator + 'static,
     N: PeerProvider + Clone + 'static,
 {
     ancestor: BlockIdAndNumber,
@@ -31,12 +35,13 @@ where
     time_service: Arc<dyn TimeService>,
     peer_provider: N,
     custom_error_handle: Arc<dyn CustomErrorHandle>,
+    checkpoints: Checkpoints,
 }
 
 impl<H, F, N> InnerSyncTask<H, F, N>
 where
     H: BlockConnectedEventHandle + Sync + 'static,
-    F: BlockIdFetcher + BlockFetcher + PeerOperator + 'static,
+    F: BlockIdFetcher + BlockFetcher + BlockHeaderFetcher + PeerOperator + 'static,
     N: PeerProvider + Clone + 'static,
 {
     pub fn new(
@@ -49,6 +54,7 @@ where
         time_service: Arc<dyn TimeService>,
         peer_provider: N,
         custom_error_handle: Arc<dyn CustomErrorHandle>,
+        checkpoints: Checkpoints,
     ) -> Self {
         Self {
             ancestor,
@@ -60,9 +66,35 @@ where
             time_service,
             peer_provider,
             custom_error_handle,
+            checkpoints,
         }
     }
 
+    /// Verify the synced block accumulator contains the checkpoints between ancestor and target.
+    fn verify_checkpoints(
+        checkpoints: &Checkpoints,
+        target: &SyncTarget,
+        ancestor: BlockIdAndNumber,
+        accumulator: &MerkleAccumulator,
+    ) -> anyhow::Result<()> {
+        for checkpoint in
+            checkpoints.range(ancestor.number.saturating_add(1), target.target_id.number())
+        {
+            let block_id = accumulator.get_leaf(checkpoint.number)?;
+            if block_id != Some(checkpoint.block_hash) {
+                return Err(RpcVerifyError::new_with_peers(
+                    target.peers.clone(),
+                    format!(
+                        "Sync target conflict with checkpoint {}, got block id: {:?}",
+                        checkpoint, block_id
+                    ),
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
     fn ancestor_block_info(&self) -> anyhow::Result<BlockInfo> {
         self.storage
             .get_block_info(self.ancestor.id)?
@@ -80,6 +112,7 @@ where
         max_retry_times: u64,
         delay_milliseconds_on_error: u64,
         skip_pow_verify_when_sync: bool,
+        block_download_option: BlockDownloadOption,
     ) -> Result<(BlockChain, TaskHandle), TaskError> {
         let buffer_size = self.target.peers.len();
 
@@ -98,6 +131,12 @@ where
                 .expect("total_items must exist") as usize,
             buffer_size,
         );
+        let checkpoints = self.checkpoints.clone();
+        let target = self.target.clone();
+        let fetcher = self.fetcher.clone();
+        let time_service = self.time_service.clone();
+        let storage = self.storage.clone();
+        let custom_error_handle = self.custom_error_handle.clone();
         let sub_accumulator_task = TaskGenerator::new(
             accumulator_sync_task.clone(),
             acc_buffer_size,
@@ -113,6 +152,35 @@ where
             self.event_handle.clone(),
             self.custom_error_handle.clone(),
         )
+        .and_then(move |(ancestor, accumulator), event_handle| {
+            Self::verify_checkpoints(&checkpoints, &target, ancestor, &accumulator)?;
+            // download and verify the block headers first, the block bodies are only downloaded
+            // after the header chain reaches the target with the claimed total difficulty.
+            let header_sync_task = BlockHeaderSyncTask::new(
+                accumulator.fork(),
+                ancestor,
+                fetcher,
+                block_download_option.header_batch_size,
+            );
+            let chain = BlockChain::new(time_service, ancestor.id, storage)?
+                .with_checkpoints(&checkpoints)?;
+            let header_collector = BlockHeaderCollector::new(
+                ancestor,
+                accumulator,
+                &chain,
+                target,
+                skip_pow_verify_when_sync,
+            )?;
+            Ok(TaskGenerator::new(
+                header_sync_task,
+                buffer_size,
+                max_retry_times,
+                delay_milliseconds_on_error,
+                header_collector,
+                event_handle,
+                custom_error_handle,
+            ))
+        })
         .and_then(move |(ancestor, accumulator), event_handle| {
             let check_local_store =
                 ancestor_block_info.total_difficulty < current_block_info.total_difficulty;
@@ -123,10 +191,11 @@ where
                 self.fetcher.clone(),
                 check_local_store,
                 self.storage.clone(),
-                1,
+                block_download_option.batch_size,
             );
             let chain =
-                BlockChain::new(self.time_service.clone(), ancestor.id, self.storage.clone())?;
+                BlockChain::new(self.time_service.clone(), ancestor.id, self.storage.clone())?
+                    .with_checkpoints(&self.checkpoints)?;
             let block_collector = BlockCollector::new_with_handle(
                 current_block_info.clone(),
                 self.target.clone(),
@@ -135,15 +204,19 @@ where
                 self.peer_provider.clone(),
                 skip_pow_verify_when_sync,
             );
+            // download block batches concurrently from different peers, the downloaded blocks are
+            // reordered and executed one by one by the block collector in a separate task, the
+            // reorder buffer bounds the batches which are downloading or waiting for execute.
             Ok(TaskGenerator::new(
                 block_sync_task,
-                buffer_size,
+                block_download_option.concurrency,
                 max_retry_times,
                 delay_milliseconds_on_error,
                 block_collector,
                 event_handle,
                 self.custom_error_handle.clone(),
-            ))
+            )
+            .with_reorder_buffer_size(block_download_option.buffer_size))
         })
         .generate();
 
@@ -5,6 +5,7 @@ use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::inner_sync_task::InnerSyncTask;
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Error, Result};
+use config::{Checkpoints, SyncConfig};
 use futures::channel::mpsc::UnboundedSender;
 use futures::future::BoxFuture;
 use futures::{FutureExt, TryFutureExt};
@@ -15,14 +16,16 @@ use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::MerkleAccumulator;
 use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_crypto::HashValue;
+use starcoin_network_rpc_api::{GetBlockHeadersByNumber, MAX_BLOCK_REQUEST_SIZE};
 use starcoin_service_registry::{ActorService, EventHandler, ServiceRef};
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
-use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::block::{Block, BlockHeader, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::U256;
 use starcoin_vm_types::time::TimeService;
+use std::cmp::min;
 use std::str::FromStr;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
@@ -32,7 +35,9 @@ use stream_task::{
     TaskHandle,
 };
 
-pub trait SyncFetcher: PeerOperator + BlockIdFetcher + BlockFetcher + BlockInfoFetcher {
+pub trait SyncFetcher:
+    PeerOperator + BlockIdFetcher + BlockFetcher + BlockHeaderFetcher + BlockInfoFetcher
+{
     fn get_best_target(&self, min_difficulty: U256) -> Result<Option<SyncTarget>> {
         if let Some(best_peers) = self.peer_selector().bests(min_difficulty) {
             //TODO fast verify best peers by accumulator
@@ -317,6 +322,62 @@ impl BlockFetcher for VerifiedRpcClient {
     }
 }
 
+pub trait BlockHeaderFetcher: Send + Sync {
+    /// Fetch `max_size` block headers from `start_number`, the result is ordered by block number.
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<Result<Vec<BlockHeader>>>;
+}
+
+impl<T> BlockHeaderFetcher for Arc<T>
+where
+    T: BlockHeaderFetcher,
+{
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<'_, Result<Vec<BlockHeader>>> {
+        BlockHeaderFetcher::fetch_block_headers(self.as_ref(), start_number, max_size)
+    }
+}
+
+impl BlockHeaderFetcher for VerifiedRpcClient {
+    fn fetch_block_headers(
+        &self,
+        start_number: BlockNumber,
+        max_size: u64,
+    ) -> BoxFuture<'_, Result<Vec<BlockHeader>>> {
+        // get_headers_by_number return headers in reverse order from the request number.
+        let req = GetBlockHeadersByNumber::new(
+            start_number.saturating_add(max_size.saturating_sub(1)),
+            1,
+            max_size,
+        );
+        self.get_headers_by_number(req)
+            .and_then(move |headers| async move {
+                let results: Result<Vec<BlockHeader>> = headers
+                    .into_iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(idx, header)| {
+                        header.ok_or_else(|| {
+                            format_err!(
+                                "Get block header by number: {} failed, remote node return None",
+                                start_number.saturating_add(idx as u64)
+                            )
+                        })
+                    })
+                    .collect();
+                results
+            })
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
 pub trait BlockInfoFetcher: Send + Sync {
     fn fetch_block_infos(
         &self,
@@ -500,7 +561,47 @@ where
     }
 }
 
+/// The options of the block download stage of the sync task.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BlockDownloadOption {
+    /// max concurrent block download requests.
+    pub concurrency: usize,
+    /// block count of every block download request.
+    pub batch_size: u64,
+    /// block header count of every block header download request.
+    pub header_batch_size: u64,
+    /// max block batches downloading or waiting for execute in the reorder buffer.
+    pub buffer_size: usize,
+}
+
+impl Default for BlockDownloadOption {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            batch_size: 10,
+            header_batch_size: MAX_BLOCK_REQUEST_SIZE,
+            buffer_size: 16,
+        }
+    }
+}
+
+impl From<&SyncConfig> for BlockDownloadOption {
+    fn from(config: &SyncConfig) -> Self {
+        Self {
+            concurrency: config.block_download_concurrency(),
+            // the peers reject the requests with more blocks than `MAX_BLOCK_REQUEST_SIZE`.
+            batch_size: min(config.block_download_batch_size(), MAX_BLOCK_REQUEST_SIZE),
+            header_batch_size: min(
+                config.block_header_download_batch_size(),
+                MAX_BLOCK_REQUEST_SIZE,
+            ),
+            buffer_size: config.block_download_buffer_size(),
+        }
+    }
+}
+
 mod accumulator_sync_task;
+mod block_header_sync_task;
 mod block_sync_task;
 mod find_ancestor_task;
 mod inner_sync_task;
@@ -512,6 +613,7 @@ mod tests;
 
 use crate::tasks::sync_score_metrics::SYNC_SCORE_METRICS;
 pub use accumulator_sync_task::{AccumulatorCollector, BlockAccumulatorSyncTask};
+pub use block_header_sync_task::{BlockHeaderCollector, BlockHeaderSyncTask};
 pub use block_sync_task::{BlockCollector, BlockSyncTask};
 pub use find_ancestor_task::{AncestorCollector, FindAncestorTask};
 
@@ -526,6 +628,8 @@ pub fn full_sync_task<H, A, F, N>(
     ancestor_event_handle: A,
     peer_provider: N,
     max_retry_times: u64,
+    block_download_option: BlockDownloadOption,
+    checkpoints: Checkpoints,
 ) -> Result<(
     BoxFuture<'static, Result<BlockChain, TaskError>>,
     TaskHandle,
@@ -631,6 +735,7 @@ where
                 time_service.clone(),
                 peer_provider.clone(),
                 ext_error_handle.clone(),
+                checkpoints.clone(),
             );
             let start_now = Instant::now();
             let (block_chain, _) = inner
@@ -639,6 +744,7 @@ where
                     max_retry_times,
                     delay_milliseconds_on_error,
                     skip_pow_verify,
+                    block_download_option,
                 )
                 .await?;
             let total_time = Instant::now()
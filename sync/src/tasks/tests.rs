@@ -6,12 +6,15 @@ use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
     full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
-    BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask, FindAncestorTask, SyncFetcher,
+    BlockCollector, BlockDownloadOption, BlockFetcher, BlockLocalStore, BlockSyncTask,
+    FindAncestorTask, SyncFetcher,
 };
 use crate::verified_rpc_client::RpcVerifyError;
 use anyhow::Context;
 use anyhow::{format_err, Result};
-use config::{BuiltinNetworkID, ChainNetwork};
+use config::{
+    BuiltinNetworkID, ChainNetwork, Checkpoint, Checkpoints, MAX_BLOCK_DOWNLOAD_BATCH_SIZE,
+};
 use futures::channel::mpsc::unbounded;
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -27,6 +30,7 @@ use starcoin_chain_api::ChainReader;
 use starcoin_chain_mock::MockChain;
 use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis;
+use starcoin_network_rpc_api::MAX_BLOCK_REQUEST_SIZE;
 use starcoin_storage::BlockStore;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::peer_info::PeerInfo;
@@ -41,6 +45,15 @@ use stream_task::{
 };
 use test_helper::DummyNetworkService;
 
+#[stest::test]
+fn test_block_download_batch_size() {
+    // the batch size of the config must be accepted by the peers.
+    assert_eq!(MAX_BLOCK_DOWNLOAD_BATCH_SIZE, MAX_BLOCK_REQUEST_SIZE);
+    let option = BlockDownloadOption::default();
+    assert!(option.batch_size <= MAX_BLOCK_REQUEST_SIZE);
+    assert!(option.header_batch_size <= MAX_BLOCK_REQUEST_SIZE);
+}
+
 #[stest::test]
 pub async fn test_full_sync_new_node() -> Result<()> {
     let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
@@ -71,6 +84,8 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -100,6 +115,8 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -148,6 +165,8 @@ pub async fn test_sync_invalid_target() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let _join_handle = node2.process_block_connect_event(receiver_1).await;
     let sync_result = sync_task.await;
@@ -166,6 +185,108 @@ pub async fn test_sync_invalid_target() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_sync_target_conflict_with_checkpoint() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 0)?;
+    node1.produce_block(10)?;
+
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+
+    let node2 = SyncNodeMocker::new(net2.clone(), 1, 0)?;
+
+    let target = arc_node1.sync_target();
+
+    let current_block_header = node2.chain().current_header();
+
+    let storage = node2.chain().get_storage();
+    let (sender_1, receiver_1) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let checkpoints = Checkpoints::new(vec![Checkpoint::new(5, HashValue::random())])?;
+    let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+        current_block_header.id(),
+        target.clone(),
+        false,
+        net2.time_service(),
+        storage.clone(),
+        sender_1,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+        BlockDownloadOption::default(),
+        checkpoints,
+    )?;
+    let _join_handle = node2.process_block_connect_event(receiver_1).await;
+    let sync_result = sync_task.await;
+    assert!(sync_result.is_err());
+    let err = sync_result.err().unwrap();
+    debug!("task_error: {:?}", err);
+    assert!(err.is_break_error());
+    if let TaskError::BreakError(err) = err {
+        let verify_err = err.downcast::<RpcVerifyError>().unwrap();
+        assert_eq!(verify_err.peers[0].clone(), arc_node1.peer_id);
+    } else {
+        panic!("Expect BreakError, but got: {:?}", err)
+    }
+    Ok(())
+}
+
+#[stest::test]
+pub async fn test_sync_fake_total_difficulty_target() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 0)?;
+    node1.produce_block(5)?;
+
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+
+    let node2 = SyncNodeMocker::new(net2.clone(), 1, 0)?;
+
+    // the target peer claims a total difficulty greater than its header chain.
+    let mut target = arc_node1.sync_target();
+    target.block_info.total_difficulty = target.block_info.total_difficulty * 2;
+
+    let current_block_header = node2.chain().current_header();
+
+    let storage = node2.chain().get_storage();
+    let (sender_1, receiver_1) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+        current_block_header.id(),
+        target.clone(),
+        false,
+        net2.time_service(),
+        storage.clone(),
+        sender_1,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
+    )?;
+    let _join_handle = node2.process_block_connect_event(receiver_1).await;
+    let sync_result = sync_task.await;
+    assert!(sync_result.is_err());
+    let err = sync_result.err().unwrap();
+    debug!("task_error: {:?}", err);
+    assert!(err.is_break_error());
+    // the block bodies should not be downloaded when the header chain mismatch the target.
+    for number in 1..=target.target_id.number() {
+        let block_id = arc_node1
+            .chain()
+            .get_header_by_number(number)?
+            .expect("header must exist")
+            .id();
+        assert!(storage.get_block(block_id)?.is_none());
+    }
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_failed_block() -> Result<()> {
     let net = ChainNetwork::new_builtin(BuiltinNetworkID::Halley);
@@ -229,6 +350,8 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -260,6 +383,8 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -307,6 +432,8 @@ pub async fn test_full_sync_fork_from_genesis() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -359,6 +486,8 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -392,6 +521,8 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
 
     let join_handle = node2.process_block_connect_event(receiver).await;
@@ -442,6 +573,8 @@ pub async fn test_full_sync_cancel() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -883,6 +1016,8 @@ async fn test_net_rpc_err() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockDownloadOption::default(),
+        Checkpoints::default(),
     )?;
     let _join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -958,3 +1093,16 @@ async fn test_sync_target() {
     assert_eq!(target.target_id.number(), low_chain_info.head().number());
     assert_eq!(target.target_id.id(), low_chain_info.head().id());
 }
+
+#[stest::test]
+async fn test_sync_block_data_buffer_guard() -> Result<()> {
+    let gauge = starcoin_metrics::IntGauge::new("test_buffer_guard", "test buffer guard")?;
+    let guard = crate::sync_metrics::IntGaugeGuard::new(gauge.clone());
+    let cloned = guard.clone();
+    assert_eq!(gauge.get(), 2);
+    drop(guard);
+    assert_eq!(gauge.get(), 1);
+    drop(cloned);
+    assert_eq!(gauge.get(), 0);
+    Ok(())
+}
@@ -3,11 +3,14 @@ mod test_sync;
 use config::NodeConfig;
 use futures::executor::block_on;
 use logger::prelude::*;
+use network_api::{PeerProvider, PeerSelector, PeerStrategy};
 use rand::random;
 use starcoin_chain_api::ChainAsyncService;
 use starcoin_node::NodeHandle;
 use starcoin_service_registry::ActorService;
 use starcoin_sync::sync::SyncService;
+use starcoin_sync::tasks::{BlockDownloadOption, BlockHeaderFetcher};
+use starcoin_sync::verified_rpc_client::VerifiedRpcClient;
 use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
@@ -103,6 +106,40 @@ fn test_sync_and_notification() {
     wait_two_node_synced(&first_node, &second_node);
 }
 
+#[stest::test(timeout = 120)]
+fn test_fetch_block_headers_by_network_rpc() {
+    let first_config = Arc::new(NodeConfig::random_for_test());
+    let first_node = run_node_by_config(first_config.clone()).unwrap();
+    let header_batch_size = BlockDownloadOption::from(&first_config.sync).header_batch_size;
+    for _i in 0..header_batch_size {
+        first_node.generate_block().unwrap();
+    }
+
+    let mut second_config = NodeConfig::random_for_test();
+    second_config.network.seeds = vec![first_config.network.self_address()].into();
+    second_config.miner.disable_miner_client = Some(true);
+    let second_node = run_node_by_config(Arc::new(second_config)).unwrap();
+    sleep(Duration::from_secs(2));
+
+    // the header request of a sync batch is verified by the peer's network rpc service.
+    let network = second_node.network();
+    let peers = block_on(async { network.peer_set().await.unwrap() });
+    let rpc_client =
+        VerifiedRpcClient::new(PeerSelector::new(peers, PeerStrategy::default()), network);
+    let headers = block_on(async {
+        rpc_client
+            .fetch_block_headers(1, header_batch_size)
+            .await
+            .unwrap()
+    });
+    assert_eq!(headers.len() as u64, header_batch_size);
+    for (idx, header) in headers.iter().enumerate() {
+        assert_eq!(header.number(), idx as u64 + 1);
+    }
+    second_node.stop().unwrap();
+    first_node.stop().unwrap();
+}
+
 fn wait_two_node_synced(first_node: &NodeHandle, second_node: &NodeHandle) {
     let first_chain = first_node.chain_service().unwrap();
     let second_chain = second_node.chain_service().unwrap();
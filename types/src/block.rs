@@ -9,6 +9,7 @@ use crate::transaction::SignedUserTransaction;
 use crate::U256;
 use bcs_ext::Sample;
 use serde::de::Error;
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 pub use starcoin_accumulator::accumulator_info::AccumulatorInfo;
 use starcoin_crypto::hash::{ACCUMULATOR_PLACEHOLDER_HASH, SPARSE_MERKLE_PLACEHOLDER_HASH};
@@ -17,6 +18,7 @@ use starcoin_crypto::{
     HashValue,
 };
 use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::sign_message::{SignedMessage, SigningMessage};
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 use std::fmt::Formatter;
 
@@ -116,9 +118,8 @@ impl From<BlockHeader> for BlockIdAndNumber {
 /// block timestamp allowed future times
 pub const ALLOWED_FUTURE_BLOCKTIME: u64 = 30000; // 30 second;
 
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, CryptoHasher, CryptoHash)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, CryptoHasher, CryptoHash)]
 pub struct BlockHeader {
-    #[serde(skip)]
     id: Option<HashValue>,
     /// Parent hash.
     parent_hash: HashValue,
@@ -149,6 +150,10 @@ pub struct BlockHeader {
     nonce: u32,
     /// block header extra
     extra: BlockHeaderExtra,
+    /// The signature of the header by the author, required by the proof-of-authority consensus.
+    /// It is a part of the block id, and is encoded in the slot of the deprecated
+    /// `author_auth_key`, so the header without signature keeps the same encoding and id.
+    author_signature: Option<SignedMessage>,
 }
 
 impl BlockHeader {
@@ -218,6 +223,7 @@ impl BlockHeader {
             body_hash,
             chain_id,
             extra,
+            author_signature: None,
         };
         header.id = Some(header.crypto_hash());
         header
@@ -261,6 +267,29 @@ impl BlockHeader {
         self.author_auth_key
     }
 
+    pub fn author_signature(&self) -> Option<&SignedMessage> {
+        self.author_signature.as_ref()
+    }
+
+    /// The message the author should sign, the hash of the header without the signature.
+    pub fn author_signing_message(&self) -> SigningMessage {
+        let mut state = BlockHeaderHasher::default();
+        bcs_ext::serialize_into(&mut state, &UnsignedBlockHeader(self))
+            .expect("Serialization of BlockHeader should not fail");
+        SigningMessage(state.finish().to_vec())
+    }
+
+    /// Set the author signature, the block id is changed by the signature.
+    pub fn with_author_signature(mut self, signature: SignedMessage) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            self.author_auth_key.is_none(),
+            "The header with deprecated author auth key can not be signed."
+        );
+        self.author_signature = Some(signature);
+        self.id = Some(self.crypto_hash());
+        Ok(self)
+    }
+
     pub fn txn_accumulator_root(&self) -> HashValue {
         self.txn_accumulator_root
     }
@@ -350,19 +379,109 @@ impl BlockHeader {
     }
 }
 
+/// The binary encoding of the `author_auth_key` slot of the block header.
+/// The first two variants have the same encoding as `Option<AuthenticationKey>`, so the headers
+/// without author signature keep the same encoding as before.
+#[derive(Serialize)]
+#[serde(rename = "AuthorAuth")]
+enum AuthorAuthRef<'a> {
+    None,
+    AuthKey(&'a AuthenticationKey),
+    AuthorSignature(&'a SignedMessage),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "AuthorAuth")]
+enum AuthorAuth {
+    None,
+    AuthKey(AuthenticationKey),
+    AuthorSignature(SignedMessage),
+}
+
+impl BlockHeader {
+    fn serialize_with<S, A>(
+        &self,
+        serializer: S,
+        author_auth: A,
+        author_signature: Option<&SignedMessage>,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        A: Serialize,
+    {
+        let len = if author_signature.is_some() { 15 } else { 14 };
+        let mut state = serializer.serialize_struct("BlockHeader", len)?;
+        state.serialize_field("parent_hash", &self.parent_hash)?;
+        state.serialize_field("timestamp", &self.timestamp)?;
+        state.serialize_field("number", &self.number)?;
+        state.serialize_field("author", &self.author)?;
+        state.serialize_field("author_auth_key", &author_auth)?;
+        state.serialize_field("txn_accumulator_root", &self.txn_accumulator_root)?;
+        state.serialize_field("block_accumulator_root", &self.block_accumulator_root)?;
+        state.serialize_field("state_root", &self.state_root)?;
+        state.serialize_field("gas_used", &self.gas_used)?;
+        state.serialize_field("difficulty", &self.difficulty)?;
+        state.serialize_field("body_hash", &self.body_hash)?;
+        state.serialize_field("chain_id", &self.chain_id)?;
+        state.serialize_field("nonce", &self.nonce)?;
+        state.serialize_field("extra", &self.extra)?;
+        if let Some(author_signature) = author_signature {
+            state.serialize_field("author_signature", author_signature)?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for BlockHeader {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            self.serialize_with(
+                serializer,
+                self.author_auth_key,
+                self.author_signature.as_ref(),
+            )
+        } else {
+            let author_auth = match (&self.author_auth_key, &self.author_signature) {
+                (_, Some(author_signature)) => AuthorAuthRef::AuthorSignature(author_signature),
+                (Some(author_auth_key), None) => AuthorAuthRef::AuthKey(author_auth_key),
+                (None, None) => AuthorAuthRef::None,
+            };
+            self.serialize_with(serializer, author_auth, None)
+        }
+    }
+}
+
+/// The header without the author signature, the author signs the hash of it.
+struct UnsignedBlockHeader<'a>(&'a BlockHeader);
+
+impl<'a> Serialize for UnsignedBlockHeader<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0
+            .serialize_with(serializer, self.0.author_auth_key, None)
+    }
+}
+
 impl<'de> Deserialize<'de> for BlockHeader {
     fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
     where
         D: Deserializer<'de>,
     {
+        // The binary format use `()` as the author signature, which takes no bytes,
+        // the signature is encoded in the auth key slot.
         #[derive(Deserialize)]
         #[serde(rename = "BlockHeader")]
-        struct BlockHeaderData {
+        struct BlockHeaderData<A, S> {
             parent_hash: HashValue,
             timestamp: u64,
             number: BlockNumber,
             author: AccountAddress,
-            author_auth_key: Option<AuthenticationKey>,
+            author_auth_key: A,
             txn_accumulator_root: HashValue,
             block_accumulator_root: HashValue,
             state_root: HashValue,
@@ -372,26 +491,60 @@ impl<'de> Deserialize<'de> for BlockHeader {
             chain_id: ChainId,
             nonce: u32,
             extra: BlockHeaderExtra,
+            #[serde(default)]
+            author_signature: S,
         }
 
-        let header_data = BlockHeaderData::deserialize(deserializer)?;
-        let block_header = Self::new_with_auth_key(
-            header_data.parent_hash,
-            header_data.timestamp,
-            header_data.number,
-            header_data.author,
-            header_data.author_auth_key,
-            header_data.txn_accumulator_root,
-            header_data.block_accumulator_root,
-            header_data.state_root,
-            header_data.gas_used,
-            header_data.difficulty,
-            header_data.body_hash,
-            header_data.chain_id,
-            header_data.nonce,
-            header_data.extra,
-        );
-        Ok(block_header)
+        impl<A, S> BlockHeaderData<A, S> {
+            fn into_header(
+                self,
+                author_auth_key: Option<AuthenticationKey>,
+                author_signature: Option<SignedMessage>,
+            ) -> BlockHeader {
+                let mut header = BlockHeader::new_with_auth_key(
+                    self.parent_hash,
+                    self.timestamp,
+                    self.number,
+                    self.author,
+                    author_auth_key,
+                    self.txn_accumulator_root,
+                    self.block_accumulator_root,
+                    self.state_root,
+                    self.gas_used,
+                    self.difficulty,
+                    self.body_hash,
+                    self.chain_id,
+                    self.nonce,
+                    self.extra,
+                );
+                // the signature is a part of the block id.
+                if author_signature.is_some() {
+                    header.author_signature = author_signature;
+                    header.id = Some(header.crypto_hash());
+                }
+                header
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            let mut header_data = BlockHeaderData::<
+                Option<AuthenticationKey>,
+                Option<SignedMessage>,
+            >::deserialize(deserializer)?;
+            let author_auth_key = header_data.author_auth_key;
+            let author_signature = header_data.author_signature.take();
+            Ok(header_data.into_header(author_auth_key, author_signature))
+        } else {
+            let header_data = BlockHeaderData::<AuthorAuth, ()>::deserialize(deserializer)?;
+            let (author_auth_key, author_signature) = match &header_data.author_auth_key {
+                AuthorAuth::None => (None, None),
+                AuthorAuth::AuthKey(author_auth_key) => (Some(*author_auth_key), None),
+                AuthorAuth::AuthorSignature(author_signature) => {
+                    (None, Some(author_signature.clone()))
+                }
+            };
+            Ok(header_data.into_header(author_auth_key, author_signature))
+        }
     }
 }
 
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block::{Block, BlockHeaderExtra, ExecutedBlock};
+use crate::block::{Block, BlockHeaderExtra, BlockNumber, ExecutedBlock};
 use crate::sync_status::SyncStatus;
 use crate::U256;
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,26 @@ pub struct NewHeadBlock(pub Arc<ExecutedBlock>);
 #[derive(Clone, Debug)]
 pub struct NewBranch(pub Arc<ExecutedBlock>);
 
+/// Fire this event when the main chain switch to a new branch which is not the child of the main head,
+/// or the new branch is refused because the reorg depth exceeds the limit.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorgEvent {
+    pub old_head: HashValue,
+    pub old_head_number: BlockNumber,
+    pub new_head: HashValue,
+    pub new_head_number: BlockNumber,
+    /// the common ancestor of the old head and new head.
+    pub ancestor: HashValue,
+    /// the count of blocks which need to rollback from the main chain.
+    pub depth: u64,
+    /// the count of blocks which need to apply to the main chain.
+    pub enacted_blocks: u64,
+    /// the count of user transactions in the rollback blocks.
+    pub retracted_txns: u64,
+    /// the reorg is refused because of the max reorg depth limit.
+    pub refused: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct MinedBlock(pub Arc<Block>);
 
@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use codespan_reporting::diagnostic::Severity;
+use codespan_reporting::term::termcolor::Buffer;
+use errmapgen::{ErrmapGen, ErrmapOptions};
+use starcoin_vm_types::access::ModuleAccess;
+use starcoin_vm_types::errmap::ErrorMapping;
+use std::collections::BTreeSet;
+
+/// Extension for error map files, which are in BCS format
+pub const ERROR_MAP_EXTENSION: &str = "errmap";
+
+/// Build the error map of the modules defined in `targets`, from the doc comments of their error
+/// constants. The error categories come from the `Errors` module of stdlib, which should be in
+/// `deps`.
+pub fn build_error_map(targets: &[String], deps: &[String]) -> Result<ErrorMapping> {
+    let env = move_model::run_model_builder(targets, deps)?;
+    if env.has_errors() {
+        let mut error_writer = Buffer::no_color();
+        env.report_diag(&mut error_writer, Severity::Warning);
+        bail!(
+            "build error map failed:\n{}",
+            String::from_utf8_lossy(error_writer.as_slice())
+        );
+    }
+    let target_modules: BTreeSet<_> = env
+        .get_modules()
+        .filter(|module_env| module_env.is_target())
+        .map(|module_env| module_env.get_verified_module().self_id())
+        .collect();
+
+    let temp_dir = tempfile::tempdir()?;
+    let output_file = temp_dir
+        .path()
+        .join("error_map")
+        .with_extension(ERROR_MAP_EXTENSION);
+    let options = ErrmapOptions {
+        output_file: output_file.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let mut errmap_gen = ErrmapGen::new(&env, &options);
+    errmap_gen.gen();
+    errmap_gen.save_result();
+
+    let mut error_map: ErrorMapping = bcs_ext::from_bytes(&std::fs::read(output_file)?)?;
+    error_map
+        .module_error_maps
+        .retain(|module_id, _| target_modules.contains(module_id));
+    Ok(error_map)
+}
@@ -20,8 +20,10 @@ use std::path::{Path, PathBuf};
 pub use move_lang::{
     compiled_unit::{verify_units, CompiledUnit},
     errors::*,
-    move_compile, move_compile_and_report, MOVE_COMPILED_EXTENSION, MOVE_EXTENSION,
+    generate_interface_files, move_compile, move_compile_and_report, MOVE_COMPILED_EXTENSION,
+    MOVE_EXTENSION,
 };
+pub mod error_map;
 pub mod utils;
 
 pub mod errors {
@@ -31,9 +31,11 @@ use vm::{
 pub mod dependencies;
 pub mod function_resolver;
 pub mod functional_test;
+pub mod manifest;
 pub mod package;
 pub mod remote_state;
 pub mod test;
+pub mod unit_test;
 
 /// Default directory where saved Move resources live
 pub const DEFAULT_STORAGE_DIR: &str = "storage";
@@ -47,6 +49,15 @@ pub const DEFAULT_TEST_DIR: &str = "tests";
 /// Default directory where Move packages live under build_dir
 pub const DEFAULT_PACKAGE_DIR: &str = "package";
 
+/// Default directory where the state forked by unit tests is cached under build_dir
+pub const DEFAULT_FORK_CACHE_DIR: &str = "fork_cache";
+
+/// Default directory where the unit test coverage reports are written under build_dir
+pub const DEFAULT_COVERAGE_DIR: &str = "coverage";
+
+/// Default directory where the on-chain dependencies of the prover are kept under build_dir
+pub const DEFAULT_PROVER_DIR: &str = "prover";
+
 /// Default dependency inclusion mode
 pub const DEFAULT_DEP_MODE: &str = "stdlib";
 
@@ -4,8 +4,13 @@
 use anyhow::{anyhow, bail, Result};
 use move_cli::dependencies::ModuleDependencyResolver;
 use move_cli::function_resolver::FunctionResolver;
+use move_cli::manifest::Manifest;
 use move_cli::package::DepMode;
-use move_cli::remote_state::{resolve_deps, MergedRemoteCache, RemoteStateView};
+use move_cli::remote_state::{
+    resolve_deps, CachedRemoteStateView, MergedRemoteCache, RemoteStateView,
+};
+use move_cli::test::{DEFAULT_TRACE_FILE, MOVE_VM_TRACING_ENV_VAR_NAME};
+use move_cli::unit_test::run_tests_on_fork;
 use move_cli::{
     package::{parse_mode_from_string, Mode},
     *,
@@ -21,22 +26,28 @@ use move_core_types::{
     transaction_argument::{convert_txn_args, TransactionArgument},
     vm_status::{AbortLocation, StatusCode, VMStatus},
 };
+use move_coverage::coverage_map::CoverageMap;
+use move_coverage::report::CoverageReport;
 use move_lang::shared::Flags;
 use move_lang::{self, compiled_unit::CompiledUnit, MOVE_COMPILED_EXTENSION};
 use move_unit_test::UnitTestingConfig;
 use move_vm_runtime::data_cache::MoveStorage;
 use move_vm_runtime::{logging::NoContextLog, move_vm::MoveVM};
 use starcoin_config::INITIAL_GAS_SCHEDULE;
+use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_functional_tests::executor::FakeExecutor;
 use starcoin_functional_tests::testsuite::PRETTY;
+use starcoin_move_compiler::error_map::{build_error_map, ERROR_MAP_EXTENSION};
 use starcoin_vm_types::gas_schedule::GasStatus;
+use starcoin_vm_types::transaction::{Module as PackageModule, Package};
 use std::num::NonZeroUsize;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     ffi::OsStr,
     fs,
     path::{Path, PathBuf},
 };
+use stdlib::prover::{prove, ProverConfig};
 use stdlib::restore_stdlib_in_dir;
 use structopt::StructOpt;
 use tempfile::tempdir;
@@ -83,6 +94,9 @@ pub struct Move {
     #[structopt(long, global = true)]
     /// block height to fork from. default to latest block number
     block_number: Option<u64>,
+    /// Re-resolve the dependencies declared in Move.toml, ignore the versions pinned in Move.lock
+    #[structopt(long = "update-lock", global = true)]
+    update_lock: bool,
     /// Print additional diagnostics
     #[structopt(short = "v", global = true)]
     verbose: bool,
@@ -131,6 +145,42 @@ pub enum Command {
         #[structopt(long = "ignore-breaking-changes")]
         ignore_breaking_changes: bool,
     },
+    /// Compile the package declared in Move.toml and save the module bytecodes under `build_dir`
+    #[structopt(name = "build")]
+    Build {
+        /// The source files of the package
+        #[structopt(
+            name = "PATH_TO_SOURCE_FILE",
+            default_value = DEFAULT_SOURCE_DIR,
+        )]
+        source_files: Vec<String>,
+    },
+    /// Build the package declared in Move.toml and bundle its modules into a package blob
+    #[structopt(name = "package")]
+    Package {
+        /// The source files of the package
+        #[structopt(
+            name = "PATH_TO_SOURCE_FILE",
+            default_value = DEFAULT_SOURCE_DIR,
+        )]
+        source_files: Vec<String>,
+    },
+    /// Run the Move prover on the package, with the dependencies resolved as `build` does
+    #[structopt(name = "prove")]
+    Prove {
+        /// The source files of the package
+        #[structopt(
+            name = "PATH_TO_SOURCE_FILE",
+            default_value = DEFAULT_SOURCE_DIR,
+        )]
+        source_files: Vec<String>,
+        /// Timeout in seconds of each verification condition
+        #[structopt(long = "vc-timeout")]
+        vc_timeout: Option<usize>,
+        /// Print the diagnostics as JSON
+        #[structopt(long = "json")]
+        json: bool,
+    },
     /// Compile/run a Move script that reads/writes resources stored on disk in `storage`.
     /// This command compiles the script first before running it.
     #[structopt(name = "run")]
@@ -244,6 +294,25 @@ pub enum Command {
         /// the execution result from Move VM.
         #[structopt(long = "stackless")]
         check_stackless_vm: bool,
+
+        /// Run the tests against the state forked from `--starcoin-rpc` at `--block-number`,
+        /// and report the gas used by each test
+        #[structopt(long = "fork")]
+        fork: bool,
+
+        /// Run the forked tests against the state cached by a previous run, without network access.
+        /// `--block-number` is required.
+        #[structopt(long = "offline", requires = "fork")]
+        offline: bool,
+
+        /// Maximum number of gas units each forked test can use
+        #[structopt(long = "fork-gas-budget", default_value = "100000000")]
+        fork_gas_budget: u64,
+
+        /// Track the coverage of the modules in the source files, and write lcov and HTML
+        /// reports into `build_dir`/coverage. Requires a debug build of move
+        #[structopt(long = "coverage")]
+        coverage: bool,
     },
 
     /// Run expected value tests using the given batch file
@@ -332,6 +401,182 @@ impl Move {
 
         Ok(state)
     }
+
+    /// Resolve the dependencies declared in the Move.toml of current directory and load them into
+    /// `state`. Return None if there is no manifest.
+    fn prepare_manifest_deps(&self, state: &OnDiskStateView) -> Result<Option<Manifest>> {
+        let root = std::env::current_dir()?;
+        let manifest = match Manifest::load(&root)? {
+            Some(manifest) => manifest,
+            None => return Ok(None),
+        };
+        let deps = manifest.resolve(
+            &root,
+            &self.get_package_dir(),
+            self.starcoin_rpc.as_str(),
+            self.block_number,
+            self.update_lock,
+        )?;
+        state.save_modules(&deps.modules)?;
+
+        if !deps.source_dirs.is_empty() {
+            if self.verbose {
+                println!("Compiling dependencies in {:?}...", deps.source_dirs);
+            }
+            let (_, compiled_units) = move_lang::move_compile_and_report(
+                &deps.source_dirs,
+                &[state.interface_files_dir()?],
+                None,
+                Flags::empty().set_sources_shadow_deps(true),
+            )?;
+            let mut serialized_modules = vec![];
+            for unit in compiled_units {
+                if let CompiledUnit::Module { module, .. } = unit {
+                    let mut module_bytes = vec![];
+                    module.serialize(&mut module_bytes)?;
+                    serialized_modules.push((module.self_id(), module_bytes));
+                }
+            }
+            state.save_modules(&serialized_modules)?;
+        }
+        Ok(Some(manifest))
+    }
+
+    /// Resolve the dependencies of the prover, return the source files and the dependencies.
+    ///
+    /// The dependencies deployed on chain are only available as bytecode, the prover uses the
+    /// interface files generated from them.
+    fn prepare_prover_deps(&self, source_files: &[String]) -> Result<(Vec<String>, Vec<String>)> {
+        let package_dir = self.get_package_dir();
+        self.mode.prepare(&package_dir, true)?;
+        let mut deps = self.mode.source_files(&package_dir)?;
+
+        let prover_dir = Path::new(&self.build_dir).join(DEFAULT_PROVER_DIR);
+        if prover_dir.exists() {
+            fs::remove_dir_all(&prover_dir)?;
+        }
+        let prover_state =
+            OnDiskStateView::create(prover_dir.clone(), prover_dir.join(DEFAULT_STORAGE_DIR))?;
+        let mut has_onchain_deps = false;
+
+        let root = std::env::current_dir()?;
+        let manifest = Manifest::load(&root)?;
+        if let Some(manifest) = &manifest {
+            let resolved = manifest.resolve(
+                &root,
+                &package_dir,
+                self.starcoin_rpc.as_str(),
+                self.block_number,
+                self.update_lock,
+            )?;
+            deps.extend(resolved.source_dirs);
+            prover_state.save_modules(&resolved.modules)?;
+            has_onchain_deps |= !resolved.modules.is_empty();
+        }
+        let source_files = package_source_files(manifest.as_ref(), source_files)?;
+
+        if self.mode.1 == DepMode::OnChain {
+            let view = RemoteStateView::from_url(self.starcoin_rpc.as_str(), self.block_number)?;
+            let mut found_modules = resolve_deps(&view, &source_files)?;
+            let module_deps = view.get_module_dependencies_recursively_for_all(&found_modules)?;
+            found_modules.extend(module_deps.values().cloned());
+            let mut serialized_modules = vec![];
+            for module in found_modules {
+                let mut module_bytes = vec![];
+                module.serialize(&mut module_bytes)?;
+                serialized_modules.push((module.self_id(), module_bytes));
+            }
+            prover_state.save_modules(&serialized_modules)?;
+            has_onchain_deps |= !serialized_modules.is_empty();
+        }
+
+        if has_onchain_deps {
+            deps.push(prover_state.interface_files_dir()?);
+        }
+        Ok((source_files, deps))
+    }
+}
+
+/// Use the source dir declared in the manifest if no source files are given.
+fn package_source_files(manifest: Option<&Manifest>, files: &[String]) -> Result<Vec<String>> {
+    match manifest {
+        Some(manifest) if files == [DEFAULT_SOURCE_DIR] => Ok(vec![manifest
+            .source_dir(&std::env::current_dir()?)
+            .display()
+            .to_string()]),
+        _ => Ok(files.to_vec()),
+    }
+}
+
+/// Compile the modules in `files` and save the bytecodes into `output_dir`
+fn build(
+    state: &OnDiskStateView,
+    files: &[String],
+    output_dir: &Path,
+    verbose: bool,
+) -> Result<Vec<CompiledModule>> {
+    if verbose {
+        println!("Building Move modules...")
+    }
+    let (_, compiled_units) = move_lang::move_compile_and_report(
+        files,
+        &[state.interface_files_dir()?],
+        None,
+        Flags::empty().set_sources_shadow_deps(true),
+    )?;
+
+    if output_dir.exists() {
+        fs::remove_dir_all(output_dir)?;
+    }
+    fs::create_dir_all(output_dir)?;
+    let mut modules = vec![];
+    for unit in compiled_units {
+        match unit {
+            CompiledUnit::Module { ident, module, .. } => {
+                let mut module_bytes = vec![];
+                module.serialize(&mut module_bytes)?;
+                let file_path = output_dir
+                    .join(ident.module_name.0.value)
+                    .with_extension(MOVE_COMPILED_EXTENSION);
+                fs::write(&file_path, &module_bytes)?;
+                if verbose {
+                    println!(
+                        "Saved module {} to {}",
+                        module.self_id(),
+                        file_path.display()
+                    );
+                }
+                modules.push(module);
+            }
+            CompiledUnit::Script { loc, .. } => {
+                if verbose {
+                    println!(
+                        "Warning: Found script in package files. The script will be ignored: {}",
+                        loc.file()
+                    )
+                }
+            }
+        }
+    }
+    Ok(modules)
+}
+
+/// Bundle the `modules` into a package blob saved as `output_file`
+fn package(modules: Vec<CompiledModule>, output_file: &Path) -> Result<()> {
+    let mut package_modules = vec![];
+    for module in modules {
+        let mut module_bytes = vec![];
+        module.serialize(&mut module_bytes)?;
+        package_modules.push(PackageModule::new(module_bytes));
+    }
+    let package = Package::new_with_modules(package_modules)?;
+    fs::write(output_file, bcs::to_bytes(&package)?)?;
+    println!(
+        "Package {} saved to {}",
+        package.crypto_hash(),
+        output_file.display()
+    );
+    Ok(())
 }
 
 /// Compile the user modules in `src` and the script in `script_file`
@@ -1225,8 +1470,33 @@ fn doctor(state: OnDiskStateView) -> Result<()> {
     Ok(())
 }
 
+/// Enable the execution tracing of the Move VM for `unit-test --coverage`.
+/// The VM reads the trace file from the env var only once, when the first VM is initialised, so
+/// this must be called before any VM is created. The tracing is only compiled in debug builds of
+/// the VM, so the coverage can not be collected by a release build.
+fn enable_coverage_tracing(move_args: &Move) -> Result<()> {
+    if let Command::UnitTest { coverage: true, .. } = &move_args.cmd {
+        if !cfg!(debug_assertions) {
+            bail!(
+                "unit-test --coverage requires a debug build of move, \
+                 the Move VM does not trace the execution in release builds"
+            );
+        }
+        let coverage_dir = Path::new(&move_args.build_dir).join(DEFAULT_COVERAGE_DIR);
+        let trace_file = coverage_dir.join(DEFAULT_TRACE_FILE);
+        fs::create_dir_all(&coverage_dir)?;
+        if trace_file.exists() {
+            fs::remove_file(&trace_file)?;
+        }
+        // the VM appends the execution trace of all tests to this file.
+        std::env::set_var(MOVE_VM_TRACING_ENV_VAR_NAME, &trace_file);
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let move_args: Move = Move::from_args();
+    enable_coverage_tracing(&move_args)?;
     match &move_args.cmd {
         Command::Scaffold { path } => test::create_test_scaffold(path),
         Command::Check {
@@ -1234,6 +1504,8 @@ fn main() -> Result<()> {
             no_republish,
         } if move_args.mode.1 == DepMode::OnChain => {
             let state = move_args.prepare_state(true)?;
+            let manifest = move_args.prepare_manifest_deps(&state)?;
+            let source_files = package_source_files(manifest.as_ref(), source_files)?;
 
             // get deps first.
             let view =
@@ -1262,6 +1534,8 @@ fn main() -> Result<()> {
             no_republish,
         } => {
             let state = move_args.prepare_state(true)?;
+            let manifest = move_args.prepare_manifest_deps(&state)?;
+            let source_files = package_source_files(manifest.as_ref(), source_files)?;
             check(state, !*no_republish, &source_files, move_args.verbose)
         }
         Command::Publish {
@@ -1270,14 +1544,73 @@ fn main() -> Result<()> {
             ignore_breaking_changes,
         } => {
             let state = move_args.prepare_state(true)?;
+            let manifest = move_args.prepare_manifest_deps(&state)?;
+            let source_files = package_source_files(manifest.as_ref(), source_files)?;
             publish(
                 state,
-                source_files,
+                &source_files,
                 !*no_republish,
                 *ignore_breaking_changes,
                 move_args.verbose,
             )
         }
+        Command::Build { source_files } | Command::Package { source_files } => {
+            let state = move_args.prepare_state(true)?;
+            let manifest = move_args.prepare_manifest_deps(&state)?.ok_or_else(|| {
+                anyhow!(
+                    "No {} found in current directory",
+                    move_cli::manifest::MANIFEST_FILE_NAME
+                )
+            })?;
+            let source_files = package_source_files(Some(&manifest), source_files)?;
+            let build_dir = Path::new(&move_args.build_dir);
+            let modules = build(
+                &state,
+                &source_files,
+                &build_dir.join(&manifest.package.name),
+                move_args.verbose,
+            )?;
+            if let Command::Package { .. } = &move_args.cmd {
+                let output_file = build_dir
+                    .join(format!(
+                        "{}-{}",
+                        manifest.package.name, manifest.package.version
+                    ))
+                    .with_extension("blob");
+                package(modules, &output_file)?;
+                let error_map = build_error_map(&source_files, &[state.interface_files_dir()?])?;
+                let error_map_file = output_file.with_extension(ERROR_MAP_EXTENSION);
+                fs::write(&error_map_file, bcs::to_bytes(&error_map)?)?;
+                if move_args.verbose {
+                    println!("Error map saved to {}", error_map_file.display());
+                }
+            }
+            Ok(())
+        }
+        Command::Prove {
+            source_files,
+            vc_timeout,
+            json,
+        } => {
+            let (source_files, deps) = move_args.prepare_prover_deps(source_files)?;
+            let config = ProverConfig {
+                vc_timeout: *vc_timeout,
+                verbose: move_args.verbose,
+            };
+            let result = prove(&source_files, &deps, &config)?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                print!("{}", result.rendered);
+            }
+            if !result.success {
+                bail!("Prover found errors in the package");
+            }
+            if !*json {
+                println!("Specifications verified successfully");
+            }
+            Ok(())
+        }
         Command::Run {
             script_file,
             script_name,
@@ -1408,7 +1741,15 @@ fn main() -> Result<()> {
             report_storage_on_error,
             source_files,
             check_stackless_vm,
+            fork,
+            offline,
+            fork_gas_budget,
+            coverage,
         } => {
+            // the tracing is enabled by `enable_coverage_tracing` at the start of main.
+            let coverage_dir = Path::new(&move_args.build_dir).join(DEFAULT_COVERAGE_DIR);
+            let trace_file = coverage_dir.join(DEFAULT_TRACE_FILE);
+
             let mut sources = source_files.clone();
             // only support packages deps
             sources.push(move_args.get_package_dir().display().to_string());
@@ -1426,7 +1767,99 @@ fn main() -> Result<()> {
             };
             let test_plan = testing_config.build_test_plan();
             if let Some(test_plan) = test_plan {
-                testing_config.run_and_report_unit_tests(test_plan, std::io::stdout())?;
+                // the modules defined in the user source files, rather than in the dependencies.
+                let user_modules: BTreeSet<_> = if *fork || *coverage {
+                    let (_, compiled_units) = move_lang::move_compile_and_report(
+                        source_files,
+                        &[move_args.get_package_dir().display().to_string()],
+                        None,
+                        Flags::empty(),
+                    )?;
+                    compiled_units
+                        .into_iter()
+                        .filter_map(|unit| match unit {
+                            CompiledUnit::Module { module, .. } => Some(module.self_id()),
+                            CompiledUnit::Script { .. } => None,
+                        })
+                        .collect()
+                } else {
+                    BTreeSet::new()
+                };
+                let covered_modules: Vec<_> = if *coverage {
+                    test_plan
+                        .module_info
+                        .values()
+                        .filter_map(|unit| match unit {
+                            CompiledUnit::Module {
+                                module, source_map, ..
+                            } if user_modules.contains(&module.self_id()) => {
+                                Some((module.clone(), source_map.clone()))
+                            }
+                            _ => None,
+                        })
+                        .collect()
+                } else {
+                    vec![]
+                };
+
+                let mut failed = 0;
+                if *fork {
+                    let remote = if *offline {
+                        None
+                    } else {
+                        Some(RemoteStateView::from_url(
+                            move_args.starcoin_rpc.as_str(),
+                            move_args.block_number,
+                        )?)
+                    };
+                    let block_number = match (&remote, move_args.block_number) {
+                        (Some(remote), _) => remote.block_number(),
+                        (None, Some(block_number)) => block_number,
+                        (None, None) => bail!("--block-number is required by offline fork tests"),
+                    };
+                    let cache_dir = Path::new(&move_args.build_dir)
+                        .join(DEFAULT_FORK_CACHE_DIR)
+                        .join(block_number.to_string());
+                    println!(
+                        "Running Move unit tests on the state of block {}",
+                        block_number
+                    );
+                    // the modules in user source files shadow the deployed ones.
+                    let results = run_tests_on_fork(
+                        &test_plan,
+                        CachedRemoteStateView::new(remote, cache_dir),
+                        &user_modules,
+                        filter.as_deref(),
+                        *fork_gas_budget,
+                        std::io::stdout(),
+                    )?;
+                    failed = results.iter().filter(|r| !r.passed).count();
+                } else {
+                    testing_config.run_and_report_unit_tests(test_plan, std::io::stdout())?;
+                }
+
+                if *coverage {
+                    let coverage_map = if trace_file.exists() {
+                        CoverageMap::from_trace_file(&trace_file)
+                    } else {
+                        CoverageMap {
+                            exec_maps: BTreeMap::new(),
+                        }
+                    };
+                    let report = CoverageReport::new(&coverage_map, &covered_modules)?;
+                    let lcov_file = coverage_dir.join("lcov.info");
+                    report.write_lcov(&mut fs::File::create(&lcov_file)?)?;
+                    let html_dir = coverage_dir.join("html");
+                    report.write_html(&html_dir)?;
+                    println!(
+                        "Coverage reports are saved to {} and {}",
+                        lcov_file.display(),
+                        html_dir.join("index.html").display()
+                    );
+                }
+                if failed > 0 {
+                    bail!("{} tests failed", failed);
+                }
             }
             Ok(())
         }
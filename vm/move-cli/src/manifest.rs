@@ -0,0 +1,413 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::remote_state::RemoteStateView;
+use anyhow::{bail, ensure, format_err, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_vm_runtime::data_cache::MoveStorage;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File name of the package manifest
+pub const MANIFEST_FILE_NAME: &str = "Move.toml";
+/// File name of the lock file which pins the resolved dependencies
+pub const LOCK_FILE_NAME: &str = "Move.lock";
+/// Directory name for the checked out git dependencies under package/
+const GIT_DEPS_DIR: &str = "git";
+
+fn default_source_dir() -> String {
+    crate::DEFAULT_SOURCE_DIR.to_string()
+}
+
+/// The `Move.toml` package manifest.
+///
+/// ```toml
+/// [package]
+/// name = "my_token"
+/// version = "0.1.0"
+///
+/// [dependencies]
+/// utils = { local = "../utils" }
+/// swap = { git = "https://github.com/someone/swap", rev = "v1.0.0", subdir = "src" }
+/// framework = { address = "0x1", modules = ["Token", "Account"] }
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Manifest {
+    pub package: PackageInfo,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: String,
+    /// Directory of the package source files, relative to the manifest
+    #[serde(default = "default_source_dir")]
+    pub source_dir: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Dependency {
+    /// Move source files in a local directory, relative to the manifest.
+    Local { local: PathBuf },
+    /// Move source files in a git repository, `rev` can be a branch, a tag or a commit.
+    Git {
+        git: String,
+        rev: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subdir: Option<String>,
+    },
+    /// Modules already published on chain under `address`.
+    OnChain {
+        address: String,
+        modules: Vec<String>,
+    },
+}
+
+/// The `Move.lock` file, written next to the manifest.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct LockFile {
+    /// Block number the on-chain dependencies are resolved at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, LockedDependency>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum LockedDependency {
+    Local {
+        local: PathBuf,
+    },
+    Git {
+        git: String,
+        rev: String,
+        /// The commit `rev` is resolved to.
+        commit: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subdir: Option<String>,
+    },
+    OnChain {
+        address: AccountAddress,
+        /// sha3-256 hash of each module's bytecode.
+        modules: BTreeMap<String, HashValue>,
+    },
+}
+
+/// Dependencies resolved from the manifest and the lock file.
+#[derive(Debug, Default)]
+pub struct ResolvedDependencies {
+    /// Source directories of the local and git dependencies.
+    pub source_dirs: Vec<String>,
+    /// Bytecode of the on-chain dependencies.
+    pub modules: Vec<(ModuleId, Vec<u8>)>,
+}
+
+impl Manifest {
+    /// Load the manifest under `root`, return None if there is no manifest.
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let manifest = toml::from_str(&content)
+            .map_err(|e| format_err!("Invalid manifest {}: {}", path.display(), e))?;
+        Ok(Some(manifest))
+    }
+
+    pub fn source_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.package.source_dir)
+    }
+
+    /// Resolve the dependencies and write the lock file under `root`.
+    ///
+    /// Dependencies pinned in an existing lock file are resolved to the pinned commit or module
+    /// hash, unless `update_lock` is set. On-chain dependencies are resolved at `block_number`,
+    /// or the block number pinned in the lock file if it is not given.
+    /// Git dependencies are checked out under `package_dir`.
+    pub fn resolve(
+        &self,
+        root: &Path,
+        package_dir: &Path,
+        starcoin_rpc: &str,
+        block_number: Option<u64>,
+        update_lock: bool,
+    ) -> Result<ResolvedDependencies> {
+        let lock_path = root.join(LOCK_FILE_NAME);
+        let old_lock = if update_lock {
+            LockFile::default()
+        } else {
+            LockFile::load(&lock_path)?.unwrap_or_default()
+        };
+        let mut new_lock = LockFile::default();
+        let mut resolved = ResolvedDependencies::default();
+        let mut on_chain_deps = vec![];
+
+        for (name, dep) in &self.dependencies {
+            let locked = old_lock.dependencies.get(name);
+            match dep {
+                Dependency::Local { local } => {
+                    let path = root.join(local);
+                    ensure!(
+                        path.exists(),
+                        "Local dependency {} not found at {}",
+                        name,
+                        path.display()
+                    );
+                    resolved.source_dirs.push(path.display().to_string());
+                    new_lock.dependencies.insert(
+                        name.clone(),
+                        LockedDependency::Local {
+                            local: local.clone(),
+                        },
+                    );
+                }
+                Dependency::Git { git, rev, subdir } => {
+                    let pinned_commit = match locked {
+                        Some(LockedDependency::Git {
+                            git: locked_git,
+                            rev: locked_rev,
+                            commit,
+                            ..
+                        }) if locked_git == git && locked_rev == rev => Some(commit.as_str()),
+                        _ => None,
+                    };
+                    let checkout_dir = package_dir.join(GIT_DEPS_DIR).join(name);
+                    let commit = checkout_git(git, rev, pinned_commit, &checkout_dir)?;
+                    let source_dir = match subdir {
+                        Some(subdir) => checkout_dir.join(subdir),
+                        None => checkout_dir,
+                    };
+                    resolved.source_dirs.push(source_dir.display().to_string());
+                    new_lock.dependencies.insert(
+                        name.clone(),
+                        LockedDependency::Git {
+                            git: git.clone(),
+                            rev: rev.clone(),
+                            commit,
+                            subdir: subdir.clone(),
+                        },
+                    );
+                }
+                Dependency::OnChain { address, modules } => {
+                    let address = AccountAddress::from_hex_literal(address).map_err(|e| {
+                        format_err!("Invalid address of dependency {}: {}", name, e)
+                    })?;
+                    on_chain_deps.push((name, address, modules));
+                }
+            }
+        }
+
+        if !on_chain_deps.is_empty() {
+            // an explicit block number overrides the one pinned in the lock file.
+            let block_number = block_number.or(old_lock.block_number);
+            let view = RemoteStateView::from_url(starcoin_rpc, block_number)?;
+            new_lock.block_number = Some(view.block_number());
+            for (name, address, modules) in on_chain_deps {
+                let pinned_hashes = match old_lock.dependencies.get(name) {
+                    Some(LockedDependency::OnChain {
+                        address: locked_address,
+                        modules,
+                    }) if locked_address == &address => Some(modules),
+                    _ => None,
+                };
+                let mut module_hashes = BTreeMap::new();
+                for module_name in modules {
+                    let module_id = ModuleId::new(address, Identifier::new(module_name.as_str())?);
+                    let bytes = view
+                        .get_module(&module_id)
+                        .map_err(|e| e.into_vm_status())?
+                        .ok_or_else(|| {
+                            format_err!(
+                                "Module {} of dependency {} not found on chain at block {}",
+                                module_id,
+                                name,
+                                view.block_number()
+                            )
+                        })?;
+                    let hash = HashValue::sha3_256_of(&bytes);
+                    if let Some(pinned_hash) = pinned_hashes.and_then(|h| h.get(module_name)) {
+                        if pinned_hash != &hash {
+                            bail!(
+                                "Module {} of dependency {} has hash {}, but {} is pinned in {}, \
+                                 use --update-lock to accept the new version",
+                                module_id,
+                                name,
+                                hash,
+                                pinned_hash,
+                                LOCK_FILE_NAME
+                            );
+                        }
+                    }
+                    module_hashes.insert(module_name.clone(), hash);
+                    resolved.modules.push((module_id, bytes));
+                }
+                new_lock.dependencies.insert(
+                    name.clone(),
+                    LockedDependency::OnChain {
+                        address,
+                        modules: module_hashes,
+                    },
+                );
+            }
+        }
+
+        new_lock.save(&lock_path)?;
+        Ok(resolved)
+    }
+}
+
+impl LockFile {
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        let lock = toml::from_str(&content)
+            .map_err(|e| format_err!("Invalid lock file {}: {}", path.display(), e))?;
+        Ok(Some(lock))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = format!(
+            "# This file is generated by move-cli, do not edit it by hand.\n{}",
+            toml::to_string(self)?
+        );
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Check out `rev` of the git repository `url` into `dest`, or the `pinned_commit` if it is set.
+/// Return the commit which is checked out.
+fn checkout_git(url: &str, rev: &str, pinned_commit: Option<&str>, dest: &Path) -> Result<String> {
+    if !dest.join(".git").exists() {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        git(
+            None,
+            &["clone", "--quiet", url, &dest.display().to_string()],
+        )?;
+    } else {
+        git(Some(dest), &["fetch", "--quiet", "--tags", "origin"])?;
+    }
+    let target = match pinned_commit {
+        Some(commit) => commit.to_string(),
+        // prefer the remote branch, so a stale local branch is not used.
+        None => {
+            let remote_branch = format!("origin/{}", rev);
+            if git(
+                Some(dest),
+                &["rev-parse", "--verify", "--quiet", &remote_branch],
+            )
+            .is_ok()
+            {
+                remote_branch
+            } else {
+                rev.to_string()
+            }
+        }
+    };
+    git(Some(dest), &["checkout", "--quiet", "--detach", &target])?;
+    git(Some(dest), &["rev-parse", "HEAD"])
+}
+
+fn git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.args(args).output()?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [package]
+            name = "my_token"
+            version = "0.1.0"
+
+            [dependencies]
+            utils = { local = "../utils" }
+            swap = { git = "https://github.com/someone/swap", rev = "v1.0.0" }
+            framework = { address = "0x1", modules = ["Token"] }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.package.source_dir, crate::DEFAULT_SOURCE_DIR);
+        assert_eq!(
+            manifest.dependencies.get("utils"),
+            Some(&Dependency::Local {
+                local: PathBuf::from("../utils")
+            })
+        );
+        assert_eq!(
+            manifest.dependencies.get("swap"),
+            Some(&Dependency::Git {
+                git: "https://github.com/someone/swap".to_string(),
+                rev: "v1.0.0".to_string(),
+                subdir: None,
+            })
+        );
+        assert_eq!(
+            manifest.dependencies.get("framework"),
+            Some(&Dependency::OnChain {
+                address: "0x1".to_string(),
+                modules: vec!["Token".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_lock_file_roundtrip() {
+        let mut lock = LockFile {
+            block_number: Some(100),
+            dependencies: BTreeMap::new(),
+        };
+        lock.dependencies.insert(
+            "swap".to_string(),
+            LockedDependency::Git {
+                git: "https://github.com/someone/swap".to_string(),
+                rev: "v1.0.0".to_string(),
+                commit: "6c9d8a0b54f3c8d4b5e0b4f1b6a1b7b8b9c0d1e2".to_string(),
+                subdir: None,
+            },
+        );
+        let mut modules = BTreeMap::new();
+        modules.insert("Token".to_string(), HashValue::sha3_256_of(b"Token"));
+        lock.dependencies.insert(
+            "framework".to_string(),
+            LockedDependency::OnChain {
+                address: AccountAddress::from_hex_literal("0x1").unwrap(),
+                modules,
+            },
+        );
+        let content = toml::to_string(&lock).unwrap();
+        let lock2: LockFile = toml::from_str(&content).unwrap();
+        assert_eq!(lock, lock2);
+    }
+}
@@ -12,6 +12,8 @@ use starcoin_types::identifier::Identifier;
 use starcoin_types::language_storage::{ModuleId, StructTag};
 use starcoin_types::vm_error::StatusCode;
 use starcoin_vm_types::errors::{Location, PartialVMError, PartialVMResult, VMResult};
+use std::fs;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 use vm::CompiledModule;
@@ -50,6 +52,7 @@ pub struct RemoteStateAsyncView {
     state_client: StateApiClient,
     chain_client: ChainApiClient,
     state_root: HashValue,
+    block_number: u64,
 }
 
 impl RemoteStateAsyncView {
@@ -58,13 +61,13 @@ impl RemoteStateAsyncView {
             .await
             .map_err(|e| anyhow!(format!("{}", e)))?;
         let chain_client: starcoin_rpc_api::chain::ChainApiClient = rpc_channel.clone().into();
-        let state_root = match block_number {
+        let (state_root, block_number) = match block_number {
             None => {
                 let chain_info = chain_client
                     .info()
                     .await
                     .map_err(|e| anyhow!(format!("{}", e)))?;
-                chain_info.head.state_root
+                (chain_info.head.state_root, chain_info.head.number.0)
             }
             Some(n) => {
                 let b: Option<BlockView> = chain_client
@@ -72,7 +75,7 @@ impl RemoteStateAsyncView {
                     .await
                     .map_err(|e| anyhow!(format!("{}", e)))?;
                 let b = b.ok_or_else(|| anyhow::anyhow!("cannot found block of height {}", n))?;
-                b.header.state_root
+                (b.header.state_root, n)
             }
         };
         let state_client: starcoin_rpc_api::state::StateApiClient = rpc_channel.clone().into();
@@ -80,6 +83,7 @@ impl RemoteStateAsyncView {
             state_client,
             chain_client,
             state_root,
+            block_number,
         })
     }
 
@@ -157,6 +161,11 @@ impl RemoteStateView {
             rt: Arc::new(rt),
         })
     }
+
+    /// The block number the remote state is forked from.
+    pub fn block_number(&self) -> u64 {
+        self.svc.block_number
+    }
 }
 
 impl MoveStorage for RemoteStateView {
@@ -175,6 +184,82 @@ impl MoveStorage for RemoteStateView {
     }
 }
 
+/// A `MoveStorage` which caches the state read from `remote` on disk under `cache_dir`,
+/// so that the state can be read again offline, with `remote` set to None.
+pub struct CachedRemoteStateView {
+    remote: Option<RemoteStateView>,
+    cache_dir: PathBuf,
+}
+
+impl CachedRemoteStateView {
+    pub fn new(remote: Option<RemoteStateView>, cache_dir: PathBuf) -> Self {
+        Self { remote, cache_dir }
+    }
+
+    fn cache_path(&self, address: &AccountAddress, kind: &str, key: &[u8]) -> PathBuf {
+        self.cache_dir
+            .join(address.to_string())
+            .join(kind)
+            .join(HashValue::sha3_256_of(key).to_hex())
+    }
+
+    fn get_or_fetch<F>(&self, path: PathBuf, fetch: F) -> PartialVMResult<Option<Vec<u8>>>
+    where
+        F: FnOnce(&RemoteStateView) -> PartialVMResult<Option<Vec<u8>>>,
+    {
+        let storage_error = |e: &dyn std::fmt::Display| {
+            PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(format!(
+                "{}: {}",
+                path.display(),
+                e
+            ))
+        };
+        if path.exists() {
+            let bytes = fs::read(&path).map_err(|e| storage_error(&e))?;
+            return bcs::from_bytes(&bytes).map_err(|e| storage_error(&e));
+        }
+        let remote = self
+            .remote
+            .as_ref()
+            .ok_or_else(|| storage_error(&"state is not cached, can not read it offline"))?;
+        let value = fetch(remote)?;
+        let bytes = bcs::to_bytes(&value).map_err(|e| storage_error(&e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| storage_error(&e))?;
+        }
+        fs::write(&path, bytes).map_err(|e| storage_error(&e))?;
+        Ok(value)
+    }
+}
+
+impl MoveStorage for CachedRemoteStateView {
+    fn get_module(&self, module_id: &ModuleId) -> VMResult<Option<Vec<u8>>> {
+        let path = self.cache_path(
+            module_id.address(),
+            "modules",
+            module_id.name().as_str().as_bytes(),
+        );
+        self.get_or_fetch(path, |remote| {
+            remote
+                .get_module(module_id)
+                .map_err(|e| PartialVMError::new(e.major_status()))
+        })
+        .map_err(|e| e.finish(Location::Undefined))
+    }
+
+    fn get_resource(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+    ) -> PartialVMResult<Option<Vec<u8>>> {
+        let key = bcs::to_bytes(tag).map_err(|e| {
+            PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(e.to_string())
+        })?;
+        let path = self.cache_path(address, "resources", &key);
+        self.get_or_fetch(path, |remote| remote.get_resource(address, tag))
+    }
+}
+
 pub fn resolve_deps(
     state: &dyn MoveStorage,
     source_files: &[String],
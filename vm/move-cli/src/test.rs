@@ -46,13 +46,13 @@ pub const TEST_ARGS_FILENAME: &str = "args.txt";
 
 /// Name of the environment variable we need to set in order to get tracing
 /// enabled in the move VM.
-const MOVE_VM_TRACING_ENV_VAR_NAME: &str = "MOVE_VM_TRACE";
+pub const MOVE_VM_TRACING_ENV_VAR_NAME: &str = "MOVE_VM_TRACE";
 
 /// The default file name (inside the build output dir) for the runtime to
 /// dump the execution trace to. The trace will be used by the coverage tool
 /// if --track-cov is set. If --track-cov is not set, then no trace file will
 /// be produced.
-const DEFAULT_TRACE_FILE: &str = "trace";
+pub const DEFAULT_TRACE_FILE: &str = "trace";
 
 fn format_diff(expected: String, actual: String) -> String {
     use difference::*;
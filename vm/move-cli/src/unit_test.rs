@@ -0,0 +1,269 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::remote_state::MergedRemoteCache;
+use anyhow::{format_err, Result};
+use move_core_types::gas_schedule::{GasAlgebra, GasUnits};
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::vm_status::StatusCode;
+use move_lang::compiled_unit::CompiledUnit;
+use move_lang::unit_test::{ExpectedFailure, TestPlan};
+use move_vm_runtime::data_cache::MoveStorage;
+use move_vm_runtime::{logging::NoContextLog, move_vm::MoveVM};
+use starcoin_config::INITIAL_GAS_SCHEDULE;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_vm_types::errors::{PartialVMResult, VMResult};
+use starcoin_vm_types::gas_schedule::GasStatus;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use vm::access::ModuleAccess;
+
+/// Modules compiled from the test sources, which shadow the modules of the forked state.
+struct TestModules(BTreeMap<ModuleId, Vec<u8>>);
+
+impl MoveStorage for TestModules {
+    fn get_module(&self, module_id: &ModuleId) -> VMResult<Option<Vec<u8>>> {
+        Ok(self.0.get(module_id).cloned())
+    }
+
+    fn get_resource(
+        &self,
+        _address: &AccountAddress,
+        _tag: &StructTag,
+    ) -> PartialVMResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+/// Result of one unit test run on the forked state.
+#[derive(Debug)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    pub gas_used: u64,
+    pub message: Option<String>,
+}
+
+/// Run the tests in `test_plan` against `fork`, the state forked from a live node.
+///
+/// Modules in `user_modules` always shadow the forked ones, so a module can be tested before it is
+/// upgraded on chain. The other modules of the test plan, e.g. the local copy of the framework, are
+/// only used if they do not exist in the forked state.
+pub fn run_tests_on_fork<S: MoveStorage, W: Write>(
+    test_plan: &TestPlan,
+    fork: S,
+    user_modules: &BTreeSet<ModuleId>,
+    filter: Option<&str>,
+    gas_budget: u64,
+    mut writer: W,
+) -> Result<Vec<TestResult>> {
+    let mut modules = BTreeMap::new();
+    for (module_id, unit) in &test_plan.module_info {
+        if let CompiledUnit::Module { module, .. } = unit {
+            if user_modules.contains(module_id)
+                || fork
+                    .get_module(module_id)
+                    .map_err(|e| e.into_vm_status())?
+                    .is_none()
+            {
+                let mut bytes = vec![];
+                module.serialize(&mut bytes)?;
+                modules.insert(module.self_id(), bytes);
+            }
+        }
+    }
+    let state = MergedRemoteCache {
+        a: TestModules(modules),
+        b: fork,
+    };
+
+    let vm = MoveVM::new();
+    let log_context = NoContextLog::new();
+    let mut results = vec![];
+    for (module_id, module_test_plan) in &test_plan.module_tests {
+        for (test_name, test_case) in &module_test_plan.tests {
+            let name = format!(
+                "{}::{}::{}",
+                module_id.address(),
+                module_id.name(),
+                test_name
+            );
+            if let Some(filter) = filter {
+                if !name.contains(filter) {
+                    continue;
+                }
+            }
+            let args = test_case
+                .arguments
+                .iter()
+                .map(|arg| {
+                    arg.simple_serialize().ok_or_else(|| {
+                        format_err!("Can not serialize argument {:?} of test {}", arg, name)
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let mut gas_status = GasStatus::new(&INITIAL_GAS_SCHEDULE, GasUnits::new(gas_budget));
+            let mut session = vm.new_session(&state);
+            let res = session.execute_function(
+                module_id,
+                IdentStr::new(test_name)?,
+                vec![],
+                args,
+                &mut gas_status,
+                &log_context,
+            );
+            let gas_used = gas_budget - gas_status.remaining_gas().get();
+            let (passed, message) = match (res, &test_case.expected_failure) {
+                (Ok(_), None) => (true, None),
+                (Ok(_), Some(_)) => (false, Some("Test was expected to fail".to_string())),
+                (Err(_), Some(ExpectedFailure::Expected)) => (true, None),
+                (Err(err), Some(ExpectedFailure::ExpectedWithCode(code))) => {
+                    if err.major_status() == StatusCode::ABORTED && err.sub_status() == Some(*code)
+                    {
+                        (true, None)
+                    } else {
+                        (
+                            false,
+                            Some(format!(
+                                "Test was expected to abort with code {}, but failed with {:?}",
+                                code, err
+                            )),
+                        )
+                    }
+                }
+                (Err(err), None) => (false, Some(format!("{:?}", err))),
+            };
+            writeln!(
+                writer,
+                "[ {:<7} ] {} (gas used: {})",
+                if passed { "PASS" } else { "FAIL" },
+                name,
+                gas_used
+            )?;
+            results.push(TestResult {
+                name,
+                passed,
+                gas_used,
+                message,
+            });
+        }
+    }
+
+    let failed: Vec<_> = results.iter().filter(|r| !r.passed).collect();
+    for result in &failed {
+        writeln!(
+            writer,
+            "\n┌── {} ──────\n│ {}\n└──────────────────",
+            result.name,
+            result.message.as_deref().unwrap_or_default()
+        )?;
+    }
+    writeln!(
+        writer,
+        "Test result: {}. Total tests: {}; passed: {}; failed: {}",
+        if failed.is_empty() { "OK" } else { "FAILED" },
+        results.len(),
+        results.len() - failed.len(),
+        failed.len()
+    )?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_unit_test::UnitTestingConfig;
+
+    const TEST_MODULE: &str = r#"
+        address 0x2 {
+        module M {
+            #[test]
+            fun test_pass() {
+                let i = 0;
+                while (i < 10) { i = i + 1 };
+            }
+
+            #[test]
+            #[expected_failure(abort_code = 7)]
+            fun test_expected_abort() {
+                abort 7
+            }
+
+            #[test]
+            fun test_fail() {
+                abort 1
+            }
+        }
+        }
+    "#;
+
+    fn build_test_plan(dir: &std::path::Path) -> TestPlan {
+        let source = dir.join("M.move");
+        std::fs::write(&source, TEST_MODULE).unwrap();
+        let config = UnitTestingConfig {
+            instruction_execution_bound: 5000,
+            filter: None,
+            list: false,
+            num_threads: 1,
+            report_statistics: false,
+            report_storage_on_error: false,
+            source_files: vec![source.display().to_string()],
+            check_stackless_vm: false,
+            verbose: false,
+        };
+        config.build_test_plan().expect("test plan should be built")
+    }
+
+    #[test]
+    fn test_run_tests_on_fork() {
+        let dir = tempfile::tempdir().unwrap();
+        let test_plan = build_test_plan(dir.path());
+        let fork = TestModules(BTreeMap::new());
+        let mut output = vec![];
+        let results = run_tests_on_fork(
+            &test_plan,
+            fork,
+            &BTreeSet::new(),
+            None,
+            1_000_000,
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(results.len(), 3);
+        let result = |test_name: &str| {
+            results
+                .iter()
+                .find(|result| result.name.ends_with(&format!("::M::{}", test_name)))
+                .expect("test should be run")
+        };
+
+        let pass = result("test_pass");
+        assert!(pass.passed);
+        assert!(pass.gas_used > 0);
+        assert!(result("test_expected_abort").passed);
+        let fail = result("test_fail");
+        assert!(!fail.passed);
+        assert!(fail.message.is_some());
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Total tests: 3; passed: 2; failed: 1"));
+    }
+
+    #[test]
+    fn test_run_tests_on_fork_with_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let test_plan = build_test_plan(dir.path());
+        let results = run_tests_on_fork(
+            &test_plan,
+            TestModules(BTreeMap::new()),
+            &BTreeSet::new(),
+            Some("test_pass"),
+            1_000_000,
+            std::io::sink(),
+        )
+        .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed);
+    }
+}
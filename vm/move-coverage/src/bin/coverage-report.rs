@@ -0,0 +1,80 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use bytecode_source_map::utils::{remap_owned_loc_to_loc, source_map_from_file, OwnedLoc};
+use move_coverage::{coverage_map::CoverageMap, report::CoverageReport};
+use starcoin_vm_types::file_format::CompiledModule;
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "Move Coverage Report",
+    about = "Creates lcov and HTML coverage reports of Move source code"
+)]
+struct Args {
+    /// The paths to the coverage maps or trace files, the coverage of all of them is aggregated
+    #[structopt(long = "input-trace-path", short = "t", required = true)]
+    pub input_trace_paths: Vec<String>,
+    /// Whether the passed-in files are raw trace files or serialized coverage maps
+    #[structopt(long = "is-raw-trace", short = "r")]
+    pub is_raw_trace_file: bool,
+    /// The paths to the module binaries, or directories of them. The source map of a module is
+    /// read from the `.mvsm` file next to it.
+    #[structopt(long = "module-path", short = "b", required = true)]
+    pub module_binary_paths: Vec<String>,
+    /// Optional path to save the lcov tracefile
+    #[structopt(long = "lcov")]
+    pub lcov_path: Option<String>,
+    /// Optional directory to save the HTML report
+    #[structopt(long = "html")]
+    pub html_dir: Option<String>,
+}
+
+fn module_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "mv"))
+            .collect()
+    } else {
+        vec![path.to_path_buf()]
+    }
+}
+
+fn main() {
+    let args = Args::from_args();
+    let source_map_extension = "mvsm";
+    let coverage_map = CoverageMap::from_files(&args.input_trace_paths, args.is_raw_trace_file);
+
+    let modules: Vec<_> = args
+        .module_binary_paths
+        .iter()
+        .flat_map(|path| module_files(Path::new(path)))
+        .map(|module_path| {
+            let bytecode_bytes = fs::read(&module_path).expect("Unable to read bytecode file");
+            let compiled_module = CompiledModule::deserialize(&bytecode_bytes)
+                .expect("Module blob can't be deserialized");
+            let source_map =
+                source_map_from_file::<OwnedLoc>(&module_path.with_extension(source_map_extension))
+                    .map(remap_owned_loc_to_loc)
+                    .unwrap();
+            (compiled_module, source_map)
+        })
+        .collect();
+
+    let report = CoverageReport::new(&coverage_map, &modules).unwrap();
+    if let Some(lcov_path) = &args.lcov_path {
+        let mut file = File::create(lcov_path).unwrap();
+        report.write_lcov(&mut file).unwrap();
+    }
+    if let Some(html_dir) = &args.html_dir {
+        report.write_html(Path::new(html_dir)).unwrap();
+    }
+}
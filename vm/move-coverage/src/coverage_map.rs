@@ -103,6 +103,44 @@ impl CoverageMap {
             .unwrap()
     }
 
+    /// Takes in multiple raw VM trace or serialized coverage map files, and returns the
+    /// aggregated coverage map.
+    pub fn from_files<P: AsRef<Path>>(filenames: &[P], is_raw_trace: bool) -> Self {
+        let mut coverage_map = CoverageMap {
+            exec_maps: BTreeMap::new(),
+        };
+        for filename in filenames {
+            if is_raw_trace {
+                coverage_map = coverage_map.update_coverage_from_trace_file(filename);
+            } else {
+                coverage_map.merge(Self::from_binary_file(filename));
+            }
+        }
+        coverage_map
+    }
+
+    /// Merge the executions of `another` into this coverage map.
+    pub fn merge(&mut self, another: CoverageMap) {
+        for (exec_id, exec_map) in another.exec_maps {
+            for (_, module_map) in exec_map.module_maps {
+                for (func_name, func_map) in module_map.function_maps {
+                    for (pc, count) in func_map {
+                        self.exec_maps
+                            .entry(exec_id.clone())
+                            .or_insert_with(|| ExecCoverageMap::new(exec_id.clone()))
+                            .insert_multi(
+                                module_map.module_addr,
+                                module_map.module_name.clone(),
+                                func_name.clone(),
+                                pc,
+                                count,
+                            );
+                    }
+                }
+            }
+        }
+    }
+
     // add entries in a cascading manner
     pub fn insert(
         &mut self,
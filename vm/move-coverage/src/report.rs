@@ -0,0 +1,368 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::coverage_map::CoverageMap;
+use crate::source_coverage::{SourceCoverageBuilder, StringSegment};
+use anyhow::{format_err, Result};
+use bytecode_source_map::source_map::SourceMap;
+use codespan::Files;
+use starcoin_vm_types::location::Loc;
+use starcoin_vm_types::{
+    access::ModuleAccess,
+    file_format::{CodeOffset, CompiledModule, FunctionDefinitionIndex},
+};
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Line and function hit counts of a source file.
+#[derive(Debug, Default)]
+pub struct FileCoverage {
+    /// Hit count of each executable line, line numbers are 1-based.
+    pub lines: BTreeMap<u32, u64>,
+    /// Declaration line and hit count of each function, keyed by `Module::function`.
+    pub functions: BTreeMap<String, (u32, u64)>,
+}
+
+impl FileCoverage {
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+
+    pub fn functions_hit(&self) -> usize {
+        self.functions
+            .values()
+            .filter(|(_, hits)| *hits > 0)
+            .count()
+    }
+}
+
+struct FileReport {
+    coverage: FileCoverage,
+    source: SourceCoverageBuilder,
+}
+
+/// Source level coverage of modules, aggregated by source file, which can be written as lcov
+/// tracefile or HTML pages.
+pub struct CoverageReport {
+    files: BTreeMap<PathBuf, FileReport>,
+}
+
+impl CoverageReport {
+    /// Build the report of `modules`, the source file of a module is the one recorded in its
+    /// source map.
+    pub fn new(
+        coverage_map: &CoverageMap,
+        modules: &[(CompiledModule, SourceMap<Loc>)],
+    ) -> Result<Self> {
+        let unified_exec_map = coverage_map.to_unified_exec_map();
+        let mut files = BTreeMap::new();
+        for (module, source_map) in modules {
+            let source_path = PathBuf::from(source_map.definition_location.file());
+            let contents = fs::read_to_string(&source_path).map_err(|e| {
+                format_err!(
+                    "Failed to read source file {}: {}",
+                    source_path.display(),
+                    e
+                )
+            })?;
+            let mut source_files = Files::new();
+            let file_id = source_files.add(source_path.as_os_str().to_os_string(), contents);
+            let line_of = |loc: &Loc| -> Result<u32> {
+                source_files
+                    .location(file_id, loc.span().start())
+                    .map(|location| location.line.0 + 1)
+                    .map_err(|e| format_err!("Invalid location {:?}: {:?}", loc, e))
+            };
+
+            let builder = SourceCoverageBuilder::new(module, coverage_map, source_map);
+            let report = match files.entry(source_path) {
+                Entry::Vacant(entry) => entry.insert(FileReport {
+                    coverage: FileCoverage::default(),
+                    source: builder,
+                }),
+                Entry::Occupied(entry) => {
+                    let report = entry.into_mut();
+                    report.source.merge(builder);
+                    report
+                }
+            };
+
+            let module_id = module.self_id();
+            let module_map = unified_exec_map
+                .module_maps
+                .get(&(*module_id.address(), module_id.name().to_owned()));
+            for (function_def_idx, function_def) in module.function_defs().iter().enumerate() {
+                let code_unit = match &function_def.code {
+                    Some(code_unit) => code_unit,
+                    // native functions have no source to cover.
+                    None => continue,
+                };
+                let fn_handle = module.function_handle_at(function_def.function);
+                let fn_name = module.identifier_at(fn_handle.name);
+                let function_def_idx = FunctionDefinitionIndex(function_def_idx as u16);
+                let fn_coverage =
+                    module_map.and_then(|fn_map| fn_map.get_function_coverage(fn_name));
+                let hits_at = |pc: u64| {
+                    fn_coverage
+                        .and_then(|cov| cov.get(&pc))
+                        .copied()
+                        .unwrap_or(0)
+                };
+
+                let function_map = source_map.get_function_source_map(function_def_idx)?;
+                report.coverage.functions.insert(
+                    format!("{}::{}", module_id.name(), fn_name),
+                    (line_of(&function_map.decl_location)?, hits_at(0)),
+                );
+                for code_offset in 0..code_unit.code.len() {
+                    let loc = source_map
+                        .get_code_location(function_def_idx, code_offset as CodeOffset)?;
+                    let line_hits = report.coverage.lines.entry(line_of(&loc)?).or_insert(0);
+                    *line_hits = (*line_hits).max(hits_at(code_offset as u64));
+                }
+            }
+        }
+        Ok(Self { files })
+    }
+
+    pub fn file_coverages(&self) -> impl Iterator<Item = (&PathBuf, &FileCoverage)> {
+        self.files
+            .iter()
+            .map(|(path, report)| (path, &report.coverage))
+    }
+
+    /// Write the report in lcov tracefile format.
+    pub fn write_lcov<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (path, coverage) in self.file_coverages() {
+            writeln!(writer, "TN:")?;
+            writeln!(writer, "SF:{}", path.display())?;
+            for (fn_name, (line, _)) in &coverage.functions {
+                writeln!(writer, "FN:{},{}", line, fn_name)?;
+            }
+            for (fn_name, (_, hits)) in &coverage.functions {
+                writeln!(writer, "FNDA:{},{}", hits, fn_name)?;
+            }
+            writeln!(writer, "FNF:{}", coverage.functions.len())?;
+            writeln!(writer, "FNH:{}", coverage.functions_hit())?;
+            for (line, hits) in &coverage.lines {
+                writeln!(writer, "DA:{},{}", line, hits)?;
+            }
+            writeln!(writer, "LF:{}", coverage.lines.len())?;
+            writeln!(writer, "LH:{}", coverage.lines_hit())?;
+            writeln!(writer, "end_of_record")?;
+        }
+        Ok(())
+    }
+
+    /// Write the report as HTML pages into `output_dir`, starting from `index.html`.
+    pub fn write_html(&self, output_dir: &Path) -> Result<()> {
+        fs::create_dir_all(output_dir)?;
+        let mut index = String::new();
+        index.push_str(&html_header("Move Coverage Report"));
+        index.push_str(
+            "<table>\n<tr><th>File</th><th>Line Coverage</th><th>Function Coverage</th></tr>\n",
+        );
+        for (file_idx, (path, report)) in self.files.iter().enumerate() {
+            let page = format!("file_{}.html", file_idx);
+            let coverage = &report.coverage;
+            index.push_str(&format!(
+                "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td></tr>\n",
+                page,
+                escape_html(&path.display().to_string()),
+                format_ratio(coverage.lines_hit(), coverage.lines.len()),
+                format_ratio(coverage.functions_hit(), coverage.functions.len()),
+            ));
+
+            let mut content = html_header(&path.display().to_string());
+            content.push_str("<p><a href=\"index.html\">Back to index</a></p>\n<pre>\n");
+            let source_coverage = report.source.compute_source_coverage(path);
+            for (line_idx, line) in source_coverage.annotated_lines.iter().enumerate() {
+                let line_number = line_idx as u32 + 1;
+                let hits = match coverage.lines.get(&line_number) {
+                    Some(hits) => hits.to_string(),
+                    None => String::new(),
+                };
+                content.push_str(&format!(
+                    "<span class=\"line-number\">{:>5}</span><span class=\"hits\">{:>8}</span>  ",
+                    line_number, hits
+                ));
+                for segment in line {
+                    match segment {
+                        StringSegment::Covered(s) => content.push_str(&escape_html(s)),
+                        StringSegment::Uncovered(s) => content.push_str(&format!(
+                            "<span class=\"uncovered\">{}</span>",
+                            escape_html(s)
+                        )),
+                    }
+                }
+                content.push('\n');
+            }
+            content.push_str("</pre>\n</body>\n</html>\n");
+            fs::write(output_dir.join(page), content)?;
+        }
+        index.push_str("</table>\n</body>\n</html>\n");
+        fs::write(output_dir.join("index.html"), index)?;
+        Ok(())
+    }
+}
+
+fn html_header(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 4px 8px; }}\n\
+         .line-number {{ color: #999; }}\n\
+         .hits {{ color: #396; }}\n\
+         .uncovered {{ background-color: #fcc; }}\n\
+         </style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    )
+}
+
+fn format_ratio(hit: usize, total: usize) -> String {
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!(
+        "{:.2}% ({}/{})",
+        hit as f64 * 100.0 / total as f64,
+        hit,
+        total
+    )
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_lang::compiled_unit::CompiledUnit;
+    use move_lang::Flags;
+    use starcoin_vm_types::identifier::Identifier;
+
+    const MODULE: &str = r#"address 0x2 {
+module M {
+    public fun covered(): u64 {
+        1
+    }
+
+    public fun uncovered(): u64 {
+        2
+    }
+}
+}
+"#;
+
+    fn compile_module(dir: &Path) -> Vec<(CompiledModule, SourceMap<Loc>)> {
+        let source = dir.join("M.move");
+        fs::write(&source, MODULE).unwrap();
+        let (_, units) = move_lang::move_compile_and_report(
+            &[source.display().to_string()],
+            &[],
+            None,
+            Flags::empty(),
+        )
+        .unwrap();
+        units
+            .into_iter()
+            .filter_map(|unit| match unit {
+                CompiledUnit::Module {
+                    module, source_map, ..
+                } => Some((module, source_map)),
+                CompiledUnit::Script { .. } => None,
+            })
+            .collect()
+    }
+
+    /// The coverage map of executing `M::covered` once.
+    fn coverage_map(module: &CompiledModule) -> CoverageMap {
+        let module_id = module.self_id();
+        let mut coverage_map = CoverageMap {
+            exec_maps: BTreeMap::new(),
+        };
+        let covered = module
+            .function_defs()
+            .iter()
+            .find(|def| {
+                module
+                    .identifier_at(module.function_handle_at(def.function).name)
+                    .as_str()
+                    == "covered"
+            })
+            .unwrap();
+        for pc in 0..covered.code.as_ref().unwrap().code.len() {
+            coverage_map.insert(
+                "test",
+                *module_id.address(),
+                module_id.name().to_owned(),
+                Identifier::new("covered").unwrap(),
+                pc as u64,
+            );
+        }
+        coverage_map
+    }
+
+    #[test]
+    fn test_lcov_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let modules = compile_module(dir.path());
+        let report = CoverageReport::new(&coverage_map(&modules[0].0), &modules).unwrap();
+
+        let (path, coverage) = report.file_coverages().next().unwrap();
+        assert!(path.ends_with("M.move"));
+        assert_eq!(coverage.functions.get("M::covered"), Some(&(3, 1)));
+        assert_eq!(coverage.functions.get("M::uncovered"), Some(&(7, 0)));
+        assert_eq!(coverage.functions_hit(), 1);
+        assert_eq!(coverage.lines.get(&4), Some(&1));
+        assert_eq!(coverage.lines.get(&8), Some(&0));
+
+        let mut lcov = vec![];
+        report.write_lcov(&mut lcov).unwrap();
+        let lcov = String::from_utf8(lcov).unwrap();
+        assert!(lcov.contains("FN:3,M::covered\n"));
+        assert!(lcov.contains("FNDA:1,M::covered\n"));
+        assert!(lcov.contains("FNDA:0,M::uncovered\n"));
+        assert!(lcov.contains("FNF:2\nFNH:1\n"));
+        assert!(lcov.contains("DA:4,1\n"));
+        assert!(lcov.contains("DA:8,0\n"));
+        assert!(lcov.ends_with("end_of_record\n"));
+    }
+
+    #[test]
+    fn test_html_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let modules = compile_module(dir.path());
+        let report = CoverageReport::new(&coverage_map(&modules[0].0), &modules).unwrap();
+
+        let html_dir = dir.path().join("html");
+        report.write_html(&html_dir).unwrap();
+        let index = fs::read_to_string(html_dir.join("index.html")).unwrap();
+        assert!(index.contains("M.move"));
+        assert!(index.contains("50.00% (1/2)"));
+        let page = fs::read_to_string(html_dir.join("file_0.html")).unwrap();
+        assert!(page.contains("<span class=\"uncovered\">"));
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(format_ratio(0, 0), "-");
+        assert_eq!(format_ratio(1, 3), "33.33% (1/3)");
+    }
+}
@@ -15,7 +15,7 @@ use starcoin_vm_types::{
     file_format::{CodeOffset, CompiledModule, FunctionDefinitionIndex},
 };
 use std::{
-    collections::BTreeMap,
+    collections::{btree_map::Entry, BTreeMap},
     fs,
     io::{self, Write},
     path::Path,
@@ -73,14 +73,15 @@ impl SourceCoverageBuilder {
                 let fn_name = module.identifier_at(fn_handle.name).to_owned();
                 let function_def_idx = FunctionDefinitionIndex(function_def_idx as u16);
 
-                // If the function summary doesn't exist then that function hasn't been called yet.
+                // If the function summary doesn't exist then that function hasn't been called yet,
+                // same for all the functions of a module which has never been executed.
                 let coverage = match &function_def.code {
                     None => Some(FunctionSourceCoverage {
                         fn_is_native: true,
                         uncovered_locations: Vec::new(),
                     }),
-                    Some(code_unit) => {
-                        module_map.map(|fn_map| match fn_map.function_maps.get(&fn_name) {
+                    Some(code_unit) => Some(
+                        match module_map.and_then(|fn_map| fn_map.function_maps.get(&fn_name)) {
                             None => {
                                 let function_map = source_map
                                     .get_function_source_map(function_def_idx)
@@ -115,8 +116,8 @@ impl SourceCoverageBuilder {
                                     uncovered_locations,
                                 }
                             }
-                        })
-                    }
+                        },
+                    ),
                 };
                 coverage.map(|x| (fn_name, x))
             })
@@ -128,6 +129,21 @@ impl SourceCoverageBuilder {
         }
     }
 
+    /// Merge the coverage of another module defined in the same source file.
+    pub fn merge(&mut self, other: SourceCoverageBuilder) {
+        for (fn_name, fn_cov) in other.uncovered_locations {
+            match self.uncovered_locations.entry(fn_name) {
+                Entry::Vacant(entry) => {
+                    entry.insert(fn_cov);
+                }
+                Entry::Occupied(mut entry) => entry
+                    .get_mut()
+                    .uncovered_locations
+                    .extend(fn_cov.uncovered_locations),
+            }
+        }
+    }
+
     pub fn compute_source_coverage(&self, file_path: &Path) -> SourceCoverage {
         let file_contents = fs::read_to_string(file_path).unwrap();
         let mut files = Files::new();
@@ -2,14 +2,55 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::{
-    errmap::{ErrorContext, ErrorMapping},
+    errmap::{ErrorContext, ErrorDescription, ErrorMapping},
     language_storage::ModuleId,
 };
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+
+/// Resolves the error descriptions of modules out of stdlib, keyed by the reason code.
+pub trait ErrorMapResolver {
+    fn get_module_error_map(&self, module_id: &ModuleId)
+        -> Option<BTreeMap<u64, ErrorDescription>>;
+}
+
+impl ErrorMapResolver for ErrorMapping {
+    fn get_module_error_map(
+        &self,
+        module_id: &ModuleId,
+    ) -> Option<BTreeMap<u64, ErrorDescription>> {
+        self.module_error_maps.get(module_id).cloned()
+    }
+}
+
+/// The error map of stdlib, it is decoded only once.
+static STDLIB_ERROR_DESCRIPTIONS: Lazy<ErrorMapping> =
+    Lazy::new(|| bcs_ext::from_bytes(stdlib::ERROR_DESCRIPTIONS).expect("Decode err map failed"));
 
 /// Given the module ID and the abort code raised from that module, returns the human-readable
 /// explanation of that abort if possible.
 pub fn get_explanation(module_id: &ModuleId, abort_code: u64) -> Option<ErrorContext> {
-    let error_descriptions: ErrorMapping =
-        bcs_ext::from_bytes(stdlib::ERROR_DESCRIPTIONS).expect("Decode err map failed");
-    error_descriptions.get_explanation(module_id, abort_code)
+    STDLIB_ERROR_DESCRIPTIONS.get_explanation(module_id, abort_code)
+}
+
+/// Same as `get_explanation`, but the reasons of modules out of stdlib are looked up by
+/// `resolver`, while the categories are always the ones defined in stdlib.
+pub fn get_explanation_with_resolver(
+    module_id: &ModuleId,
+    abort_code: u64,
+    resolver: &dyn ErrorMapResolver,
+) -> Option<ErrorContext> {
+    if let Some(explanation) = get_explanation(module_id, abort_code) {
+        return Some(explanation);
+    }
+    let category = STDLIB_ERROR_DESCRIPTIONS
+        .error_categories
+        .get(&(abort_code & 0xFFu64))?;
+    let reason = resolver
+        .get_module_error_map(module_id)?
+        .remove(&(abort_code >> 8))?;
+    Some(ErrorContext {
+        category: category.clone(),
+        reason,
+    })
 }
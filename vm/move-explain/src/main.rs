@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    account_address::AccountAddress, errmap::ErrorMapping, identifier::Identifier,
+    language_storage::ModuleId,
 };
+use std::path::PathBuf;
 use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -17,6 +19,9 @@ struct Args {
     /// The abort code returned with a `MoveAbort` error
     #[structopt(long = "abort-code", short = "a")]
     abort_code: u64,
+    /// The error map file built with the module, required if the module is not in stdlib
+    #[structopt(long = "error-map", short = "e", parse(from_os_str))]
+    error_map: Option<PathBuf>,
 }
 
 fn main() {
@@ -36,7 +41,21 @@ fn main() {
         Identifier::new(module_name).expect("Invalid module name encountered"),
     );
 
-    match starcoin_move_explain::get_explanation(&module_id, args.abort_code) {
+    let explanation = match &args.error_map {
+        Some(error_map) => {
+            let error_map: ErrorMapping = bcs_ext::from_bytes(
+                &std::fs::read(error_map).expect("Unable to read error map file"),
+            )
+            .expect("Invalid error map file");
+            starcoin_move_explain::get_explanation_with_resolver(
+                &module_id,
+                args.abort_code,
+                &error_map,
+            )
+        }
+        None => starcoin_move_explain::get_explanation(&module_id, args.abort_code),
+    };
+    match explanation {
         None => println!(
             "Unable to find a description for {}::{}",
             args.location, args.abort_code
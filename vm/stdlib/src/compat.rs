@@ -1,13 +1,18 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::StdlibVersion;
+use crate::{modules_diff, StdlibVersion};
 use starcoin_crypto::HashValue;
+use starcoin_vm_types::access::ModuleAccess;
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::{core_code_address, genesis_address};
+use starcoin_vm_types::compatibility::Compatibility;
+use starcoin_vm_types::file_format::CompiledModule;
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
+use starcoin_vm_types::normalized::Module;
 use starcoin_vm_types::transaction::ScriptFunction;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub trait StdlibCompat {
     fn upgrade_module_type_tag(&self) -> TypeTag;
@@ -81,3 +86,44 @@ impl StdlibCompat for StdlibVersion {
         )
     }
 }
+
+/// The compatibility of a module of an upgrade with the deployed one.
+pub enum ModuleUpgradeCompat {
+    /// The module is not deployed.
+    New,
+    /// The module is the same as the deployed one.
+    Unchanged,
+    /// The module is changed, with the compatibility the VM checks when the module is deployed.
+    Changed(Compatibility),
+}
+
+/// Check the compatibility of every module in `new_modules` with the deployed `pre_modules`,
+/// the changed modules are the ones in `modules_diff`.
+pub fn check_modules_upgrade_compat(
+    pre_modules: &[CompiledModule],
+    new_modules: &[CompiledModule],
+) -> Vec<(ModuleId, ModuleUpgradeCompat)> {
+    let changed = modules_diff(pre_modules, new_modules)
+        .iter()
+        .map(|module| module.self_id())
+        .collect::<BTreeSet<_>>();
+    let pre_modules = pre_modules
+        .iter()
+        .map(|module| (module.self_id(), module))
+        .collect::<BTreeMap<_, _>>();
+    new_modules
+        .iter()
+        .map(|module| {
+            let module_id = module.self_id();
+            let compat = match pre_modules.get(&module_id) {
+                None => ModuleUpgradeCompat::New,
+                Some(_) if !changed.contains(&module_id) => ModuleUpgradeCompat::Unchanged,
+                Some(pre_module) => ModuleUpgradeCompat::Changed(Compatibility::check(
+                    &Module::new(pre_module),
+                    &Module::new(module),
+                )),
+            };
+            (module_id, compat)
+        })
+        .collect()
+}
@@ -26,6 +26,7 @@ use std::{
 };
 mod compat;
 pub use compat::*;
+pub mod prover;
 use starcoin_move_compiler::shared::Flags;
 pub use starcoin_move_compiler::utils::iterate_directory;
 
@@ -0,0 +1,244 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Run the Move prover on user packages and collect its diagnostics.
+
+use anyhow::{bail, Result};
+use codespan::Span;
+use codespan_reporting::diagnostic::{LabelStyle, Severity};
+use codespan_reporting::term::termcolor::Buffer;
+use log::LevelFilter;
+use move_model::model::Loc;
+use serde::Serialize;
+use std::time::Instant;
+
+/// The log targets of the prover, the caller sets their level in its logger.
+pub const PROVER_LOG_TARGETS: &[&str] =
+    &["move_prover", "boogie_backend", "bytecode", "move_model"];
+
+/// Options of a prover run.
+#[derive(Clone, Debug, Default)]
+pub struct ProverConfig {
+    /// Timeout in seconds of each verification condition, the prover default is used if absent.
+    pub vc_timeout: Option<usize>,
+    /// Print the progress of the prover.
+    pub verbose: bool,
+}
+
+/// A source location a diagnostic points to, line and column are 1-based.
+#[derive(Clone, Debug, Serialize)]
+pub struct DiagnosticLabel {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    pub primary: bool,
+    pub message: String,
+}
+
+/// An error or warning reported by the prover.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProverDiagnostic {
+    pub severity: String,
+    pub message: String,
+    pub labels: Vec<DiagnosticLabel>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProverResult {
+    /// Whether all the specifications are verified.
+    pub success: bool,
+    pub diagnostics: Vec<ProverDiagnostic>,
+    /// The diagnostics rendered as the prover prints them.
+    #[serde(skip)]
+    pub rendered: String,
+}
+
+/// Verify the specifications in `sources`, `deps` are the source or interface files of the
+/// modules the sources depend on, their specifications are assumed but not verified.
+///
+/// Returns an error only if the prover can not run, e.g. the Boogie or Z3 executable is missing.
+#[allow(clippy::field_reassign_with_default)]
+pub fn prove(sources: &[String], deps: &[String], config: &ProverConfig) -> Result<ProverResult> {
+    let mut options = move_prover::cli::Options::default();
+    options.move_sources = sources.to_vec();
+    options.move_deps = deps.to_vec();
+    options.verbosity_level = if config.verbose {
+        LevelFilter::Info
+    } else {
+        LevelFilter::Warn
+    };
+    if let Some(vc_timeout) = config.vc_timeout {
+        options.backend.vc_timeout = vc_timeout;
+    }
+    // let the prover report only bugs, the other diagnostics are collected below.
+    options.prover.report_severity = Severity::Bug;
+    // the prover logs through the `log` facade into the logger of the process, e.g. the one set
+    // up by the starcoin cli, see `PROVER_LOG_TARGETS`, a logger can only be set once a process.
+
+    let env = move_model::run_model_builder(&options.move_sources, &options.move_deps)?;
+    let mut prover_output = Buffer::no_color();
+    let prove_result = if env.has_errors() {
+        Ok(())
+    } else {
+        move_prover::run_move_prover_with_model(
+            &env,
+            &mut prover_output,
+            options,
+            Some(Instant::now()),
+        )
+    };
+
+    let mut diagnostics = vec![];
+    let mut rendered = Buffer::no_color();
+    env.report_diag_with_filter(&mut rendered, |diag| {
+        if diag.severity < Severity::Warning {
+            return false;
+        }
+        let labels = diag
+            .labels
+            .iter()
+            .map(|label| {
+                let loc = Loc::new(
+                    label.file_id,
+                    Span::new(label.range.start as u32, label.range.end as u32),
+                );
+                let (file, line, column) = match env.get_file_and_location(&loc) {
+                    Some((file, location)) => (file, location.line.0 + 1, location.column.0 + 1),
+                    None => (
+                        env.get_file(label.file_id).to_string_lossy().to_string(),
+                        0,
+                        0,
+                    ),
+                };
+                DiagnosticLabel {
+                    file,
+                    line,
+                    column,
+                    primary: label.style == LabelStyle::Primary,
+                    message: label.message.clone(),
+                }
+            })
+            .collect();
+        diagnostics.push(ProverDiagnostic {
+            severity: severity_name(diag.severity).to_string(),
+            message: diag.message.clone(),
+            labels,
+            notes: diag.notes.clone(),
+        });
+        true
+    });
+
+    let success = !env.has_errors();
+    if let Err(e) = prove_result {
+        if success {
+            bail!(
+                "{}\n{}",
+                e,
+                String::from_utf8_lossy(prover_output.as_slice())
+            );
+        }
+    }
+    Ok(ProverResult {
+        success,
+        diagnostics,
+        rendered: String::from_utf8_lossy(rendered.as_slice()).to_string(),
+    })
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSING_SPEC: &str = r#"
+        address 0x2 {
+        module Math {
+            public fun add(a: u64, b: u64): u64 {
+                a + b
+            }
+            spec add {
+                aborts_if a + b > MAX_U64;
+                ensures result == a + b;
+            }
+        }
+        }
+    "#;
+
+    const FAILING_SPEC: &str = r#"
+        address 0x2 {
+        module Math {
+            public fun add(a: u64, b: u64): u64 {
+                a + b
+            }
+            spec add {
+                aborts_if a + b > MAX_U64;
+                ensures result == a;
+            }
+        }
+        }
+    "#;
+
+    /// The prover tools are located by the env vars, the tests are skipped without them.
+    fn prover_tools_configured() -> bool {
+        ["BOOGIE_EXE", "Z3_EXE"]
+            .iter()
+            .all(|var| !std::env::var(var).unwrap_or_default().is_empty())
+    }
+
+    fn prove_source(source: &str) -> ProverResult {
+        let dir = tempfile::tempdir().unwrap();
+        let source_file = dir.path().join("Math.move");
+        std::fs::write(&source_file, source).unwrap();
+        prove(
+            &[source_file.to_string_lossy().to_string()],
+            &[],
+            &ProverConfig::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prove_passing_spec() {
+        if !prover_tools_configured() {
+            return;
+        }
+        let result = prove_source(PASSING_SPEC);
+        assert!(result.success, "{}", result.rendered);
+        assert!(result
+            .diagnostics
+            .iter()
+            .all(|diag| diag.severity != "error"));
+    }
+
+    #[test]
+    fn test_prove_failing_spec() {
+        if !prover_tools_configured() {
+            return;
+        }
+        let result = prove_source(FAILING_SPEC);
+        assert!(!result.success);
+        let error = result
+            .diagnostics
+            .iter()
+            .find(|diag| diag.severity == "error")
+            .expect("the failed spec should be reported");
+        assert!(error.message.contains("post-condition"), "{:?}", error);
+        let label = error
+            .labels
+            .iter()
+            .find(|label| label.primary)
+            .expect("the error should point to the spec");
+        assert!(label.file.ends_with("Math.move"));
+        assert!(label.line > 0);
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+import com.novi.bcs.BcsSerializer
+import java.math.BigInteger
+import org.starcoin.stdlib.Helpers
+import org.starcoin.types.AccountAddress
+import org.starcoin.types.Identifier
+import org.starcoin.types.StructTag
+import org.starcoin.types.TypeTag
+
+fun make_address(values: ByteArray): AccountAddress {
+    assert(values.size == 16)
+    return AccountAddress(values.toTypedArray())
+}
+
+fun main() {
+    val token = TypeTag.Struct(
+        StructTag(
+            make_address(byteArrayOf(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1)),
+            Identifier("STC"),
+            Identifier("STC"),
+            listOf(),
+        )
+    )
+    val payee = make_address(ByteArray(16) { 0x22 })
+    val amount = BigInteger.valueOf(1234567)
+    val payload = Helpers.encode_peer_to_peer_v2_script_function(token, payee, amount)
+
+    val serializer = BcsSerializer()
+    payload.serialize(serializer)
+    for (o in serializer.get_bytes()) {
+        print("${o.toInt() and 0xFF} ")
+    }
+    println()
+}
@@ -0,0 +1,29 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+import { BcsSerializer } from "./bcs/mod.ts";
+import * as StarcoinTypes from "./starcoin_types/mod.ts";
+import { Helpers } from "./starcoin_stdlib/mod.ts";
+
+function make_address(content: number[]): StarcoinTypes.AccountAddress {
+  if (content.length !== 16) {
+    throw new Error("address must be 16 bytes");
+  }
+  return new StarcoinTypes.AccountAddress(content.map((x) => [x]));
+}
+
+const token = new StarcoinTypes.TypeTagVariantStruct(
+  new StarcoinTypes.StructTag(
+    make_address([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    new StarcoinTypes.Identifier("STC"),
+    new StarcoinTypes.Identifier("STC"),
+    [],
+  ),
+);
+const payee = make_address(new Array(16).fill(0x22));
+const amount = BigInt(1234567);
+const payload = Helpers.encode_peer_to_peer_v2_script_function(token, payee, amount);
+
+const serializer = new BcsSerializer();
+payload.serialize(serializer);
+console.log(Array.from(serializer.getBytes()).map((x) => x + " ").join(""));
@@ -92,6 +92,24 @@ pub(crate) fn mangle_type(type_tag: &TypeTag) -> String {
     }
 }
 
+/// Mangle a type tag into a name which can be used as a part of identifier in every language.
+pub(crate) fn mangle_type_ident(type_tag: &TypeTag) -> String {
+    use TypeTag::*;
+    match type_tag {
+        Bool => "bool".into(),
+        U8 => "u8".into(),
+        U64 => "u64".into(),
+        U128 => "u128".into(),
+        Address => "address".into(),
+        Vector(type_tag) => match type_tag.as_ref() {
+            U8 => "u8vector".into(),
+            _ => type_not_allowed(type_tag),
+        },
+
+        Struct(_) | Signer => type_not_allowed(type_tag),
+    }
+}
+
 pub(crate) fn get_external_definitions(diem_types: &str) -> serde_generate::ExternalDefinitions {
     let definitions = vec![(
         diem_types,
@@ -22,6 +22,8 @@ enum Language {
     Cpp,
     Java,
     Dart,
+    TypeScript,
+    Kotlin,
 }
 }
 
@@ -48,7 +50,7 @@ struct Options {
 
     /// Module name for the transaction builders installed in the `target_source_dir`.
     /// * Rust crates may contain a version number, e.g. "test:1.2.0".
-    /// * In Java, this is expected to be a package name, e.g. "com.test" to create Java files in `com/test`.
+    /// * In Java and Kotlin, this is expected to be a package name, e.g. "com.test" to create files in `com/test`.
     /// * In Go, this is expected to be of the format "go_module/path/go_package_name",
     /// and `diem_types` is assumed to be in "go_module/path/diem_types".
     #[structopt(long)]
@@ -114,6 +116,16 @@ fn main() {
                     // buildgen::dart::output(&mut out, &abis, class_name).unwrap()
                     panic!("Code generation in dart requires --target_source_dir");
                 }
+                Language::TypeScript => buildgen::typescript::output(&mut out, &abis).unwrap(),
+                Language::Kotlin => buildgen::kotlin::output(
+                    &mut out,
+                    options
+                        .module_name
+                        .as_deref()
+                        .unwrap_or("org.starcoin.stdlib"),
+                    &abis,
+                )
+                .unwrap(),
             }
             return;
         }
@@ -132,6 +144,11 @@ fn main() {
                 Language::Cpp => Box::new(serdegen::cpp::Installer::new(install_dir.clone())),
                 Language::Java => Box::new(serdegen::java::Installer::new(install_dir.clone())),
                 Language::Dart => Box::new(serdegen::dart::Installer::new(install_dir.clone())),
+                Language::TypeScript => {
+                    Box::new(serdegen::typescript::Installer::new(install_dir.clone()))
+                }
+                // The Kotlin builders use the types generated for Java.
+                Language::Kotlin => Box::new(serdegen::java::Installer::new(install_dir.clone())),
             };
 
         match options.language {
@@ -153,7 +170,7 @@ fn main() {
                 },
                 vec!["starcoin-types"],
             ),
-            Language::Java => (
+            Language::Java | Language::Kotlin => (
                 "org.starcoin.types".to_string(),
                 vec!["org", "starcoin", "types"],
             ),
@@ -184,6 +201,8 @@ fn main() {
             Language::Cpp => Box::new(buildgen::cpp::Installer::new(install_dir)),
             Language::Java => Box::new(buildgen::java::Installer::new(install_dir)),
             Language::Dart => Box::new(buildgen::dart::Installer::new(install_dir)),
+            Language::TypeScript => Box::new(buildgen::typescript::Installer::new(install_dir)),
+            Language::Kotlin => Box::new(buildgen::kotlin::Installer::new(install_dir)),
         };
 
     if let Some(name) = options.module_name {
@@ -0,0 +1,624 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common;
+use heck::{CamelCase, ShoutySnakeCase};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, TypeTag},
+};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use starcoin_vm_types::transaction::{
+    ArgumentABI, ScriptABI, ScriptFunctionABI, TransactionScriptABI, TypeArgumentABI,
+};
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output transaction builders and decoders in Kotlin for the given ABIs.
+/// The Kotlin builders use the starcoin types and the serde and BCS runtimes generated by the
+/// Java backend of serde-generate (`org.starcoin.types`, `com.novi.serde`, `com.novi.bcs`).
+pub fn output(out: &mut dyn Write, package_name: &str, abis: &[ScriptABI]) -> Result<()> {
+    let mut emitter = KotlinEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+        package_name,
+    };
+    emitter.output_preamble()?;
+
+    emitter.output_script_call_classes(&common::transaction_script_abis(abis))?;
+    emitter.output_script_function_call_classes(&common::script_function_abis(abis))?;
+
+    writeln!(emitter.out, "\nobject Helpers {{")?;
+    emitter.out.indent();
+
+    for abi in common::transaction_script_abis(abis) {
+        emitter.output_code_constant(&abi)?;
+    }
+    // Must be defined after the constants.
+    emitter.output_transaction_script_decoder_map(&common::transaction_script_abis(abis))?;
+    emitter.output_script_function_decoder_map(&common::script_function_abis(abis))?;
+
+    emitter.output_encode_method(abis)?;
+    emitter.output_decode_method()?;
+
+    for abi in abis {
+        emitter.output_script_encoder_function(abi)?;
+    }
+    for abi in common::transaction_script_abis(abis).iter() {
+        emitter.output_transaction_script_decoder_function(abi)?;
+    }
+    for abi in common::script_function_abis(abis).iter() {
+        emitter.output_script_function_decoder_function(abi)?;
+    }
+
+    emitter.output_argument_helpers(abis)?;
+
+    emitter.out.unindent();
+    writeln!(emitter.out, "}}")
+}
+
+/// Shared state for the Kotlin code generator.
+struct KotlinEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Name of the package owning the generated definitions (e.g. "org.starcoin.stdlib")
+    package_name: &'a str,
+}
+
+impl<'a, T> KotlinEmitter<'a, T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(self.out, "package {}\n", self.package_name)?;
+        writeln!(
+            self.out,
+            r#"import com.novi.bcs.BcsDeserializer
+import com.novi.bcs.BcsSerializer
+import com.novi.serde.Bytes
+import java.math.BigInteger
+import org.starcoin.types.AccountAddress
+import org.starcoin.types.Identifier
+import org.starcoin.types.ModuleId
+import org.starcoin.types.Script
+import org.starcoin.types.ScriptFunction
+import org.starcoin.types.TransactionPayload
+import org.starcoin.types.TypeTag"#
+        )
+    }
+
+    fn output_script_call_classes(&mut self, abis: &[TransactionScriptABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\n/**\n * Structured representation of a call into a known Move script.\n */\nsealed class ScriptCall {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            self.output_call_class(
+                "ScriptCall",
+                abi.name(),
+                abi.doc(),
+                abi.ty_args(),
+                abi.args(),
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_call_classes(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\n/**\n * Structured representation of a call into a known Move script function.\n */\nsealed class ScriptFunctionCall {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            self.output_call_class(
+                "ScriptFunctionCall",
+                abi.name(),
+                abi.doc(),
+                abi.ty_args(),
+                abi.args(),
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_call_class(
+        &mut self,
+        base: &str,
+        name: &str,
+        doc: &str,
+        ty_args: &[TypeArgumentABI],
+        args: &[ArgumentABI],
+    ) -> Result<()> {
+        let fields = Self::quote_type_parameters(ty_args)
+            .into_iter()
+            .chain(Self::quote_parameters(args))
+            .map(|field| format!("val {}", field))
+            .collect::<Vec<_>>();
+        // Data classes must have at least one property.
+        if fields.is_empty() {
+            writeln!(
+                self.out,
+                "\n{}object {} : {}()",
+                Self::quote_doc(doc, vec![], None),
+                name.to_camel_case(),
+                base
+            )
+        } else {
+            writeln!(
+                self.out,
+                "\n{}data class {}({}) : {}()",
+                Self::quote_doc(doc, vec![], None),
+                name.to_camel_case(),
+                fields.join(", "),
+                base
+            )
+        }
+    }
+
+    fn output_encode_method(&mut self, abis: &[ScriptABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Build a Starcoin [Script] from a structured value [ScriptCall].
+ */
+fun encode_script(call: ScriptCall): Script = when (call) {{"#
+        )?;
+        self.out.indent();
+        for abi in common::transaction_script_abis(abis) {
+            writeln!(
+                self.out,
+                "is ScriptCall.{} -> encode_{}_script({})",
+                abi.name().to_camel_case(),
+                abi.name(),
+                Self::quote_call_fields(abi.ty_args(), abi.args()),
+            )?;
+        }
+        writeln!(
+            self.out,
+            "else -> throw IllegalArgumentException(\"Unknown script call\")"
+        )?;
+        self.out.unindent();
+        writeln!(
+            self.out,
+            r#"}}
+
+/**
+ * Build a Starcoin [TransactionPayload] from a structured value [ScriptFunctionCall].
+ */
+fun encode_script_function(call: ScriptFunctionCall): TransactionPayload = when (call) {{"#
+        )?;
+        self.out.indent();
+        for abi in common::script_function_abis(abis) {
+            writeln!(
+                self.out,
+                "is ScriptFunctionCall.{} -> encode_{}_script_function({})",
+                abi.name().to_camel_case(),
+                abi.name(),
+                Self::quote_call_fields(abi.ty_args(), abi.args()),
+            )?;
+        }
+        writeln!(
+            self.out,
+            "else -> throw IllegalArgumentException(\"Unknown script function call\")"
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_decode_method(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Try to recognize a Starcoin [Script] and convert it into a structured value [ScriptCall].
+ */
+fun decode_script(script: Script): ScriptCall {{
+    val helper = TRANSACTION_SCRIPT_DECODER_MAP[script.code]
+        ?: throw IllegalArgumentException("Unknown script bytecode")
+    return helper(script)
+}}
+
+/**
+ * Try to recognize a Starcoin [TransactionPayload] and convert it into a structured value [ScriptFunctionCall].
+ */
+fun decode_script_function_payload(payload: TransactionPayload): ScriptFunctionCall {{
+    if (payload !is TransactionPayload.ScriptFunction) {{
+        throw IllegalArgumentException("Unknown transaction payload")
+    }}
+    val script = payload.value
+    val helper = SCRIPT_FUNCTION_DECODER_MAP[script.module.name.value + script.function.value]
+        ?: throw IllegalArgumentException("Unknown script function")
+    return helper(payload)
+}}"#
+        )
+    }
+
+    fn output_script_encoder_function(&mut self, abi: &ScriptABI) -> Result<()> {
+        let params = [
+            Self::quote_type_parameters(abi.ty_args()),
+            Self::quote_parameters(abi.args()),
+        ]
+        .concat()
+        .join(", ");
+        let params_doc = abi
+            .ty_args()
+            .iter()
+            .map(TypeArgumentABI::name)
+            .chain(abi.args().iter().map(ArgumentABI::name))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        match abi {
+            ScriptABI::TransactionScript(abi) => {
+                writeln!(
+                    self.out,
+                    "\n{}fun encode_{}_script({}): Script {{",
+                    Self::quote_doc(abi.doc(), params_doc, Some("Encoded [Script] value.")),
+                    abi.name(),
+                    params,
+                )?;
+                self.out.indent();
+                writeln!(
+                    self.out,
+                    r#"val ty_args = listOf<TypeTag>({})
+val args = listOf<Bytes>({})
+return Script(Bytes({}_CODE), ty_args, args)"#,
+                    Self::quote_type_arguments(abi.ty_args()),
+                    Self::quote_arguments(abi.args()),
+                    abi.name().to_shouty_snake_case(),
+                )?;
+            }
+            ScriptABI::ScriptFunction(abi) => {
+                writeln!(
+                    self.out,
+                    "\n{}fun encode_{}_script_function({}): TransactionPayload {{",
+                    Self::quote_doc(
+                        abi.doc(),
+                        params_doc,
+                        Some("Encoded [TransactionPayload] value.")
+                    ),
+                    abi.name(),
+                    params,
+                )?;
+                self.out.indent();
+                writeln!(
+                    self.out,
+                    r#"val ty_args = listOf<TypeTag>({})
+val args = listOf<Bytes>({})
+val script_function = ScriptFunction(
+    {},
+    {},
+    ty_args,
+    args,
+)
+return TransactionPayload.ScriptFunction(script_function)"#,
+                    Self::quote_type_arguments(abi.ty_args()),
+                    Self::quote_arguments(abi.args()),
+                    Self::quote_module_id(abi.module_name()),
+                    Self::quote_identifier(abi.name()),
+                )?;
+            }
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_transaction_script_decoder_function(
+        &mut self,
+        abi: &TransactionScriptABI,
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nprivate fun decode_{}_script({}script: Script): ScriptCall {{",
+            abi.name(),
+            // prevent warning "unused variable"
+            if abi.ty_args().is_empty() && abi.args().is_empty() {
+                "@Suppress(\"UNUSED_PARAMETER\") "
+            } else {
+                ""
+            }
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "return {}",
+            Self::quote_decoded_call("ScriptCall", abi.name(), abi.ty_args(), abi.args()),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_decoder_function(&mut self, abi: &ScriptFunctionABI) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nprivate fun decode_{}_script_function(payload: TransactionPayload): ScriptFunctionCall {{",
+            abi.name(),
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"if (payload !is TransactionPayload.ScriptFunction) {{
+    throw IllegalArgumentException("Transaction payload not a script function")
+}}"#
+        )?;
+        if !(abi.ty_args().is_empty() && abi.args().is_empty()) {
+            writeln!(self.out, "val script = payload.value")?;
+        }
+        writeln!(
+            self.out,
+            "return {}",
+            Self::quote_decoded_call("ScriptFunctionCall", abi.name(), abi.ty_args(), abi.args()),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_code_constant(&mut self, abi: &TransactionScriptABI) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nprivate val {}_CODE: ByteArray = byteArrayOf({})",
+            abi.name().to_shouty_snake_case(),
+            abi.code()
+                .iter()
+                .map(|x| format!("{}", *x as i8))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn output_transaction_script_decoder_map(
+        &mut self,
+        abis: &[TransactionScriptABI],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nprivate val TRANSACTION_SCRIPT_DECODER_MAP: Map<Bytes, (Script) -> ScriptCall> = mapOf("
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "Bytes({}_CODE) to ::decode_{}_script,",
+                abi.name().to_shouty_snake_case(),
+                abi.name()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, ")")
+    }
+
+    fn output_script_function_decoder_map(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nprivate val SCRIPT_FUNCTION_DECODER_MAP: Map<String, (TransactionPayload) -> ScriptFunctionCall> = mapOf("
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "\"{0}{1}\" to ::decode_{1}_script_function,",
+                abi.module_name().name(),
+                abi.name()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, ")")
+    }
+
+    fn output_argument_helpers(&mut self, abis: &[ScriptABI]) -> Result<()> {
+        let required_types = common::get_required_decoding_helper_types(abis);
+        for required_type in required_types {
+            self.output_argument_helper(required_type)?;
+        }
+        Ok(())
+    }
+
+    fn output_argument_helper(&mut self, type_tag: &TypeTag) -> Result<()> {
+        use TypeTag::*;
+        let (serialize, deserialize) = match type_tag {
+            Bool => (
+                "serializer.serialize_bool(arg)",
+                "deserializer.deserialize_bool()",
+            ),
+            U8 => (
+                "serializer.serialize_u8(arg)",
+                "deserializer.deserialize_u8()",
+            ),
+            U64 => (
+                "serializer.serialize_u64(arg)",
+                "deserializer.deserialize_u64()",
+            ),
+            U128 => (
+                "serializer.serialize_u128(arg)",
+                "deserializer.deserialize_u128()",
+            ),
+            Address => (
+                "arg.serialize(serializer)",
+                "AccountAddress.deserialize(deserializer)",
+            ),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => (
+                    "serializer.serialize_bytes(arg)",
+                    "deserializer.deserialize_bytes()",
+                ),
+                _ => common::type_not_allowed(type_tag),
+            },
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        };
+        writeln!(
+            self.out,
+            r#"
+private fun encode_{0}_argument(arg: {1}): Bytes {{
+    val serializer = BcsSerializer()
+    {2}
+    return Bytes(serializer.get_bytes())
+}}
+
+private fun decode_{0}_argument(arg: Bytes): {1} {{
+    val deserializer = BcsDeserializer(arg.content())
+    return {3}
+}}"#,
+            common::mangle_type_ident(type_tag),
+            Self::quote_type(type_tag),
+            serialize,
+            deserialize,
+        )
+    }
+
+    fn quote_identifier(ident: &str) -> String {
+        format!("Identifier(\"{}\")", ident)
+    }
+
+    fn quote_address(address: &AccountAddress) -> String {
+        format!(
+            "AccountAddress(arrayOf<Byte>({}))",
+            address
+                .to_vec()
+                .iter()
+                .map(|x| format!("{}", *x as i8))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn quote_module_id(module_id: &ModuleId) -> String {
+        format!(
+            "ModuleId({}, {})",
+            Self::quote_address(module_id.address()),
+            Self::quote_identifier(module_id.name().as_str())
+        )
+    }
+
+    fn quote_doc(doc: &str, params: Vec<String>, return_doc: Option<&str>) -> String {
+        let mut doc = common::prepare_doc_string(doc).replace("*/", "* /");
+        if !params.is_empty() || return_doc.is_some() {
+            doc += "\n";
+        }
+        for param in params {
+            doc = format!("{}\n@param {}", doc, param);
+        }
+        if let Some(return_doc) = return_doc {
+            doc = format!("{}\n@return {}", doc, return_doc);
+        }
+        let text = textwrap::indent(&doc, " * ").replace("\n\n", "\n *\n");
+        format!("/**\n{}\n */\n", text)
+    }
+
+    fn quote_type_parameters(ty_args: &[TypeArgumentABI]) -> Vec<String> {
+        ty_args
+            .iter()
+            .map(|ty_arg| format!("{}: TypeTag", ty_arg.name()))
+            .collect()
+    }
+
+    fn quote_parameters(args: &[ArgumentABI]) -> Vec<String> {
+        args.iter()
+            .map(|arg| format!("{}: {}", arg.name(), Self::quote_type(arg.type_tag())))
+            .collect()
+    }
+
+    fn quote_type_arguments(ty_args: &[TypeArgumentABI]) -> String {
+        ty_args
+            .iter()
+            .map(|ty_arg| ty_arg.name().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_arguments(args: &[ArgumentABI]) -> String {
+        args.iter()
+            .map(|arg| {
+                format!(
+                    "encode_{}_argument({})",
+                    common::mangle_type_ident(arg.type_tag()),
+                    arg.name()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_call_fields(ty_args: &[TypeArgumentABI], args: &[ArgumentABI]) -> String {
+        ty_args
+            .iter()
+            .map(TypeArgumentABI::name)
+            .chain(args.iter().map(ArgumentABI::name))
+            .map(|name| format!("call.{}", name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_decoded_call(
+        base: &str,
+        name: &str,
+        ty_args: &[TypeArgumentABI],
+        args: &[ArgumentABI],
+    ) -> String {
+        if ty_args.is_empty() && args.is_empty() {
+            return format!("{}.{}", base, name.to_camel_case());
+        }
+        let fields = (0..ty_args.len())
+            .map(|index| format!("script.ty_args[{}]", index))
+            .chain(args.iter().enumerate().map(|(index, arg)| {
+                format!(
+                    "decode_{}_argument(script.args[{}])",
+                    common::mangle_type_ident(arg.type_tag()),
+                    index
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}.{}({})", base, name.to_camel_case(), fields)
+    }
+
+    fn quote_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "Boolean".into(),
+            U8 => "Byte".into(),
+            U64 => "Long".into(),
+            U128 => "BigInteger".into(),
+            Address => "AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "Bytes".into(),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        }
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        package_name: &str,
+        abis: &[ScriptABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let mut dir_path = self.install_dir.clone();
+        for part in package_name.split('.') {
+            dir_path = dir_path.join(part);
+        }
+        std::fs::create_dir_all(&dir_path)?;
+        let mut source = std::fs::File::create(dir_path.join("Helpers.kt"))?;
+        output(&mut source, package_name, abis)?;
+        Ok(())
+    }
+}
@@ -12,10 +12,14 @@ pub mod cpp;
 pub mod dart;
 /// Support for code-generation in Java 8.
 pub mod java;
+/// Support for code-generation in Kotlin.
+pub mod kotlin;
 /// Support for code-generation in Python 3.
 pub mod python3;
 /// Support for code-generation in Rust.
 pub mod rust;
+/// Support for code-generation in TypeScript.
+pub mod typescript;
 
 /// Internals shared between languages.
 mod common;
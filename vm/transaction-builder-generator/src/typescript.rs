@@ -0,0 +1,652 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common;
+use heck::{CamelCase, ShoutySnakeCase};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, TypeTag},
+};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use starcoin_vm_types::transaction::{
+    ArgumentABI, ScriptABI, ScriptFunctionABI, TransactionScriptABI, TypeArgumentABI,
+};
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output transaction builders and decoders in TypeScript for the given ABIs.
+/// The generated module expects the starcoin types, the serde and BCS runtimes generated by
+/// serde-generate to be installed next to it (`../starcoin_types`, `../serde`, `../bcs`).
+pub fn output(out: &mut dyn Write, abis: &[ScriptABI]) -> Result<()> {
+    let mut emitter = TypeScriptEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(2)),
+    };
+    emitter.output_preamble()?;
+
+    emitter.output_script_call_classes(&common::transaction_script_abis(abis))?;
+    emitter.output_script_function_call_classes(&common::script_function_abis(abis))?;
+
+    writeln!(emitter.out, "\nexport class Helpers {{")?;
+    emitter.out.indent();
+
+    emitter.output_encode_method()?;
+    emitter.output_decode_method()?;
+
+    for abi in abis {
+        emitter.output_script_encoder_function(abi)?;
+    }
+    for abi in common::transaction_script_abis(abis).iter() {
+        emitter.output_transaction_script_decoder_function(abi)?;
+    }
+    for abi in common::script_function_abis(abis).iter() {
+        emitter.output_script_function_decoder_function(abi)?;
+    }
+
+    for abi in common::transaction_script_abis(abis) {
+        emitter.output_code_constant(&abi)?;
+    }
+    // Must be defined after the constants.
+    emitter.output_transaction_script_encoder_map(&common::transaction_script_abis(abis))?;
+    emitter.output_script_function_encoder_map(&common::script_function_abis(abis))?;
+    emitter.output_transaction_script_decoder_map(&common::transaction_script_abis(abis))?;
+    emitter.output_script_function_decoder_map(&common::script_function_abis(abis))?;
+
+    emitter.output_argument_helpers(abis)?;
+
+    emitter.out.unindent();
+    writeln!(emitter.out, "}}")
+}
+
+/// Shared state for the TypeScript code generator.
+struct TypeScriptEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+}
+
+impl<T> TypeScriptEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"import {{ BcsDeserializer, BcsSerializer }} from "../bcs/mod.ts";
+import * as StarcoinTypes from "../starcoin_types/mod.ts";"#
+        )
+    }
+
+    fn output_script_call_classes(&mut self, abis: &[TransactionScriptABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Structured representation of a call into a known Move script.
+ */
+export abstract class ScriptCall {{
+  /**
+   * The name of the script, which identifies the variant of the call.
+   * The class name is not used since it can be changed by minifiers.
+   */
+  abstract readonly tag: string;
+}}"#
+        )?;
+        for abi in abis {
+            self.output_call_class(
+                "ScriptCall",
+                abi.name(),
+                abi.doc(),
+                abi.ty_args(),
+                abi.args(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_script_function_call_classes(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Structured representation of a call into a known Move script function.
+ */
+export abstract class ScriptFunctionCall {{
+  /**
+   * The name of the script function, which identifies the variant of the call.
+   * The class name is not used since it can be changed by minifiers.
+   */
+  abstract readonly tag: string;
+}}"#
+        )?;
+        for abi in abis {
+            self.output_call_class(
+                "ScriptFunctionCall",
+                abi.name(),
+                abi.doc(),
+                abi.ty_args(),
+                abi.args(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_call_class(
+        &mut self,
+        base: &str,
+        name: &str,
+        doc: &str,
+        ty_args: &[TypeArgumentABI],
+        args: &[ArgumentABI],
+    ) -> Result<()> {
+        let fields = Self::quote_type_parameters(ty_args)
+            .into_iter()
+            .chain(Self::quote_parameters(args))
+            .map(|field| format!("public {}", field))
+            .collect::<Vec<_>>();
+        writeln!(
+            self.out,
+            "\n{}export class {}Variant{} extends {} {{",
+            Self::quote_doc(doc, vec![], None),
+            base,
+            name.to_camel_case(),
+            base
+        )?;
+        self.out.indent();
+        writeln!(self.out, "readonly tag = {};\n", Self::quote_tag(name))?;
+        writeln!(
+            self.out,
+            "constructor({}) {{\n  super();\n}}",
+            fields.join(", ")
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_encode_method(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Build a Starcoin `Script` from a structured object `ScriptCall`.
+ */
+static encode_script(call: ScriptCall): StarcoinTypes.Script {{
+  const helper = Helpers.TRANSACTION_SCRIPT_ENCODER_MAP[call.tag];
+  if (helper === undefined) {{
+    throw new Error("Unknown script call " + call.tag);
+  }}
+  return helper(call);
+}}
+
+/**
+ * Build a Starcoin `TransactionPayload` from a structured object `ScriptFunctionCall`.
+ */
+static encode_script_function(call: ScriptFunctionCall): StarcoinTypes.TransactionPayload {{
+  const helper = Helpers.SCRIPT_FUNCTION_ENCODER_MAP[call.tag];
+  if (helper === undefined) {{
+    throw new Error("Unknown script function call " + call.tag);
+  }}
+  return helper(call);
+}}"#
+        )
+    }
+
+    fn output_decode_method(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/**
+ * Try to recognize a Starcoin `Script` and convert it into a structured object `ScriptCall`.
+ */
+static decode_script(script: StarcoinTypes.Script): ScriptCall {{
+  const helper = Helpers.TRANSACTION_SCRIPT_DECODER_MAP[script.code.toString()];
+  if (helper === undefined) {{
+    throw new Error("Unknown script bytecode");
+  }}
+  return helper(script);
+}}
+
+/**
+ * Try to recognize a Starcoin `TransactionPayload` and convert it into a structured object `ScriptFunctionCall`.
+ */
+static decode_script_function_payload(payload: StarcoinTypes.TransactionPayload): ScriptFunctionCall {{
+  if (!(payload instanceof StarcoinTypes.TransactionPayloadVariantScriptFunction)) {{
+    throw new Error("Unexpected transaction payload");
+  }}
+  const script = payload.value;
+  const helper = Helpers.SCRIPT_FUNCTION_DECODER_MAP[script.module.name.value + script.function.value];
+  if (helper === undefined) {{
+    throw new Error("Unknown script function");
+  }}
+  return helper(payload);
+}}"#
+        )
+    }
+
+    fn output_script_encoder_function(&mut self, abi: &ScriptABI) -> Result<()> {
+        let params = [
+            Self::quote_type_parameters(abi.ty_args()),
+            Self::quote_parameters(abi.args()),
+        ]
+        .concat()
+        .join(", ");
+        let params_doc = abi
+            .ty_args()
+            .iter()
+            .map(TypeArgumentABI::name)
+            .chain(abi.args().iter().map(ArgumentABI::name))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        match abi {
+            ScriptABI::TransactionScript(abi) => {
+                writeln!(
+                    self.out,
+                    "\n{}static encode_{}_script({}): StarcoinTypes.Script {{",
+                    Self::quote_doc(abi.doc(), params_doc, Some("Encoded `Script` value.")),
+                    abi.name(),
+                    params,
+                )?;
+                self.out.indent();
+                writeln!(
+                    self.out,
+                    r#"const ty_args: StarcoinTypes.TypeTag[] = [{}];
+const args: Uint8Array[] = [{}];
+return new StarcoinTypes.Script(Helpers.{}_CODE, ty_args, args);"#,
+                    Self::quote_type_arguments(abi.ty_args()),
+                    Self::quote_arguments(abi.args()),
+                    abi.name().to_shouty_snake_case(),
+                )?;
+            }
+            ScriptABI::ScriptFunction(abi) => {
+                writeln!(
+                    self.out,
+                    "\n{}static encode_{}_script_function({}): StarcoinTypes.TransactionPayload {{",
+                    Self::quote_doc(
+                        abi.doc(),
+                        params_doc,
+                        Some("Encoded `TransactionPayload` value.")
+                    ),
+                    abi.name(),
+                    params,
+                )?;
+                self.out.indent();
+                writeln!(
+                    self.out,
+                    r#"const ty_args: StarcoinTypes.TypeTag[] = [{}];
+const args: Uint8Array[] = [{}];
+const script_function = new StarcoinTypes.ScriptFunction(
+  {},
+  {},
+  ty_args,
+  args,
+);
+return new StarcoinTypes.TransactionPayloadVariantScriptFunction(script_function);"#,
+                    Self::quote_type_arguments(abi.ty_args()),
+                    Self::quote_arguments(abi.args()),
+                    Self::quote_module_id(abi.module_name()),
+                    Self::quote_identifier(abi.name()),
+                )?;
+            }
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_transaction_script_decoder_function(
+        &mut self,
+        abi: &TransactionScriptABI,
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic decode_{}_script({}script: StarcoinTypes.Script): ScriptCall {{",
+            abi.name(),
+            // prevent warning "unused variable"
+            if abi.ty_args().is_empty() && abi.args().is_empty() {
+                "_"
+            } else {
+                ""
+            }
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "return new ScriptCallVariant{}({});",
+            abi.name().to_camel_case(),
+            Self::quote_decoded_arguments(abi.ty_args(), abi.args()),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_decoder_function(&mut self, abi: &ScriptFunctionABI) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic decode_{}_script_function(payload: StarcoinTypes.TransactionPayload): ScriptFunctionCall {{",
+            abi.name(),
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"if (!(payload instanceof StarcoinTypes.TransactionPayloadVariantScriptFunction)) {{
+  throw new Error("Transaction payload not a script function");
+}}
+const {}script = payload.value;
+return new ScriptFunctionCallVariant{}({});"#,
+            // prevent warning "unused variable"
+            if abi.ty_args().is_empty() && abi.args().is_empty() {
+                "_"
+            } else {
+                ""
+            },
+            abi.name().to_camel_case(),
+            Self::quote_decoded_arguments(abi.ty_args(), abi.args()),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_code_constant(&mut self, abi: &TransactionScriptABI) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic readonly {}_CODE: Uint8Array = new Uint8Array([{}]);",
+            abi.name().to_shouty_snake_case(),
+            abi.code()
+                .iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn output_transaction_script_encoder_map(
+        &mut self,
+        abis: &[TransactionScriptABI],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic TRANSACTION_SCRIPT_ENCODER_MAP: {{ [name: string]: (call: ScriptCall) => StarcoinTypes.Script }} = {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "{3}: (call: ScriptCall) => {{
+  const obj = call as ScriptCallVariant{0};
+  return Helpers.encode_{1}_script({2});
+}},",
+                abi.name().to_camel_case(),
+                abi.name(),
+                Self::quote_call_fields(abi.ty_args(), abi.args()),
+                Self::quote_tag(abi.name()),
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")
+    }
+
+    fn output_script_function_encoder_map(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic SCRIPT_FUNCTION_ENCODER_MAP: {{ [name: string]: (call: ScriptFunctionCall) => StarcoinTypes.TransactionPayload }} = {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "{3}: (call: ScriptFunctionCall) => {{
+  const obj = call as ScriptFunctionCallVariant{0};
+  return Helpers.encode_{1}_script_function({2});
+}},",
+                abi.name().to_camel_case(),
+                abi.name(),
+                Self::quote_call_fields(abi.ty_args(), abi.args()),
+                Self::quote_tag(abi.name()),
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")
+    }
+
+    fn output_transaction_script_decoder_map(
+        &mut self,
+        abis: &[TransactionScriptABI],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic TRANSACTION_SCRIPT_DECODER_MAP: {{ [code: string]: (script: StarcoinTypes.Script) => ScriptCall }} = {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "[Helpers.{}_CODE.toString()]: Helpers.decode_{}_script,",
+                abi.name().to_shouty_snake_case(),
+                abi.name()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")
+    }
+
+    fn output_script_function_decoder_map(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nstatic SCRIPT_FUNCTION_DECODER_MAP: {{ [name: string]: (payload: StarcoinTypes.TransactionPayload) => ScriptFunctionCall }} = {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "\"{0}{1}\": Helpers.decode_{1}_script_function,",
+                abi.module_name().name(),
+                abi.name()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")
+    }
+
+    fn output_argument_helpers(&mut self, abis: &[ScriptABI]) -> Result<()> {
+        let required_types = common::get_required_decoding_helper_types(abis);
+        for required_type in required_types {
+            self.output_argument_helper(required_type)?;
+        }
+        Ok(())
+    }
+
+    fn output_argument_helper(&mut self, type_tag: &TypeTag) -> Result<()> {
+        use TypeTag::*;
+        let (serialize, deserialize) = match type_tag {
+            Bool => (
+                "serializer.serializeBool(arg);".to_string(),
+                "deserializer.deserializeBool()".to_string(),
+            ),
+            U8 => (
+                "serializer.serializeU8(arg);".to_string(),
+                "deserializer.deserializeU8()".to_string(),
+            ),
+            U64 => (
+                "serializer.serializeU64(arg);".to_string(),
+                "deserializer.deserializeU64()".to_string(),
+            ),
+            U128 => (
+                "serializer.serializeU128(arg);".to_string(),
+                "deserializer.deserializeU128()".to_string(),
+            ),
+            Address => (
+                "arg.serialize(serializer);".to_string(),
+                "StarcoinTypes.AccountAddress.deserialize(deserializer)".to_string(),
+            ),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => (
+                    "serializer.serializeBytes(arg);".to_string(),
+                    "deserializer.deserializeBytes()".to_string(),
+                ),
+                _ => common::type_not_allowed(type_tag),
+            },
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        };
+        writeln!(
+            self.out,
+            r#"
+static encode_{0}_argument(arg: {1}): Uint8Array {{
+  const serializer = new BcsSerializer();
+  {2}
+  return serializer.getBytes();
+}}
+
+static decode_{0}_argument(arg: Uint8Array): {1} {{
+  const deserializer = new BcsDeserializer(arg);
+  return {3};
+}}"#,
+            common::mangle_type_ident(type_tag),
+            Self::quote_type(type_tag),
+            serialize,
+            deserialize,
+        )
+    }
+
+    fn quote_tag(name: &str) -> String {
+        format!("\"{}\"", name)
+    }
+
+    fn quote_identifier(ident: &str) -> String {
+        format!("new StarcoinTypes.Identifier(\"{}\")", ident)
+    }
+
+    fn quote_address(address: &AccountAddress) -> String {
+        format!(
+            "new StarcoinTypes.AccountAddress([{}])",
+            address
+                .to_vec()
+                .iter()
+                .map(|x| format!("[{}]", x))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn quote_module_id(module_id: &ModuleId) -> String {
+        format!(
+            "new StarcoinTypes.ModuleId({}, {})",
+            Self::quote_address(module_id.address()),
+            Self::quote_identifier(module_id.name().as_str())
+        )
+    }
+
+    fn quote_doc(doc: &str, params: Vec<String>, return_doc: Option<&str>) -> String {
+        let mut doc = common::prepare_doc_string(doc).replace("*/", "* /");
+        if !params.is_empty() || return_doc.is_some() {
+            doc += "\n";
+        }
+        for param in params {
+            doc = format!("{}\n@param {}", doc, param);
+        }
+        if let Some(return_doc) = return_doc {
+            doc = format!("{}\n@returns {}", doc, return_doc);
+        }
+        let text = textwrap::indent(&doc, " * ").replace("\n\n", "\n *\n");
+        format!("/**\n{}\n */\n", text)
+    }
+
+    fn quote_type_parameters(ty_args: &[TypeArgumentABI]) -> Vec<String> {
+        ty_args
+            .iter()
+            .map(|ty_arg| format!("{}: StarcoinTypes.TypeTag", ty_arg.name()))
+            .collect()
+    }
+
+    fn quote_parameters(args: &[ArgumentABI]) -> Vec<String> {
+        args.iter()
+            .map(|arg| format!("{}: {}", arg.name(), Self::quote_type(arg.type_tag())))
+            .collect()
+    }
+
+    fn quote_type_arguments(ty_args: &[TypeArgumentABI]) -> String {
+        ty_args
+            .iter()
+            .map(|ty_arg| ty_arg.name().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_arguments(args: &[ArgumentABI]) -> String {
+        args.iter()
+            .map(|arg| {
+                format!(
+                    "Helpers.encode_{}_argument({})",
+                    common::mangle_type_ident(arg.type_tag()),
+                    arg.name()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_call_fields(ty_args: &[TypeArgumentABI], args: &[ArgumentABI]) -> String {
+        ty_args
+            .iter()
+            .map(TypeArgumentABI::name)
+            .chain(args.iter().map(ArgumentABI::name))
+            .map(|name| format!("obj.{}", name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_decoded_arguments(ty_args: &[TypeArgumentABI], args: &[ArgumentABI]) -> String {
+        (0..ty_args.len())
+            .map(|index| format!("script.ty_args[{}]", index))
+            .chain(args.iter().enumerate().map(|(index, arg)| {
+                format!(
+                    "Helpers.decode_{}_argument(script.args[{}])",
+                    common::mangle_type_ident(arg.type_tag()),
+                    index
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "boolean".into(),
+            U8 => "number".into(),
+            U64 => "bigint".into(),
+            U128 => "bigint".into(),
+            Address => "StarcoinTypes.AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "Uint8Array".into(),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        }
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[ScriptABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut source = std::fs::File::create(&dir_path.join("mod.ts"))?;
+        output(&mut source, abis)?;
+        Ok(())
+    }
+}
@@ -28,6 +28,8 @@ fn get_stdlib_script_abis() -> Vec<ScriptABI> {
 const EXPECTED_OUTPUT : &str = "2 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 15 84 114 97 110 115 102 101 114 83 99 114 105 112 116 115 26 112 101 101 114 95 116 111 95 112 101 101 114 95 119 105 116 104 95 109 101 116 97 100 97 116 97 1 7 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 3 83 84 67 3 83 84 67 0 4 3 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 4 32 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 2 135 214 18 0 0 0 0 0 0 0 0 0 0 0 0 0 4 0 \n";
 // const OUTPUT : &str = "181 1 161 28 235 11 1 0 0 0 6 1 0 2 3 2 17 4 19 4 5 23 28 7 51 56 8 107 16 0 0 0 1 0 1 1 1 0 2 2 3 0 0 3 4 1 1 1 0 6 2 6 2 5 10 2 0 1 5 1 1 4 6 12 5 4 10 2 5 6 12 5 10 2 4 10 2 1 9 0 7 65 99 99 111 117 110 116 14 99 114 101 97 116 101 95 97 99 99 111 117 110 116 9 101 120 105 115 116 115 95 97 116 22 112 97 121 95 102 114 111 109 95 119 105 116 104 95 109 101 116 97 100 97 116 97 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 1 1 5 1 14 10 1 17 1 32 3 5 5 8 10 1 11 2 56 0 11 0 10 1 10 3 11 4 56 1 2 1 7 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 3 76 66 82 3 76 66 82 0 4 3 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 4 32 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 2 135 214 18 0 0 0 0 0 0 0 0 0 0 0 0 0 4 0 \n";
 const OUTPUT: &str = "255 1 161 28 235 11 2 0 0 0 7 1 0 4 3 4 22 4 26 4 5 30 33 7 63 80 8 143 1 16 6 159 1 10 0 0 0 1 0 2 3 4 0 0 3 6 3 1 4 1 4 7 7 0 0 5 8 2 1 4 1 5 3 5 5 6 12 5 10 2 4 10 2 3 5 1 3 0 1 5 1 1 1 9 0 1 10 2 1 3 4 6 12 5 4 10 2 7 65 99 99 111 117 110 116 6 69 114 114 111 114 115 9 101 120 105 115 116 115 95 97 116 14 99 114 101 97 116 101 95 97 99 99 111 117 110 116 16 105 110 118 97 108 105 100 95 97 114 103 117 109 101 110 116 22 112 97 121 95 102 114 111 109 95 119 105 116 104 95 109 101 116 97 100 97 116 97 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 3 8 101 0 0 0 0 0 0 0 1 4 0 1 27 10 1 17 0 32 3 5 5 21 11 2 56 0 12 5 10 1 10 5 33 7 0 17 2 12 7 12 6 11 6 3 21 11 0 1 11 7 39 11 0 10 1 10 3 11 4 56 1 2 1 7 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 3 83 84 67 3 83 84 67 0 4 3 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 4 32 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 2 135 214 18 0 0 0 0 0 0 0 0 0 0 0 0 0 4 0 \n";
+// The BCS bytes of the `TransferScripts::peer_to_peer_v2` payload built in the TypeScript and Kotlin demos.
+const SCRIPT_FUNCTION_OUTPUT: &str = "2 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 15 84 114 97 110 115 102 101 114 83 99 114 105 112 116 115 15 112 101 101 114 95 116 111 95 112 101 101 114 95 118 50 1 7 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 3 83 84 67 3 83 84 67 0 2 16 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 34 16 135 214 18 0 0 0 0 0 0 0 0 0 0 0 0 0 \n";
 
 #[ignore]
 #[test]
@@ -287,3 +289,138 @@ fn test_that_java_code_compiles_and_demo_runs() {
     assert!(output.status.success());
     assert_eq!(std::str::from_utf8(&output.stdout).unwrap(), OUTPUT);
 }
+
+#[test]
+fn test_that_typescript_and_kotlin_builders_cover_all_abis() {
+    let abis = get_stdlib_script_abis();
+    let mut typescript = Vec::new();
+    buildgen::typescript::output(&mut typescript, &abis).unwrap();
+    let typescript = String::from_utf8(typescript).unwrap();
+    let mut kotlin = Vec::new();
+    buildgen::kotlin::output(&mut kotlin, "org.starcoin.stdlib", &abis).unwrap();
+    let kotlin = String::from_utf8(kotlin).unwrap();
+
+    // The encoders are dispatched by the explicit tag, not the class name.
+    assert!(!typescript.contains("constructor.name"));
+    for abi in &abis {
+        let (encoder, decoder) = if abi.is_transaction_script_abi() {
+            (
+                format!("encode_{}_script(", abi.name()),
+                format!("decode_{}_script(", abi.name()),
+            )
+        } else {
+            (
+                format!("encode_{}_script_function(", abi.name()),
+                format!("decode_{}_script_function(", abi.name()),
+            )
+        };
+        assert!(typescript.contains(&encoder), "missing {}", encoder);
+        assert!(typescript.contains(&decoder), "missing {}", decoder);
+        let tag = format!("readonly tag = \"{}\";", abi.name());
+        assert!(typescript.contains(&tag), "missing {}", tag);
+        assert!(kotlin.contains(&encoder), "missing {}", encoder);
+        assert!(kotlin.contains(&decoder), "missing {}", decoder);
+    }
+}
+
+#[ignore]
+#[test]
+fn test_that_typescript_code_compiles_and_demo_runs() {
+    let registry = get_starcoin_registry();
+    let abis = get_stdlib_script_abis();
+    let dir = tempdir().unwrap();
+
+    let installer = serdegen::typescript::Installer::new(dir.path().to_path_buf());
+    let config = serdegen::CodeGeneratorConfig::new("starcoin_types".to_string())
+        .with_encodings(vec![serdegen::Encoding::Bcs]);
+    installer.install_module(&config, &registry).unwrap();
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+
+    let abi_installer = buildgen::typescript::Installer::new(dir.path().to_path_buf());
+    abi_installer
+        .install_transaction_builders("starcoin_stdlib", &abis)
+        .unwrap();
+
+    std::fs::copy(
+        "examples/typescript/stdlib_demo.ts",
+        dir.path().join("stdlib_demo.ts"),
+    )
+    .unwrap();
+
+    let output = Command::new("deno")
+        .arg("run")
+        .arg(dir.path().join("stdlib_demo.ts"))
+        .output()
+        .unwrap();
+    eprintln!(
+        "stderr: \n {}",
+        std::str::from_utf8(&output.stderr).unwrap()
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        std::str::from_utf8(&output.stdout).unwrap(),
+        SCRIPT_FUNCTION_OUTPUT
+    );
+}
+
+#[ignore]
+#[test]
+fn test_that_kotlin_code_compiles_and_demo_runs() {
+    let registry = get_starcoin_registry();
+    let abis = get_stdlib_script_abis();
+    let dir = tempdir().unwrap();
+
+    // The Kotlin builders use the starcoin types generated for Java.
+    let installer = serdegen::java::Installer::new(dir.path().to_path_buf());
+    let config = serdegen::CodeGeneratorConfig::new("org.starcoin.types".to_string())
+        .with_encodings(vec![serdegen::Encoding::Bcs]);
+    installer.install_module(&config, &registry).unwrap();
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+
+    let abi_installer = buildgen::kotlin::Installer::new(dir.path().to_path_buf());
+    abi_installer
+        .install_transaction_builders("org.starcoin.stdlib", &abis)
+        .unwrap();
+
+    let java_paths = std::iter::empty()
+        .chain(std::fs::read_dir(dir.path().join("com/novi/serde")).unwrap())
+        .chain(std::fs::read_dir(dir.path().join("com/novi/bcs")).unwrap())
+        .chain(std::fs::read_dir(dir.path().join("org/starcoin/types")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-d")
+        .arg(dir.path())
+        .args(java_paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let jar_path = dir.path().join("stdlib_demo.jar");
+    let status = Command::new("kotlinc")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg(dir.path().join("org/starcoin/stdlib/Helpers.kt"))
+        .arg("examples/kotlin/StdlibDemo.kt")
+        .arg("-include-runtime")
+        .arg("-d")
+        .arg(&jar_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let classpath = std::env::join_paths(vec![jar_path.as_path(), dir.path()]).unwrap();
+    let output = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(classpath)
+        .arg("StdlibDemoKt")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        std::str::from_utf8(&output.stdout).unwrap(),
+        SCRIPT_FUNCTION_OUTPUT
+    );
+}
@@ -7,10 +7,10 @@ use crate::event::EventHandle;
 use crate::move_resource::MoveResource;
 use serde::{Deserialize, Serialize};
 
-pub const _STRATEGY_ARBITRARY: u8 = 0;
+pub const STRATEGY_ARBITRARY: u8 = 0;
 pub const STRATEGY_TWO_PHASE: u8 = 1;
 pub const STRATEGY_NEW_MODULE: u8 = 2;
-pub const _STRATEGY_FREEZE: u8 = 3;
+pub const STRATEGY_FREEZE: u8 = 3;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModuleUpgradeStrategy {
@@ -18,6 +18,10 @@ pub struct ModuleUpgradeStrategy {
 }
 
 impl ModuleUpgradeStrategy {
+    pub fn strategy(&self) -> u8 {
+        self.strategy
+    }
+
     pub fn only_new_module(&self) -> bool {
         self.strategy == STRATEGY_NEW_MODULE
     }
@@ -50,6 +54,10 @@ impl TwoPhaseUpgradeV2Resource {
             None => false,
         }
     }
+
+    pub fn plan(&self) -> Option<&UpgradePlanV2Resource> {
+        self.plan.as_ref()
+    }
 }
 impl MoveResource for TwoPhaseUpgradeV2Resource {
     const MODULE_NAME: &'static str = "PackageTxnManager";
@@ -76,6 +84,23 @@ pub struct UpgradePlanV2Resource {
     version: u64,
     enforced: bool,
 }
+impl UpgradePlanV2Resource {
+    pub fn package_hash(&self) -> &[u8] {
+        self.package_hash.as_slice()
+    }
+
+    pub fn active_after_time(&self) -> u64 {
+        self.active_after_time
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn enforced(&self) -> bool {
+        self.enforced
+    }
+}
 impl MoveResource for UpgradePlanV2Resource {
     const MODULE_NAME: &'static str = "PackageTxnManager";
     const STRUCT_NAME: &'static str = "UpgradePlanV2";
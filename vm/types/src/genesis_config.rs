@@ -118,6 +118,8 @@ pub enum ConsensusStrategy {
     Argon = 1,
     Keccak = 2,
     CryptoNight = 3,
+    /// The consensus implementation is registered by downstream crate, see `consensus::registry`.
+    Custom = 255,
 }
 
 impl ConsensusStrategy {
@@ -139,6 +141,7 @@ impl fmt::Display for ConsensusStrategy {
             ConsensusStrategy::Argon => write!(f, "argon"),
             ConsensusStrategy::Keccak => write!(f, "keccak"),
             ConsensusStrategy::CryptoNight => write!(f, "cryptonight"),
+            ConsensusStrategy::Custom => write!(f, "custom"),
         }
     }
 }
@@ -152,6 +155,7 @@ impl FromStr for ConsensusStrategy {
             "argon" => Ok(ConsensusStrategy::Argon),
             "keccak" => Ok(ConsensusStrategy::Keccak),
             "cryptonight" => Ok(ConsensusStrategy::CryptoNight),
+            "custom" => Ok(ConsensusStrategy::Custom),
             s => Err(format_err!("Unknown ConsensusStrategy: {}", s)),
         }
     }
@@ -115,6 +115,9 @@ pub mod vm_status {
 pub mod effects {
     pub use move_core_types::effects::*;
 }
+pub mod errmap {
+    pub use move_core_types::errmap::*;
+}
 pub mod bytecode_verifier {
     pub use bytecode_verifier::{dependencies, script_signature, verify_module, verify_script};
 }
@@ -0,0 +1,102 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::access::ModuleAccess;
+use crate::access_path::AccessPath;
+use crate::file_format::{CompiledModule, FunctionDefinitionIndex};
+use crate::identifier::Identifier;
+use crate::state_view::StateView;
+use crate::vm_status::{AbortLocation, StatusCode, StatusType};
+use serde::{Deserialize, Serialize};
+use vm::errors::{Location, VMError};
+
+/// A code offset the VM attached to the error of a transaction.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ErrorOffset {
+    /// The index of the function definition in the module.
+    pub function: u16,
+    /// The function name, `None` for script or if the module can not be resolved.
+    pub function_name: Option<Identifier>,
+    pub code_offset: u16,
+}
+
+/// The context of a `MoveAbort` or `ExecutionFailure`, captured by the executor so failed
+/// transactions can be debugged without replaying them.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AbortContext {
+    pub status_code: StatusCode,
+    /// The abort code of `MoveAbort`.
+    pub abort_code: Option<u64>,
+    pub location: AbortLocation,
+    pub function: u16,
+    pub function_name: Option<Identifier>,
+    pub code_offset: u16,
+    /// The code offsets the VM attached to the error, all in the module of `location`, the first
+    /// one is where the error is raised. The VM does not record the frames of the callers, so
+    /// this is not a call stack.
+    pub offsets: Vec<ErrorOffset>,
+    pub message: Option<String>,
+}
+
+impl AbortContext {
+    /// Capture the context of `error` if it is raised by executing Move code, the modules are
+    /// resolved from `state_view` to get the function names.
+    pub fn from_vm_error(error: &VMError, state_view: &dyn StateView) -> Option<Self> {
+        if error.major_status().status_type() != StatusType::Execution {
+            return None;
+        }
+        let location = match error.location() {
+            Location::Module(module_id) => AbortLocation::Module(module_id.clone()),
+            Location::Script => AbortLocation::Script,
+            Location::Undefined => return None,
+        };
+        let module = match &location {
+            AbortLocation::Module(module_id) => state_view
+                .get(&AccessPath::from(module_id))
+                .ok()
+                .flatten()
+                .and_then(|code| CompiledModule::deserialize(code.as_slice()).ok()),
+            AbortLocation::Script => None,
+        };
+        let offsets: Vec<_> = error
+            .offsets()
+            .iter()
+            .map(|(function, code_offset)| ErrorOffset {
+                function: function.0,
+                function_name: module
+                    .as_ref()
+                    .and_then(|module| function_name(module, *function)),
+                code_offset: *code_offset,
+            })
+            .collect();
+        let (function, function_name, code_offset) = match offsets.first() {
+            Some(offset) => (
+                offset.function,
+                offset.function_name.clone(),
+                offset.code_offset,
+            ),
+            None => (0, None, 0),
+        };
+        let status_code = error.major_status();
+        Some(Self {
+            status_code,
+            abort_code: if status_code == StatusCode::ABORTED {
+                error.sub_status()
+            } else {
+                None
+            },
+            location,
+            function,
+            function_name,
+            code_offset,
+            offsets,
+            message: error.message().cloned(),
+        })
+    }
+}
+
+fn function_name(module: &CompiledModule, function: FunctionDefinitionIndex) -> Option<Identifier> {
+    let function_def = module.function_defs().get(function.0 as usize)?;
+    let function_handle = module.function_handle_at(function_def.function);
+    Some(module.identifier_at(function_handle.name).to_owned())
+}
@@ -29,6 +29,7 @@ use starcoin_crypto::{
 use std::ops::Deref;
 use std::{convert::TryFrom, fmt};
 
+pub use abort_context::{AbortContext, ErrorOffset};
 pub use error::CallError;
 pub use error::Error as TransactionError;
 pub use module::Module;
@@ -44,6 +45,7 @@ pub use transaction_argument::{
     parse_transaction_argument, parse_transaction_arguments, TransactionArgument,
 };
 
+mod abort_context;
 pub mod authenticator;
 mod error;
 pub mod helpers;
@@ -637,7 +639,7 @@ impl From<VMStatus> for TransactionStatus {
 }
 
 /// The output of executing a transaction.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionOutput {
     write_set: WriteSet,
 
@@ -649,8 +651,24 @@ pub struct TransactionOutput {
 
     /// The execution status.
     status: TransactionStatus,
+
+    /// The context of the abort or execution failure, only for debugging. It is not a part of
+    /// the execution result, so it is skipped by the serialization and the equality.
+    #[serde(skip)]
+    abort_context: Option<AbortContext>,
+}
+
+impl PartialEq for TransactionOutput {
+    fn eq(&self, other: &Self) -> bool {
+        self.write_set == other.write_set
+            && self.events == other.events
+            && self.gas_used == other.gas_used
+            && self.status == other.status
+    }
 }
 
+impl Eq for TransactionOutput {}
+
 impl TransactionOutput {
     pub fn new(
         write_set: WriteSet,
@@ -663,9 +681,15 @@ impl TransactionOutput {
             events,
             gas_used,
             status,
+            abort_context: None,
         }
     }
 
+    pub fn with_abort_context(mut self, abort_context: Option<AbortContext>) -> Self {
+        self.abort_context = abort_context;
+        self
+    }
+
     pub fn write_set(&self) -> &WriteSet {
         &self.write_set
     }
@@ -682,6 +706,10 @@ impl TransactionOutput {
         &self.status
     }
 
+    pub fn abort_context(&self) -> Option<&AbortContext> {
+        self.abort_context.as_ref()
+    }
+
     pub fn into_inner(self) -> (WriteSet, Vec<ContractEvent>, u64, TransactionStatus) {
         (self.write_set, self.events, self.gas_used, self.status)
     }
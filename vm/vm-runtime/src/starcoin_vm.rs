@@ -41,14 +41,16 @@ use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::identifier::IdentStr;
 use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::on_chain_config::MoveLanguageVersion;
-use starcoin_vm_types::transaction::{DryRunTransaction, Module, Package, TransactionPayloadType};
+use starcoin_vm_types::transaction::{
+    AbortContext, DryRunTransaction, Module, Package, TransactionPayloadType,
+};
 use starcoin_vm_types::transaction_metadata::TransactionPayloadMetadata;
 use starcoin_vm_types::value::{serialize_values, MoveValue};
 use starcoin_vm_types::vm_status::KeptVMStatus;
 use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
 use starcoin_vm_types::{
     effects::{ChangeSet as MoveChangeSet, Event as MoveEvent},
-    errors::{self, IndexKind, Location},
+    errors::{self, IndexKind, Location, VMError},
     event::EventKey,
     gas_schedule::{self, CostTable, GasAlgebra, GasCarrier, GasUnits, InternalGasUnits},
     language_storage::TypeTag,
@@ -387,7 +389,7 @@ impl StarcoinVM {
         cost_strategy: &mut GasStatus,
         txn_data: &TransactionMetadata,
         package: &Package,
-    ) -> Result<(VMStatus, TransactionOutput), VMStatus> {
+    ) -> Result<(VMStatus, TransactionOutput), ExecutionError> {
         let mut session = self.move_vm.new_session(remote_cache);
 
         {
@@ -417,7 +419,7 @@ impl StarcoinVM {
             match Self::only_new_module_strategy(remote_cache, package_address) {
                 Err(e) => {
                     warn!("[VM]Update module strategy deserialize err : {:?}", e);
-                    return Err(VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE));
+                    return Err(VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE).into());
                 }
                 Ok(only_new_module) => {
                     for module in package.modules() {
@@ -425,7 +427,10 @@ impl StarcoinVM {
                             Ok(module) => module,
                             Err(err) => {
                                 warn!("[VM] module deserialization failed {:?}", err);
-                                return Err(err.finish(Location::Undefined).into_vm_status());
+                                return Err(err
+                                    .finish(Location::Undefined)
+                                    .into_vm_status()
+                                    .into());
                             }
                         };
 
@@ -440,7 +445,8 @@ impl StarcoinVM {
                                 compiled_module.self_handle_idx().0,
                             )
                             .finish(Location::Undefined)
-                            .into_vm_status());
+                            .into_vm_status()
+                            .into());
                         }
                         self.check_compatibility_if_exist(
                             &session,
@@ -485,7 +491,7 @@ impl StarcoinVM {
                         vec![sender],
                         cost_strategy,
                     )
-                    .map_err(|e| e.into_vm_status())?;
+                    .map_err(|e| ExecutionError::new(e, remote_cache))?;
             }
             charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
 
@@ -496,6 +502,7 @@ impl StarcoinVM {
                 cost_strategy.remaining_gas(),
                 txn_data,
             )
+            .map_err(Into::into)
         }
     }
 
@@ -506,7 +513,7 @@ impl StarcoinVM {
         cost_strategy: &mut GasStatus,
         txn_data: &TransactionMetadata,
         payload: &TransactionPayload,
-    ) -> Result<(VMStatus, TransactionOutput), VMStatus> {
+    ) -> Result<(VMStatus, TransactionOutput), ExecutionError> {
         let mut session = self.move_vm.new_session(remote_cache);
 
         // Run the validation logic
@@ -549,10 +556,10 @@ impl StarcoinVM {
                         cost_strategy,
                     ),
                 TransactionPayload::Package(_) => {
-                    return Err(VMStatus::Error(StatusCode::UNREACHABLE));
+                    return Err(VMStatus::Error(StatusCode::UNREACHABLE).into());
                 }
             }
-            .map_err(|e| e.into_vm_status())?;
+            .map_err(|e| ExecutionError::new(e, remote_cache))?;
 
             charge_global_write_gas_usage(cost_strategy, &session, &txn_data.sender())?;
 
@@ -563,6 +570,7 @@ impl StarcoinVM {
                 cost_strategy.remaining_gas(),
                 txn_data,
             )
+            .map_err(Into::into)
         }
     }
 
@@ -833,19 +841,23 @@ impl StarcoinVM {
                         );
                         status_and_output
                     }
-                    Err(err) => {
+                    Err(ExecutionError {
+                        status: err,
+                        abort_context,
+                    }) => {
                         let txn_status = TransactionStatus::from(err.clone());
                         log_vm_status(txn_id, &txn_data, &err, None);
                         if txn_status.is_discarded() {
                             discard_error_vm_status(err)
                         } else {
-                            self.failed_transaction_cleanup(
+                            let (status, output) = self.failed_transaction_cleanup(
                                 err,
                                 gas_schedule,
                                 gas_status.remaining_gas(),
                                 &txn_data,
                                 remote_cache,
-                            )
+                            );
+                            (status, output.with_abort_context(abort_context))
                         }
                     }
                 }
@@ -902,18 +914,22 @@ impl StarcoinVM {
         };
         Ok(match result {
             Ok(status_and_output) => status_and_output,
-            Err(err) => {
+            Err(ExecutionError {
+                status: err,
+                abort_context,
+            }) => {
                 let txn_status = TransactionStatus::from(err.clone());
                 if txn_status.is_discarded() {
                     discard_error_vm_status(err)
                 } else {
-                    self.failed_transaction_cleanup(
+                    let (status, output) = self.failed_transaction_cleanup(
                         err,
                         gas_schedule,
                         gas_status.remaining_gas(),
                         &txn_data,
                         &remote_cache,
-                    )
+                    );
+                    (status, output.with_abort_context(abort_context))
                 }
             }
         })
@@ -1159,6 +1175,32 @@ pub(crate) fn charge_global_write_gas_usage<R: MoveStorage>(
         .map_err(|p_err| p_err.finish(Location::Undefined).into_vm_status())
 }
 
+/// The error of executing a txn payload, with the context captured if the error is raised by
+/// the Move code.
+struct ExecutionError {
+    status: VMStatus,
+    abort_context: Option<AbortContext>,
+}
+
+impl ExecutionError {
+    fn new(error: VMError, state_view: &dyn StateView) -> Self {
+        let abort_context = AbortContext::from_vm_error(&error, state_view);
+        Self {
+            status: error.into_vm_status(),
+            abort_context,
+        }
+    }
+}
+
+impl From<VMStatus> for ExecutionError {
+    fn from(status: VMStatus) -> Self {
+        Self {
+            status,
+            abort_context: None,
+        }
+    }
+}
+
 pub(crate) fn discard_error_vm_status(err: VMStatus) -> (VMStatus, TransactionOutput) {
     info!("discard error vm_status output: {:?}", err);
     let vm_status = err.clone();
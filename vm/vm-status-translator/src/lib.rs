@@ -1,8 +1,10 @@
 use anyhow::Result;
 use serde::Deserialize;
 use serde::Serialize;
+pub use starcoin_move_explain::ErrorMapResolver;
 use starcoin_vm_types::access::ModuleAccess;
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::errmap::ErrorContext;
 use starcoin_vm_types::file_format::{CompiledModule, FunctionDefinitionIndex};
 use starcoin_vm_types::identifier::Identifier;
 use starcoin_vm_types::language_storage::ModuleId;
@@ -43,32 +45,59 @@ pub fn locate_execution_failure(
 pub struct MoveAbortExplain {
     pub category_code: u64,
     pub category_name: Option<String>,
+    #[serde(default)]
+    pub category_description: Option<String>,
     pub reason_code: u64,
     pub reason_name: Option<String>,
+    #[serde(default)]
+    pub reason_description: Option<String>,
 }
 
 pub fn explain_move_abort(abort_location: AbortLocation, abort_code: u64) -> MoveAbortExplain {
-    let category = abort_code & 0xFFu64;
-    let reason_code = abort_code >> 8;
-
     let err_context = match abort_location {
         AbortLocation::Module(module_id) => {
             starcoin_move_explain::get_explanation(&module_id, abort_code)
         }
         AbortLocation::Script => None,
     };
+    to_move_abort_explain(abort_code, err_context)
+}
+
+/// Explain the abort with the error maps of stdlib, and the ones provided by `resolver` for the
+/// modules out of stdlib.
+pub fn explain_move_abort_with_resolver(
+    abort_location: AbortLocation,
+    abort_code: u64,
+    resolver: &dyn ErrorMapResolver,
+) -> MoveAbortExplain {
+    let err_context = match abort_location {
+        AbortLocation::Module(module_id) => {
+            starcoin_move_explain::get_explanation_with_resolver(&module_id, abort_code, resolver)
+        }
+        AbortLocation::Script => None,
+    };
+    to_move_abort_explain(abort_code, err_context)
+}
+
+fn to_move_abort_explain(abort_code: u64, err_context: Option<ErrorContext>) -> MoveAbortExplain {
+    let category = abort_code & 0xFFu64;
+    let reason_code = abort_code >> 8;
     match err_context {
         Some(ctx) => MoveAbortExplain {
             category_code: category,
             category_name: Some(ctx.category.code_name),
+            category_description: Some(ctx.category.code_description),
             reason_code,
             reason_name: Some(ctx.reason.code_name),
+            reason_description: Some(ctx.reason.code_description),
         },
         None => MoveAbortExplain {
             category_code: category,
             category_name: None,
+            category_description: None,
             reason_code,
             reason_name: None,
+            reason_description: None,
         },
     }
 }
@@ -100,9 +129,12 @@ pub enum VmStatusExplainView {
     },
 }
 
+/// Explain the `vm_status`, the abort in modules out of stdlib is explained with the error maps
+/// provided by `resolver`.
 pub fn explain_vm_status(
     state_view: &dyn StateView,
     vm_status: VMStatus,
+    resolver: &dyn ErrorMapResolver,
 ) -> Result<VmStatusExplainView> {
     let vm_status_explain = match &vm_status {
         VMStatus::Executed => VmStatusExplainView::Executed,
@@ -110,7 +142,7 @@ pub fn explain_vm_status(
         VMStatus::MoveAbort(location, abort_code) => VmStatusExplainView::MoveAbort {
             location: location.clone(),
             abort_code: *abort_code,
-            explain: explain_move_abort(location.clone(), *abort_code),
+            explain: explain_move_abort_with_resolver(location.clone(), *abort_code, resolver),
         },
         VMStatus::ExecutionFailure {
             status_code,